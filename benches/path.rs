@@ -0,0 +1,96 @@
+//! Benchmark of the validation paths.
+//!
+//! Builds the paths of the generated schemas and validates a specification with the schemas,
+//! reports the time and the number of the allocations per iteration,
+//! run with `cargo bench --bench path`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use roas::common::path::Path;
+use roas::v3_0::spec::Spec;
+use roas::validation::Validate;
+
+const SCHEMAS: usize = 30_000;
+const PROPERTIES: usize = 10;
+
+/// Counts the allocations of the benchmarked code.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn measure(name: &str, iterations: u32, f: impl Fn()) {
+    f();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let elapsed = start.elapsed() / iterations;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / iterations as usize;
+    println!(
+        "{:<16} {:>12?} {:>12} allocations",
+        name, elapsed, allocations
+    );
+}
+
+fn build_paths() {
+    let schemas = Path::root().field("components").field("schemas");
+    for i in 0..SCHEMAS {
+        let schema = schemas.item(i % 100);
+        for j in 0..PROPERTIES {
+            black_box(schema.field("properties").item(j));
+        }
+    }
+}
+
+fn spec() -> Spec {
+    let properties = (0..PROPERTIES)
+        .map(|j| {
+            (
+                format!("field{}", j),
+                serde_json::json!({"type": "string", "minLength": 1}),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let schemas = (0..SCHEMAS)
+        .map(|i| {
+            (
+                format!("Schema{}", i),
+                serde_json::json!({"type": "object", "properties": properties}),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::from_value(serde_json::json!({
+        "openapi": "3.0.3",
+        "info": {"title": "bench", "version": "1"},
+        "paths": {},
+        "components": {"schemas": schemas},
+    }))
+    .unwrap()
+}
+
+fn main() {
+    measure("build paths", 50, build_paths);
+
+    let spec = spec();
+    measure("validate", 10, || {
+        let _ = black_box(spec.validate(Default::default()));
+    });
+}
@@ -0,0 +1,96 @@
+/*
+ * C API of the roas validator.
+ *
+ * Build the library with the `capi` feature, e.g.
+ * `cargo rustc --release --features capi --crate-type cdylib`.
+ *
+ * All strings are NUL-terminated UTF-8, the returned strings are owned by the report
+ * and stay valid until the report is freed.
+ */
+
+#ifndef ROAS_H
+#define ROAS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The validation options, see `roas::validation::Options`. */
+#define ROAS_IGNORE_MISSING_TAGS (1u << 0)
+#define ROAS_IGNORE_EXTERNAL_REFERENCES (1u << 1)
+#define ROAS_IGNORE_NON_UNIQ_OPERATION_IDS (1u << 2)
+#define ROAS_IGNORE_UNUSED_TAGS (1u << 3)
+#define ROAS_IGNORE_UNUSED_SCHEMAS (1u << 4)
+#define ROAS_IGNORE_UNUSED_PARAMETERS (1u << 5)
+#define ROAS_IGNORE_UNUSED_RESPONSES (1u << 6)
+#define ROAS_IGNORE_UNUSED_SERVER_VARIABLES (1u << 7)
+#define ROAS_IGNORE_UNUSED_EXAMPLES (1u << 8)
+#define ROAS_IGNORE_UNUSED_REQUEST_BODIES (1u << 9)
+#define ROAS_IGNORE_UNUSED_HEADERS (1u << 10)
+#define ROAS_IGNORE_UNUSED_SECURITY_SCHEMES (1u << 11)
+#define ROAS_IGNORE_UNUSED_LINKS (1u << 12)
+#define ROAS_IGNORE_UNUSED_CALLBACKS (1u << 13)
+#define ROAS_IGNORE_EMPTY_RESPONSES (1u << 14)
+#define ROAS_IGNORE_UNSUPPORTED_SCHEMAS (1u << 15)
+
+/* The opt-in lints, see `roas::validation::Lints`. */
+#define ROAS_LINT_UNKNOWN_FORMATS (1u << 0)
+#define ROAS_LINT_UNREGISTERED_STATUS_CODES (1u << 1)
+#define ROAS_LINT_TAG_GROUPS (1u << 2)
+#define ROAS_LINT_REQUIRED_READ_ONLY_PROPERTIES (1u << 3)
+#define ROAS_LINT_RESERVED_HEADERS (1u << 4)
+
+/* A parsed specification of any supported version. */
+typedef struct RoasSpec RoasSpec;
+
+/* The result of the parsing or the validation. */
+typedef struct RoasReport RoasReport;
+
+/*
+ * Parses the specification from the JSON string.
+ * Returns NULL if the document cannot be parsed,
+ * the reason is stored into `report` if it is not NULL.
+ * The specification must be freed by `roas_free_spec`.
+ */
+RoasSpec *roas_parse(const char *json, RoasReport **report);
+
+/*
+ * Validates the specification with the options, a combination of the `ROAS_*` flags.
+ * The report must be freed by `roas_free_report`.
+ */
+RoasReport *roas_validate(const RoasSpec *spec, uint32_t options);
+
+/*
+ * Validates the specification with the options and the lints,
+ * a combination of the `ROAS_LINT_*` flags.
+ * The report must be freed by `roas_free_report`.
+ */
+RoasReport *roas_validate_with_lints(const RoasSpec *spec, uint32_t options, uint32_t lints);
+
+/* Returns true if no errors have been found. */
+bool roas_report_valid(const RoasReport *report);
+
+/* Returns the version of the specification, NULL if it is unknown. */
+const char *roas_report_version(const RoasReport *report);
+
+/* Returns the number of the errors. */
+size_t roas_report_error_count(const RoasReport *report);
+
+/* Returns the error by its index, NULL if the index is out of range. */
+const char *roas_report_error(const RoasReport *report, size_t index);
+
+/* Frees the report, NULL is ignored. */
+void roas_free_report(RoasReport *report);
+
+/* Frees the specification, NULL is ignored. */
+void roas_free_spec(RoasSpec *spec);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ROAS_H */
@@ -0,0 +1,336 @@
+//! C API
+//!
+//! The functions are exported with the C ABI and declared in `include/roas.h`,
+//! so the validator can be linked into the non-Rust applications.
+//! Build the library with `cargo rustc --release --features capi --crate-type cdylib`
+//! or with `--crate-type staticlib`.
+//!
+//! The specification and the report are opaque handles,
+//! created by [roas_parse] and [roas_validate] and freed by [roas_free_spec] and [roas_free_report].
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use enumset::EnumSet;
+
+use crate::common::versioned::AnySpec;
+use crate::validation::{Lints, Options};
+
+/// A parsed specification of any supported version.
+pub struct RoasSpec(AnySpec);
+
+/// The result of the parsing or the validation.
+#[derive(Debug, Default)]
+pub struct RoasReport {
+    version: Option<CString>,
+    errors: Vec<CString>,
+}
+
+impl RoasReport {
+    fn new(version: Option<String>, errors: Vec<String>) -> Self {
+        RoasReport {
+            version: version.map(c_string),
+            errors: errors.into_iter().map(c_string).collect(),
+        }
+    }
+}
+
+/// Converts the string, the NUL characters are escaped.
+fn c_string(s: String) -> CString {
+    CString::new(s.replace('\0', "\\0")).unwrap_or_default()
+}
+
+/// Runs the body of an exported function and returns the fallback value if it panics,
+/// a panic must not unwind into the foreign code.
+fn guard<T>(fallback: impl FnOnce() -> T, body: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| fallback())
+}
+
+/// The report of a function that panicked.
+fn panicked(version: Option<String>) -> RoasReport {
+    RoasReport::new(
+        version,
+        vec!["internal error: the function panicked".to_owned()],
+    )
+}
+
+fn parse(json: &str) -> Result<RoasSpec, RoasReport> {
+    AnySpec::parse(json)
+        .map(RoasSpec)
+        .map_err(|(version, err)| RoasReport::new(version, vec![err]))
+}
+
+fn validate(spec: &RoasSpec, options: EnumSet<Options>, lints: EnumSet<Lints>) -> RoasReport {
+    let version = spec.0.version();
+    let result = spec.0.validate_with_lints(options, lints);
+    RoasReport::new(Some(version), result.err().unwrap_or_default())
+}
+
+/// Parses the specification from the JSON string.
+///
+/// Returns NULL if the document cannot be parsed,
+/// the reason is stored into `report` if it is not NULL.
+///
+/// # Safety
+///
+/// `json` must be NULL or a NUL-terminated string,
+/// `report` must be NULL or a valid pointer to store the report into.
+#[no_mangle]
+pub unsafe extern "C" fn roas_parse(
+    json: *const c_char,
+    report: *mut *mut RoasReport,
+) -> *mut RoasSpec {
+    let result = guard(
+        || Err(panicked(None)),
+        || {
+            if json.is_null() {
+                return Err(RoasReport::new(
+                    None,
+                    vec!["json must not be NULL".to_owned()],
+                ));
+            }
+            match CStr::from_ptr(json).to_str() {
+                Ok(json) => parse(json),
+                Err(e) => Err(RoasReport::new(None, vec![e.to_string()])),
+            }
+        },
+    );
+    match result {
+        Ok(spec) => Box::into_raw(Box::new(spec)),
+        Err(err) => {
+            if !report.is_null() {
+                *report = Box::into_raw(Box::new(err));
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Validates the specification with the options,
+/// a bit set of [Options] in the order of their declaration, see the `ROAS_*` flags.
+///
+/// # Safety
+///
+/// `spec` must be NULL or returned by [roas_parse] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_validate(spec: *const RoasSpec, options: u32) -> *mut RoasReport {
+    roas_validate_with_lints(spec, options, 0)
+}
+
+/// Validates the specification with the options and the opt-in lints,
+/// a bit set of [Lints] in the order of their declaration, see the `ROAS_LINT_*` flags.
+///
+/// # Safety
+///
+/// `spec` must be NULL or returned by [roas_parse] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_validate_with_lints(
+    spec: *const RoasSpec,
+    options: u32,
+    lints: u32,
+) -> *mut RoasReport {
+    let report = guard(
+        || panicked(None),
+        || match spec.as_ref() {
+            Some(spec) => validate(
+                spec,
+                EnumSet::from_u32_truncated(options),
+                EnumSet::from_u32_truncated(lints),
+            ),
+            None => RoasReport::new(None, vec!["spec must not be NULL".to_owned()]),
+        },
+    );
+    Box::into_raw(Box::new(report))
+}
+
+/// Returns `true` if no errors have been found.
+///
+/// # Safety
+///
+/// `report` must be NULL or returned by [roas_parse] or [roas_validate] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_report_valid(report: *const RoasReport) -> bool {
+    guard(
+        || false,
+        || report.as_ref().is_some_and(|x| x.errors.is_empty()),
+    )
+}
+
+/// Returns the version of the specification, NULL if it is unknown.
+///
+/// # Safety
+///
+/// `report` must be NULL or returned by [roas_parse] or [roas_validate] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_report_version(report: *const RoasReport) -> *const c_char {
+    guard(ptr::null, || {
+        report
+            .as_ref()
+            .and_then(|x| x.version.as_ref())
+            .map_or(ptr::null(), |x| x.as_ptr())
+    })
+}
+
+/// Returns the number of the errors.
+///
+/// # Safety
+///
+/// `report` must be NULL or returned by [roas_parse] or [roas_validate] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_report_error_count(report: *const RoasReport) -> usize {
+    guard(|| 0, || report.as_ref().map_or(0, |x| x.errors.len()))
+}
+
+/// Returns the error by its index, NULL if the index is out of range.
+///
+/// # Safety
+///
+/// `report` must be NULL or returned by [roas_parse] or [roas_validate] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_report_error(
+    report: *const RoasReport,
+    index: usize,
+) -> *const c_char {
+    guard(ptr::null, || {
+        report
+            .as_ref()
+            .and_then(|x| x.errors.get(index))
+            .map_or(ptr::null(), |x| x.as_ptr())
+    })
+}
+
+/// Frees the report, NULL is ignored.
+///
+/// # Safety
+///
+/// `report` must be NULL or returned by [roas_parse] or [roas_validate] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_free_report(report: *mut RoasReport) {
+    guard(
+        || (),
+        || {
+            if !report.is_null() {
+                drop(Box::from_raw(report));
+            }
+        },
+    )
+}
+
+/// Frees the specification, NULL is ignored.
+///
+/// # Safety
+///
+/// `spec` must be NULL or returned by [roas_parse] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn roas_free_spec(spec: *mut RoasSpec) {
+    guard(
+        || (),
+        || {
+            if !spec.is_null() {
+                drop(Box::from_raw(spec));
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(report: *const RoasReport) -> Vec<String> {
+        unsafe {
+            (0..roas_report_error_count(report))
+                .map(|i| {
+                    CStr::from_ptr(roas_report_error(report, i))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_parse_and_validate() {
+        let json = CString::new(
+            r#"{"openapi": "3.0.3", "info": {"title": "foo", "version": "1"}, "paths": {}, "tags": [{"name": "pets"}]}"#,
+        )
+        .unwrap();
+        unsafe {
+            let spec = roas_parse(json.as_ptr(), ptr::null_mut());
+            assert!(!spec.is_null());
+
+            let report = roas_validate(spec, 0);
+            assert!(!roas_report_valid(report));
+            assert_eq!(
+                CStr::from_ptr(roas_report_version(report)).to_str(),
+                Ok("3.0.3")
+            );
+            assert_eq!(errors(report), vec!["#/tags/pets: unused"]);
+            assert!(roas_report_error(report, 1).is_null());
+            roas_free_report(report);
+
+            let report = roas_validate(spec, Options::IgnoreUnusedTags.only().as_u32());
+            assert!(roas_report_valid(report), "{:?}", errors(report));
+            roas_free_report(report);
+
+            roas_free_spec(spec);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let json = CString::new(r#"{"openapi": "4.0.0"}"#).unwrap();
+        unsafe {
+            let mut report = ptr::null_mut();
+            let spec = roas_parse(json.as_ptr(), &mut report);
+            assert!(spec.is_null());
+            assert!(!roas_report_valid(report));
+            assert_eq!(errors(report), vec!["unsupported version: 4.0.0"]);
+            roas_free_report(report);
+
+            let json = CString::new(r#"{"openapi": "3.0.9"}"#).unwrap();
+            let spec = roas_parse(json.as_ptr(), &mut report);
+            assert!(spec.is_null());
+            assert_eq!(errors(report), vec!["unsupported version: 3.0.9"]);
+            roas_free_report(report);
+
+            let spec = roas_parse(ptr::null(), &mut report);
+            assert!(spec.is_null());
+            assert_eq!(errors(report), vec!["json must not be NULL"]);
+            roas_free_report(report);
+
+            let report = roas_validate(ptr::null(), 0);
+            assert_eq!(errors(report), vec!["spec must not be NULL"]);
+            roas_free_report(report);
+        }
+    }
+
+    /// Returns the `#define` of the flag, e.g. `ROAS_IGNORE_MISSING_TAGS`.
+    fn define(prefix: &str, flag: impl std::fmt::Debug, i: usize) -> String {
+        let mut name = String::from(prefix);
+        let mut lowercase = false;
+        for c in format!("{:?}", flag).chars() {
+            if lowercase && c.is_ascii_uppercase() {
+                name.push('_');
+            }
+            lowercase = c.is_ascii_lowercase();
+            name.push(c.to_ascii_uppercase());
+        }
+        format!("#define {} (1u << {})", name, i)
+    }
+
+    #[test]
+    fn test_header_options() {
+        let header = include_str!("../include/roas.h");
+        for (i, option) in EnumSet::<Options>::all().iter().enumerate() {
+            let define = define("ROAS_", option, i);
+            assert!(header.contains(&define), "missing `{}`", define);
+        }
+        for (i, lint) in EnumSet::<Lints>::all().iter().enumerate() {
+            let define = define("ROAS_LINT_", lint, i);
+            assert!(header.contains(&define), "missing `{}`", define);
+        }
+    }
+}
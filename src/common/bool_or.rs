@@ -1,4 +1,5 @@
 use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::path::Path;
 use crate::common::reference::{RefOr, ResolveReference};
 use serde::{Deserialize, Serialize};
 
@@ -10,7 +11,7 @@ pub enum BoolOr<T> {
 }
 
 impl<D> BoolOr<RefOr<Box<D>>> {
-    pub fn validate_with_context_boxed<T>(&self, ctx: &mut Context<T>, path: String)
+    pub fn validate_with_context_boxed<T>(&self, ctx: &mut Context<T>, path: Path)
     where
         T: ResolveReference<D>,
         D: ValidateWithContext<T>,
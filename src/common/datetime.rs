@@ -0,0 +1,389 @@
+//! Date and time formats
+//!
+//! Parsing and formatting of the string values with the `date`, `date-time`, `time`
+//! and `duration` formats, as defined by [RFC3339](https://www.rfc-editor.org/rfc/rfc3339).
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::datetime::{DateTime, Duration};
+//!
+//! let value: DateTime = "2024-02-29T10:30:00.5+02:00".parse().unwrap();
+//! assert_eq!(value.date.day, 29);
+//! assert_eq!(value.time.offset, 120);
+//! assert_eq!(value.to_string(), "2024-02-29T10:30:00.5+02:00");
+//!
+//! let value: Duration = "P1DT12H".parse().unwrap();
+//! assert_eq!(value.hours, 12);
+//! assert!("2023-02-29".parse::<roas::common::datetime::Date>().is_err());
+//! ```
+
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::common::formats::StringFormat;
+
+/// DateTimeError is returned when a value does not conform to its format.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("invalid {format} `{value}`")]
+pub struct DateTimeError {
+    /// The expected format of the value.
+    pub format: StringFormat,
+
+    /// The invalid value.
+    pub value: String,
+}
+
+/// A calendar date, the `full-date` of RFC3339, e.g. `2024-02-29`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// A time of day with the offset from UTC, the `full-time` of RFC3339, e.g. `10:30:00Z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    /// The value `60` is allowed for a leap second.
+    pub second: u8,
+    /// The fraction of the second, the digits after the ninth are dropped.
+    pub nanosecond: u32,
+    /// The offset from UTC in minutes, `0` for `Z`.
+    pub offset: i16,
+}
+
+/// A date and a time, the `date-time` of RFC3339, e.g. `2024-02-29T10:30:00Z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+/// A duration, the `duration` of RFC3339 Appendix A, e.g. `P1Y2M3DT4H5M6S` or `P2W`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
+pub struct Duration {
+    pub years: u64,
+    pub months: u64,
+    pub weeks: u64,
+    pub days: u64,
+    pub hours: u64,
+    pub minutes: u64,
+    pub seconds: u64,
+}
+
+/// Checks that the value conforms to the format,
+/// the formats other than `date`, `date-time`, `time` and `duration` are not checked.
+pub fn check_format(format: &StringFormat, value: &str) -> Result<(), DateTimeError> {
+    let valid = match format {
+        StringFormat::Date => parse_date(value).is_some(),
+        StringFormat::DateTime => parse_date_time(value).is_some(),
+        StringFormat::Time => parse_time(value).is_some(),
+        StringFormat::Duration => parse_duration(value).is_some(),
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(error(format.clone(), value))
+    }
+}
+
+fn error(format: StringFormat, value: &str) -> DateTimeError {
+    DateTimeError {
+        format,
+        value: value.to_owned(),
+    }
+}
+
+/// Parses exactly `n` ASCII digits at the start of the bytes.
+fn digits(bytes: &[u8], n: usize) -> Option<u32> {
+    let digits = bytes.get(..n)?;
+    digits.iter().try_fold(0, |acc, &b| {
+        b.is_ascii_digit().then(|| acc * 10 + u32::from(b - b'0'))
+    })
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn parse_date(s: &str) -> Option<Date> {
+    let b = s.as_bytes();
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    let year = digits(b, 4)? as u16;
+    let month = digits(&b[5..], 2)? as u8;
+    let day = digits(&b[8..], 2)? as u8;
+    ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)).then_some(Date {
+        year,
+        month,
+        day,
+    })
+}
+
+fn parse_time(s: &str) -> Option<Time> {
+    let b = s.as_bytes();
+    if b.len() < 9 || b[2] != b':' || b[5] != b':' {
+        return None;
+    }
+    let hour = digits(b, 2)? as u8;
+    let minute = digits(&b[3..], 2)? as u8;
+    let second = digits(&b[6..], 2)? as u8;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let mut rest = &b[8..];
+    let mut nanosecond = 0;
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let len = fraction.iter().take_while(|x| x.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        let precision = len.min(9);
+        nanosecond = digits(fraction, precision)? * 10_u32.pow(9 - precision as u32);
+        rest = &fraction[len..];
+    }
+    let offset = match rest {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), offset @ ..] if offset.len() == 5 && offset[2] == b':' => {
+            let hours = digits(offset, 2)? as i16;
+            let minutes = digits(&offset[3..], 2)? as i16;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 60 + minutes;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+    Some(Time {
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset,
+    })
+}
+
+fn parse_date_time(s: &str) -> Option<DateTime> {
+    if !matches!(s.as_bytes().get(10), Some(b'T' | b't')) {
+        return None;
+    }
+    Some(DateTime {
+        date: parse_date(&s[..10])?,
+        time: parse_time(&s[11..])?,
+    })
+}
+
+/// Parses the components like `1Y2M` in the order of the designators,
+/// returns `None` if the value is empty or malformed.
+fn parse_components<const N: usize>(s: &str, designators: [char; N]) -> Option<[u64; N]> {
+    let mut values = [0; N];
+    let mut next = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let designator = rest[len..].chars().next()?;
+        let i = next + designators[next..].iter().position(|x| *x == designator)?;
+        values[i] = rest[..len].parse().ok()?;
+        next = i + 1;
+        rest = &rest[len + 1..];
+    }
+    (!s.is_empty()).then_some(values)
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let rest = s.strip_prefix('P')?;
+    if rest.ends_with('W') {
+        let [weeks] = parse_components(rest, ['W'])?;
+        return Some(Duration {
+            weeks,
+            ..Default::default()
+        });
+    }
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+    let [years, months, days] = match date {
+        "" if time.is_some() => [0; 3],
+        _ => parse_components(date, ['Y', 'M', 'D'])?,
+    };
+    let [hours, minutes, seconds] = match time {
+        Some(time) => parse_components(time, ['H', 'M', 'S'])?,
+        None => [0; 3],
+    };
+    Some(Duration {
+        years,
+        months,
+        weeks: 0,
+        days,
+        hours,
+        minutes,
+        seconds,
+    })
+}
+
+impl FromStr for Date {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_date(s).ok_or_else(|| error(StringFormat::Date, s))
+    }
+}
+
+impl FromStr for Time {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_time(s).ok_or_else(|| error(StringFormat::Time, s))
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_date_time(s).ok_or_else(|| error(StringFormat::DateTime, s))
+    }
+}
+
+impl FromStr for Duration {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(s).ok_or_else(|| error(StringFormat::Duration, s))
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        match self.offset {
+            0 => write!(f, "Z"),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+            }
+        }
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "P")?;
+        if *self == Duration::default() {
+            return write!(f, "0D");
+        }
+        for (value, designator) in [
+            (self.years, 'Y'),
+            (self.months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ] {
+            if value > 0 {
+                write!(f, "{}{}", value, designator)?;
+            }
+        }
+        if self.hours > 0 || self.minutes > 0 || self.seconds > 0 {
+            write!(f, "T")?;
+            for (value, designator) in [(self.hours, 'H'), (self.minutes, 'M'), (self.seconds, 'S')]
+            {
+                if value > 0 {
+                    write!(f, "{}{}", value, designator)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_format() {
+        for (format, value, valid) in [
+            (StringFormat::Date, "2024-02-29", true),
+            (StringFormat::Date, "2023-02-29", false),
+            (StringFormat::Date, "2024-13-01", false),
+            (StringFormat::Date, "2024-1-01", false),
+            (StringFormat::Time, "23:59:60.123Z", true),
+            (StringFormat::Time, "10:30:00-05:30", true),
+            (StringFormat::Time, "10:30:00", false),
+            (StringFormat::Time, "24:00:00Z", false),
+            (StringFormat::Time, "10:30:00.Z", false),
+            (StringFormat::DateTime, "2024-02-29t10:30:00z", true),
+            (StringFormat::DateTime, "2024-02-29 10:30:00Z", false),
+            (StringFormat::DateTime, "2024-02-29", false),
+            (StringFormat::Duration, "P1Y2M3DT4H5M6S", true),
+            (StringFormat::Duration, "PT1M", true),
+            (StringFormat::Duration, "P2W", true),
+            (StringFormat::Duration, "P", false),
+            (StringFormat::Duration, "PT", false),
+            (StringFormat::Duration, "P1D2Y", false),
+            (StringFormat::Duration, "P1W2D", false),
+            (StringFormat::Duration, "1D", false),
+            (StringFormat::Email, "any", true),
+        ] {
+            assert_eq!(
+                check_format(&format, value).is_ok(),
+                valid,
+                "{} {}",
+                format,
+                value
+            );
+        }
+        assert_eq!(
+            check_format(&StringFormat::Date, "x")
+                .unwrap_err()
+                .to_string(),
+            "invalid date `x`"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value: DateTime = "2024-02-29T10:30:00.250-05:30".parse().unwrap();
+        assert_eq!(value.time.nanosecond, 250_000_000);
+        assert_eq!(value.time.offset, -330);
+        assert_eq!(value.to_string(), "2024-02-29T10:30:00.25-05:30");
+        for value in ["P1Y2M3DT4H5M6S", "PT1M", "P2W", "P0D"] {
+            assert_eq!(value.parse::<Duration>().unwrap().to_string(), value);
+        }
+    }
+}
@@ -14,7 +14,7 @@
 //! Example:
 //!
 //! ```rust
-//! use std::collections::BTreeMap;
+//! use roas::common::extensions::Extensions;
 //! use serde::{Deserialize, Serialize};
 //!
 //! #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -23,19 +23,126 @@
 //!     #[serde(flatten)]
 //!     #[serde(with = "roas::common::extensions")]
 //!     #[serde(skip_serializing_if = "Option::is_none")]
-//!     pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+//!     pub extensions: Option<Extensions>,
 //! }
 //! ```
+//!
+//! The [ExtensionsExt] trait adds the typed accessors to the extensions:
+//!
+//! ```rust
+//! use roas::common::extensions::ExtensionsExt;
+//! use roas::v3_0::info::Info;
+//!
+//! let mut info = Info::default();
+//! info.extensions.insert_serializable("x-rate-limit", &100).unwrap();
+//! assert_eq!(info.extensions.get_as::<u32>("x-rate-limit").unwrap(), Some(100));
+//! assert!(info.extensions.insert_serializable("rate-limit", &100).is_err());
+//! ```
 use std::collections::BTreeMap;
 use std::fmt;
 
-use serde::de::{Error, MapAccess, Visitor};
+use serde::de::{DeserializeOwned, Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// The specification extensions of an object, the keys start with `x-`.
+pub type Extensions = BTreeMap<String, serde_json::Value>;
+
+/// ExtensionError is an error type for accessing the extensions.
+#[derive(Debug, Error)]
+pub enum ExtensionError {
+    /// InvalidKey is returned when the key does not start with `x-`.
+    #[error("extension `{0}` must start with `x-`")]
+    InvalidKey(String),
+
+    /// Json is returned when the value cannot be converted.
+    #[error("extension `{key}`: {source}")]
+    Json {
+        key: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Typed accessors of the specification extensions,
+/// implemented for both [Extensions] and `Option<Extensions>` fields.
+pub trait ExtensionsExt {
+    /// Returns the value of the extension converted to the type,
+    /// `None` if the extension is not defined.
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ExtensionError>;
+
+    /// Serializes and inserts the value of the extension,
+    /// returns the previous value.
+    /// The key must start with `x-`, otherwise the extension would be skipped by the serializer.
+    fn insert_serializable<T: Serialize + ?Sized>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> Result<Option<serde_json::Value>, ExtensionError>;
+
+    /// Returns an iterator over the extensions, the entries not starting with `x-` are skipped.
+    fn iter_extensions(&self) -> impl Iterator<Item = (&str, &serde_json::Value)>;
+}
+
+impl ExtensionsExt for Extensions {
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ExtensionError> {
+        self.get(key)
+            .map(|x| T::deserialize(x))
+            .transpose()
+            .map_err(|source| ExtensionError::Json {
+                key: key.to_owned(),
+                source,
+            })
+    }
+
+    fn insert_serializable<T: Serialize + ?Sized>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> Result<Option<serde_json::Value>, ExtensionError> {
+        if !key.starts_with("x-") {
+            return Err(ExtensionError::InvalidKey(key.to_owned()));
+        }
+        let value = serde_json::to_value(value).map_err(|source| ExtensionError::Json {
+            key: key.to_owned(),
+            source,
+        })?;
+        Ok(self.insert(key.to_owned(), value))
+    }
+
+    fn iter_extensions(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.iter()
+            .filter(|(k, _)| k.starts_with("x-"))
+            .map(|(k, v)| (k.as_str(), v))
+    }
+}
 
-pub fn deserialize<'de, D>(
-    deserializer: D,
-) -> Result<Option<BTreeMap<String, serde_json::Value>>, D::Error>
+impl ExtensionsExt for Option<Extensions> {
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ExtensionError> {
+        match self {
+            Some(ext) => ext.get_as(key),
+            None => Ok(None),
+        }
+    }
+
+    fn insert_serializable<T: Serialize + ?Sized>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> Result<Option<serde_json::Value>, ExtensionError> {
+        if !key.starts_with("x-") {
+            return Err(ExtensionError::InvalidKey(key.to_owned()));
+        }
+        self.get_or_insert_with(Extensions::new)
+            .insert_serializable(key, value)
+    }
+
+    fn iter_extensions(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.iter().flat_map(|x| x.iter_extensions())
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Extensions>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -69,10 +176,7 @@ where
     Ok(if map.is_empty() { None } else { Some(map) })
 }
 
-pub fn serialize<S>(
-    ext: &Option<BTreeMap<String, serde_json::Value>>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
+pub fn serialize<S>(ext: &Option<Extensions>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -143,6 +247,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extensions_ext() {
+        let mut ext: Option<Extensions> = None;
+        assert_eq!(ext.get_as::<u32>("x-limit").unwrap(), None);
+        assert_eq!(ext.iter_extensions().count(), 0);
+
+        assert_eq!(ext.insert_serializable("x-limit", &10).unwrap(), None);
+        assert_eq!(
+            ext.insert_serializable("x-limit", &20).unwrap(),
+            Some(10.into())
+        );
+        assert_eq!(ext.get_as::<u32>("x-limit").unwrap(), Some(20));
+        assert!(
+            matches!(
+                ext.get_as::<String>("x-limit"),
+                Err(ExtensionError::Json { key, .. }) if key == "x-limit"
+            ),
+            "wrong type"
+        );
+        assert_eq!(
+            ext.insert_serializable("limit", &1)
+                .unwrap_err()
+                .to_string(),
+            "extension `limit` must start with `x-`"
+        );
+
+        let ext = ext.as_mut().unwrap();
+        ext.insert("skipped".to_owned(), 1.into());
+        ext.insert_serializable("x-tags", &["a", "b"]).unwrap();
+        assert_eq!(
+            ext.get_as::<Vec<String>>("x-tags").unwrap(),
+            Some(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(
+            ext.iter_extensions().map(|(k, _)| k).collect::<Vec<_>>(),
+            vec!["x-limit", "x-tags"]
+        );
+    }
+
     #[test]
     fn test_extensions_serialize() {
         assert_eq!(
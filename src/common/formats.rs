@@ -18,12 +18,60 @@ pub enum StringFormat {
     /// As defined by `date-time` - [RFC3339](https://www.rfc-editor.org/rfc/rfc3339)
     DateTime,
 
+    /// As defined by `full-time` - [RFC3339](https://www.rfc-editor.org/rfc/rfc3339)
+    Time,
+
+    /// As defined by `duration` - [RFC3339, Appendix A](https://www.rfc-editor.org/rfc/rfc3339#appendix-A)
+    Duration,
+
     /// Used to hint UIs the input needs to be obscured.
     Password,
 
     /// As defined by [RFC4122](https://www.rfc-editor.org/rfc/rfc4122)
     UUID,
 
+    /// As defined by `Mailbox` - [RFC5321, Section 4.1.2](https://www.rfc-editor.org/rfc/rfc5321#section-4.1.2)
+    Email,
+
+    /// As defined by `Mailbox` - [RFC6531](https://www.rfc-editor.org/rfc/rfc6531)
+    IdnEmail,
+
+    /// As defined by [RFC1123, Section 2.1](https://www.rfc-editor.org/rfc/rfc1123#section-2.1)
+    Hostname,
+
+    /// As defined by [RFC5890, Section 2.3.2.3](https://www.rfc-editor.org/rfc/rfc5890#section-2.3.2.3)
+    IdnHostname,
+
+    /// As defined by `dotted-quad` - [RFC2673, Section 3.2](https://www.rfc-editor.org/rfc/rfc2673#section-3.2)
+    IPv4,
+
+    /// As defined by [RFC4291, Section 2.2](https://www.rfc-editor.org/rfc/rfc4291#section-2.2)
+    IPv6,
+
+    /// As defined by [RFC3986](https://www.rfc-editor.org/rfc/rfc3986)
+    URI,
+
+    /// As defined by `URI-reference` - [RFC3986](https://www.rfc-editor.org/rfc/rfc3986)
+    URIReference,
+
+    /// As defined by [RFC3987](https://www.rfc-editor.org/rfc/rfc3987)
+    IRI,
+
+    /// As defined by `IRI-reference` - [RFC3987](https://www.rfc-editor.org/rfc/rfc3987)
+    IRIReference,
+
+    /// As defined by [RFC6570](https://www.rfc-editor.org/rfc/rfc6570)
+    URITemplate,
+
+    /// As defined by [RFC6901](https://www.rfc-editor.org/rfc/rfc6901)
+    JSONPointer,
+
+    /// A relative JSON pointer, as defined by the JSON Schema specification.
+    RelativeJSONPointer,
+
+    /// A regular expression, as defined by [ECMA-262](https://262.ecma-international.org)
+    Regex,
+
     /// A custom string format
     Custom(String),
 }
@@ -35,13 +83,67 @@ impl Display for StringFormat {
             StringFormat::Binary => write!(f, "binary"),
             StringFormat::Date => write!(f, "date"),
             StringFormat::DateTime => write!(f, "date-time"),
+            StringFormat::Time => write!(f, "time"),
+            StringFormat::Duration => write!(f, "duration"),
             StringFormat::Password => write!(f, "password"),
             StringFormat::UUID => write!(f, "uuid"),
+            StringFormat::Email => write!(f, "email"),
+            StringFormat::IdnEmail => write!(f, "idn-email"),
+            StringFormat::Hostname => write!(f, "hostname"),
+            StringFormat::IdnHostname => write!(f, "idn-hostname"),
+            StringFormat::IPv4 => write!(f, "ipv4"),
+            StringFormat::IPv6 => write!(f, "ipv6"),
+            StringFormat::URI => write!(f, "uri"),
+            StringFormat::URIReference => write!(f, "uri-reference"),
+            StringFormat::IRI => write!(f, "iri"),
+            StringFormat::IRIReference => write!(f, "iri-reference"),
+            StringFormat::URITemplate => write!(f, "uri-template"),
+            StringFormat::JSONPointer => write!(f, "json-pointer"),
+            StringFormat::RelativeJSONPointer => write!(f, "relative-json-pointer"),
+            StringFormat::Regex => write!(f, "regex"),
             StringFormat::Custom(s) => write!(f, "{}", s),
         }
     }
 }
 
+impl StringFormat {
+    /// Returns `true` if the format is defined by the OpenAPI or JSON Schema specifications,
+    /// `false` for the custom formats.
+    pub fn is_standard(&self) -> bool {
+        !matches!(self, StringFormat::Custom(_))
+    }
+}
+
+impl From<&str> for StringFormat {
+    fn from(value: &str) -> Self {
+        match value {
+            "byte" => StringFormat::Byte,
+            "binary" => StringFormat::Binary,
+            "date" => StringFormat::Date,
+            "date-time" => StringFormat::DateTime,
+            "time" => StringFormat::Time,
+            "duration" => StringFormat::Duration,
+            "password" => StringFormat::Password,
+            "uuid" => StringFormat::UUID,
+            "email" => StringFormat::Email,
+            "idn-email" => StringFormat::IdnEmail,
+            "hostname" => StringFormat::Hostname,
+            "idn-hostname" => StringFormat::IdnHostname,
+            "ipv4" => StringFormat::IPv4,
+            "ipv6" => StringFormat::IPv6,
+            "uri" => StringFormat::URI,
+            "uri-reference" => StringFormat::URIReference,
+            "iri" => StringFormat::IRI,
+            "iri-reference" => StringFormat::IRIReference,
+            "uri-template" => StringFormat::URITemplate,
+            "json-pointer" => StringFormat::JSONPointer,
+            "relative-json-pointer" => StringFormat::RelativeJSONPointer,
+            "regex" => StringFormat::Regex,
+            _ => StringFormat::Custom(value.to_owned()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum IntegerFormat {
     // signed 32 bits
@@ -112,6 +214,49 @@ impl Display for CollectionFormat {
     }
 }
 
+impl CollectionFormat {
+    /// Returns the delimiter of the values, `None` for `multi`.
+    pub fn delimiter(&self) -> Option<char> {
+        match self {
+            CollectionFormat::CSV => Some(','),
+            CollectionFormat::SSV => Some(' '),
+            CollectionFormat::TSV => Some('\t'),
+            CollectionFormat::PIPES => Some('|'),
+            CollectionFormat::Multi => None,
+        }
+    }
+
+    /// Splits the raw values of a parameter into the array values.
+    ///
+    /// The `raw` holds the values of all occurrences of the parameter,
+    /// e.g. `["foo", "bar"]` for the `multi` format of `?tag=foo&tag=bar`
+    /// or `["foo,bar"]` for the `csv` format of `?tag=foo,bar`.
+    /// The empty values of the delimited formats produce no array values.
+    pub fn split<'a>(&self, raw: &[&'a str]) -> Vec<&'a str> {
+        match self.delimiter() {
+            Some(delimiter) => raw
+                .iter()
+                .filter(|x| !x.is_empty())
+                .flat_map(|x| x.split(delimiter))
+                .collect(),
+            None => raw.to_vec(),
+        }
+    }
+
+    /// Joins the array values into the raw values of a parameter,
+    /// a single value for the delimited formats or a value per occurrence for `multi`.
+    pub fn join<S: AsRef<str>>(&self, values: &[S]) -> Vec<String> {
+        match self.delimiter() {
+            Some(delimiter) => vec![values
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join(delimiter.encode_utf8(&mut [0; 4]))],
+            None => values.iter().map(|x| x.as_ref().to_owned()).collect(),
+        }
+    }
+}
+
 impl Serialize for StringFormat {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.serialize_str(self.to_string().as_str())
@@ -129,22 +274,14 @@ impl<'de> Deserialize<'de> for StringFormat {
             type Value = StringFormat;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("one of `byte`, `binary`, `date`, `date-time`, `password`, `uuid` or a custom string")
+                formatter.write_str("a string format")
             }
 
             fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                match value {
-                    "byte" => Ok(StringFormat::Byte),
-                    "binary" => Ok(StringFormat::Binary),
-                    "date" => Ok(StringFormat::Date),
-                    "date-time" => Ok(StringFormat::DateTime),
-                    "password" => Ok(StringFormat::Password),
-                    "uuid" => Ok(StringFormat::UUID),
-                    _ => Ok(StringFormat::Custom(String::from(value))),
-                }
+                Ok(StringFormat::from(value))
             }
         }
 
@@ -188,6 +325,39 @@ mod tests {
             StringFormat::UUID,
             "deserialize uuid",
         );
+        for format in [
+            StringFormat::Byte,
+            StringFormat::Binary,
+            StringFormat::Date,
+            StringFormat::DateTime,
+            StringFormat::Time,
+            StringFormat::Duration,
+            StringFormat::Password,
+            StringFormat::UUID,
+            StringFormat::Email,
+            StringFormat::IdnEmail,
+            StringFormat::Hostname,
+            StringFormat::IdnHostname,
+            StringFormat::IPv4,
+            StringFormat::IPv6,
+            StringFormat::URI,
+            StringFormat::URIReference,
+            StringFormat::IRI,
+            StringFormat::IRIReference,
+            StringFormat::URITemplate,
+            StringFormat::JSONPointer,
+            StringFormat::RelativeJSONPointer,
+            StringFormat::Regex,
+        ] {
+            let name = format!("{:?}", format.to_string());
+            assert_eq!(
+                serde_json::from_str::<StringFormat>(&name).unwrap(),
+                format,
+                "deserialize {}",
+                name,
+            );
+            assert!(format.is_standard(), "{} is standard", name);
+        }
         assert_eq!(
             serde_json::from_str::<StringFormat>(r#""foo-bar""#).unwrap(),
             StringFormat::Custom(String::from("foo-bar")),
@@ -375,4 +545,23 @@ mod tests {
             "serialize csv",
         );
     }
+
+    #[test]
+    fn test_collection_format_split_join() {
+        for (format, raw, values) in [
+            (CollectionFormat::CSV, vec!["foo,bar"], vec!["foo", "bar"]),
+            (CollectionFormat::SSV, vec!["foo bar"], vec!["foo", "bar"]),
+            (CollectionFormat::TSV, vec!["foo\tbar"], vec!["foo", "bar"]),
+            (CollectionFormat::PIPES, vec!["foo|bar"], vec!["foo", "bar"]),
+            (
+                CollectionFormat::Multi,
+                vec!["foo", "bar"],
+                vec!["foo", "bar"],
+            ),
+            (CollectionFormat::CSV, vec![""], vec![]),
+        ] {
+            assert_eq!(format.split(&raw), values, "split {}", format);
+            assert_eq!(format.join(&values), raw, "join {}", format);
+        }
+    }
 }
@@ -1,13 +1,20 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use enumset::EnumSet;
 use regex::Regex;
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use serde::Deserialize;
 
-use crate::validation::{Error, Options};
+use crate::common::extensions::Extensions;
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use crate::common::extensions::{ExtensionError, ExtensionsExt};
+use crate::common::formats::StringFormat;
+use crate::common::path::Path;
+use crate::validation::{Error, Lints, Options};
 
 pub trait ValidateWithContext<T> {
-    fn validate_with_context(&self, ctx: &mut Context<T>, path: String);
+    fn validate_with_context(&self, ctx: &mut Context<T>, path: Path);
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -16,14 +23,53 @@ pub struct Context<'a, T> {
     pub visited: HashSet<String>,
     pub errors: Vec<String>,
     pub options: EnumSet<Options>,
+    pub lints: EnumSet<Lints>,
+    pub patterns: Patterns,
+}
+
+/// The regular expressions compiled during the validation, each unique pattern is compiled once.
+#[derive(Debug, Clone, Default)]
+pub struct Patterns(HashMap<String, Result<Regex, regex::Error>>);
+
+impl Patterns {
+    /// Returns the compiled regular expression, compiling it on the first call.
+    pub fn get(&mut self, pattern: &str) -> &Result<Regex, regex::Error> {
+        if !self.0.contains_key(pattern) {
+            self.0.insert(pattern.to_owned(), Regex::new(pattern));
+        }
+        &self.0[pattern]
+    }
+
+    /// Returns the number of the compiled patterns.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no pattern has been compiled.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[cfg(all(feature = "parallel", any(feature = "v2", feature = "v3_0")))]
+    fn merge(&mut self, other: Patterns) {
+        for (pattern, regex) in other.0 {
+            self.0.entry(pattern).or_insert(regex);
+        }
+    }
+}
+
+impl PartialEq for Patterns {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.keys().all(|x| other.0.contains_key(x))
+    }
 }
 
 pub trait PushError<T> {
-    fn error(&mut self, path: String, args: T);
+    fn error(&mut self, path: Path, args: T);
 }
 
 impl<'a, T> PushError<&str> for Context<'a, T> {
-    fn error(&mut self, path: String, msg: &str) {
+    fn error(&mut self, path: Path, msg: &str) {
         if msg.starts_with('.') {
             self.errors.push(format!("{}{}", path, msg));
         } else {
@@ -33,13 +79,13 @@ impl<'a, T> PushError<&str> for Context<'a, T> {
 }
 
 impl<'a, T> PushError<String> for Context<'a, T> {
-    fn error(&mut self, path: String, msg: String) {
+    fn error(&mut self, path: Path, msg: String) {
         self.error(path, msg.as_str());
     }
 }
 
 impl<'a, T> PushError<fmt::Arguments<'_>> for Context<'a, T> {
-    fn error(&mut self, path: String, args: fmt::Arguments<'_>) {
+    fn error(&mut self, path: Path, args: fmt::Arguments<'_>) {
         self.error(path, args.to_string().as_str());
     }
 }
@@ -61,17 +107,104 @@ impl<'a, T> Context<'a, T> {
     pub fn is_option(&self, option: Options) -> bool {
         self.options.contains(option)
     }
+
+    pub fn is_lint(&self, lint: Lints) -> bool {
+        self.lints.contains(lint)
+    }
+
+    /// Enables the opt-in lints.
+    pub fn with_lints(mut self, lints: EnumSet<Lints>) -> Self {
+        self.lints = lints;
+        self
+    }
 }
 
 impl Context<'_, ()> {
-    pub fn new<T>(spec: &T, options: EnumSet<Options>) -> Context<T> {
+    pub fn new<T>(spec: &T, options: EnumSet<Options>) -> Context<'_, T> {
         Context {
             spec,
             visited: HashSet::new(),
             errors: Vec::new(),
             options,
+            lints: EnumSet::empty(),
+            patterns: Patterns::default(),
+        }
+    }
+}
+
+/// Validates the independent items, like the path items of a specification.
+///
+/// With the `parallel` feature the items are split between the threads,
+/// each thread validates its items with a copy of the context
+/// and the copies are merged in the order of the items.
+/// The errors reported by several threads, e.g. for a component referenced by items
+/// of different threads, are kept once, so the errors are the same as
+/// of the sequential validation.
+/// The visited sets of the threads are merged, but an item cannot see what
+/// the items of other threads have visited, so the uniqueness checks,
+/// like the duplicate operation ids, must be done before or after this call.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn validate_each<'a, T: Sync, I: Sync>(
+    ctx: &mut Context<'a, T>,
+    items: &[I],
+    validate: impl Fn(&mut Context<'a, T>, &I) + Sync,
+) {
+    #[cfg(feature = "parallel")]
+    {
+        let threads = std::thread::available_parallelism().map_or(1, |x| x.get());
+        if threads > 1 && items.len() > 1 {
+            return validate_in_threads(ctx, items, &validate, threads);
         }
     }
+    for item in items {
+        validate(ctx, item);
+    }
+}
+
+#[cfg(all(feature = "parallel", any(feature = "v2", feature = "v3_0")))]
+fn validate_in_threads<'a, T: Sync, I: Sync>(
+    ctx: &mut Context<'a, T>,
+    items: &[I],
+    validate: &(impl Fn(&mut Context<'a, T>, &I) + Sync),
+    threads: usize,
+) {
+    let results = std::thread::scope(|scope| {
+        let handles = items
+            .chunks(items.len().div_ceil(threads))
+            .map(|chunk| {
+                let mut copy = Context {
+                    spec: ctx.spec,
+                    visited: ctx.visited.clone(),
+                    errors: Vec::new(),
+                    options: ctx.options,
+                    lints: ctx.lints,
+                    patterns: ctx.patterns.clone(),
+                };
+                scope.spawn(move || {
+                    for item in chunk {
+                        validate(&mut copy, item);
+                    }
+                    copy
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|x| x.join().expect("validation thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    let mut reported = HashSet::new();
+    for copy in results {
+        let errors = copy
+            .errors
+            .into_iter()
+            .filter(|x| !reported.contains(x))
+            .collect::<Vec<_>>();
+        reported.extend(errors.iter().cloned());
+        ctx.errors.extend(errors);
+        ctx.visited.extend(copy.visited);
+        ctx.patterns.merge(copy.patterns);
+    }
 }
 
 impl<'a, T> From<Context<'a, T>> for Result<(), Error> {
@@ -84,9 +217,24 @@ impl<'a, T> From<Context<'a, T>> for Result<(), Error> {
     }
 }
 
-pub fn validate_email<T>(email: &Option<String>, ctx: &mut Context<T>, path: String) {
+/// Returns `true` if the email has a non-empty local part and a domain with a dot,
+/// separated by a single `@`, and no whitespaces.
+fn is_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|x| !x.is_empty())
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+pub fn validate_email<T>(email: &Option<String>, ctx: &mut Context<T>, path: Path) {
     if let Some(email) = email {
-        if !email.contains('@') {
+        if !is_email(email) {
             ctx.error(
                 path,
                 format_args!("must be a valid email address, found `{}`", email),
@@ -98,25 +246,177 @@ pub fn validate_email<T>(email: &Option<String>, ctx: &mut Context<T>, path: Str
 const HTTP: &str = "http://";
 const HTTPS: &str = "https://";
 
-pub fn validate_optional_url<T>(url: &Option<String>, ctx: &mut Context<T>, path: String) {
+pub fn validate_optional_url<T>(url: &Option<String>, ctx: &mut Context<T>, path: Path) {
     if let Some(url) = url {
         validate_required_url(url, ctx, path);
     }
 }
 
-pub fn validate_required_url<T>(url: &String, ctx: &mut Context<T>, path: String) {
-    if !url.starts_with(HTTP) && !url.starts_with(HTTPS) {
+/// Returns `true` if the url is an absolute HTTP(S) URL with a host and without whitespaces.
+fn is_url(url: &str) -> bool {
+    let rest = match url.strip_prefix(HTTP).or_else(|| url.strip_prefix(HTTPS)) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    !host.is_empty() && !url.chars().any(char::is_whitespace)
+}
+
+pub fn validate_required_url<T>(url: &String, ctx: &mut Context<T>, path: Path) {
+    if !is_url(url) {
         ctx.error(path, format_args!("must be a valid URL, found `{}`", url));
     }
 }
 
-pub fn validate_required_string<T>(s: &str, ctx: &mut Context<T>, path: String) {
+/// Returns `true` if the characters are allowed in a URI,
+/// the `{` and `}` are allowed as well for the templates like `/pets/{id}`
+/// and a `%` must start a percent-encoded octet.
+fn is_uri_chars(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'%' => {
+                let hex = |j: usize| bytes.get(j).is_some_and(u8::is_ascii_hexdigit);
+                if !hex(i + 1) || !hex(i + 2) {
+                    return false;
+                }
+            }
+            b'<' | b'>' | b'"' | b'\\' | b'^' | b'`' | b'|' => return false,
+            _ if b.is_ascii_whitespace() || b.is_ascii_control() => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Returns the scheme of the URI, if the URI starts with one, e.g. `urn` for `urn:isbn:1`.
+pub(crate) fn uri_scheme(s: &str) -> Option<&str> {
+    let (scheme, _) = s.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    valid.then_some(scheme)
+}
+
+/// Returns `true` if the uri is an absolute URI with a scheme,
+/// the HTTP(S) URIs must have a host.
+pub(crate) fn is_uri(uri: &str) -> bool {
+    match uri_scheme(uri) {
+        Some(scheme)
+            if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https") =>
+        {
+            is_url(&uri.to_ascii_lowercase()) && is_uri_chars(uri)
+        }
+        Some(_) => is_uri_chars(uri),
+        None => false,
+    }
+}
+
+/// Returns `true` if the uri is an absolute URI or a relative reference,
+/// like `../pets`, `#/components/schemas/Pet` or `//example.com/api`.
+fn is_uri_reference(uri: &str) -> bool {
+    let first = uri.split(['/', '?', '#']).next().unwrap_or_default();
+    if first.contains(':') {
+        is_uri(uri)
+    } else {
+        is_uri_chars(uri)
+    }
+}
+
+/// Validates that the uri is an absolute URI, e.g. `https://example.com` or `urn:example:pet`.
+pub fn validate_uri<T>(uri: &str, ctx: &mut Context<T>, path: Path) {
+    if !is_uri(uri) {
+        ctx.error(path, format_args!("must be a valid URI, found `{}`", uri));
+    }
+}
+
+/// Validates that the uri is an absolute URI or a relative reference.
+pub fn validate_uri_reference<T>(uri: &str, ctx: &mut Context<T>, path: Path) {
+    if !is_uri_reference(uri) {
+        ctx.error(
+            path,
+            format_args!("must be a valid URI reference, found `{}`", uri),
+        );
+    }
+}
+
+/// Validates that the required uri is not empty and is an absolute URI or a relative reference.
+pub fn validate_required_uri_reference<T>(uri: &str, ctx: &mut Context<T>, path: Path) {
+    if uri.is_empty() {
+        ctx.error(path, "must not be empty");
+    } else {
+        validate_uri_reference(uri, ctx, path);
+    }
+}
+
+/// Reports the extensions whose names do not start with `x-`,
+/// such entries are skipped by the serializer and would be lost silently.
+pub fn validate_extensions<T>(extensions: &Option<Extensions>, ctx: &mut Context<T>, path: Path) {
+    for name in extensions.iter().flat_map(|x| x.keys()) {
+        if !name.starts_with("x-") {
+            ctx.error(
+                path.field(name.clone()),
+                "extension name must start with `x-`",
+            );
+        }
+    }
+}
+
+/// The names of the tags of a group of the `x-tagGroups` extension,
+/// the typed model is [crate::common::vendor::TagGroups] with the `vendor_ext` feature.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+#[derive(Deserialize)]
+struct TagGroupTags {
+    tags: Vec<String>,
+}
+
+/// Reports the invalid root `x-tagGroups` extension and its tags not declared by the specification,
+/// if [Lints::TagGroups] is enabled.
+/// The missing tags are not reported if [Options::IgnoreMissingTags] is set.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn validate_tag_groups<T>(
+    extensions: &Option<Extensions>,
+    declared: &[&str],
+    ctx: &mut Context<T>,
+) {
+    if !ctx.is_lint(Lints::TagGroups) {
+        return;
+    }
+    let path = Path::root().field("x-tagGroups");
+    let groups = match extensions.get_as::<Vec<TagGroupTags>>("x-tagGroups") {
+        Ok(Some(groups)) => groups,
+        Ok(None) => return,
+        Err(ExtensionError::Json { source, .. }) => {
+            ctx.error(path, format_args!("invalid value: {}", source));
+            return;
+        }
+        Err(e) => {
+            ctx.error(path, e.to_string());
+            return;
+        }
+    };
+    if ctx.is_option(Options::IgnoreMissingTags) {
+        return;
+    }
+    for (i, group) in groups.iter().enumerate() {
+        for (j, tag) in group.tags.iter().enumerate() {
+            if !declared.contains(&tag.as_str()) {
+                ctx.error(
+                    path.index(i).field("tags").index(j),
+                    format_args!("`{}` not found in spec", tag),
+                );
+            }
+        }
+    }
+}
+
+pub fn validate_required_string<T>(s: &str, ctx: &mut Context<T>, path: Path) {
     if s.is_empty() {
         ctx.error(path, "must not be empty");
     }
 }
 
-pub fn validate_string_matches<T>(s: &str, pattern: &Regex, ctx: &mut Context<T>, path: String) {
+pub fn validate_string_matches<T>(s: &str, pattern: &Regex, ctx: &mut Context<T>, path: Path) {
     if !pattern.is_match(s) {
         ctx.error(
             path,
@@ -129,19 +429,253 @@ pub fn validate_optional_string_matches<T>(
     s: &Option<String>,
     pattern: &Regex,
     ctx: &mut Context<T>,
-    path: String,
+    path: Path,
 ) {
     if let Some(s) = s {
         validate_string_matches(s, pattern, ctx, path);
     }
 }
 
-pub fn validate_pattern<T>(pattern: &str, ctx: &mut Context<T>, path: String) {
-    match Regex::new(pattern) {
-        Ok(_) => {}
-        Err(e) => ctx.error(
-            path,
-            format_args!("pattern `{}` is invalid: {}", pattern, e),
-        ),
+pub fn validate_pattern<T>(pattern: &str, ctx: &mut Context<T>, path: Path) {
+    if let Err(e) = ctx.patterns.get(pattern) {
+        let msg = format!("pattern `{}` is invalid: {}", pattern, e);
+        ctx.error(path, msg);
+    }
+}
+
+/// Reports the custom string format if [Lints::UnknownFormats] is enabled,
+/// the custom formats are allowed by the specification, so they are not reported by default.
+pub fn validate_format<T>(format: &Option<StringFormat>, ctx: &mut Context<T>, path: Path) {
+    if let Some(format) = format {
+        if !format.is_standard() && ctx.is_lint(Lints::UnknownFormats) {
+            ctx.error(path, format_args!("unknown format `{}`", format));
+        }
+    }
+}
+
+/// Normalizes a path template by replacing the names of all templated segments with `{}`,
+/// so `/pets/{id}` and `/pets/{petId}` both become `/pets/{}`.
+pub fn normalize_path_template(path: &str) -> String {
+    let mut res = String::with_capacity(path.len());
+    let mut in_template = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_template = true;
+                res.push_str("{}");
+            }
+            '}' if in_template => in_template = false,
+            _ if in_template => {}
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_pattern() {
+        let mut ctx = Context::new(&(), EnumSet::empty());
+        for pattern in ["^[a-z]+$", "^[a-z]+$", "(", "("] {
+            validate_pattern(pattern, &mut ctx, Path::from("pattern"));
+        }
+        assert_eq!(ctx.patterns.len(), 2, "each pattern is compiled once");
+        assert_eq!(
+            ctx.errors.len(),
+            2,
+            "each invalid pattern is reported: {:?}",
+            ctx.errors
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_validate_each() {
+        let mut ctx = Context::new(&(), EnumSet::empty());
+        let items = (0..100).collect::<Vec<_>>();
+        validate_each(&mut ctx, &items, |ctx, i| {
+            ctx.error(Path::from("items").index(*i), "invalid");
+            if i % 10 == 5 && ctx.visit("shared".to_owned()) {
+                ctx.error(Path::from("shared"), "invalid");
+            }
+        });
+        let mut expected = (0..100)
+            .map(|i| format!("items[{}]: invalid", i))
+            .collect::<Vec<_>>();
+        expected.insert(6, "shared: invalid".to_owned());
+        assert_eq!(ctx.errors, expected);
+        assert!(ctx.is_visited("shared"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "parallel", any(feature = "v2", feature = "v3_0")))]
+    fn test_validate_in_threads() {
+        let validate = |ctx: &mut Context<()>, i: &usize| {
+            ctx.error(Path::from("items").index(*i), "invalid");
+            if i % 10 == 5 && ctx.visit("shared".to_owned()) {
+                ctx.error(Path::from("shared"), "invalid");
+            }
+            ctx.visit(format!("item{}", i));
+        };
+        let items = (0..100).collect::<Vec<_>>();
+        let mut sequential = Context::new(&(), EnumSet::empty());
+        for item in &items {
+            validate(&mut sequential, item);
+        }
+        for threads in [2, 3, 8] {
+            let mut ctx = Context::new(&(), EnumSet::empty());
+            validate_in_threads(&mut ctx, &items, &validate, threads);
+            assert_eq!(ctx.errors, sequential.errors, "{} threads", threads);
+            assert_eq!(ctx.visited, sequential.visited, "{} threads", threads);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_validate_tag_groups() {
+        let extensions = Some(Extensions::from([(
+            "x-tagGroups".to_owned(),
+            serde_json::json!([{"name": "Store", "tags": ["pets", "orders"]}]),
+        )]));
+        let validate = |options: EnumSet<Options>, lints: EnumSet<Lints>| {
+            let mut ctx = Context::new(&(), options).with_lints(lints);
+            validate_tag_groups(&extensions, &["pets"], &mut ctx);
+            ctx.errors
+        };
+        assert!(validate(Options::new(), Lints::new()).is_empty(), "opt-in");
+        assert_eq!(
+            validate(Options::new(), Lints::TagGroups.only()),
+            vec!["#.x-tagGroups[0].tags[1]: `orders` not found in spec"]
+        );
+        assert!(validate(Options::IgnoreMissingTags.only(), Lints::TagGroups.only()).is_empty());
+
+        let extensions = Some(Extensions::from([(
+            "x-tagGroups".to_owned(),
+            serde_json::json!({"name": "Store"}),
+        )]));
+        let mut ctx = Context::new(&(), Options::new()).with_lints(Lints::TagGroups.only());
+        validate_tag_groups(&extensions, &[], &mut ctx);
+        assert_eq!(ctx.errors.len(), 1, "{:?}", ctx.errors);
+        assert!(
+            ctx.errors[0].starts_with("#.x-tagGroups: invalid value: "),
+            "{:?}",
+            ctx.errors
+        );
+    }
+
+    #[test]
+    fn test_normalize_path_template() {
+        assert_eq!(normalize_path_template("/pets"), "/pets", "no templates");
+        assert_eq!(
+            normalize_path_template("/pets/{id}"),
+            normalize_path_template("/pets/{petId}"),
+            "same hierarchy with different names",
+        );
+        assert_eq!(
+            normalize_path_template("/pets/{id}/photos/{photoId}.{ext}"),
+            "/pets/{}/photos/{}.{}",
+            "multiple templates",
+        );
+        assert_ne!(
+            normalize_path_template("/pets/{id}"),
+            normalize_path_template("/pets/{id}/photos"),
+            "different hierarchy",
+        );
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com"), "host only");
+        assert!(is_url("http://example.com:8080/a?b#c"), "full url");
+        assert!(!is_url("example.com"), "no scheme");
+        assert!(!is_url("https://"), "no host");
+        assert!(!is_url("https:///path"), "empty host");
+        assert!(!is_url("https://example.com/a b"), "whitespace");
+    }
+
+    #[test]
+    fn test_is_uri() {
+        assert!(is_uri("https://example.com/pets"), "url");
+        assert!(is_uri("HTTPS://example.com"), "upper case scheme");
+        assert!(is_uri("urn:example:pet"), "urn");
+        assert!(is_uri("mailto:support@example.com"), "mailto");
+        assert!(is_uri("https://example.com/a%20b"), "percent-encoded");
+        assert!(!is_uri("https:///pets"), "url without host");
+        assert!(!is_uri("/pets"), "relative");
+        assert!(!is_uri("1urn:example"), "invalid scheme");
+        assert!(!is_uri("urn:a b"), "whitespace");
+        assert!(!is_uri("urn:a%2"), "truncated percent-encoding");
+        assert!(!is_uri("urn:<pet>"), "invalid characters");
+    }
+
+    #[test]
+    fn test_is_uri_reference() {
+        assert!(is_uri_reference("https://example.com"), "absolute");
+        assert!(is_uri_reference("/v1"), "absolute path");
+        assert!(is_uri_reference("../pets?limit=10"), "relative path");
+        assert!(is_uri_reference("//example.com/api"), "network path");
+        assert!(is_uri_reference("#/paths/~1pets~1{id}/get"), "fragment");
+        assert!(is_uri_reference("https://{env}.example.com"), "template");
+        assert!(
+            is_uri_reference("pets/a:b"),
+            "colon after the first segment"
+        );
+        assert!(!is_uri_reference("/pets/a b"), "whitespace");
+        assert!(!is_uri_reference("1a:b"), "invalid scheme");
+        assert!(!is_uri_reference("https://"), "url without host");
+    }
+
+    #[test]
+    fn test_validate_format() {
+        let formats = [
+            Some(StringFormat::Email),
+            Some(StringFormat::Custom("uriref".to_owned())),
+            None,
+        ];
+        let mut ctx = Context::new(&(), Options::new());
+        for format in &formats {
+            validate_format(format, &mut ctx, Path::from("format"));
+        }
+        assert!(ctx.errors.is_empty(), "not reported: {:?}", ctx.errors);
+
+        let mut ctx = Context::new(&(), Options::new()).with_lints(Lints::UnknownFormats.only());
+        for format in &formats {
+            validate_format(format, &mut ctx, Path::from("format"));
+        }
+        assert_eq!(ctx.errors, vec!["format: unknown format `uriref`"]);
+    }
+
+    #[test]
+    fn test_validate_extensions() {
+        let mut ctx = Context::new(&(), Options::new());
+        validate_extensions(&None, &mut ctx, Path::root());
+        validate_extensions(
+            &Some(Extensions::from([
+                ("x-foo".to_owned(), 1.into()),
+                ("foo".to_owned(), 2.into()),
+            ])),
+            &mut ctx,
+            Path::root().field("info"),
+        );
+        assert_eq!(
+            ctx.errors,
+            vec!["#.info.foo: extension name must start with `x-`"]
+        );
+    }
+
+    #[test]
+    fn test_is_email() {
+        assert!(is_email("support@example.com"), "simple");
+        assert!(is_email("first.last+tag@mail.example.co.uk"), "subdomains");
+        assert!(!is_email("example.com"), "no at");
+        assert!(!is_email("@example.com"), "no local part");
+        assert!(!is_email("support@"), "no domain");
+        assert!(!is_email("support@localhost"), "no dot in domain");
+        assert!(!is_email("support@example..com"), "empty label");
+        assert!(!is_email("a@b@example.com"), "two at");
+        assert!(!is_email("sup port@example.com"), "whitespace");
     }
 }
@@ -0,0 +1,230 @@
+//! HTTP helpers
+
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+/// The HTTP method of an operation.
+///
+/// The methods are stored in the path items as lowercase keys,
+/// a custom method is stored as is, e.g. `search` for `SEARCH`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Method {
+    Get,
+    Put,
+    Post,
+    Delete,
+    Options,
+    Head,
+    Patch,
+    /// Not supported by v2.0
+    Trace,
+    /// A custom HTTP method, like `SEARCH` or `QUERY`.
+    Custom(String),
+}
+
+impl Method {
+    /// Returns the key used for the method in a path item.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "get",
+            Method::Put => "put",
+            Method::Post => "post",
+            Method::Delete => "delete",
+            Method::Options => "options",
+            Method::Head => "head",
+            Method::Patch => "patch",
+            Method::Trace => "trace",
+            Method::Custom(s) => s.as_str(),
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<&str> for Method {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "get" => Method::Get,
+            "put" => Method::Put,
+            "post" => Method::Post,
+            "delete" => Method::Delete,
+            "options" => Method::Options,
+            "head" => Method::Head,
+            "patch" => Method::Patch,
+            "trace" => Method::Trace,
+            s => Method::Custom(s.to_owned()),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Method::from(s))
+    }
+}
+
+impl Serialize for Method {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Method {
+    fn deserialize<D>(deserializer: D) -> Result<Method, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MethodVisitor;
+
+        impl<'de> Visitor<'de> for MethodVisitor {
+            type Value = Method;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an HTTP method")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Method::from(value))
+            }
+        }
+
+        deserializer.deserialize_str(MethodVisitor)
+    }
+}
+
+/// StatusCodeError is an error type for the keys of the responses.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum StatusCodeError {
+    /// Invalid is returned when the value is neither a status code within [100..599] range
+    /// nor a range like `2XX`.
+    #[error(
+        "status code must be an integer within [100..599] range or a range like `2XX`, found `{0}`"
+    )]
+    Invalid(String),
+
+    /// UnsupportedRange is returned when the ranges are not supported, e.g. by v2.0.
+    #[error("status code range `{0}` is not supported")]
+    UnsupportedRange(StatusCode),
+}
+
+/// The key of a response, either an HTTP status code or a range like `2XX`.
+///
+/// A value created by [From] is not validated, see [StatusCode::validate].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusCode {
+    /// An HTTP status code, e.g. `404`.
+    Code(u16),
+
+    /// The first digit of a range of status codes, e.g. `2` for `2XX`.
+    /// Not supported by v2.0
+    Range(u8),
+}
+
+impl StatusCode {
+    /// Returns the status code if it is within [100..599] range
+    /// or the range is one of `1XX`, `2XX`, `3XX`, `4XX` and `5XX`.
+    pub fn validate(self) -> Result<Self, StatusCodeError> {
+        match self {
+            StatusCode::Code(100..=599) | StatusCode::Range(1..=5) => Ok(self),
+            _ => Err(StatusCodeError::Invalid(self.to_string())),
+        }
+    }
+}
+
+impl Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusCode::Code(code) => write!(f, "{}", code),
+            StatusCode::Range(range) => write!(f, "{}XX", range),
+        }
+    }
+}
+
+impl From<u16> for StatusCode {
+    fn from(value: u16) -> Self {
+        StatusCode::Code(value)
+    }
+}
+
+impl FromStr for StatusCode {
+    type Err = StatusCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let status = match s.as_bytes() {
+            [d @ b'0'..=b'9', b'X', b'X'] => StatusCode::Range(d - b'0'),
+            _ => StatusCode::Code(
+                s.parse()
+                    .map_err(|_| StatusCodeError::Invalid(s.to_owned()))?,
+            ),
+        };
+        status.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_from_str() {
+        assert_eq!(Method::from("GET"), Method::Get, "uppercase");
+        assert_eq!(Method::from("patch"), Method::Patch, "lowercase");
+        assert_eq!(
+            Method::from("SEARCH"),
+            Method::Custom("search".to_owned()),
+            "custom method"
+        );
+        assert_eq!(Method::Custom("search".to_owned()).to_string(), "search");
+    }
+
+    #[test]
+    fn test_method_serde() {
+        assert_eq!(
+            serde_json::to_value(Method::Delete).unwrap(),
+            serde_json::json!("delete"),
+            "serialize",
+        );
+        assert_eq!(
+            serde_json::from_value::<Method>(serde_json::json!("Trace")).unwrap(),
+            Method::Trace,
+            "deserialize",
+        );
+    }
+
+    #[test]
+    fn test_status_code() {
+        assert_eq!("404".parse(), Ok(StatusCode::Code(404)));
+        assert_eq!("2XX".parse(), Ok(StatusCode::Range(2)));
+        assert_eq!(StatusCode::Range(5).to_string(), "5XX");
+        assert_eq!(
+            "600".parse::<StatusCode>(),
+            Err(StatusCodeError::Invalid("600".to_owned()))
+        );
+        assert_eq!(
+            "6XX".parse::<StatusCode>(),
+            Err(StatusCodeError::Invalid("6XX".to_owned()))
+        );
+        assert_eq!(
+            "ok".parse::<StatusCode>(),
+            Err(StatusCodeError::Invalid("ok".to_owned()))
+        );
+        assert_eq!(
+            StatusCode::from(99).validate(),
+            Err(StatusCodeError::Invalid("99".to_owned()))
+        );
+    }
+}
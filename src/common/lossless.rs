@@ -0,0 +1,280 @@
+//! Lossless round trip.
+//!
+//! The objects of the specification keep only the fields defined by the specification
+//! and the extensions, any other field is dropped during deserialization.
+//! The module provides a wrapper that captures such fields into a side-channel map
+//! and re-emits them on serialization, so `parse → edit → serialize` does not lose data
+//! even for out-of-spec documents.
+//!
+//! The captured fields are keyed by a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901)
+//! to their location, for example `#/paths/~1pets/get/requred`.
+//! A captured field is re-emitted only if its parent object still exists after the edit
+//! and the field has not been added meanwhile.
+//! The items of the arrays are not identified by their positions:
+//! a field captured in an array item is re-emitted into the item that is equal to the original one,
+//! wherever it has been moved, and is dropped if the item has been changed or removed.
+//!
+//! Example:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use roas::common::lossless::Lossless;
+//!
+//! #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+//! struct Foo {
+//!     pub foo: String,
+//! }
+//!
+//! let value = serde_json::json!({"foo": "foo", "bar": "bar"});
+//! let mut foo = serde_json::from_value::<Lossless<Foo>>(value).unwrap();
+//! foo.value.foo = "baz".to_owned();
+//!
+//! assert_eq!(
+//!     serde_json::to_value(foo).unwrap(),
+//!     serde_json::json!({"foo": "baz", "bar": "bar"}),
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::common::path::{escape_token, unescape_token, Path, Segment};
+use crate::common::strict::walk_unknown;
+
+/// Lossless wraps an object and keeps the fields not supported by the object.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Lossless<T> {
+    /// The deserialized object.
+    pub value: T,
+
+    /// The fields that are not supported by the object, keyed by JSON Pointer.
+    pub unknown: BTreeMap<String, Value>,
+
+    /// The known content of the array items with the unknown fields, keyed by JSON Pointer,
+    /// to find the items after the arrays have been edited.
+    items: BTreeMap<String, Value>,
+}
+
+impl<T> Lossless<T> {
+    /// Create a new Lossless without unknown fields.
+    pub fn new(value: T) -> Self {
+        Lossless {
+            value,
+            unknown: BTreeMap::new(),
+            items: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the JSON Pointer of the parent object of the captured field in the value,
+    /// the indexes of the array items are replaced by the current positions of the items.
+    fn locate(&self, value: &Value, pointer: &str) -> Option<String> {
+        let mut value = value;
+        let mut original = String::from("#");
+        let mut located = String::new();
+        for token in pointer.strip_prefix('#')?.split('/').skip(1) {
+            original.push('/');
+            original.push_str(token);
+            value = match value {
+                Value::Object(map) => {
+                    located.push('/');
+                    located.push_str(token);
+                    map.get(unescape_token(token).as_ref())?
+                }
+                Value::Array(items) => {
+                    let item = self.items.get(&original)?;
+                    let index = token.parse::<usize>().ok();
+                    let index = match index.filter(|x| items.get(*x) == Some(item)) {
+                        Some(index) => index,
+                        None => items.iter().position(|x| x == item)?,
+                    };
+                    located.push('/');
+                    located.push_str(&index.to_string());
+                    &items[index]
+                }
+                _ => return None,
+            };
+        }
+        Some(located)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Lossless<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let original = Value::deserialize(deserializer)?;
+        let value = T::deserialize(&original).map_err(serde::de::Error::custom)?;
+        let known = serde_json::to_value(&value).map_err(serde::de::Error::custom)?;
+        let mut unknown = BTreeMap::new();
+        let mut items = BTreeMap::new();
+        walk_unknown(&original, &known, Path::root(), &mut |path, _, value| {
+            // keep the known content of the array items on the way to the field
+            let mut pointer = String::from("#");
+            let mut item = &known;
+            for segment in path.segments() {
+                match (segment, item) {
+                    (Segment::Index(index), Value::Array(x)) => {
+                        pointer.push_str(&format!("/{}", index));
+                        item = &x[*index];
+                        items.entry(pointer.clone()).or_insert_with(|| item.clone());
+                    }
+                    (Segment::Field(name), Value::Object(x)) => {
+                        pointer.push('/');
+                        pointer.push_str(&escape_token(name));
+                        match x.get(name.as_ref()) {
+                            Some(x) => item = x,
+                            None => break,
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            unknown.insert(format!("#{}", path.to_pointer()), value.clone());
+        });
+        Ok(Lossless {
+            value,
+            unknown,
+            items,
+        })
+    }
+}
+
+impl<T> Serialize for Lossless<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut value = serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?;
+        for (pointer, v) in &self.unknown {
+            let Some((parent, key)) = pointer.rsplit_once('/') else {
+                continue;
+            };
+            let Some(parent) = self.locate(&value, parent) else {
+                continue;
+            };
+            if let Some(Value::Object(parent)) = value.pointer_mut(&parent) {
+                parent
+                    .entry(unescape_token(key))
+                    .or_insert_with(|| v.clone());
+            }
+        }
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+    struct Foo {
+        pub foo: String,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bars: Option<BTreeMap<String, Foo>>,
+    }
+
+    #[test]
+    fn test_lossless_deserialize() {
+        let foo = serde_json::from_value::<Lossless<Foo>>(serde_json::json!({
+            "foo": "foo",
+            "baz": 42,
+            "bars": {
+                "/bar": {"foo": "bar", "baz": [1, 2]},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            foo.value,
+            serde_json::from_value::<Foo>(serde_json::json!({
+                "foo": "foo",
+                "bars": {
+                    "/bar": {"foo": "bar"},
+                },
+            }))
+            .unwrap(),
+            "known fields",
+        );
+        assert_eq!(
+            foo.unknown,
+            BTreeMap::from([
+                ("#/bars/~1bar/baz".to_owned(), serde_json::json!([1, 2])),
+                ("#/baz".to_owned(), serde_json::json!(42)),
+            ]),
+            "unknown fields",
+        );
+    }
+
+    #[test]
+    fn test_lossless_serialize() {
+        let value = serde_json::json!({
+            "foo": "foo",
+            "baz": 42,
+            "bars": {
+                "/bar": {"foo": "bar", "baz": [1, 2]},
+            },
+        });
+        assert_eq!(
+            serde_json::to_value(serde_json::from_value::<Lossless<Foo>>(value.clone()).unwrap())
+                .unwrap(),
+            value,
+            "round trip",
+        );
+
+        let mut foo = serde_json::from_value::<Lossless<Foo>>(value).unwrap();
+        foo.value.bars = None;
+        assert_eq!(
+            serde_json::to_value(foo).unwrap(),
+            serde_json::json!({
+                "foo": "foo",
+                "baz": 42,
+            }),
+            "removed parent",
+        );
+    }
+
+    #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+    struct Bar {
+        pub foos: Vec<Foo>,
+    }
+
+    #[test]
+    fn test_lossless_edited_array() {
+        let mut bar = serde_json::from_value::<Lossless<Bar>>(serde_json::json!({
+            "foos": [{"foo": "a"}, {"foo": "b", "baz": 42}],
+        }))
+        .unwrap();
+        assert_eq!(
+            bar.unknown,
+            BTreeMap::from([("#/foos/1/baz".to_owned(), serde_json::json!(42))]),
+        );
+
+        bar.value.foos.remove(0);
+        bar.value.foos.push(Foo {
+            foo: "c".to_owned(),
+            bars: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&bar).unwrap(),
+            serde_json::json!({"foos": [{"foo": "b", "baz": 42}, {"foo": "c"}]}),
+            "moved item",
+        );
+
+        bar.value.foos[0].foo = "d".to_owned();
+        assert_eq!(
+            serde_json::to_value(&bar).unwrap(),
+            serde_json::json!({"foos": [{"foo": "d"}, {"foo": "c"}]}),
+            "changed item",
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Media type matching
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::media_type::match_key;
+//!
+//! let keys = ["*/*", "text/*", "text/plain"];
+//! assert_eq!(match_key(keys, "text/plain; charset=utf-8"), Some("text/plain"));
+//! assert_eq!(match_key(keys, "text/html"), Some("text/*"));
+//! assert_eq!(match_key(keys, "image/png"), Some("*/*"));
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Returns the key of a content map, like the `content` of a request body or a response,
+/// that matches the actual content type.
+///
+/// The most specific key wins: `text/plain; charset=utf-8` over `text/plain`
+/// over `text/*` over `*/*`.
+/// The types and the parameter names are compared case-insensitively,
+/// a key with parameters matches only if the content type has all of them.
+/// The first key wins if several keys are equally specific.
+pub fn match_key<'a, I>(available_keys: I, content_type: &str) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let actual = MediaRange::parse(content_type)?;
+    let mut best: Option<(u8, &str)> = None;
+    for key in available_keys {
+        let Some(range) = MediaRange::parse(key) else {
+            continue;
+        };
+        let Some(score) = range.score(&actual) else {
+            continue;
+        };
+        if best.is_none_or(|(x, _)| score > x) {
+            best = Some((score, key));
+        }
+    }
+    best.map(|(_, key)| key)
+}
+
+/// Returns the entry of a content map that best matches the actual content type,
+/// see [match_key] for the matching rules.
+///
+/// Any typed media type, like `mime::Mime`, can be used via its string representation.
+pub fn best_match<'a, V>(
+    content: &'a BTreeMap<String, V>,
+    content_type: &str,
+) -> Option<(&'a str, &'a V)> {
+    let key = match_key(content.keys().map(String::as_str), content_type)?;
+    Some((key, &content[key]))
+}
+
+struct MediaRange {
+    kind: String,
+    subtype: String,
+    parameters: Vec<(String, String)>,
+}
+
+impl MediaRange {
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split(';');
+        let (kind, subtype) = parts.next()?.trim().split_once('/')?;
+        let parameters = parts
+            .filter_map(|x| x.split_once('='))
+            .map(|(k, v)| {
+                (
+                    k.trim().to_lowercase(),
+                    v.trim().trim_matches('"').to_lowercase(),
+                )
+            })
+            .collect();
+        Some(MediaRange {
+            kind: kind.trim().to_lowercase(),
+            subtype: subtype.trim().to_lowercase(),
+            parameters,
+        })
+    }
+
+    /// Returns how specific the range is for the content type, `None` if it does not match.
+    fn score(&self, actual: &MediaRange) -> Option<u8> {
+        if !self
+            .parameters
+            .iter()
+            .all(|x| actual.parameters.contains(x))
+        {
+            return None;
+        }
+        let score = match (self.kind.as_str(), self.subtype.as_str()) {
+            ("*", "*") => 1,
+            (kind, "*") if kind == actual.kind => 2,
+            (kind, subtype) if kind == actual.kind && subtype == actual.subtype => 3,
+            _ => return None,
+        };
+        Some(score * 2 + u8::from(!self.parameters.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_key() {
+        let keys = [
+            "*/*",
+            "text/*",
+            "text/plain",
+            "text/plain; charset=utf-8",
+            "application/json",
+        ];
+        for (content_type, expected) in [
+            (
+                "text/plain; charset=UTF-8",
+                Some("text/plain; charset=utf-8"),
+            ),
+            ("text/plain; charset=ascii", Some("text/plain")),
+            ("Text/Plain", Some("text/plain")),
+            ("text/html", Some("text/*")),
+            ("application/json; charset=utf-8", Some("application/json")),
+            ("image/png", Some("*/*")),
+            ("invalid", None),
+        ] {
+            assert_eq!(match_key(keys, content_type), expected, "{}", content_type);
+        }
+        assert_eq!(
+            match_key(["application/json"], "text/plain"),
+            None,
+            "no match"
+        );
+    }
+
+    #[test]
+    fn test_best_match() {
+        let content = BTreeMap::from([
+            ("application/*".to_owned(), 1),
+            ("application/json".to_owned(), 2),
+        ]);
+        assert_eq!(
+            best_match(&content, "application/json; charset=utf-8"),
+            Some(("application/json", &2))
+        );
+        assert_eq!(
+            best_match(&content, "application/xml"),
+            Some(("application/*", &1))
+        );
+        assert_eq!(best_match(&content, "text/plain"), None);
+    }
+}
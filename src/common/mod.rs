@@ -1,7 +1,30 @@
 //! Common Library
 
 pub mod bool_or;
+pub mod datetime;
 pub mod extensions;
 pub mod formats;
 pub mod helpers;
+pub mod http;
+pub mod lossless;
+pub mod media_type;
+pub mod normalize;
+pub mod operation_id;
+pub mod parse;
+pub mod partial;
+pub mod patch;
+pub mod path;
+pub mod paths;
 pub mod reference;
+pub mod schema;
+pub mod semantic;
+pub mod spec;
+pub mod stats;
+pub mod status;
+pub mod strict;
+pub mod url;
+#[cfg(feature = "vendor_ext")]
+pub mod vendor;
+#[cfg(any(feature = "capi", feature = "wasm"))]
+pub(crate) mod versioned;
+pub mod visit;
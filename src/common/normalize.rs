@@ -0,0 +1,262 @@
+//! Canonical form of the specifications
+//!
+//! The normalization rewrites a specification into a canonical form,
+//! so the semantic diffs and the content hashes of equivalent specifications are stable:
+//!
+//! * the maps are sorted by the keys, which the [BTreeMap] does already,
+//! * the methods of the operations are lower-cased,
+//! * the [TrailingSlash] policy is applied to the paths,
+//!   the normalization fails with [NormalizeError] if two paths become the same path,
+//! * the empty optional lists and maps are dropped, except the security requirements,
+//!   where an empty list has a meaning.
+//!
+//! The migration of `example` to `examples` applies to the v3.1 documents only,
+//! so none of the supported versions is affected.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::normalize::{NormalizeOptions, TrailingSlash};
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "tags": [],
+//!     "paths": {"/pets/": {"get": {"parameters": [], "responses": {}}}},
+//! })).unwrap();
+//!
+//! spec.normalize(NormalizeOptions {
+//!     trailing_slash: TrailingSlash::Remove,
+//! }).unwrap();
+//! assert_eq!(
+//!     serde_json::to_value(&spec).unwrap(),
+//!     serde_json::json!({
+//!         "openapi": "3.0.3",
+//!         "info": {"title": "foo", "version": "1"},
+//!         "paths": {"/pets": {"get": {"responses": {}}}},
+//!     }),
+//! );
+//! ```
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use std::collections::btree_map::Entry;
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use crate::common::paths::Paths;
+
+/// NormalizeError is an error type of the normalization.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum NormalizeError {
+    /// PathCollision is returned when two paths become the same path
+    /// after the trailing slash policy is applied.
+    #[error("paths `{path}` and `{other}` collide as `{normalized}`")]
+    PathCollision {
+        path: String,
+        other: String,
+        normalized: String,
+    },
+}
+
+/// Options of the normalization.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NormalizeOptions {
+    /// The policy of the trailing slashes of the paths.
+    pub trailing_slash: TrailingSlash,
+}
+
+/// The policy of the trailing slashes of the paths, the root path `/` is never changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// Keeps the paths as they are.
+    #[default]
+    Keep,
+
+    /// Removes the trailing slashes, e.g. `/pets/` becomes `/pets`.
+    Remove,
+
+    /// Adds a trailing slash, e.g. `/pets` becomes `/pets/`.
+    Add,
+}
+
+impl TrailingSlash {
+    /// Applies the policy to the path.
+    pub fn apply(&self, path: &str) -> String {
+        match self {
+            TrailingSlash::Remove if path.len() > 1 => match path.trim_end_matches('/') {
+                "" => "/".to_owned(),
+                trimmed => trimmed.to_owned(),
+            },
+            TrailingSlash::Add if !path.ends_with('/') => format!("{}/", path),
+            _ => path.to_owned(),
+        }
+    }
+}
+
+/// Applies the trailing slash policy to the paths.
+/// The paths are not changed if two of them collide.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn normalize_paths<T>(
+    paths: &mut Paths<T>,
+    policy: &TrailingSlash,
+) -> Result<(), NormalizeError> {
+    if *policy == TrailingSlash::Keep {
+        return Ok(());
+    }
+    let mut targets: BTreeMap<String, &String> = BTreeMap::new();
+    for path in paths.0.keys() {
+        match targets.entry(policy.apply(path)) {
+            Entry::Occupied(entry) => {
+                return Err(NormalizeError::PathCollision {
+                    path: entry.get().to_string(),
+                    other: path.clone(),
+                    normalized: entry.key().clone(),
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(path);
+            }
+        }
+    }
+    paths.0 = std::mem::take(&mut paths.0)
+        .into_iter()
+        .map(|(path, item)| (policy.apply(&path), item))
+        .collect();
+    Ok(())
+}
+
+/// Lower-cases the method names of the operations,
+/// an operation is dropped if the same lower-cased method is defined already.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn lowercase_methods<T>(operations: &mut Option<BTreeMap<String, T>>) {
+    if let Some(map) = operations {
+        if map.keys().any(|x| x.chars().any(|c| c.is_uppercase())) {
+            let mut lowercased = BTreeMap::new();
+            for (method, operation) in std::mem::take(map) {
+                lowercased.entry(method.to_lowercase()).or_insert(operation);
+            }
+            *map = lowercased;
+        }
+    }
+}
+
+/// A collection that is dropped by the normalization when it is empty.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) trait Collection {
+    fn is_empty(&self) -> bool;
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl<T> Collection for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl<K, V> Collection for BTreeMap<K, V> {
+    fn is_empty(&self) -> bool {
+        BTreeMap::is_empty(self)
+    }
+}
+
+/// Replaces an empty collection with `None`.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn drop_empty<T: Collection>(value: &mut Option<T>) {
+    if value.as_ref().is_some_and(Collection::is_empty) {
+        *value = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_slash() {
+        for (policy, path, expected) in [
+            (TrailingSlash::Keep, "/pets/", "/pets/"),
+            (TrailingSlash::Remove, "/pets/", "/pets"),
+            (TrailingSlash::Remove, "/pets", "/pets"),
+            (TrailingSlash::Remove, "/", "/"),
+            (TrailingSlash::Remove, "//", "/"),
+            (TrailingSlash::Add, "/pets", "/pets/"),
+            (TrailingSlash::Add, "/", "/"),
+        ] {
+            assert_eq!(policy.apply(path), expected, "{:?} {}", policy, path);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_normalize_paths() {
+        let mut paths = Paths::from(BTreeMap::from([
+            ("/pets".to_owned(), 1),
+            ("/users/".to_owned(), 2),
+            ("//".to_owned(), 3),
+        ]));
+        normalize_paths(&mut paths, &TrailingSlash::Remove).unwrap();
+        assert_eq!(
+            paths.0,
+            BTreeMap::from([
+                ("/".to_owned(), 3),
+                ("/pets".to_owned(), 1),
+                ("/users".to_owned(), 2),
+            ]),
+            "remove",
+        );
+
+        normalize_paths(&mut paths, &TrailingSlash::Add).unwrap();
+        assert_eq!(
+            paths.0,
+            BTreeMap::from([
+                ("/".to_owned(), 3),
+                ("/pets/".to_owned(), 1),
+                ("/users/".to_owned(), 2),
+            ]),
+            "add",
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_normalize_paths_collision() {
+        for policy in [TrailingSlash::Add, TrailingSlash::Remove] {
+            let original = BTreeMap::from([("/pets".to_owned(), 1), ("/pets/".to_owned(), 2)]);
+            let mut paths = Paths::from(original.clone());
+            assert_eq!(
+                normalize_paths(&mut paths, &policy)
+                    .unwrap_err()
+                    .to_string(),
+                format!(
+                    "paths `/pets` and `/pets/` collide as `{}`",
+                    policy.apply("/pets")
+                ),
+                "{:?}",
+                policy,
+            );
+            assert_eq!(paths.0, original, "{:?}: paths are not changed", policy);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_lowercase_methods() {
+        let mut operations = Some(BTreeMap::from([
+            ("GET".to_owned(), 1),
+            ("Post".to_owned(), 2),
+        ]));
+        lowercase_methods(&mut operations);
+        assert_eq!(
+            operations,
+            Some(BTreeMap::from([
+                ("get".to_owned(), 1),
+                ("post".to_owned(), 2),
+            ])),
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Generation of the missing `operationId`s
+//!
+//! The SDK generators require an `operationId` for each operation,
+//! [OperationIdStrategy] builds it from the method and the path of the operation.
+//! A generated id taken by another operation gets a numeric suffix starting with `2`,
+//! e.g. `getPets2`.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::operation_id::CamelCase;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets/{id}": {"get": {"responses": {}}},
+//!     },
+//! })).unwrap();
+//!
+//! assert_eq!(spec.ensure_operation_ids(CamelCase), 1);
+//! assert!(spec.operation_by_id("getPetsById").is_some());
+//! ```
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use std::collections::HashSet;
+
+use crate::common::http::Method;
+
+/// Builds an `operationId` from the method and the path of an operation.
+///
+/// Implemented for the closures, so a custom strategy can be passed as is:
+///
+/// ```rust
+/// use roas::common::http::Method;
+/// use roas::common::operation_id::OperationIdStrategy;
+///
+/// let strategy = |method: &Method, path: &str| format!("{} {}", method, path);
+/// assert_eq!(strategy.operation_id(&Method::Get, "/pets"), "get /pets");
+/// ```
+pub trait OperationIdStrategy {
+    /// Returns the `operationId` for the operation.
+    fn operation_id(&self, method: &Method, path: &str) -> String;
+}
+
+impl<F: Fn(&Method, &str) -> String> OperationIdStrategy for F {
+    fn operation_id(&self, method: &Method, path: &str) -> String {
+        self(method, path)
+    }
+}
+
+/// Joins the method and the path segments in camel case,
+/// a path parameter is prefixed with `By`, e.g. `get /pets/{id}` becomes `getPetsById`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CamelCase;
+
+impl OperationIdStrategy for CamelCase {
+    fn operation_id(&self, method: &Method, path: &str) -> String {
+        let mut id = method.as_str().to_lowercase();
+        for word in words(path) {
+            id.push_str(&capitalize(&word));
+        }
+        id
+    }
+}
+
+/// Joins the method and the path segments in snake case,
+/// a path parameter is prefixed with `by`, e.g. `get /pets/{id}` becomes `get_pets_by_id`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SnakeCase;
+
+impl OperationIdStrategy for SnakeCase {
+    fn operation_id(&self, method: &Method, path: &str) -> String {
+        let mut id = method.as_str().to_lowercase();
+        for word in words(path) {
+            id.push('_');
+            id.push_str(&word.to_lowercase());
+        }
+        id
+    }
+}
+
+/// Splits the path into the words, the path parameters are prefixed with `by`.
+fn words(path: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for segment in path.split('/') {
+        if segment.starts_with('{') {
+            words.push("by".to_owned());
+        }
+        words.extend(
+            segment
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|x| !x.is_empty())
+                .map(str::to_owned),
+        );
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Generates the unique `operationId`s.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) struct OperationIds<'s, S> {
+    strategy: &'s S,
+    used: HashSet<String>,
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl<'s, S: OperationIdStrategy> OperationIds<'s, S> {
+    /// Creates the generator, the existing ids are never generated.
+    pub(crate) fn new<'a>(strategy: &'s S, existing: impl Iterator<Item = &'a str>) -> Self {
+        OperationIds {
+            strategy,
+            used: existing.map(str::to_owned).collect(),
+        }
+    }
+
+    /// Returns a new unique `operationId` for the operation.
+    pub(crate) fn generate(&mut self, method: &Method, path: &str) -> String {
+        let base = self.strategy.operation_id(method, path);
+        let mut id = base.clone();
+        let mut n = 2;
+        while self.used.contains(&id) {
+            id = format!("{}{}", base, n);
+            n += 1;
+        }
+        self.used.insert(id.clone());
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategies() {
+        for (method, path, camel, snake) in [
+            (Method::Get, "/pets", "getPets", "get_pets"),
+            (Method::Get, "/pets/{id}", "getPetsById", "get_pets_by_id"),
+            (
+                Method::Post,
+                "/users/{user_id}/pet-tags",
+                "postUsersByUserIdPetTags",
+                "post_users_by_user_id_pet_tags",
+            ),
+            (Method::Delete, "/", "delete", "delete"),
+            (
+                Method::Custom("search".to_owned()),
+                "/v1/files.json",
+                "searchV1FilesJson",
+                "search_v1_files_json",
+            ),
+        ] {
+            assert_eq!(CamelCase.operation_id(&method, path), camel, "{}", path);
+            assert_eq!(SnakeCase.operation_id(&method, path), snake, "{}", path);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_generate() {
+        let mut ids = OperationIds::new(&CamelCase, ["getPets"].into_iter());
+        assert_eq!(ids.generate(&Method::Get, "/pets"), "getPets2");
+        assert_eq!(ids.generate(&Method::Get, "/pets/"), "getPets3");
+        assert_eq!(ids.generate(&Method::Get, "/users"), "getUsers");
+    }
+}
@@ -0,0 +1,647 @@
+//! Deserialization with the location of the errors
+//!
+//! The errors of serde do not tell where in the document the deserialization failed,
+//! so a wrong value deep in a large specification is hard to find.
+//! The functions of the module deserialize the value as usual and, only if it fails,
+//! repeat the deserialization while tracking the path to the failed value.
+//! The path is displayed in the notation of the validation errors,
+//! e.g. `#.paths[/pets].get.responses.200.content[application/json].schema.items`:
+//! the fields of the objects are separated by dots and the keys of the maps are in brackets.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::parse::from_str;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let raw = r#"{
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "Pets", "version": "1"},
+//!     "paths": {
+//!         "/pets": {
+//!             "get": {
+//!                 "responses": {
+//!                     "200": {
+//!                         "description": "OK",
+//!                         "content": {
+//!                             "application/json": {
+//!                                 "schema": {"type": "array", "items": {"type": "string", "maxLength": "ten"}}
+//!                             }
+//!                         }
+//!                     }
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }"#;
+//! let err = from_str::<Spec>(raw).unwrap_err();
+//! assert_eq!(
+//!     err.path.to_string(),
+//!     "#.paths[/pets].get.responses.200.content[application/json].schema.items.maxLength",
+//! );
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::io::Read;
+
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::path::{Path, Segment};
+
+/// ParseError is returned when a document cannot be deserialized.
+#[derive(Debug, Error)]
+#[error("{path}: {source}")]
+pub struct ParseError {
+    /// The location of the value that failed, the root of the document for the syntax errors.
+    pub path: Path,
+
+    /// The error of the deserialization.
+    pub source: serde_json::Error,
+}
+
+/// Deserializes the value, the error contains the location of the failed value.
+pub fn from_value<T: DeserializeOwned>(value: &Value) -> Result<T, ParseError> {
+    T::deserialize(value).map_err(|source| ParseError {
+        path: locate_error::<T>(value),
+        source,
+    })
+}
+
+/// Deserializes the string, the error contains the location of the failed value.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, ParseError> {
+    serde_json::from_str(s).map_err(|source| ParseError {
+        path: serde_json::from_str::<Value>(s)
+            .map(|value| locate_error::<T>(&value))
+            .unwrap_or_else(|_| Path::root()),
+        source,
+    })
+}
+
+/// Deserializes the JSON document from the reader without buffering the whole document,
+/// the failed value cannot be located after it is consumed,
+/// so the line and the column of the error are the only location.
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, ParseError> {
+    serde_json::from_reader(reader).map_err(|source| ParseError {
+        path: Path::root(),
+        source,
+    })
+}
+
+thread_local! {
+    /// Set while the failed deserialization is repeated to find the location of the error.
+    static LOCATING: Cell<bool> = const { Cell::new(false) };
+
+    /// The error of the last buffered value and its location relative to the value.
+    static BUFFERED: RefCell<Option<(String, Path)>> = const { RefCell::new(None) };
+}
+
+/// Deserializes the value collected by a type that chooses its variant by the fields,
+/// like [crate::common::reference::RefOr], so the location of an error inside of the value is not lost.
+pub(crate) fn from_buffered<T: DeserializeOwned, E: Error>(value: Value) -> Result<T, E> {
+    if !LOCATING.with(Cell::get) {
+        return T::deserialize(value).map_err(E::custom);
+    }
+    locate(&value, Path::new("")).map_err(|(err, path)| {
+        let message = err.to_string();
+        BUFFERED.with(|x| *x.borrow_mut() = Some((message.clone(), path)));
+        E::custom(message)
+    })
+}
+
+/// Deserializes the fields collected by a type that chooses its variant by the fields,
+/// like the schemas, see [from_buffered].
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn from_map<T: DeserializeOwned, E: Error>(
+    map: serde_json::Map<String, Value>,
+) -> Result<T, E> {
+    from_buffered(Value::Object(map))
+}
+
+/// Repeats the failed deserialization of the value and returns the location of the error.
+fn locate_error<T: DeserializeOwned>(value: &Value) -> Path {
+    LOCATING.with(|x| x.set(true));
+    let path = locate::<T>(value, Path::root())
+        .err()
+        .map_or_else(Path::root, |(_, path)| path);
+    LOCATING.with(|x| x.set(false));
+    BUFFERED.with(|x| x.borrow_mut().take());
+    path
+}
+
+/// Deserializes the value while tracking the location of the error.
+fn locate<T: DeserializeOwned>(value: &Value, root: Path) -> Result<T, (serde_json::Error, Path)> {
+    let track = Track::default();
+    T::deserialize(Tracked {
+        inner: value,
+        value,
+        path: root.clone(),
+        track: &track,
+    })
+    .map_err(|err| {
+        track.fail(&root, &err);
+        let path = track.path.into_inner().unwrap_or(root);
+        (err, path)
+    })
+}
+
+#[derive(Default)]
+struct Track {
+    path: RefCell<Option<Path>>,
+}
+
+impl Track {
+    /// Keeps the location of the innermost failed value,
+    /// extended by the location inside of the buffered value that produced the same error.
+    fn fail<E: Error>(&self, path: &Path, err: &E) {
+        let mut location = self.path.borrow_mut();
+        if location.is_some() {
+            return;
+        }
+        let mut path = path.clone();
+        if let Some((message, inner)) = BUFFERED.with(|x| x.borrow_mut().take()) {
+            if message == err.to_string() {
+                for segment in inner.segments() {
+                    path = match segment {
+                        Segment::Root(_) => path,
+                        Segment::Field(name) => path.field(name.clone()),
+                        Segment::Index(index) => path.index(*index),
+                        Segment::Item(key) => path.item(key),
+                    };
+                }
+            }
+        }
+        *location = Some(path);
+    }
+}
+
+/// Wraps the deserializer of the given value.
+struct Tracked<'a, D> {
+    inner: D,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+impl<'a, D> Tracked<'a, D> {
+    fn visitor<V>(&self, visitor: V) -> TrackedVisitor<'a, V> {
+        TrackedVisitor {
+            inner: visitor,
+            value: self.value,
+            path: self.path.clone(),
+            track: self.track,
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                let visitor = self.visitor(visitor);
+                self.inner
+                    .$method($($arg,)* visitor)
+                    .inspect_err(|err| self.track.fail(&self.path, err))
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Tracked<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Wraps the seed of a nested value.
+struct TrackedSeed<'a, S> {
+    inner: S,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TrackedSeed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let path = self.path.clone();
+        self.inner
+            .deserialize(Tracked {
+                inner: deserializer,
+                value: self.value,
+                path: self.path,
+                track: self.track,
+            })
+            .inspect_err(|err| self.track.fail(&path, err))
+    }
+}
+
+struct TrackedVisitor<'a, V> {
+    inner: V,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: Error>(self, v: $ty) -> Result<V::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TrackedVisitor<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_none<E: Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.inner.visit_some(Tracked {
+            inner: deserializer,
+            value: self.value,
+            path: self.path,
+            track: self.track,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(Tracked {
+            inner: deserializer,
+            value: self.value,
+            path: self.path,
+            track: self.track,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_seq(TrackedSeq {
+            inner: seq,
+            items: self.value.as_array().map(|x| x.iter()),
+            index: 0,
+            value: self.value,
+            path: self.path,
+            track: self.track,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        // the visitors of the structs, including the custom ones, expect `struct Name`
+        let fields = Expecting(&self.inner).to_string().starts_with("struct ");
+        self.inner.visit_map(TrackedMap {
+            inner: map,
+            entries: self.value.as_object().map(|x| x.iter()),
+            fields,
+            current: None,
+            value: self.value,
+            path: self.path,
+            track: self.track,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        // the content of a variant is the only value of an object, a unit variant is a string
+        let (value, path) = match self.value.as_object().and_then(|x| x.iter().next()) {
+            Some((key, value)) => (value, self.path.field(key.clone())),
+            None => (self.value, self.path),
+        };
+        self.inner.visit_enum(TrackedEnum {
+            inner: data,
+            value,
+            path,
+            track: self.track,
+        })
+    }
+}
+
+struct TrackedSeq<'a, A> {
+    inner: A,
+    items: Option<std::slice::Iter<'a, Value>>,
+    index: usize,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for TrackedSeq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        let (value, path) = match self.items.as_mut().and_then(Iterator::next) {
+            Some(value) => (value, self.path.index(self.index)),
+            None => (self.value, self.path.clone()),
+        };
+        self.index += 1;
+        self.inner.next_element_seed(TrackedSeed {
+            inner: seed,
+            value,
+            path,
+            track: self.track,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// Displays what the visitor expects.
+struct Expecting<'a, V>(&'a V);
+
+impl<'de, V: Visitor<'de>> fmt::Display for Expecting<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(f)
+    }
+}
+
+struct TrackedMap<'a, A> {
+    inner: A,
+    entries: Option<serde_json::map::Iter<'a>>,
+    /// `true` if the keys are the fields of an object, not the keys of a map.
+    fields: bool,
+    current: Option<(&'a Value, Path)>,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TrackedMap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        self.current = self
+            .entries
+            .as_mut()
+            .and_then(Iterator::next)
+            .map(|(key, value)| {
+                let path = if self.fields {
+                    self.path.field(key.clone())
+                } else {
+                    self.path.item(key)
+                };
+                (value, path)
+            });
+        self.inner
+            .next_key_seed(seed)
+            .inspect_err(|err| self.track.fail(&self.path, err))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        let (value, path) = self
+            .current
+            .take()
+            .unwrap_or_else(|| (self.value, self.path.clone()));
+        self.inner.next_value_seed(TrackedSeed {
+            inner: seed,
+            value,
+            path,
+            track: self.track,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct TrackedEnum<'a, A> {
+    inner: A,
+    value: &'a Value,
+    path: Path,
+    track: &'a Track,
+}
+
+impl<'a, 'de, A: EnumAccess<'de>> EnumAccess<'de> for TrackedEnum<'a, A> {
+    type Error = A::Error;
+    type Variant = TrackedEnum<'a, A::Variant>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), A::Error> {
+        let (variant, access) = self
+            .inner
+            .variant_seed(seed)
+            .inspect_err(|err| self.track.fail(&self.path, err))?;
+        Ok((
+            variant,
+            TrackedEnum {
+                inner: access,
+                value: self.value,
+                path: self.path,
+                track: self.track,
+            },
+        ))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for TrackedEnum<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, A::Error> {
+        self.inner.newtype_variant_seed(TrackedSeed {
+            inner: seed,
+            value: self.value,
+            path: self.path,
+            track: self.track,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.inner.tuple_variant(
+            len,
+            TrackedVisitor {
+                inner: visitor,
+                value: self.value,
+                path: self.path,
+                track: self.track,
+            },
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.inner.struct_variant(
+            fields,
+            TrackedVisitor {
+                inner: visitor,
+                value: self.value,
+                path: self.path,
+                track: self.track,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        name: String,
+        bars: Option<Vec<Bar>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Bar {
+        values: std::collections::BTreeMap<String, u8>,
+    }
+
+    #[test]
+    fn test_from_value() {
+        let err = from_value::<Foo>(&serde_json::json!({
+            "name": "foo",
+            "bars": [{"values": {"a": 1}}, {"values": {"b": 2, "c": "3"}}],
+        }))
+        .unwrap_err();
+        assert_eq!(err.path.to_string(), "#.bars[1].values[c]");
+        assert_eq!(
+            err.to_string(),
+            "#.bars[1].values[c]: invalid type: string \"3\", expected u8"
+        );
+
+        let err = from_value::<Foo>(&serde_json::json!({"bars": []})).unwrap_err();
+        assert_eq!(err.to_string(), "#: missing field `name`", "missing field");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let err = from_str::<Foo>(r#"{"name": "foo", "bars": [{"values": []}]}"#).unwrap_err();
+        assert_eq!(err.path.to_string(), "#.bars[0].values");
+        assert_eq!(err.source.line(), 1, "the position is kept");
+
+        let err = from_reader::<_, Foo>(&br#"{"name": "foo", "bars": {}}"#[..]).unwrap_err();
+        assert_eq!(err.path.to_string(), "#", "the reader is consumed");
+        assert_eq!(err.source.column(), 25);
+
+        let err = from_str::<Foo>(r#"{"name": "#).unwrap_err();
+        assert_eq!(err.path.to_string(), "#", "syntax error");
+        assert!(err.source.is_eof());
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_buffered() {
+        use crate::common::reference::RefOr;
+        use crate::v3_0::schema::Schema;
+
+        let err = from_value::<Vec<RefOr<Schema>>>(&serde_json::json!([
+            {"$ref": "#/components/schemas/Pet"},
+            {"type": "object", "properties": {"tags": {"type": "array", "items": {"type": "string", "minLength": "one"}}}},
+        ]))
+        .unwrap_err();
+        assert_eq!(
+            err.path.to_string(),
+            "#[1].properties[tags].items.minLength"
+        );
+        assert!(
+            err.source
+                .to_string()
+                .starts_with("invalid type: string \"one\""),
+            "{}",
+            err.source
+        );
+        assert!(
+            !LOCATING.with(Cell::get) && BUFFERED.with(|x| x.borrow().is_none()),
+            "the state is reset"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Partial reading of the specifications
+//!
+//! Reads the selected sections of a specification from a reader,
+//! the rest of the document is parsed but skipped without being materialized,
+//! so indexing a lot of specifications needs a fraction of the memory of [serde_json::from_reader].
+//! The functions work with the documents of any version,
+//! the type of a section is chosen by the caller.
+//!
+//! The reader is not buffered, wrap it in a [std::io::BufReader] when reading a file.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::partial::{read_info, read_path_item, read_paths_index};
+//! use roas::v3_0::info::Info;
+//! use roas::v3_0::path_item::PathItem;
+//!
+//! let raw = br#"{
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "Pets", "version": "1"},
+//!     "paths": {
+//!         "/pets": {"get": {"responses": {}}},
+//!         "/pets/{id}": {"get": {"responses": {}}}
+//!     }
+//! }"#;
+//!
+//! let info = read_info::<_, Info>(&raw[..]).unwrap().unwrap();
+//! assert_eq!(info.title, "Pets");
+//! assert_eq!(read_paths_index(&raw[..]).unwrap(), vec!["/pets", "/pets/{id}"]);
+//! assert!(read_path_item::<_, PathItem>(&raw[..], "/pets").unwrap().is_some());
+//! ```
+
+use std::fmt;
+use std::io::Read;
+use std::marker::PhantomData;
+
+use serde::de::{DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::Deserializer;
+
+/// Reads the version of the specification, the `openapi` or the `swagger` field.
+pub fn read_version<R: Read>(reader: R) -> Result<Option<String>, serde_json::Error> {
+    read_fields(reader, &["openapi", "swagger"], PhantomData::<String>)
+}
+
+/// Reads the `info` section of the specification.
+pub fn read_info<R: Read, T: DeserializeOwned>(reader: R) -> Result<Option<T>, serde_json::Error> {
+    read_fields(reader, &["info"], PhantomData::<T>)
+}
+
+/// Reads the paths of the specification, without the path items.
+pub fn read_paths_index<R: Read>(reader: R) -> Result<Vec<String>, serde_json::Error> {
+    read_fields(reader, &["paths"], PathsIndex).map(Option::unwrap_or_default)
+}
+
+/// Reads the path item of the given path, `None` if the path is not defined.
+pub fn read_path_item<R: Read, T: DeserializeOwned>(
+    reader: R,
+    path: &str,
+) -> Result<Option<T>, serde_json::Error> {
+    read_fields(
+        reader,
+        &["paths"],
+        PathItem {
+            path,
+            item: PhantomData,
+        },
+    )
+    .map(Option::flatten)
+}
+
+/// Deserializes the value of the first of the top-level fields with the seed,
+/// all other values are skipped.
+fn read_fields<R, S, V>(reader: R, fields: &[&str], seed: S) -> Result<Option<V>, serde_json::Error>
+where
+    R: Read,
+    S: for<'de> DeserializeSeed<'de, Value = V>,
+{
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let value = deserializer.deserialize_map(Fields {
+        fields,
+        seed: Some(seed),
+    })?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+struct Fields<'f, S> {
+    fields: &'f [&'f str],
+    seed: Option<S>,
+}
+
+impl<'de, S: DeserializeSeed<'de>> Visitor<'de> for Fields<'_, S> {
+    type Value = Option<S::Value>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a specification object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match self.seed.take() {
+                Some(seed) if self.fields.contains(&key.as_str()) => {
+                    value = Some(map.next_value_seed(seed)?);
+                }
+                seed => {
+                    self.seed = seed;
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(value)
+    }
+}
+
+struct PathsIndex;
+
+impl<'de> DeserializeSeed<'de> for PathsIndex {
+    type Value = Vec<String>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PathsIndex {
+    type Value = Vec<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a paths object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut paths = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            map.next_value::<IgnoredAny>()?;
+            paths.push(key);
+        }
+        Ok(paths)
+    }
+}
+
+struct PathItem<'p, T> {
+    path: &'p str,
+    item: PhantomData<T>,
+}
+
+impl<'de, T: DeserializeOwned> DeserializeSeed<'de> for PathItem<'_, T> {
+    type Value = Option<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Visitor<'de> for PathItem<'_, T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a paths object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut item = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if item.is_none() && key == self.path {
+                item = Some(map.next_value::<T>()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &[u8] = br#"{
+        "swagger": "2.0",
+        "info": {"title": "Pets", "version": "1"},
+        "paths": {
+            "/pets": {"get": {"operationId": "listPets"}},
+            "/pets/{id}": {"get": {"operationId": "getPet"}}
+        },
+        "definitions": {"Pet": {"type": "object"}}
+    }"#;
+
+    #[test]
+    fn test_read_version() {
+        assert_eq!(read_version(RAW).unwrap(), Some("2.0".to_owned()));
+        assert_eq!(read_version(&b"{}"[..]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_info() {
+        assert_eq!(
+            read_info::<_, serde_json::Value>(RAW).unwrap(),
+            Some(serde_json::json!({"title": "Pets", "version": "1"})),
+        );
+    }
+
+    #[test]
+    fn test_read_paths() {
+        assert_eq!(read_paths_index(RAW).unwrap(), vec!["/pets", "/pets/{id}"]);
+        assert_eq!(
+            read_path_item::<_, serde_json::Value>(RAW, "/pets/{id}").unwrap(),
+            Some(serde_json::json!({"get": {"operationId": "getPet"}})),
+        );
+        assert_eq!(
+            read_path_item::<_, serde_json::Value>(RAW, "/users").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_document() {
+        assert!(
+            read_paths_index(&b"{\"paths\": {}"[..]).is_err(),
+            "truncated"
+        );
+        assert!(read_paths_index(&b"[]"[..]).is_err(), "not an object");
+        assert!(read_paths_index(&b"{} {}"[..]).is_err(), "trailing data");
+    }
+}
@@ -0,0 +1,404 @@
+//! JSON Patch and JSON Merge Patch.
+//!
+//! The module implements [JSON Patch](https://www.rfc-editor.org/rfc/rfc6902)
+//! and [JSON Merge Patch](https://www.rfc-editor.org/rfc/rfc7396),
+//! so overlay-style customizations can be applied to the typed objects of the specification.
+//! The typed object is serialized, patched and deserialized back,
+//! and it is not modified if the patch fails.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::patch::PatchOp;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//! })).unwrap();
+//! spec.apply_json_patch(&[PatchOp::Replace {
+//!     path: "/info/title".to_owned(),
+//!     value: serde_json::json!("bar"),
+//! }]).unwrap();
+//!
+//! assert_eq!(spec.info.title, "bar");
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::path::unescape_token;
+
+/// A single operation of a JSON Patch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Adds the value to an object or inserts it into an array.
+    Add { path: String, value: Value },
+
+    /// Removes the value.
+    Remove { path: String },
+
+    /// Replaces the existing value.
+    Replace { path: String, value: Value },
+
+    /// Removes the value at `from` and adds it to `path`.
+    Move { from: String, path: String },
+
+    /// Copies the value at `from` to `path`.
+    Copy { from: String, path: String },
+
+    /// Tests that the value is equal to the given one.
+    Test { path: String, value: Value },
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl PatchOp {
+    fn paths(&self) -> Vec<&String> {
+        match self {
+            PatchOp::Add { path, .. }
+            | PatchOp::Remove { path }
+            | PatchOp::Replace { path, .. }
+            | PatchOp::Test { path, .. } => vec![path],
+            PatchOp::Move { from, path } | PatchOp::Copy { from, path } => vec![from, path],
+        }
+    }
+
+    fn with_paths(&self, f: impl Fn(&str) -> String) -> PatchOp {
+        match self.clone() {
+            PatchOp::Add { path, value } => PatchOp::Add {
+                path: f(&path),
+                value,
+            },
+            PatchOp::Remove { path } => PatchOp::Remove { path: f(&path) },
+            PatchOp::Replace { path, value } => PatchOp::Replace {
+                path: f(&path),
+                value,
+            },
+            PatchOp::Move { from, path } => PatchOp::Move {
+                from: f(&from),
+                path: f(&path),
+            },
+            PatchOp::Copy { from, path } => PatchOp::Copy {
+                from: f(&from),
+                path: f(&path),
+            },
+            PatchOp::Test { path, value } => PatchOp::Test {
+                path: f(&path),
+                value,
+            },
+        }
+    }
+}
+
+/// PatchError is an error type for the patch application.
+#[derive(Debug, Error)]
+pub enum PatchError {
+    /// NotFound is returned when the path of an operation does not exist.
+    #[error("path `{0}` not found")]
+    NotFound(String),
+
+    /// InvalidPath is returned when the path of an operation is not a valid JSON Pointer
+    /// or cannot be used by the operation.
+    #[error("invalid path `{0}`")]
+    InvalidPath(String),
+
+    /// TestFailed is returned when the value of a test operation does not match.
+    #[error("test failed for path `{0}`")]
+    TestFailed(String),
+
+    /// Json is returned when the patched value cannot be deserialized or serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl PatchError {
+    /// Prepends the pointer of the patched subtree to the path of the error,
+    /// so the error points into the whole document.
+    pub(crate) fn with_root(self, root: &str) -> Self {
+        match self {
+            PatchError::NotFound(path) => PatchError::NotFound(format!("{}{}", root, path)),
+            PatchError::InvalidPath(path) => PatchError::InvalidPath(format!("{}{}", root, path)),
+            PatchError::TestFailed(path) => PatchError::TestFailed(format!("{}{}", root, path)),
+            PatchError::Json(e) => PatchError::Json(e),
+        }
+    }
+}
+
+/// Applies the JSON Patch to the value.
+///
+/// The operations are applied in order, the value is left partially patched on error.
+pub fn apply_patch(value: &mut Value, ops: &[PatchOp]) -> Result<(), PatchError> {
+    for op in ops {
+        match op {
+            PatchOp::Add { path, value: v } => add(value, path, v.clone())?,
+            PatchOp::Remove { path } => {
+                remove(value, path)?;
+            }
+            PatchOp::Replace { path, value: v } => match value.pointer_mut(path) {
+                Some(x) => *x = v.clone(),
+                None => return Err(PatchError::NotFound(path.clone())),
+            },
+            PatchOp::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(PatchError::InvalidPath(path.clone()));
+                }
+                let v = remove(value, from)?;
+                add(value, path, v)?;
+            }
+            PatchOp::Copy { from, path } => {
+                let v = value
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| PatchError::NotFound(from.clone()))?;
+                add(value, path, v)?;
+            }
+            PatchOp::Test { path, value: v } => match value.pointer(path) {
+                Some(x) if x == v => {}
+                Some(_) => return Err(PatchError::TestFailed(path.clone())),
+                None => return Err(PatchError::NotFound(path.clone())),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Applies the JSON Merge Patch to the value.
+pub fn merge_patch(value: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *value = patch.clone();
+        return;
+    };
+    if !value.is_object() {
+        *value = Value::Object(Default::default());
+    }
+    if let Value::Object(map) = value {
+        for (key, patch) in patch {
+            if patch.is_null() {
+                map.remove(key);
+            } else {
+                merge_patch(map.entry(key).or_insert(Value::Null), patch);
+            }
+        }
+    }
+}
+
+/// Applies the JSON Patch to the typed object.
+pub fn apply_patch_typed<T>(item: &mut T, ops: &[PatchOp]) -> Result<(), PatchError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let mut value = serde_json::to_value(&*item)?;
+    apply_patch(&mut value, ops)?;
+    *item = T::deserialize(value)?;
+    Ok(())
+}
+
+/// Applies the JSON Merge Patch to the typed object.
+pub fn merge_patch_typed<T>(item: &mut T, patch: &Value) -> Result<(), PatchError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let mut value = serde_json::to_value(&*item)?;
+    merge_patch(&mut value, patch);
+    *item = T::deserialize(value)?;
+    Ok(())
+}
+
+/// Returns the unescaped key of the `prefix` child shared by all operations,
+/// the pointer to that child and the operations with the paths relative to it,
+/// so only the affected subtree needs to be patched.
+///
+/// Returns `None` if the operations touch different children or the child itself.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn split_subtree(
+    ops: &[PatchOp],
+    prefix: &str,
+) -> Option<(String, String, Vec<PatchOp>)> {
+    let mut key: Option<&str> = None;
+    for path in ops.iter().flat_map(|x| x.paths()) {
+        let (child, rest) = path.strip_prefix(prefix)?.split_once('/')?;
+        if rest.is_empty() || key.is_some_and(|x| x != child) {
+            return None;
+        }
+        key = Some(child);
+    }
+    let key = key?;
+    let root = format!("{}{}", prefix, key);
+    let ops = ops
+        .iter()
+        .map(|op| op.with_paths(|x| x[root.len()..].to_owned()))
+        .collect();
+    Some((unescape_token(key).into_owned(), root, ops))
+}
+
+fn split_path(path: &str) -> Result<(&str, String), PatchError> {
+    match path.rsplit_once('/') {
+        Some((parent, key)) => Ok((parent, unescape_token(key).into_owned())),
+        None => Err(PatchError::InvalidPath(path.to_owned())),
+    }
+}
+
+fn add(value: &mut Value, path: &str, v: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *value = v;
+        return Ok(());
+    }
+    let (parent, key) = split_path(path)?;
+    match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(key, v);
+        }
+        Some(Value::Array(items)) if key == "-" => items.push(v),
+        Some(Value::Array(items)) => match key.parse::<usize>() {
+            Ok(i) if i <= items.len() => items.insert(i, v),
+            _ => return Err(PatchError::InvalidPath(path.to_owned())),
+        },
+        _ => return Err(PatchError::NotFound(path.to_owned())),
+    }
+    Ok(())
+}
+
+fn remove(value: &mut Value, path: &str) -> Result<Value, PatchError> {
+    let (parent, key) = split_path(path)?;
+    let removed = match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => map.remove(&key),
+        Some(Value::Array(items)) => match key.parse::<usize>() {
+            Ok(i) if i < items.len() => Some(items.remove(i)),
+            _ => None,
+        },
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::NotFound(path.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_op_serde() {
+        assert_eq!(
+            serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                {"op": "add", "path": "/foo", "value": 1},
+                {"op": "move", "from": "/foo", "path": "/bar"},
+            ]))
+            .unwrap(),
+            vec![
+                PatchOp::Add {
+                    path: "/foo".to_owned(),
+                    value: serde_json::json!(1),
+                },
+                PatchOp::Move {
+                    from: "/foo".to_owned(),
+                    path: "/bar".to_owned(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let mut value = serde_json::json!({"foo": {"a/b": [1, 3]}, "bar": true});
+        apply_patch(
+            &mut value,
+            &serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                {"op": "add", "path": "/foo/a~1b/1", "value": 2},
+                {"op": "add", "path": "/foo/a~1b/-", "value": 4},
+                {"op": "test", "path": "/bar", "value": true},
+                {"op": "replace", "path": "/bar", "value": false},
+                {"op": "copy", "from": "/bar", "path": "/baz"},
+                {"op": "move", "from": "/foo/a~1b", "path": "/items"},
+                {"op": "remove", "path": "/bar"},
+            ]))
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"foo": {}, "baz": false, "items": [1, 2, 3, 4]}),
+        );
+
+        for (op, err) in [
+            (
+                serde_json::json!({"op": "remove", "path": "/missing"}),
+                "path `/missing` not found",
+            ),
+            (
+                serde_json::json!({"op": "add", "path": "/items/9", "value": 1}),
+                "invalid path `/items/9`",
+            ),
+            (
+                serde_json::json!({"op": "test", "path": "/baz", "value": true}),
+                "test failed for path `/baz`",
+            ),
+            (
+                serde_json::json!({"op": "move", "from": "/foo", "path": "/foo/bar"}),
+                "invalid path `/foo/bar`",
+            ),
+        ] {
+            let op = serde_json::from_value::<PatchOp>(op).unwrap();
+            assert_eq!(
+                apply_patch(&mut value, std::slice::from_ref(&op))
+                    .unwrap_err()
+                    .to_string(),
+                err,
+                "{:?}",
+                op,
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let mut value = serde_json::json!({"a": "b", "c": {"d": "e", "f": "g"}, "h": [1]});
+        merge_patch(
+            &mut value,
+            &serde_json::json!({"a": "z", "c": {"f": null}, "h": {"i": 1}}),
+        );
+        assert_eq!(
+            value,
+            serde_json::json!({"a": "z", "c": {"d": "e"}, "h": {"i": 1}}),
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_split_subtree() {
+        let ops = serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+            {"op": "remove", "path": "/paths/~1pets/get"},
+            {"op": "copy", "from": "/paths/~1pets/put", "path": "/paths/~1pets/post"},
+        ]))
+        .unwrap();
+        assert_eq!(
+            split_subtree(&ops, "/paths/"),
+            Some((
+                "/pets".to_owned(),
+                "/paths/~1pets".to_owned(),
+                serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                    {"op": "remove", "path": "/get"},
+                    {"op": "copy", "from": "/put", "path": "/post"},
+                ]))
+                .unwrap(),
+            )),
+            "single path item",
+        );
+
+        let ops = serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+            {"op": "remove", "path": "/paths/~1pets/get"},
+            {"op": "remove", "path": "/paths/~1users/get"},
+        ]))
+        .unwrap();
+        assert_eq!(split_subtree(&ops, "/paths/"), None, "different path items");
+
+        let ops = serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+            {"op": "remove", "path": "/paths/~1pets"},
+        ]))
+        .unwrap();
+        assert_eq!(split_subtree(&ops, "/paths/"), None, "path item itself");
+    }
+}
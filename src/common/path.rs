@@ -0,0 +1,323 @@
+//! Locations of the objects in a document
+//!
+//! A [Path] is built segment by segment while the document is validated,
+//! the segments are shared with the parent path, so creating a nested path is cheap:
+//! it allocates the new segment only, the names of the fields and the indexes of the lists
+//! are stored without copying, the keys of the maps are interned per thread,
+//! so the repeated keys, e.g. the property names or the status codes, are allocated once,
+//! and the text of the path is built only when it is displayed.
+//! The path is displayed in the notation of the validation errors, e.g. `#.paths[/pets].get`,
+//! and can be converted to a JSON Pointer or used to look the object up in the document.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::path::Path;
+//!
+//! let path = Path::root().field("paths").item("/pets").field("get");
+//! assert_eq!(path.to_string(), "#.paths[/pets].get");
+//! assert_eq!(path.to_pointer(), "/paths/~1pets/get");
+//!
+//! let doc = serde_json::json!({"paths": {"/pets": {"get": {"responses": {}}}}});
+//! assert_eq!(path.lookup(&doc), Some(&serde_json::json!({"responses": {}})));
+//! ```
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+/// A segment of a [Path].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// The start of the path, e.g. `#` for the root of the document.
+    Root(Cow<'static, str>),
+
+    /// A field of an object, displayed as `.name`.
+    Field(Cow<'static, str>),
+
+    /// An item of a list, displayed as `[index]`.
+    Index(usize),
+
+    /// An item of a map, displayed as `[key]`.
+    Item(Arc<str>),
+}
+
+/// The location of an object in a document.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Path(Arc<Node>);
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Node {
+    parent: Option<Path>,
+    segment: Segment,
+}
+
+impl Path {
+    /// Creates a path starting with the given text.
+    pub fn new(root: impl Into<Cow<'static, str>>) -> Self {
+        Path(Arc::new(Node {
+            parent: None,
+            segment: Segment::Root(root.into()),
+        }))
+    }
+
+    /// Returns the path of the root of the document, `#`.
+    pub fn root() -> Self {
+        Path::new("#")
+    }
+
+    /// Returns the path of the field of the object.
+    pub fn field(&self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.push(Segment::Field(name.into()))
+    }
+
+    /// Returns the path of the item of the list.
+    pub fn index(&self, index: usize) -> Self {
+        self.push(Segment::Index(index))
+    }
+
+    /// Returns the path of the item of the map.
+    pub fn item(&self, key: impl Display) -> Self {
+        self.push(Segment::Item(intern(key)))
+    }
+
+    fn push(&self, segment: Segment) -> Self {
+        Path(Arc::new(Node {
+            parent: Some(self.clone()),
+            segment,
+        }))
+    }
+
+    /// Returns the segments from the root to the last one.
+    pub fn segments(&self) -> Vec<&Segment> {
+        let mut segments = Vec::new();
+        let mut node = Some(self);
+        while let Some(path) = node {
+            segments.push(&path.0.segment);
+            node = path.0.parent.as_ref();
+        }
+        segments.reverse();
+        segments
+    }
+
+    /// Returns the JSON Pointer of the path relative to the root, e.g. `/paths/~1pets/get`.
+    pub fn to_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in self.segments() {
+            let token = match segment {
+                Segment::Root(_) => continue,
+                Segment::Field(name) => name.as_ref(),
+                Segment::Index(index) => {
+                    pointer.push_str(&format!("/{}", index));
+                    continue;
+                }
+                Segment::Item(key) => key.as_ref(),
+            };
+            pointer.push('/');
+            pointer.push_str(&escape_token(token));
+        }
+        pointer
+    }
+
+    /// Returns the value at the path, the root segment stands for the given value.
+    pub fn lookup<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.segments()
+            .into_iter()
+            .try_fold(value, |value, segment| match segment {
+                Segment::Root(_) => Some(value),
+                Segment::Field(name) => value.get(name.as_ref()),
+                Segment::Index(index) => value.get(index),
+                Segment::Item(key) => match value {
+                    Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+                    _ => value.get(key.as_ref()),
+                },
+            })
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for segment in self.segments() {
+            match segment {
+                Segment::Root(root) => write!(f, "{}", root)?,
+                Segment::Field(name) => write!(f, ".{}", name)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+                Segment::Item(key) => write!(f, "[{}]", key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escapes the reference token of a JSON Pointer, `~` as `~0` and `/` as `~1`.
+pub(crate) fn escape_token(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Unescapes the reference token of a JSON Pointer, `~1` as `/` and `~0` as `~`.
+pub(crate) fn unescape_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// The most map keys interned per thread, the rarer keys are allocated on every use.
+const INTERNED_KEYS: usize = 4096;
+
+type Keys = HashSet<Arc<str>, BuildHasherDefault<KeyHasher>>;
+
+thread_local! {
+    /// The buffer to format the keys and the interned keys.
+    static KEYS: RefCell<(String, Keys)> = RefCell::default();
+}
+
+/// The FNV-1a hasher, faster than the default one for the short keys,
+/// the set of the keys is bounded, so the collisions cannot grow it.
+struct KeyHasher(u64);
+
+impl Default for KeyHasher {
+    fn default() -> Self {
+        KeyHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// Returns the shared text of the key, allocating it only when the key is seen first.
+fn intern(key: impl Display) -> Arc<str> {
+    KEYS.with_borrow_mut(|(buffer, keys)| {
+        buffer.clear();
+        let _ = write!(buffer, "{}", key);
+        if let Some(key) = keys.get(buffer.as_str()) {
+            return key.clone();
+        }
+        let key = Arc::<str>::from(buffer.as_str());
+        if keys.len() < INTERNED_KEYS {
+            keys.insert(key.clone());
+        }
+        key
+    })
+}
+
+impl From<&str> for Path {
+    fn from(value: &str) -> Self {
+        Path::new(value.to_owned())
+    }
+}
+
+impl From<String> for Path {
+    fn from(value: String) -> Self {
+        Path::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Path::root().to_string(), "#");
+        assert_eq!(
+            Path::root()
+                .field("components")
+                .field("schemas")
+                .item("Pet")
+                .field("properties")
+                .field("name".to_owned())
+                .to_string(),
+            "#.components.schemas[Pet].properties.name",
+        );
+        assert_eq!(Path::from("schema").index(1).to_string(), "schema[1]");
+    }
+
+    #[test]
+    fn test_intern() {
+        let first = Path::root().item("Pet");
+        let second = Path::root().field("schemas").item(String::from("Pet"));
+        match (first.segments()[1], second.segments()[2]) {
+            (Segment::Item(first), Segment::Item(second)) => {
+                assert!(Arc::ptr_eq(first, second), "the key is shared");
+            }
+            segments => panic!("unexpected segments: {:?}", segments),
+        }
+    }
+
+    #[test]
+    fn test_to_pointer() {
+        assert_eq!(Path::root().to_pointer(), "");
+        assert_eq!(
+            Path::root()
+                .field("paths")
+                .item("/pets/{id}")
+                .field("get")
+                .to_pointer(),
+            "/paths/~1pets~1{id}/get",
+        );
+        assert_eq!(Path::root().item("a~b").to_pointer(), "/a~0b");
+        assert_eq!(
+            Path::root().field("servers").index(0).to_pointer(),
+            "/servers/0"
+        );
+    }
+
+    #[test]
+    fn test_escape_token() {
+        for (token, escaped) in [
+            ("pets", "pets"),
+            ("/pets/{id}", "~1pets~1{id}"),
+            ("a~/b", "a~0~1b"),
+        ] {
+            assert_eq!(escape_token(token), escaped);
+            assert_eq!(unescape_token(escaped), token);
+        }
+        assert_eq!(unescape_token("~01"), "~1", "unescaped once");
+    }
+
+    #[test]
+    fn test_lookup() {
+        let doc = json!({
+            "servers": [{"url": "https://example.com"}],
+            "tags": {"0": "zero"},
+        });
+        assert_eq!(
+            Path::root()
+                .field("servers")
+                .index(0)
+                .field("url")
+                .lookup(&doc),
+            Some(&json!("https://example.com")),
+        );
+        assert_eq!(
+            Path::root().field("tags").item(0).lookup(&doc),
+            Some(&json!("zero")),
+        );
+        assert_eq!(Path::root().field("servers").index(1).lookup(&doc), None);
+        assert_eq!(Path::root().field("tags").index(0).lookup(&doc), None);
+        assert_eq!(Path::root().field("info").lookup(&doc), None);
+    }
+}
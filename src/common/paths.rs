@@ -0,0 +1,341 @@
+//! Paths Object
+//!
+//! The map of the relative paths to the individual endpoints and their operations,
+//! shared by all versions of the specification.
+//!
+//! Example:
+//!
+//! ```rust
+//! use std::collections::BTreeMap;
+//!
+//! use roas::common::paths::Paths;
+//!
+//! let paths = Paths::from(BTreeMap::from([
+//!     ("/pets/mine".to_owned(), "mine"),
+//!     ("/pets/{id}".to_owned(), "pet"),
+//! ]));
+//!
+//! assert_eq!(paths.get("/pets/{petId}"), None);
+//! assert_eq!(paths.get_equivalent("/pets/{petId}"), Some(&"pet"));
+//!
+//! let (template, params, item) = paths.match_request_path("/pets/42").unwrap();
+//! assert_eq!(template, "/pets/{id}");
+//! assert_eq!(params["id"], "42");
+//! assert_eq!(*item, "pet");
+//!
+//! let (template, _, _) = paths.match_request_path("/pets/mine").unwrap();
+//! assert_eq!(template, "/pets/mine");
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+/// The values of the path template expressions, keyed by the expression name.
+pub type PathParams = BTreeMap<String, String>;
+
+/// Holds the relative paths to the individual endpoints and their operations.
+/// The path is appended to the URL from the servers or the base path
+/// in order to construct the full URL.
+///
+/// The map is accessible directly by dereferencing, e.g. `get` looks the exact path up,
+/// the methods of the type take the path templating into account.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct Paths<T>(pub BTreeMap<String, T>);
+
+impl<T> Default for Paths<T> {
+    fn default() -> Self {
+        Paths(BTreeMap::new())
+    }
+}
+
+impl<T> Deref for Paths<T> {
+    type Target = BTreeMap<String, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Paths<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<BTreeMap<String, T>> for Paths<T> {
+    fn from(value: BTreeMap<String, T>) -> Self {
+        Paths(value)
+    }
+}
+
+impl<T> FromIterator<(String, T)> for Paths<T> {
+    fn from_iter<I: IntoIterator<Item = (String, T)>>(iter: I) -> Self {
+        Paths(BTreeMap::from_iter(iter))
+    }
+}
+
+impl<T> IntoIterator for Paths<T> {
+    type Item = (String, T);
+    type IntoIter = std::collections::btree_map::IntoIter<String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Paths<T> {
+    type Item = (&'a String, &'a T);
+    type IntoIter = std::collections::btree_map::Iter<'a, String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Paths<T> {
+    type Item = (&'a String, &'a mut T);
+    type IntoIter = std::collections::btree_map::IterMut<'a, String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+impl<T> Paths<T> {
+    /// Returns the path item by the exact path or by an equivalent templated path,
+    /// e.g. `/pets/{petId}` finds the item defined as `/pets/{id}`.
+    ///
+    /// The exact path is looked up first, the paths are scanned only for a missing templated path.
+    pub fn get_equivalent(&self, path: &str) -> Option<&T> {
+        self.key(path).and_then(|x| self.0.get(x))
+    }
+
+    /// Returns the mutable path item by the exact path or by an equivalent templated path.
+    pub fn get_equivalent_mut(&mut self, path: &str) -> Option<&mut T> {
+        let key = self.key(path)?.to_owned();
+        self.0.get_mut(&key)
+    }
+
+    /// Matches the request path, without the server URL or the base path,
+    /// against the path templates and returns the matched template,
+    /// the values of the template expressions and the path item.
+    ///
+    /// The concrete paths are matched before their templated counterparts,
+    /// so the template with the fewest expressions wins.
+    /// The query string and the fragment of the request path are ignored,
+    /// the values are not percent-decoded.
+    pub fn match_request_path(&self, path: &str) -> Option<(&str, PathParams, &T)> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut best: Option<(usize, &str, PathParams, &T)> = None;
+        for (template, item) in &self.0 {
+            let Some(params) = match_template(template, path) else {
+                continue;
+            };
+            let expressions = template.matches('{').count();
+            if best.as_ref().is_none_or(|x| expressions < x.0) {
+                best = Some((expressions, template, params, item));
+            }
+        }
+        best.map(|(_, template, params, item)| (template, params, item))
+    }
+
+    fn key(&self, path: &str) -> Option<&str> {
+        if let Some((key, _)) = self.0.get_key_value(path) {
+            return Some(key);
+        }
+        // a path without templates is equivalent to itself only
+        if !path.contains('{') {
+            return None;
+        }
+        self.0
+            .keys()
+            .find(|x| is_equivalent(x, path))
+            .map(|x| x.as_str())
+    }
+}
+
+/// Returns `true` if the paths are equal, except for the names of the template expressions,
+/// like comparing the paths normalized by [crate::common::helpers::normalize_path_template],
+/// but without allocating.
+fn is_equivalent(a: &str, b: &str) -> bool {
+    let mut a = a.split('{');
+    let mut b = b.split('{');
+    // the first parts are literals, the other parts start with an expression name
+    if a.next() != b.next() {
+        return false;
+    }
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) => {
+                let a = a.split_once('}').map_or("", |(_, rest)| rest);
+                let b = b.split_once('}').map_or("", |(_, rest)| rest);
+                if a != b {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+enum Part<'a> {
+    Literal(&'a str),
+    Expression(&'a str),
+}
+
+fn parse_segment(segment: &str) -> Option<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = segment;
+    while !rest.is_empty() {
+        match rest.find('{') {
+            Some(0) => {
+                let end = rest.find('}')?;
+                parts.push(Part::Expression(&rest[1..end]));
+                rest = &rest[end + 1..];
+            }
+            Some(start) => {
+                parts.push(Part::Literal(&rest[..start]));
+                rest = &rest[start..];
+            }
+            None => {
+                parts.push(Part::Literal(rest));
+                rest = "";
+            }
+        }
+    }
+    Some(parts)
+}
+
+/// Matches the path against the template and returns the values of the template expressions.
+pub(crate) fn match_template(template: &str, path: &str) -> Option<PathParams> {
+    if template == path {
+        return Some(PathParams::new());
+    }
+    if !template.contains('{') || template.matches('/').count() != path.matches('/').count() {
+        return None;
+    }
+    let mut params = PathParams::new();
+    for (template, segment) in template.split('/').zip(path.split('/')) {
+        let parts = parse_segment(template)?;
+        if !match_parts(&parts, segment, &mut params) {
+            return None;
+        }
+    }
+    Some(params)
+}
+
+/// Matches the segment against the parts, an expression matches a non-empty value.
+fn match_parts(parts: &[Part], segment: &str, params: &mut PathParams) -> bool {
+    match parts.split_first() {
+        None => segment.is_empty(),
+        Some((Part::Literal(literal), rest)) => segment
+            .strip_prefix(literal)
+            .is_some_and(|segment| match_parts(rest, segment, params)),
+        Some((Part::Expression(name), [])) => {
+            if segment.is_empty() {
+                return false;
+            }
+            params.insert((*name).to_owned(), segment.to_owned());
+            true
+        }
+        Some((Part::Expression(name), rest)) => {
+            for (i, _) in segment.char_indices().skip(1) {
+                if match_parts(rest, &segment[i..], params) {
+                    params.insert((*name).to_owned(), segment[..i].to_owned());
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths() -> Paths<&'static str> {
+        Paths::from_iter(
+            [
+                "/pets",
+                "/pets/{id}",
+                "/pets/mine",
+                "/pets/{id}/photos/{photoId}",
+                "/files/{name}.{ext}",
+            ]
+            .map(|x| (x.to_owned(), x)),
+        )
+    }
+
+    #[test]
+    fn test_get() {
+        let paths = paths();
+        assert_eq!(paths.get_equivalent("/pets"), Some(&"/pets"), "exact path");
+        assert_eq!(
+            paths.get_equivalent("/pets/{petId}"),
+            Some(&"/pets/{id}"),
+            "equivalent template",
+        );
+        assert_eq!(paths.get("/pets/{petId}"), None, "exact lookup");
+        assert_eq!(
+            paths.get_equivalent("/files/{file}.{type}"),
+            Some(&"/files/{name}.{ext}"),
+            "several expressions",
+        );
+        assert_eq!(
+            paths.get_equivalent("/pets/{id}/photos"),
+            None,
+            "missing path"
+        );
+    }
+
+    #[test]
+    fn test_match_request_path() {
+        let paths = paths();
+        for (path, expected) in [
+            ("/pets", Some(("/pets", vec![]))),
+            ("/pets/42", Some(("/pets/{id}", vec![("id", "42")]))),
+            ("/pets/mine", Some(("/pets/mine", vec![]))),
+            ("/pets/42?limit=1", Some(("/pets/{id}", vec![("id", "42")]))),
+            (
+                "/pets/42/photos/7",
+                Some((
+                    "/pets/{id}/photos/{photoId}",
+                    vec![("id", "42"), ("photoId", "7")],
+                )),
+            ),
+            (
+                "/files/archive.tar.gz",
+                Some((
+                    "/files/{name}.{ext}",
+                    vec![("ext", "tar.gz"), ("name", "archive")],
+                )),
+            ),
+            ("/pets/", None),
+            ("/pets/42/photos", None),
+            ("/files/archive", None),
+        ] {
+            let expected = expected.map(|(template, params)| {
+                (
+                    template,
+                    params
+                        .into_iter()
+                        .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                        .collect::<PathParams>(),
+                )
+            });
+            assert_eq!(
+                paths
+                    .match_request_path(path)
+                    .map(|(template, params, _)| (template, params)),
+                expected,
+                "{}",
+                path,
+            );
+        }
+    }
+}
@@ -2,10 +2,13 @@
 
 use std::collections::BTreeMap;
 
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
 use crate::common::helpers::{Context, PushError, ValidateWithContext};
+use crate::common::parse::from_buffered;
+use crate::common::path::Path;
 use crate::validation::Options;
 
 /// ResolveReference is a trait for resolving references.
@@ -23,6 +26,28 @@ pub enum ResolveError {
     /// External is returned when the resolving of an external reference failed.
     #[error("resolving of an external reference `{0}` is not supported")]
     ExternalUnsupported(String),
+
+    /// Load is returned when the loading of an external document failed.
+    #[error("loading of `{uri}` failed: {reason}")]
+    Load { uri: String, reason: String },
+}
+
+/// DocumentLoader loads the external documents referenced by a specification,
+/// e.g. from the file system or over HTTP.
+///
+/// Any `Fn(&str) -> Result<serde_json::Value, ResolveError>` closure is a loader.
+pub trait DocumentLoader {
+    /// Loads the document by its URI.
+    fn load(&self, uri: &str) -> Result<serde_json::Value, ResolveError>;
+}
+
+impl<F> DocumentLoader for F
+where
+    F: Fn(&str) -> Result<serde_json::Value, ResolveError>,
+{
+    fn load(&self, uri: &str) -> Result<serde_json::Value, ResolveError> {
+        self(uri)
+    }
 }
 
 /// RefOr is a simple object to allow storing a reference to another component or a component itself.
@@ -43,7 +68,7 @@ pub enum ResolveError {
 ///     pub foo: Option<RefOr<Foo>>,
 /// }
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum RefOr<T> {
     /// A reference to another component.
@@ -53,6 +78,21 @@ pub enum RefOr<T> {
     Item(T),
 }
 
+impl<'de, T: DeserializeOwned> Deserialize<'de> for RefOr<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // an object with the `$ref` string is a reference, so the errors of the item are not hidden
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value.get("$ref").is_some_and(serde_json::Value::is_string) {
+            Ok(RefOr::Ref(from_buffered(value)?))
+        } else {
+            Ok(RefOr::Item(from_buffered(value)?))
+        }
+    }
+}
+
 /// Ref is a simple object to allow referencing other components in the OpenAPI document,
 /// internally and externally.
 /// The $ref string value contains a URI [RFC3986](https://www.rfc-editor.org/rfc/rfc3986),
@@ -84,7 +124,7 @@ pub struct Ref {
 }
 
 impl<D> RefOr<D> {
-    pub fn validate_with_context<T>(&self, ctx: &mut Context<T>, path: String)
+    pub fn validate_with_context<T>(&self, ctx: &mut Context<T>, path: Path)
     where
         T: ResolveReference<D>,
         D: ValidateWithContext<T>,
@@ -95,13 +135,13 @@ impl<D> RefOr<D> {
                 if ctx.visit(r.reference.clone()) {
                     match self.get_item(ctx.spec) {
                         Ok(d) => {
-                            d.validate_with_context(ctx, r.reference.clone());
+                            d.validate_with_context(ctx, r.reference.clone().into());
                         }
                         Err(e) => match e {
                             ResolveError::NotFound(r) => {
                                 ctx.error(path, format_args!(".$ref: `{}` not found", r));
                             }
-                            ResolveError::ExternalUnsupported(_) => {
+                            ResolveError::ExternalUnsupported(_) | ResolveError::Load { .. } => {
                                 if !ctx.is_option(Options::IgnoreExternalReferences) {
                                     ctx.error(path, format_args!(".$ref: {}", e));
                                 }
@@ -127,7 +167,7 @@ impl<D> RefOr<D> {
     }
 
     /// Get the item from the RefOr by returning the Item or resolving a reference.
-    pub fn get_item<'a, T>(&'a self, spec: &'a T) -> Result<&D, ResolveError>
+    pub fn get_item<'a, T>(&'a self, spec: &'a T) -> Result<&'a D, ResolveError>
     where
         T: ResolveReference<D>,
     {
@@ -149,7 +189,7 @@ impl<D> RefOr<D> {
 }
 
 impl<D> RefOr<Box<D>> {
-    pub fn validate_with_context_boxed<T>(&self, ctx: &mut Context<T>, path: String)
+    pub fn validate_with_context_boxed<T>(&self, ctx: &mut Context<T>, path: Path)
     where
         T: ResolveReference<D>,
         D: ValidateWithContext<T>,
@@ -166,7 +206,7 @@ impl<D> RefOr<Box<D>> {
 }
 
 impl Ref {
-    pub fn validate_with_context<T, D>(&self, ctx: &mut Context<T>, path: String)
+    pub fn validate_with_context<T, D>(&self, ctx: &mut Context<T>, path: Path)
     where
         T: ResolveReference<D>,
         D: ValidateWithContext<T>,
@@ -257,4 +297,28 @@ mod tests {
             "deserialize ref",
         );
     }
+
+    #[test]
+    fn test_ref_siblings_round_trip() {
+        let value = serde_json::json!({
+            "$ref": "#/components/schemas/Foo",
+            "summary": "foo summary",
+            "description": "foo description",
+        });
+        let foo = serde_json::from_value::<RefOr<Foo>>(value.clone()).unwrap();
+        assert_eq!(
+            foo,
+            RefOr::Ref(Ref {
+                reference: String::from("#/components/schemas/Foo"),
+                summary: Some(String::from("foo summary")),
+                description: Some(String::from("foo description")),
+            }),
+            "deserialize ref with siblings",
+        );
+        assert_eq!(
+            serde_json::to_value(foo).unwrap(),
+            value,
+            "serialize ref with siblings",
+        );
+    }
 }
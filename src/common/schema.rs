@@ -0,0 +1,390 @@
+//! Version-agnostic access to the schemas
+//!
+//! [SchemaLike] is implemented by the schema of each version,
+//! so the schema analyzers, like documentation generators or linters,
+//! can walk the schemas of any version without matching the version specific types.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::schema::{SchemaLike, SchemaRef};
+//!
+//! fn describe(schema: &dyn SchemaLike) -> Vec<String> {
+//!     schema
+//!         .properties()
+//!         .map(|(name, property)| match property {
+//!             SchemaRef::Ref(reference) => format!("{}: {}", name, reference),
+//!             SchemaRef::Schema(schema) => format!("{}: {}", name, schema.kind()),
+//!         })
+//!         .collect()
+//! }
+//!
+//! let schema = serde_json::from_value::<roas::v3_0::schema::Schema>(serde_json::json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "id": {"type": "integer"},
+//!         "owner": {"$ref": "#/components/schemas/Owner"},
+//!     },
+//! })).unwrap();
+//!
+//! assert_eq!(describe(&schema), vec!["id: integer", "owner: #/components/schemas/Owner"]);
+//! ```
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use serde_json::Value;
+
+use crate::common::reference::RefOr;
+
+/// The kind of a schema, the `type` of a single schema or the composition keyword.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// A schema without the `type`, accepts any value.
+    Any,
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array,
+    Object,
+    Null,
+    /// The v2.0 only `file` type.
+    File,
+    AllOf,
+    AnyOf,
+    OneOf,
+    Not,
+    /// A schema with an unknown or vendor specific type.
+    Other(String),
+}
+
+impl Display for SchemaKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaKind::Any => write!(f, "any"),
+            SchemaKind::String => write!(f, "string"),
+            SchemaKind::Integer => write!(f, "integer"),
+            SchemaKind::Number => write!(f, "number"),
+            SchemaKind::Boolean => write!(f, "boolean"),
+            SchemaKind::Array => write!(f, "array"),
+            SchemaKind::Object => write!(f, "object"),
+            SchemaKind::Null => write!(f, "null"),
+            SchemaKind::File => write!(f, "file"),
+            SchemaKind::AllOf => write!(f, "allOf"),
+            SchemaKind::AnyOf => write!(f, "anyOf"),
+            SchemaKind::OneOf => write!(f, "oneOf"),
+            SchemaKind::Not => write!(f, "not"),
+            SchemaKind::Other(t) => write!(f, "{}", t),
+        }
+    }
+}
+
+/// The constraints of a schema,
+/// the constraints not applicable to the kind of the schema are not set.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Constraints<'a> {
+    /// The `format` of the value.
+    pub format: Option<String>,
+
+    /// The allowed values.
+    pub enum_values: Option<Vec<Value>>,
+
+    /// The minimum length of a string.
+    pub min_length: Option<u64>,
+
+    /// The maximum length of a string.
+    pub max_length: Option<u64>,
+
+    /// The regular expression a string must match.
+    pub pattern: Option<&'a str>,
+
+    /// The minimum of a number.
+    pub minimum: Option<f64>,
+
+    /// `true` if the minimum itself is not allowed.
+    pub exclusive_minimum: bool,
+
+    /// The maximum of a number.
+    pub maximum: Option<f64>,
+
+    /// `true` if the maximum itself is not allowed.
+    pub exclusive_maximum: bool,
+
+    /// A number must be a multiple of the value.
+    pub multiple_of: Option<f64>,
+
+    /// The minimum number of items of an array.
+    pub min_items: Option<u64>,
+
+    /// The maximum number of items of an array.
+    pub max_items: Option<u64>,
+
+    /// `true` if the items of an array must be unique.
+    pub unique_items: bool,
+
+    /// The minimum number of properties of an object.
+    pub min_properties: Option<u64>,
+
+    /// The maximum number of properties of an object.
+    pub max_properties: Option<u64>,
+
+    /// The required properties of an object, empty if not specified.
+    pub required: &'a [String],
+
+    /// `true` if the schema is declared as "read only".
+    pub read_only: bool,
+
+    /// `true` if the schema allows the `null` value, always `false` for v2.0.
+    pub nullable: bool,
+}
+
+/// A nested schema, either a reference or the schema itself.
+#[derive(Clone, Copy)]
+pub enum SchemaRef<'a> {
+    /// A reference to another schema, e.g. `#/components/schemas/Pet`.
+    Ref(&'a str),
+
+    /// The schema itself.
+    Schema(&'a dyn SchemaLike),
+}
+
+impl<'a, T: SchemaLike> From<&'a RefOr<T>> for SchemaRef<'a> {
+    fn from(value: &'a RefOr<T>) -> Self {
+        match value {
+            RefOr::Ref(r) => SchemaRef::Ref(&r.reference),
+            RefOr::Item(schema) => SchemaRef::Schema(schema),
+        }
+    }
+}
+
+/// The operations common for the schemas of all versions.
+pub trait SchemaLike {
+    /// Returns the kind of the schema.
+    fn kind(&self) -> SchemaKind;
+
+    /// Returns the title of the schema.
+    fn title(&self) -> Option<&str>;
+
+    /// Returns the description of the schema.
+    fn description(&self) -> Option<&str>;
+
+    /// Returns the constraints of the schema.
+    fn constraints(&self) -> Constraints<'_>;
+
+    /// Returns an iterator over the properties of an object schema in the name order,
+    /// empty for the other kinds.
+    fn properties(&self) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_>;
+
+    /// Returns the schema of the items of an array schema.
+    fn items(&self) -> Option<SchemaRef<'_>>;
+
+    /// Returns the members of the composition: the `allOf`, `anyOf` or `oneOf` schemas,
+    /// or the single `not` schema.
+    ///
+    /// The v2.0 object schemas are composed by their `allOf` field.
+    fn members(&self) -> Vec<SchemaRef<'_>>;
+}
+
+impl<T: SchemaLike + ?Sized> SchemaLike for Box<T> {
+    fn kind(&self) -> SchemaKind {
+        (**self).kind()
+    }
+
+    fn title(&self) -> Option<&str> {
+        (**self).title()
+    }
+
+    fn description(&self) -> Option<&str> {
+        (**self).description()
+    }
+
+    fn constraints(&self) -> Constraints<'_> {
+        (**self).constraints()
+    }
+
+    fn properties(&self) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_> {
+        (**self).properties()
+    }
+
+    fn items(&self) -> Option<SchemaRef<'_>> {
+        (**self).items()
+    }
+
+    fn members(&self) -> Vec<SchemaRef<'_>> {
+        (**self).members()
+    }
+}
+
+/// Converts the allowed values of a schema to JSON values.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn enum_values<T: Clone + Into<Value>>(values: &Option<Vec<T>>) -> Option<Vec<Value>> {
+    values
+        .as_ref()
+        .map(|x| x.iter().cloned().map(Into::into).collect())
+}
+
+/// Returns an iterator over the properties of an object schema.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn properties<T: SchemaLike>(
+    properties: &Option<BTreeMap<String, RefOr<T>>>,
+) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_> {
+    Box::new(
+        properties
+            .iter()
+            .flatten()
+            .map(|(name, x)| (name.as_str(), x.into())),
+    )
+}
+
+#[cfg(all(test, any(feature = "v2", feature = "v3_0")))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Describes the schema and its nested schemas in a single line.
+    fn outline(schema: &dyn SchemaLike) -> String {
+        fn nested(schema: SchemaRef) -> String {
+            match schema {
+                SchemaRef::Ref(reference) => reference.to_owned(),
+                SchemaRef::Schema(schema) => outline(schema),
+            }
+        }
+
+        let mut res = schema.kind().to_string();
+        let properties = schema
+            .properties()
+            .map(|(name, x)| format!("{}: {}", name, nested(x)))
+            .collect::<Vec<_>>();
+        if !properties.is_empty() {
+            res.push_str(&format!(" {{{}}}", properties.join(", ")));
+        }
+        if let Some(items) = schema.items() {
+            res.push_str(&format!(" [{}]", nested(items)));
+        }
+        let members = schema.members().into_iter().map(nested).collect::<Vec<_>>();
+        if !members.is_empty() {
+            res.push_str(&format!(" ({})", members.join(" | ")));
+        }
+        res
+    }
+
+    #[test]
+    #[cfg(feature = "v2")]
+    fn test_v2() {
+        use crate::v2::schema::Schema;
+
+        let schema = serde_json::from_value::<Schema>(json!({
+            "type": "object",
+            "title": "Pet",
+            "required": ["id"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 1, "exclusiveMinimum": true},
+                "tags": {"type": "array", "items": {"type": "string"}, "uniqueItems": true},
+                "owner": {"$ref": "#/definitions/Owner"},
+                "photo": {"type": "file"},
+            },
+            "allOf": [{"$ref": "#/definitions/Base"}],
+        }))
+        .unwrap();
+        assert_eq!(
+            outline(&schema),
+            "object {id: integer, owner: #/definitions/Owner, photo: file, tags: array [string]} (#/definitions/Base)"
+        );
+        assert_eq!(schema.title(), Some("Pet"));
+        assert_eq!(
+            schema.constraints(),
+            Constraints {
+                required: &["id".to_owned()],
+                ..Default::default()
+            }
+        );
+
+        let schema = serde_json::from_value::<Schema>(json!({
+            "type": "integer",
+            "format": "int32",
+            "minimum": 1,
+            "exclusiveMinimum": true,
+            "enum": [1, 2],
+        }))
+        .unwrap();
+        assert_eq!(
+            schema.constraints(),
+            Constraints {
+                format: Some("int32".to_owned()),
+                enum_values: Some(vec![json!(1), json!(2)]),
+                minimum: Some(1.0),
+                exclusive_minimum: true,
+                ..Default::default()
+            }
+        );
+
+        let schema =
+            serde_json::from_value::<Schema>(json!({"type": "uuid", "description": "custom"}))
+                .unwrap();
+        assert_eq!(schema.kind(), SchemaKind::Other("uuid".to_owned()));
+        assert_eq!(schema.description(), Some("custom"));
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_v3_0() {
+        use crate::v3_0::schema::Schema;
+
+        let schema = serde_json::from_value::<Schema>(json!({
+            "oneOf": [
+                {"$ref": "#/components/schemas/Pet"},
+                {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"},
+                        "any": {},
+                    },
+                },
+                {"not": {"type": "null"}},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(
+            outline(&schema),
+            "oneOf (#/components/schemas/Pet | object {any: any, name: string} | not (null))"
+        );
+
+        let schema = serde_json::from_value::<Schema>(json!({
+            "type": "string",
+            "format": "date",
+            "minLength": 1,
+            "pattern": "^[0-9-]+$",
+            "nullable": true,
+        }))
+        .unwrap();
+        assert_eq!(
+            schema.constraints(),
+            Constraints {
+                format: Some("date".to_owned()),
+                min_length: Some(1),
+                pattern: Some("^[0-9-]+$"),
+                nullable: true,
+                ..Default::default()
+            }
+        );
+
+        let schema = serde_json::from_value::<Schema>(json!({
+            "type": "array",
+            "items": {"type": "number", "maximum": 10.5, "multipleOf": 0.5},
+            "maxItems": 3,
+        }))
+        .unwrap();
+        assert_eq!(schema.constraints().max_items, Some(3));
+        match schema.items() {
+            Some(SchemaRef::Schema(items)) => {
+                assert_eq!(items.kind(), SchemaKind::Number);
+                assert_eq!(items.constraints().maximum, Some(10.5));
+                assert_eq!(items.constraints().multiple_of, Some(0.5));
+            }
+            _ => panic!("items must be a schema"),
+        }
+    }
+}
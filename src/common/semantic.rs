@@ -0,0 +1,214 @@
+//! Semantic comparison of the specifications
+//!
+//! The derived `PartialEq` compares every field, so two specifications describing the same API
+//! are not equal if, for instance, a description has been reworded.
+//! The semantic comparison ignores the differences that do not change the API:
+//!
+//! * the order of the keys, which is never significant,
+//! * `required: false`, which is the same as the absent `required` field,
+//! * the descriptions, if [EqOptions::ignore_descriptions] is set,
+//! * the extensions, the `x-` fields, if [EqOptions::ignore_extensions] is set.
+//!
+//! The values of `example`, `default`, `enum` and the `value` of the examples
+//! are payloads and are compared as they are.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::semantic::EqOptions;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let left = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1", "description": "The Foo API"},
+//!     "paths": {},
+//!     "x-internal": true,
+//! })).unwrap();
+//! let right = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//! })).unwrap();
+//!
+//! assert!(!left.semantically_eq(&right, EqOptions::default()));
+//! assert!(left.semantically_eq(
+//!     &right,
+//!     EqOptions {
+//!         ignore_descriptions: true,
+//!         ignore_extensions: true,
+//!     },
+//! ));
+//! ```
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use serde::Serialize;
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use serde_json::{Map, Value};
+
+/// Options of the semantic comparison.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EqOptions {
+    /// Ignores the `description` fields.
+    pub ignore_descriptions: bool,
+
+    /// Ignores the extensions, the fields starting with `x-`.
+    pub ignore_extensions: bool,
+}
+
+/// The fields holding the maps of the names, e.g. the names of the properties,
+/// which are kept as they are, even if a name looks like a field to ignore.
+/// The `paths` and `responses` are not listed, because they support the extensions
+/// and their keys, the paths and the status codes, never look like a field to ignore.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+const NAME_MAPS: [&str; 16] = [
+    "properties",
+    "definitions",
+    "schemas",
+    "parameters",
+    "examples",
+    "headers",
+    "securitySchemes",
+    "securityDefinitions",
+    "requestBodies",
+    "links",
+    "callbacks",
+    "content",
+    "encoding",
+    "mapping",
+    "variables",
+    "scopes",
+];
+
+/// The fields holding the payloads, which are compared as they are.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+const PAYLOADS: [&str; 4] = ["example", "default", "enum", "value"];
+
+/// Returns `true` if the values are semantically equal, see the module docs for details.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn semantically_eq<T: Serialize>(left: &T, right: &T, options: &EqOptions) -> bool {
+    match (
+        canonical_value(left, options),
+        canonical_value(right, options),
+    ) {
+        (Ok(left), Ok(right)) => left == right,
+        _ => false,
+    }
+}
+
+/// Returns the hash of the canonical value, see the module docs for details.
+#[cfg(feature = "v3_0")]
+pub(crate) fn semantic_hash<T: Serialize>(value: &T) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    if let Ok(value) = canonical_value(value, &EqOptions::default()) {
+        value.to_string().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Serializes the value and drops the fields ignored by the semantic comparison.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn canonical_value<T: Serialize>(
+    value: &T,
+    options: &EqOptions,
+) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(value)?;
+    canonicalize(&mut value, false, options);
+    Ok(value)
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+fn canonicalize(value: &mut Value, names: bool, options: &EqOptions) {
+    match value {
+        Value::Object(map) => {
+            if !names {
+                drop_fields(map, options);
+            }
+            for (key, value) in map.iter_mut() {
+                if names {
+                    canonicalize(value, false, options);
+                } else if !key.starts_with("x-") && !PAYLOADS.contains(&key.as_str()) {
+                    canonicalize(value, NAME_MAPS.contains(&key.as_str()), options);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                canonicalize(item, false, options);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+fn drop_fields(map: &mut Map<String, Value>, options: &EqOptions) {
+    map.retain(|key, value| match key.as_str() {
+        "required" => value != &Value::Bool(false),
+        "description" => !(options.ignore_descriptions && value.is_string()),
+        _ => !(options.ignore_extensions && key.starts_with("x-")),
+    });
+}
+
+#[cfg(all(test, any(feature = "v2", feature = "v3_0")))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_value() {
+        let value = json!({
+            "description": "foo",
+            "required": false,
+            "x-foo": {"description": "bar"},
+            "properties": {
+                "description": {"type": "string", "description": "baz"},
+                "x-name": {"type": "string"},
+            },
+            "example": {"description": "qux", "required": false},
+            "responses": {
+                "200": {"description": "ok", "x-bar": 1},
+                "x-baz": 2,
+            },
+        });
+        assert_eq!(
+            canonical_value(&value, &EqOptions::default()).unwrap(),
+            json!({
+                "description": "foo",
+                "x-foo": {"description": "bar"},
+                "properties": {
+                    "description": {"type": "string", "description": "baz"},
+                    "x-name": {"type": "string"},
+                },
+                "example": {"description": "qux", "required": false},
+                "responses": {
+                    "200": {"description": "ok", "x-bar": 1},
+                    "x-baz": 2,
+                },
+            }),
+            "default options",
+        );
+        assert_eq!(
+            canonical_value(
+                &value,
+                &EqOptions {
+                    ignore_descriptions: true,
+                    ignore_extensions: true,
+                }
+            )
+            .unwrap(),
+            json!({
+                "properties": {
+                    "description": {"type": "string"},
+                    "x-name": {"type": "string"},
+                },
+                "example": {"description": "qux", "required": false},
+                "responses": {"200": {}},
+            }),
+            "all options",
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Version-agnostic access to the specifications
+//!
+//! [SpecLike] is implemented by the specification of each version,
+//! so the generic tooling, like linters or catalogs, can be written once against `dyn SpecLike`.
+//! The objects that differ between the versions are exposed as JSON values.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::spec::SpecLike;
+//! use roas::validation::Options;
+//!
+//! fn describe(spec: &dyn SpecLike) -> Vec<String> {
+//!     spec.operations()
+//!         .map(|x| format!("{} {} {}", spec.title(), x.method, x.path))
+//!         .collect()
+//! }
+//!
+//! let spec = serde_json::from_value::<roas::v3_0::spec::Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "Pets", "version": "1"},
+//!     "paths": {"/pets": {"get": {"responses": {"200": {"description": "OK"}}}}},
+//! })).unwrap();
+//!
+//! let spec: &dyn SpecLike = &spec;
+//! assert_eq!(describe(spec), vec!["Pets get /pets"]);
+//! assert!(spec.validate(Options::new()).is_ok());
+//! ```
+
+use serde_json::Value;
+
+use crate::common::http::Method;
+use crate::common::stats::Stats;
+use crate::validation::Validate;
+
+/// The common fields of an operation of any version.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationView<'a> {
+    /// The path of the operation, e.g. `/pets/{id}`.
+    pub path: &'a str,
+
+    /// The HTTP method of the operation.
+    pub method: Method,
+
+    /// The `operationId` of the operation.
+    pub operation_id: Option<&'a str>,
+
+    /// The short summary of the operation.
+    pub summary: Option<&'a str>,
+
+    /// The description of the operation.
+    pub description: Option<&'a str>,
+
+    /// The tags of the operation, empty if not specified.
+    pub tags: &'a [String],
+
+    /// `true` if the operation is deprecated.
+    pub deprecated: bool,
+}
+
+/// The operations common for the specifications of all versions.
+pub trait SpecLike: Validate {
+    /// Returns the version of the specification, e.g. `2.0` or `3.0.3`.
+    fn spec_version(&self) -> String;
+
+    /// Returns the title of the API.
+    fn title(&self) -> &str;
+
+    /// Returns the version of the API, the `info.version` field.
+    fn api_version(&self) -> &str;
+
+    /// Returns an iterator over all operations in the document order.
+    fn operations(&self) -> Box<dyn Iterator<Item = OperationView<'_>> + '_>;
+
+    /// Returns the names of the reusable schemas,
+    /// the `definitions` for v2.0 and the `components.schemas` for v3.0.
+    fn schema_names(&self) -> Vec<&str>;
+
+    /// Returns the reusable schema by its name as a JSON value.
+    fn schema_value(&self, name: &str) -> Option<Value>;
+
+    /// Returns the statistics of the specification, see [Stats] for details.
+    fn stats(&self) -> Stats;
+}
+
+#[cfg(all(test, any(feature = "v2", feature = "v3_0")))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn summary(spec: &dyn SpecLike) -> (String, Vec<String>, Vec<&str>) {
+        (
+            format!(
+                "{} {} {}",
+                spec.spec_version(),
+                spec.title(),
+                spec.api_version()
+            ),
+            spec.operations()
+                .map(|x| {
+                    format!(
+                        "{} {} {:?} {:?} {}",
+                        x.method, x.path, x.operation_id, x.tags, x.deprecated
+                    )
+                })
+                .collect(),
+            spec.schema_names(),
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "v2")]
+    fn test_v2() {
+        let spec = serde_json::from_value::<crate::v2::spec::Spec>(json!({
+            "swagger": "2.0",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "tags": ["pets"],
+                        "responses": {"200": {"description": "OK"}},
+                    },
+                },
+            },
+            "definitions": {"Pet": {"type": "string"}},
+        }))
+        .unwrap();
+        assert_eq!(
+            summary(&spec),
+            (
+                "2.0 Pets 1".to_owned(),
+                vec![r#"get /pets Some("listPets") ["pets"] false"#.to_owned()],
+                vec!["Pet"],
+            )
+        );
+        assert_eq!(spec.schema_value("Pet"), Some(json!({"type": "string"})));
+        assert_eq!(spec.schema_value("Missing"), None);
+        assert_eq!(SpecLike::stats(&spec).paths, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_v3_0() {
+        let spec = serde_json::from_value::<crate::v3_0::spec::Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "deprecated": true,
+                        "responses": {"200": {"description": "OK"}},
+                    },
+                },
+            },
+            "components": {"schemas": {"Pet": {"type": "string"}}},
+        }))
+        .unwrap();
+        assert_eq!(
+            summary(&spec),
+            (
+                "3.0.3 Pets 1".to_owned(),
+                vec!["post /pets None [] true".to_owned()],
+                vec!["Pet"],
+            )
+        );
+        assert_eq!(spec.schema_value("Pet"), Some(json!({"type": "string"})));
+        let spec: Box<dyn SpecLike> = Box::new(spec);
+        assert!(spec
+            .validate(crate::validation::Options::IgnoreUnusedSchemas.only())
+            .is_ok());
+    }
+}
@@ -0,0 +1,157 @@
+//! Statistics of a specification
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets": {
+//!             "get": {"summary": "List pets", "responses": {}},
+//!             "post": {"security": [{"key": []}], "deprecated": true, "responses": {}},
+//!         },
+//!     },
+//! })).unwrap();
+//!
+//! let stats = spec.stats();
+//! assert_eq!(stats.operations, 2);
+//! assert_eq!(stats.deprecated_operations, 1);
+//! assert_eq!(stats.undocumented_operations, 1);
+//! assert_eq!(stats.security_coverage(), 50.0);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Counts the objects of a specification.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Stats {
+    /// The number of the paths.
+    pub paths: usize,
+
+    /// The number of the operations.
+    pub operations: usize,
+
+    /// The number of the operations per method, e.g. `get`.
+    pub operations_by_method: BTreeMap<String, usize>,
+
+    /// The number of the deprecated operations.
+    pub deprecated_operations: usize,
+
+    /// The number of the operations without `summary` and `description`.
+    pub undocumented_operations: usize,
+
+    /// The number of the operations without `operationId`.
+    pub operations_without_id: usize,
+
+    /// The number of the operations requiring a security scheme,
+    /// either declared by the operation or inherited from the specification.
+    /// An operation with an empty security requirement is not secured.
+    pub secured_operations: usize,
+
+    /// The number of the schemas, the `definitions` for v2 or the `components.schemas` for v3.
+    pub schemas: usize,
+
+    /// The number of the reusable parameters.
+    pub parameters: usize,
+
+    /// The number of the reusable responses.
+    pub responses: usize,
+
+    /// The number of the security schemes.
+    pub security_schemes: usize,
+
+    /// The number of the tags.
+    pub tags: usize,
+}
+
+impl Stats {
+    /// Returns the percentage of the secured operations, `0` if there are no operations.
+    pub fn security_coverage(&self) -> f64 {
+        if self.operations == 0 {
+            return 0.0;
+        }
+        self.secured_operations as f64 * 100.0 / self.operations as f64
+    }
+
+    /// Counts an operation.
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    pub(crate) fn add_operation(
+        &mut self,
+        method: &str,
+        deprecated: bool,
+        documented: bool,
+        has_id: bool,
+        secured: bool,
+    ) {
+        self.operations += 1;
+        *self
+            .operations_by_method
+            .entry(method.to_lowercase())
+            .or_default() += 1;
+        self.deprecated_operations += usize::from(deprecated);
+        self.undocumented_operations += usize::from(!documented);
+        self.operations_without_id += usize::from(!has_id);
+        self.secured_operations += usize::from(secured);
+    }
+}
+
+/// Returns `true` if the security requirements, of the operation or the inherited ones,
+/// require at least one security scheme.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn is_secured(
+    operation: &Option<Vec<BTreeMap<String, Vec<String>>>>,
+    spec: &Option<Vec<BTreeMap<String, Vec<String>>>>,
+) -> bool {
+    match operation.as_ref().or(spec.as_ref()) {
+        Some(requirements) => {
+            !requirements.is_empty() && requirements.iter().all(|x| !x.is_empty())
+        }
+        None => false,
+    }
+}
+
+#[cfg(all(test, any(feature = "v2", feature = "v3_0")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_secured() {
+        let key = Some(vec![BTreeMap::from([("key".to_owned(), vec![])])]);
+        let optional = Some(vec![
+            BTreeMap::from([("key".to_owned(), vec![])]),
+            BTreeMap::new(),
+        ]);
+        assert!(is_secured(&key, &None), "operation");
+        assert!(is_secured(&None, &key), "inherited");
+        assert!(
+            !is_secured(&Some(vec![]), &key),
+            "disabled by the operation"
+        );
+        assert!(!is_secured(&optional, &None), "optional");
+        assert!(!is_secured(&None, &None), "none");
+    }
+
+    #[test]
+    fn test_security_coverage() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.security_coverage(), 0.0);
+        stats.add_operation("GET", false, true, true, true);
+        stats.add_operation("get", false, true, true, false);
+        stats.add_operation("post", false, true, true, false);
+        stats.add_operation("put", false, true, true, true);
+        assert_eq!(stats.security_coverage(), 50.0);
+        assert_eq!(
+            stats.operations_by_method,
+            BTreeMap::from([
+                ("get".to_owned(), 2),
+                ("post".to_owned(), 1),
+                ("put".to_owned(), 1),
+            ]),
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! HTTP status code helpers
+//!
+//! The functions accept the keys of the responses,
+//! either a status code like `204` or a range like `2XX`.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::status::{class, is_success, reason_phrase, StatusClass};
+//!
+//! assert!(is_success("204"));
+//! assert!(is_success("2XX"));
+//! assert_eq!(class("404"), Some(StatusClass::ClientError));
+//! assert_eq!(reason_phrase(404), Some("Not Found"));
+//! ```
+
+use std::fmt;
+use std::fmt::Display;
+
+use crate::common::http::StatusCode;
+
+/// The class of an HTTP status code, defined by its first digit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusClass {
+    /// `1XX`
+    Informational,
+    /// `2XX`
+    Success,
+    /// `3XX`
+    Redirection,
+    /// `4XX`
+    ClientError,
+    /// `5XX`
+    ServerError,
+}
+
+impl StatusClass {
+    /// Returns the name of the class, e.g. `Client Error`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusClass::Informational => "Informational",
+            StatusClass::Success => "Success",
+            StatusClass::Redirection => "Redirection",
+            StatusClass::ClientError => "Client Error",
+            StatusClass::ServerError => "Server Error",
+        }
+    }
+}
+
+impl Display for StatusClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Returns the class of the status code or the range,
+/// `None` if the key is neither a valid status code nor a valid range.
+pub fn class(key: &str) -> Option<StatusClass> {
+    let digit = match key.parse::<StatusCode>().ok()? {
+        StatusCode::Code(code) => code / 100,
+        StatusCode::Range(range) => range as u16,
+    };
+    match digit {
+        1 => Some(StatusClass::Informational),
+        2 => Some(StatusClass::Success),
+        3 => Some(StatusClass::Redirection),
+        4 => Some(StatusClass::ClientError),
+        5 => Some(StatusClass::ServerError),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the status code or the range is successful, e.g. `204` or `2XX`.
+pub fn is_success(key: &str) -> bool {
+    class(key) == Some(StatusClass::Success)
+}
+
+/// Returns `true` if the status code or the range is a client or a server error.
+pub fn is_error(key: &str) -> bool {
+    matches!(
+        class(key),
+        Some(StatusClass::ClientError | StatusClass::ServerError)
+    )
+}
+
+/// Returns the canonical reason phrase of the status code
+/// registered in the IANA HTTP Status Code Registry, e.g. `Not Found` for `404`.
+pub fn reason_phrase(code: u16) -> Option<&'static str> {
+    let phrase = match code {
+        100 => "Continue",
+        101 => "Switching Protocols",
+        102 => "Processing",
+        103 => "Early Hints",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        203 => "Non-Authoritative Information",
+        204 => "No Content",
+        205 => "Reset Content",
+        206 => "Partial Content",
+        207 => "Multi-Status",
+        208 => "Already Reported",
+        226 => "IM Used",
+        300 => "Multiple Choices",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        305 => "Use Proxy",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        402 => "Payment Required",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        407 => "Proxy Authentication Required",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Content Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        417 => "Expectation Failed",
+        421 => "Misdirected Request",
+        422 => "Unprocessable Content",
+        423 => "Locked",
+        424 => "Failed Dependency",
+        425 => "Too Early",
+        426 => "Upgrade Required",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        451 => "Unavailable For Legal Reasons",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        506 => "Variant Also Negotiates",
+        507 => "Insufficient Storage",
+        508 => "Loop Detected",
+        510 => "Not Extended",
+        511 => "Network Authentication Required",
+        _ => return None,
+    };
+    Some(phrase)
+}
+
+/// Returns the description of the key for rendering,
+/// the reason phrase of a status code or the class name of a range,
+/// e.g. `Not Found` for `404` and `Success` for `2XX`.
+pub fn describe(key: &str) -> Option<&'static str> {
+    match key.parse::<StatusCode>().ok()? {
+        StatusCode::Code(code) => reason_phrase(code),
+        StatusCode::Range(_) => class(key).map(|x| x.as_str()),
+    }
+}
+
+/// Returns `true` if the key is a range or a status code with a registered reason phrase.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn is_registered(key: &str) -> bool {
+    match key.parse::<StatusCode>() {
+        Ok(StatusCode::Code(code)) => reason_phrase(code).is_some(),
+        Ok(StatusCode::Range(_)) => true,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class() {
+        assert_eq!(class("100"), Some(StatusClass::Informational));
+        assert_eq!(class("2XX"), Some(StatusClass::Success));
+        assert_eq!(class("399"), Some(StatusClass::Redirection));
+        assert_eq!(class("4XX"), Some(StatusClass::ClientError));
+        assert_eq!(class("599"), Some(StatusClass::ServerError));
+        assert_eq!(class("600"), None);
+        assert_eq!(class("default"), None);
+
+        assert!(is_success("204"));
+        assert!(!is_success("304"));
+        assert!(is_error("5XX"));
+        assert!(!is_error("2XX"));
+    }
+
+    #[test]
+    fn test_reason_phrase() {
+        assert_eq!(reason_phrase(200), Some("OK"));
+        assert_eq!(reason_phrase(422), Some("Unprocessable Content"));
+        assert_eq!(reason_phrase(299), None);
+
+        assert_eq!(describe("404"), Some("Not Found"));
+        assert_eq!(describe("5XX"), Some("Server Error"));
+        assert_eq!(describe("299"), None);
+        assert_eq!(describe("default"), None);
+    }
+
+    #[test]
+    #[cfg(any(feature = "v2", feature = "v3_0"))]
+    fn test_is_registered() {
+        assert!(is_registered("201"));
+        assert!(is_registered("1XX"));
+        assert!(!is_registered("299"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Strict deserialization.
+//!
+//! The objects of the specification are deserialized permissively,
+//! so any field that is not defined by the specification is silently dropped.
+//! The module provides a helper to detect such fields, so a typo like `requred`
+//! is reported instead of being ignored.
+//!
+//! The value is deserialized as usual and then serialized back,
+//! any field of the original value that disappeared during the round trip is unknown.
+//! The fields starting with `x-` and the fields with `null` value are never reported.
+//!
+//! Example:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use roas::common::strict::{from_value_strict, StrictError};
+//!
+//! #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+//! struct Foo {
+//!     pub required: Vec<String>,
+//! }
+//!
+//! let res = from_value_strict::<Foo>(serde_json::json!({"requred": ["bar"], "required": []}));
+//! assert_eq!(res.unwrap_err().to_string(), "unknown fields: #.requred");
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::path::Path;
+
+/// StrictError is an error type for the strict deserialization.
+#[derive(Debug, Error)]
+pub enum StrictError {
+    /// Json is returned when the value cannot be deserialized or serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// UnknownFields is returned when the value contains fields not defined by the specification.
+    #[error("unknown fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+}
+
+/// Deserializes the value and fails if it contains fields that are not supported by the type.
+pub fn from_value_strict<T>(value: Value) -> Result<T, StrictError>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let res = T::deserialize(&value)?;
+    let mut unknown = Vec::new();
+    walk_unknown(
+        &value,
+        &serde_json::to_value(&res)?,
+        Path::root(),
+        &mut |path, key, value| {
+            if !key.starts_with("x-") && !value.is_null() {
+                unknown.push(path.to_string());
+            }
+        },
+    );
+    if unknown.is_empty() {
+        Ok(res)
+    } else {
+        Err(StrictError::UnknownFields(unknown))
+    }
+}
+
+/// Calls the function with the location, the name and the value of each field
+/// of the original value that is missing in the known value,
+/// the serialized form of the value deserialized from the original one.
+pub(crate) fn walk_unknown<'v>(
+    original: &'v Value,
+    known: &Value,
+    path: Path,
+    f: &mut impl FnMut(Path, &'v str, &'v Value),
+) {
+    match (original, known) {
+        (Value::Object(original), Value::Object(known)) => {
+            for (key, value) in original {
+                let path = path.field(key.clone());
+                match known.get(key) {
+                    Some(known) => walk_unknown(value, known, path, f),
+                    None => f(path, key, value),
+                }
+            }
+        }
+        (Value::Array(original), Value::Array(known)) => {
+            for (i, (value, known)) in original.iter().zip(known.iter()).enumerate() {
+                walk_unknown(value, known, path.index(i), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+    struct Foo {
+        pub foo: String,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bars: Option<Vec<Foo>>,
+
+        #[serde(flatten)]
+        #[serde(with = "crate::common::extensions")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub extensions: Option<BTreeMap<String, Value>>,
+    }
+
+    #[test]
+    fn test_from_value_strict() {
+        assert_eq!(
+            from_value_strict::<Foo>(serde_json::json!({
+                "foo": "foo",
+                "bars": [{"foo": "bar", "x-bar": 42}],
+                "x-foo": "bar",
+            }))
+            .unwrap(),
+            serde_json::from_value::<Foo>(serde_json::json!({
+                "foo": "foo",
+                "bars": [{"foo": "bar", "x-bar": 42}],
+                "x-foo": "bar",
+            }))
+            .unwrap(),
+            "known fields and extensions",
+        );
+
+        assert_eq!(
+            from_value_strict::<Foo>(serde_json::json!({
+                "foo": "foo",
+                "bars": null,
+            }))
+            .unwrap(),
+            Foo {
+                foo: "foo".to_owned(),
+                ..Default::default()
+            },
+            "null field",
+        );
+
+        assert_eq!(
+            from_value_strict::<Foo>(serde_json::json!({
+                "foo": "foo",
+                "baz": true,
+                "bars": [{"foo": "bar"}, {"foo": "baz", "baz": []}],
+            }))
+            .unwrap_err()
+            .to_string(),
+            "unknown fields: #.bars[1].baz, #.baz",
+            "unknown fields",
+        );
+
+        assert_eq!(
+            from_value_strict::<Foo>(serde_json::json!({
+                "bars": [],
+            }))
+            .unwrap_err()
+            .to_string(),
+            "missing field `foo`",
+            "invalid value",
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Typed URLs
+//!
+//! The URL-valued fields are stored as strings to preserve the document as is,
+//! the accessors like `parsed_url` return them as a validated [Url].
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::url::Url;
+//!
+//! let url: Url = "https://example.com/docs?lang=en".parse().unwrap();
+//! assert_eq!(url.scheme(), "https");
+//! assert_eq!(url.host(), Some("example.com"));
+//! assert!("/docs".parse::<Url>().is_err());
+//! ```
+
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::common::helpers::{is_uri, uri_scheme};
+
+/// UrlError is an error type for parsing a URL.
+#[derive(Debug, Error, PartialEq)]
+pub enum UrlError {
+    /// Invalid is returned when the value is not an absolute URL.
+    #[error("invalid URL `{0}`")]
+    Invalid(String),
+}
+
+/// An absolute URL with a scheme, e.g. `https://example.com` or `mailto:support@example.com`,
+/// the HTTP(S) URLs must have a host.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Url(String);
+
+impl Url {
+    /// Parses and validates the URL.
+    pub fn parse(value: &str) -> Result<Self, UrlError> {
+        if is_uri(value) {
+            Ok(Url(value.to_owned()))
+        } else {
+            Err(UrlError::Invalid(value.to_owned()))
+        }
+    }
+
+    /// Returns the URL as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the scheme of the URL, e.g. `https`.
+    pub fn scheme(&self) -> &str {
+        uri_scheme(&self.0).unwrap_or_default()
+    }
+
+    /// Returns the host of the URL without the user info and the port,
+    /// `None` if the URL has no authority, like `urn:isbn:1`.
+    pub fn host(&self) -> Option<&str> {
+        let rest = self.0[self.scheme().len() + 1..].strip_prefix("//")?;
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = authority.rsplit_once('@').map_or(authority, |(_, x)| x);
+        match host.find(']') {
+            Some(end) if host.starts_with('[') => Some(&host[..=end]),
+            _ => host.split(':').next(),
+        }
+    }
+}
+
+impl FromStr for Url {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Url::parse(s)
+    }
+}
+
+impl Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Url {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Parses the optional URL, `Ok(None)` if the URL is not set.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn parse_optional(url: &Option<String>) -> Result<Option<Url>, UrlError> {
+    url.as_deref().map(Url::parse).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_parse() {
+        for (value, scheme, host) in [
+            ("https://example.com", "https", Some("example.com")),
+            (
+                "http://user@example.com:8080/a?b#c",
+                "http",
+                Some("example.com"),
+            ),
+            ("https://[::1]:8443/", "https", Some("[::1]")),
+            ("urn:isbn:1", "urn", None),
+        ] {
+            let url = Url::parse(value).unwrap();
+            assert_eq!(url.as_str(), value);
+            assert_eq!(url.scheme(), scheme, "{}", value);
+            assert_eq!(url.host(), host, "{}", value);
+        }
+        for value in ["", "/docs", "https://", "https://exa mple.com"] {
+            assert_eq!(
+                Url::parse(value),
+                Err(UrlError::Invalid(value.to_owned())),
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,464 @@
+//! Typed models of the widely used vendor extensions
+//!
+//! The extensions are kept as JSON values by the objects,
+//! [Extensible::extension] converts the extension to its model by the name of the model,
+//! see [VendorExtension::NAME].
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::vendor::{AmazonApigatewayIntegration, Extensible, IntegrationType};
+//! use roas::v3_0::operation::Operation;
+//!
+//! let operation = serde_json::from_value::<Operation>(serde_json::json!({
+//!     "responses": {"200": {"description": "OK"}},
+//!     "x-amazon-apigateway-integration": {
+//!         "type": "aws_proxy",
+//!         "httpMethod": "POST",
+//!         "uri": "arn:aws:apigateway:us-east-1:lambda:path/2015-03-31/functions/arn/invocations",
+//!     },
+//! })).unwrap();
+//!
+//! let integration = operation.extension::<AmazonApigatewayIntegration>().unwrap().unwrap();
+//! assert_eq!(integration._type, IntegrationType::AwsProxy);
+//! assert_eq!(integration.http_method.as_deref(), Some("POST"));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::common::extensions::{ExtensionError, Extensions, ExtensionsExt};
+
+/// A typed model of a vendor extension.
+pub trait VendorExtension: DeserializeOwned + Serialize {
+    /// The name of the extension, e.g. `x-amazon-apigateway-integration`.
+    const NAME: &'static str;
+}
+
+/// An object with the specification extensions.
+pub trait Extensible {
+    /// Returns the specification extensions of the object.
+    fn extensions(&self) -> &Option<Extensions>;
+
+    /// Returns a mutable reference to the specification extensions of the object.
+    fn extensions_mut(&mut self) -> &mut Option<Extensions>;
+
+    /// Returns the vendor extension converted to its model,
+    /// `None` if the extension is not defined.
+    fn extension<T: VendorExtension>(&self) -> Result<Option<T>, ExtensionError> {
+        self.extensions().get_as(T::NAME)
+    }
+
+    /// Sets the vendor extension, returns the previous value.
+    fn set_extension<T: VendorExtension>(
+        &mut self,
+        value: &T,
+    ) -> Result<Option<serde_json::Value>, ExtensionError> {
+        self.extensions_mut().insert_serializable(T::NAME, value)
+    }
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+macro_rules! impl_extensible {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Extensible for $t {
+                fn extensions(&self) -> &Option<Extensions> {
+                    &self.extensions
+                }
+
+                fn extensions_mut(&mut self) -> &mut Option<Extensions> {
+                    &mut self.extensions
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "v2")]
+impl_extensible!(
+    crate::v2::spec::Spec,
+    crate::v2::path_item::PathItem,
+    crate::v2::operation::Operation,
+    crate::v2::info::Info,
+    crate::v2::tag::Tag,
+);
+
+#[cfg(feature = "v3_0")]
+impl_extensible!(
+    crate::v3_0::spec::Spec,
+    crate::v3_0::path_item::PathItem,
+    crate::v3_0::operation::Operation,
+    crate::v3_0::server::Server,
+    crate::v3_0::info::Info,
+    crate::v3_0::tag::Tag,
+);
+
+/// The type of the backend of an Amazon API Gateway integration.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationType {
+    /// An AWS service action.
+    #[serde(alias = "AWS")]
+    Aws,
+
+    /// A Lambda function invoked with the whole request.
+    #[serde(alias = "AWS_PROXY")]
+    AwsProxy,
+
+    /// An HTTP endpoint.
+    #[serde(alias = "HTTP")]
+    Http,
+
+    /// An HTTP endpoint called with the whole request.
+    #[serde(alias = "HTTP_PROXY")]
+    HttpProxy,
+
+    /// A response returned by the API Gateway itself.
+    #[default]
+    #[serde(alias = "MOCK")]
+    Mock,
+}
+
+/// The backend integration of an operation, the `x-amazon-apigateway-integration` extension.
+///
+/// See the [API Gateway documentation](https://docs.aws.amazon.com/apigateway/latest/developerguide/api-gateway-swagger-extensions-integration.html).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AmazonApigatewayIntegration {
+    /// **Required** The type of the integration.
+    #[serde(rename = "type")]
+    pub _type: IntegrationType,
+
+    /// The URI of the backend, an ARN for the AWS integrations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+
+    /// The HTTP method used to call the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_method: Option<String>,
+
+    /// The type of the connection, `INTERNET` or `VPC_LINK`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_type: Option<String>,
+
+    /// The ID of the VPC link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<String>,
+
+    /// The ARN of the IAM role used to call the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+
+    /// How the request of an unmapped content type is passed,
+    /// `when_no_match`, `when_no_templates` or `never`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passthrough_behavior: Option<String>,
+
+    /// The mappings of the method request parameters to the integration request parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_parameters: Option<BTreeMap<String, String>>,
+
+    /// The mapping templates of the request body by the content type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_templates: Option<BTreeMap<String, String>>,
+
+    /// The responses of the backend by the regular expression of the status code or error message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<BTreeMap<String, AmazonApigatewayIntegrationResponse>>,
+
+    /// The timeout of the integration in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_in_millis: Option<u64>,
+
+    /// How the binary payload is converted, `CONVERT_TO_BINARY` or `CONVERT_TO_TEXT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_handling: Option<String>,
+
+    /// The ID of the group of the cached parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_namespace: Option<String>,
+
+    /// The request parameters used as the cache key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_key_parameters: Option<Vec<String>>,
+
+    /// The version of the payload sent to a Lambda function, `1.0` or `2.0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_format_version: Option<String>,
+}
+
+impl VendorExtension for AmazonApigatewayIntegration {
+    const NAME: &'static str = "x-amazon-apigateway-integration";
+}
+
+/// A response of an Amazon API Gateway integration.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AmazonApigatewayIntegrationResponse {
+    /// **Required** The status code of the method response.
+    pub status_code: String,
+
+    /// The mappings of the integration response parameters to the method response parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_parameters: Option<BTreeMap<String, String>>,
+
+    /// The mapping templates of the response body by the content type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_templates: Option<BTreeMap<String, String>>,
+
+    /// How the binary payload is converted, `CONVERT_TO_BINARY` or `CONVERT_TO_TEXT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_handling: Option<String>,
+}
+
+/// The backend of an operation or of the whole API for Google Cloud API Gateway and Endpoints,
+/// the `x-google-backend` extension.
+///
+/// See the [Endpoints documentation](https://cloud.google.com/endpoints/docs/openapi/openapi-extensions#x-google-backend).
+/// Unlike the specification, the extension uses the snake_case names.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct GoogleBackend {
+    /// **Required** The URL of the backend.
+    pub address: String,
+
+    /// The audience of the JWT token sent to the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt_audience: Option<String>,
+
+    /// `true` if no JWT token is sent to the backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_auth: Option<bool>,
+
+    /// How the request path is sent to the backend,
+    /// `APPEND_PATH_TO_ADDRESS` or `CONSTANT_ADDRESS`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_translation: Option<String>,
+
+    /// The timeout of the request in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<f64>,
+
+    /// The protocol used to call the backend, `http/1.1` or `h2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+}
+
+impl VendorExtension for GoogleBackend {
+    const NAME: &'static str = "x-google-backend";
+}
+
+/// The groups of the tags used by the documentation renderers like Redoc,
+/// the root `x-tagGroups` extension.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(transparent)]
+pub struct TagGroups(pub Vec<TagGroup>);
+
+impl VendorExtension for TagGroups {
+    const NAME: &'static str = "x-tagGroups";
+}
+
+/// A named group of tags.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct TagGroup {
+    /// **Required** The name of the group.
+    pub name: String,
+
+    /// **Required** The names of the tags in the group.
+    pub tags: Vec<String>,
+}
+
+/// The logo of the API shown by Redoc, the `x-logo` extension of the Info Object.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Logo {
+    /// **Required** The URL of the image.
+    pub url: String,
+
+    /// The background color of the logo, a CSS color, e.g. `#FFFFFF`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+
+    /// The alternative text of the image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_text: Option<String>,
+
+    /// The URL the logo links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+}
+
+impl VendorExtension for Logo {
+    const NAME: &'static str = "x-logo";
+}
+
+/// The code samples of an operation shown by Redoc and Swagger UI plugins,
+/// the `x-codeSamples` extension of the Operation Object.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(transparent)]
+pub struct CodeSamples(pub Vec<CodeSample>);
+
+impl VendorExtension for CodeSamples {
+    const NAME: &'static str = "x-codeSamples";
+}
+
+/// A code sample of an operation.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct CodeSample {
+    /// **Required** The language of the sample, e.g. `Python`.
+    pub lang: String,
+
+    /// The label of the sample, the language is shown if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    /// **Required** The source code of the sample.
+    pub source: String,
+}
+
+/// The name of a tag shown by Redoc instead of the tag name,
+/// the `x-displayName` extension of the Tag Object.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(transparent)]
+pub struct DisplayName(pub String);
+
+impl VendorExtension for DisplayName {
+    const NAME: &'static str = "x-displayName";
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_v3_0() {
+        let mut spec = serde_json::from_value::<crate::v3_0::spec::Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {},
+            "x-tagGroups": [{"name": "Store", "tags": ["pets", "orders"]}],
+            "x-google-backend": {
+                "address": "https://backend.example.com",
+                "path_translation": "APPEND_PATH_TO_ADDRESS",
+                "deadline": 10.0,
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.extension::<TagGroups>().unwrap(),
+            Some(TagGroups(vec![TagGroup {
+                name: "Store".to_owned(),
+                tags: vec!["pets".to_owned(), "orders".to_owned()],
+            }]))
+        );
+        let backend = spec.extension::<GoogleBackend>().unwrap().unwrap();
+        assert_eq!(backend.address, "https://backend.example.com");
+        assert_eq!(backend.deadline, Some(10.0));
+        assert_eq!(
+            spec.extension::<AmazonApigatewayIntegration>().unwrap(),
+            None
+        );
+
+        spec.set_extension(&TagGroups::default()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap()["x-tagGroups"],
+            json!([])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "v2")]
+    fn test_v2() {
+        let operation = serde_json::from_value::<crate::v2::operation::Operation>(json!({
+            "responses": {"200": {"description": "OK"}},
+            "x-amazon-apigateway-integration": {
+                "type": "HTTP",
+                "uri": "https://backend.example.com/pets",
+                "httpMethod": "GET",
+                "timeoutInMillis": 29000,
+                "responses": {
+                    "default": {
+                        "statusCode": "200",
+                        "responseParameters": {
+                            "method.response.header.Cache-Control": "'no-cache'",
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        let integration = operation
+            .extension::<AmazonApigatewayIntegration>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(integration._type, IntegrationType::Http);
+        assert_eq!(integration.timeout_in_millis, Some(29000));
+        assert_eq!(integration.responses.unwrap()["default"].status_code, "200");
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_documentation_extensions() {
+        use crate::validation::{Options, Validate};
+
+        let spec = serde_json::from_value::<crate::v3_0::spec::Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "Pets",
+                "version": "1",
+                "x-logo": {"url": "https://example.com/logo.png", "altText": "Pets"},
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "tags": ["pets"],
+                        "responses": {"200": {"description": "OK"}},
+                        "x-codeSamples": [{"lang": "Shell", "source": "curl /pets"}],
+                    },
+                },
+            },
+            "tags": [{"name": "pets", "x-displayName": "Pets"}],
+            "x-tagGroups": [{"name": "Store", "tags": ["pets", "orders"]}],
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.info.extension::<Logo>().unwrap().unwrap().alt_text,
+            Some("Pets".to_owned())
+        );
+        assert_eq!(
+            spec.paths["/pets"]
+                .get()
+                .unwrap()
+                .extension::<CodeSamples>()
+                .unwrap(),
+            Some(CodeSamples(vec![CodeSample {
+                lang: "Shell".to_owned(),
+                label: None,
+                source: "curl /pets".to_owned(),
+            }]))
+        );
+        assert_eq!(
+            spec.tags.as_ref().unwrap()[0]
+                .extension::<DisplayName>()
+                .unwrap(),
+            Some(DisplayName("Pets".to_owned()))
+        );
+        assert!(spec.validate(Options::new()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_extension() {
+        let extensions = Some(Extensions::from([(
+            GoogleBackend::NAME.to_owned(),
+            json!({"deadline": 1}),
+        )]));
+        assert!(extensions
+            .get_as::<GoogleBackend>(GoogleBackend::NAME)
+            .is_err());
+    }
+}
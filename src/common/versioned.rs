@@ -0,0 +1,104 @@
+//! Specifications of any supported version
+//!
+//! The version of a document is detected by the `openapi` or the `swagger` field
+//! and the document is parsed into the specification of that version,
+//! used by the entry points for the non-Rust callers, which accept any version.
+
+use enumset::EnumSet;
+
+use crate::common::parse::from_str;
+use crate::common::partial::read_version;
+use crate::validation::{Lints, Options, Validate};
+
+/// A parsed specification of any supported version.
+pub(crate) enum AnySpec {
+    #[cfg(feature = "v2")]
+    V2(crate::v2::spec::Spec),
+    #[cfg(feature = "v3_0")]
+    V3_0(crate::v3_0::spec::Spec),
+}
+
+impl AnySpec {
+    /// Parses the JSON document of one of the supported versions,
+    /// returns the version of the document, if any, with the error.
+    pub(crate) fn parse(json: &str) -> Result<Self, (Option<String>, String)> {
+        let version = read_version(json.as_bytes()).map_err(|e| (None, e.to_string()))?;
+        let spec = match version.as_deref() {
+            #[cfg(feature = "v2")]
+            Some(x) if crate::v2::spec::VERSIONS.contains(&x) => from_str(json).map(AnySpec::V2),
+            #[cfg(feature = "v3_0")]
+            Some(x) if crate::v3_0::spec::VERSIONS.contains(&x) => {
+                from_str(json).map(AnySpec::V3_0)
+            }
+            _ => {
+                let err = crate::Error::UnsupportedVersion(version.clone());
+                return Err((version, err.to_string()));
+            }
+        };
+        spec.map_err(|e| (version, e.to_string()))
+    }
+
+    /// Returns the version of the specification.
+    pub(crate) fn version(&self) -> String {
+        match self {
+            #[cfg(feature = "v2")]
+            AnySpec::V2(spec) => spec.swagger.to_string(),
+            #[cfg(feature = "v3_0")]
+            AnySpec::V3_0(spec) => spec.openapi.to_string(),
+        }
+    }
+
+    /// Validates the specification, returns the found errors.
+    pub(crate) fn validate_with_lints(
+        &self,
+        options: EnumSet<Options>,
+        lints: EnumSet<Lints>,
+    ) -> Result<(), Vec<String>> {
+        let result = match self {
+            #[cfg(feature = "v2")]
+            AnySpec::V2(spec) => spec.validate_with_lints(options, lints),
+            #[cfg(feature = "v3_0")]
+            AnySpec::V3_0(spec) => spec.validate_with_lints(options, lints),
+        };
+        result.map_err(|e| e.errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        for (json, expected) in [
+            (
+                r#"{"openapi": "3.0.9", "info": {"title": "foo", "version": "1"}, "paths": {}}"#,
+                Err((
+                    Some("3.0.9".to_owned()),
+                    "unsupported version: 3.0.9".to_owned(),
+                )),
+            ),
+            (
+                r#"{"info": {}}"#,
+                Err((None, "unsupported version: none".to_owned())),
+            ),
+            #[cfg(feature = "v3_0")]
+            (
+                r#"{"openapi": "3.0.3", "info": {"title": "foo", "version": "1"}, "paths": {}}"#,
+                Ok("3.0.3".to_owned()),
+            ),
+            #[cfg(feature = "v2")]
+            (
+                r#"{"swagger": "2.0", "info": {"title": "foo", "version": "1"}, "paths": {}}"#,
+                Ok("2.0".to_owned()),
+            ),
+        ] {
+            assert_eq!(
+                AnySpec::parse(json).map(|x| x.version()),
+                expected,
+                "{}",
+                json
+            );
+        }
+    }
+}
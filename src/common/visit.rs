@@ -0,0 +1,192 @@
+//! Document traversal
+//!
+//! The [Visit] trait has a callback per kind of object,
+//! all of them do nothing by default, so a visitor implements only the ones it needs.
+//! The [Walk] trait is implemented by the specification of each version
+//! and walks the whole document in the document order,
+//! passing the location of each object as a JSON Pointer fragment, e.g. `#/paths/~1pets/get`.
+//! The [VisitMut] and [WalkMut] traits are the mutable counterparts for in-place rewrites.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::http::Method;
+//! use roas::common::visit::{Visit, Walk};
+//! use roas::v3_0::operation::Operation;
+//! use roas::v3_0::spec::Spec;
+//!
+//! #[derive(Default)]
+//! struct Operations(Vec<String>);
+//!
+//! impl Visit<Spec> for Operations {
+//!     fn visit_operation(&mut self, path: &str, _method: &Method, _operation: &Operation) {
+//!         self.0.push(path.to_owned());
+//!     }
+//! }
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets": {"get": {"responses": {}}, "post": {"responses": {}}},
+//!     },
+//! })).unwrap();
+//!
+//! let mut visitor = Operations::default();
+//! spec.walk(&mut visitor);
+//! assert_eq!(visitor.0, vec!["#/paths/~1pets/get", "#/paths/~1pets/post"]);
+//! ```
+
+use crate::common::extensions::Extensions;
+use crate::common::http::Method;
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+use crate::common::path::escape_token;
+use crate::common::reference::Ref;
+
+/// Defines the types of the objects of a specification version.
+pub trait Document {
+    /// The Path Item Object.
+    type PathItem;
+
+    /// The Operation Object.
+    type Operation;
+
+    /// The Parameter Object.
+    type Parameter;
+
+    /// The Response Object.
+    type Response;
+
+    /// The Schema Object.
+    type Schema;
+}
+
+/// Visit is a set of callbacks called by [Walk::walk] for the objects of the document.
+///
+/// The callback of an object is called before the callbacks of its nested objects.
+/// The references are reported by [Visit::visit_reference] and are not followed.
+#[allow(unused_variables)]
+pub trait Visit<D: Document> {
+    /// Called for each path item, including the path items of the callbacks.
+    fn visit_path_item(&mut self, path: &str, path_item: &D::PathItem) {}
+
+    /// Called for each operation of a path item.
+    fn visit_operation(&mut self, path: &str, method: &Method, operation: &D::Operation) {}
+
+    /// Called for each parameter defined inline.
+    fn visit_parameter(&mut self, path: &str, parameter: &D::Parameter) {}
+
+    /// Called for each response defined inline.
+    fn visit_response(&mut self, path: &str, response: &D::Response) {}
+
+    /// Called for each schema defined inline, including the nested schemas.
+    fn visit_schema(&mut self, path: &str, schema: &D::Schema) {}
+
+    /// Called for each reference instead of the referenced object.
+    fn visit_reference(&mut self, path: &str, reference: &Ref) {}
+
+    /// Called for the specification extensions of the objects, if any.
+    /// The `path` is the location of the extended object.
+    fn visit_extensions(&mut self, path: &str, extensions: &Extensions) {}
+}
+
+/// Walk drives a visitor through the document.
+pub trait Walk: Document + Sized {
+    /// Walks the whole document in the document order.
+    fn walk<V: Visit<Self>>(&self, visitor: &mut V);
+}
+
+/// VisitMut is a set of callbacks called by [WalkMut::walk_mut] for the objects of the document.
+///
+/// The callback of an object is called before its nested objects are walked,
+/// so the nested objects added or changed by the callback are walked as well.
+/// The references are reported by [VisitMut::visit_reference_mut] and are not followed.
+///
+/// Example:
+///
+/// ```rust
+/// use roas::common::extensions::Extensions;
+/// use roas::common::visit::{VisitMut, WalkMut};
+/// use roas::v3_0::spec::Spec;
+///
+/// struct StripExtensions;
+///
+/// impl VisitMut<Spec> for StripExtensions {
+///     fn visit_extensions_mut(
+///         &mut self,
+///         _path: &str,
+///         extensions: &mut Option<Extensions>,
+///     ) {
+///         *extensions = None;
+///     }
+/// }
+///
+/// let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+///     "openapi": "3.0.3",
+///     "info": {"title": "foo", "version": "1"},
+///     "x-root": true,
+///     "paths": {"/pets": {"get": {"x-internal": true, "responses": {}}}},
+/// })).unwrap();
+///
+/// spec.walk_mut(&mut StripExtensions);
+/// assert_eq!(spec.extensions, None);
+/// assert_eq!(spec.paths["/pets"].get().unwrap().extensions, None);
+/// ```
+#[allow(unused_variables)]
+pub trait VisitMut<D: Document> {
+    /// Called for each path item, including the path items of the callbacks.
+    fn visit_path_item_mut(&mut self, path: &str, path_item: &mut D::PathItem) {}
+
+    /// Called for each operation of a path item.
+    fn visit_operation_mut(&mut self, path: &str, method: &Method, operation: &mut D::Operation) {}
+
+    /// Called for each parameter defined inline.
+    fn visit_parameter_mut(&mut self, path: &str, parameter: &mut D::Parameter) {}
+
+    /// Called for each response defined inline.
+    fn visit_response_mut(&mut self, path: &str, response: &mut D::Response) {}
+
+    /// Called for each schema defined inline, including the nested schemas.
+    fn visit_schema_mut(&mut self, path: &str, schema: &mut D::Schema) {}
+
+    /// Called for each reference instead of the referenced object.
+    fn visit_reference_mut(&mut self, path: &str, reference: &mut Ref) {}
+
+    /// Called for the specification extensions of each object that supports them,
+    /// including the objects without any, so the extensions can be added or removed.
+    /// The `path` is the location of the extended object.
+    fn visit_extensions_mut(&mut self, path: &str, extensions: &mut Option<Extensions>) {}
+}
+
+/// WalkMut drives a mutable visitor through the document.
+pub trait WalkMut: Document + Sized {
+    /// Walks the whole document in the document order.
+    fn walk_mut<V: VisitMut<Self>>(&mut self, visitor: &mut V);
+}
+
+/// Appends the token to the JSON Pointer, escaping `~` and `/`.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn pointer(path: &str, token: &str) -> String {
+    format!("{}/{}", path, escape_token(token))
+}
+
+/// Rewrites the references to an object and to the objects nested in it.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) struct RenameReference {
+    /// The old location, e.g. `#/components/schemas/Pet`.
+    pub(crate) from: String,
+
+    /// The new location, e.g. `#/components/schemas/Animal`.
+    pub(crate) to: String,
+}
+
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+impl<D: Document> VisitMut<D> for RenameReference {
+    fn visit_reference_mut(&mut self, _path: &str, reference: &mut Ref) {
+        if let Some(rest) = reference.reference.strip_prefix(&self.from) {
+            if rest.is_empty() || rest.starts_with('/') {
+                reference.reference = format!("{}{}", self.to, rest);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! The errors of the crate
+//!
+//! [Error] combines the errors of the parsing, the validation and the resolving of the references,
+//! so the library callers can handle all of them with a single type.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::spec::Spec;
+//! use roas::Error;
+//!
+//! let err = r#"{"swagger": "2.0", "info": {"title": "Pets", "version": "1"}, "paths": {}}"#
+//!     .parse::<Spec>()
+//!     .unwrap_err();
+//! assert!(matches!(err, Error::UnsupportedVersion(Some(ref x)) if x == "2.0"));
+//! ```
+
+use thiserror::Error;
+
+use crate::common::parse::ParseError;
+use crate::common::reference::ResolveError;
+
+/// Error is the error type of the crate.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Parse is returned when the document cannot be deserialized,
+    /// the error contains the location of the failed value.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    /// ValidationFailed is returned when the specification is not valid,
+    /// the error contains the report of all found errors.
+    #[error(transparent)]
+    ValidationFailed(#[from] crate::validation::Error),
+
+    /// UnsupportedVersion is returned when the version of the document is not supported by the type,
+    /// `None` if the document has no version.
+    #[error("unsupported version: {}", .0.as_deref().unwrap_or("none"))]
+    UnsupportedVersion(Option<String>),
+
+    /// Reference is returned when a reference cannot be resolved.
+    #[error(transparent)]
+    Reference(#[from] ResolveError),
+
+    /// Serialize is returned when a foreign document cannot be serialized to JSON
+    /// before it is converted.
+    #[error("cannot serialize the document: {0}")]
+    Serialize(#[source] serde_json::Error),
+}
+
+/// Parses the document and reports an unsupported version instead of the parse error,
+/// if the version is not one of the given ones.
+#[cfg(any(feature = "v2", feature = "v3_0"))]
+pub(crate) fn parse_versioned<T: serde::de::DeserializeOwned>(
+    s: &str,
+    versions: &[&str],
+) -> Result<T, Error> {
+    crate::common::parse::from_str(s).map_err(|err| {
+        match crate::common::partial::read_version(s.as_bytes()) {
+            Ok(version) if !version.as_deref().is_some_and(|x| versions.contains(&x)) => {
+                Error::UnsupportedVersion(version)
+            }
+            _ => Error::Parse(err),
+        }
+    })
+}
+
+/// Deserializes the value and reports an unsupported version instead of the parse error,
+/// if the version is not one of the given ones.
+#[cfg(feature = "v3_0")]
+pub(crate) fn from_value_versioned<T: serde::de::DeserializeOwned>(
+    value: &serde_json::Value,
+    versions: &[&str],
+) -> Result<T, Error> {
+    crate::common::parse::from_value(value).map_err(|err| {
+        let version = ["openapi", "swagger"]
+            .iter()
+            .find_map(|x| value.get(x))
+            .map(|x| x.as_str().map_or_else(|| x.to_string(), str::to_owned));
+        match version {
+            Some(ref x) if versions.contains(&x.as_str()) => Error::Parse(err),
+            _ => Error::UnsupportedVersion(version),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            Error::UnsupportedVersion(Some("3.1.0".to_owned())).to_string(),
+            "unsupported version: 3.1.0"
+        );
+        assert_eq!(
+            Error::UnsupportedVersion(None).to_string(),
+            "unsupported version: none"
+        );
+        assert_eq!(
+            Error::from(ResolveError::NotFound("#/definitions/Pet".to_owned())).to_string(),
+            "reference `#/definitions/Pet` not found"
+        );
+        assert_eq!(
+            Error::from(crate::validation::Error {
+                errors: vec!["#.info.title: must not be empty".to_owned()],
+            })
+            .to_string(),
+            "1 errors found:\n- #.info.title: must not be empty\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_from_value_versioned() {
+        let versions = ["1.0"];
+        let value = serde_json::json!({"openapi": "1.0", "title": true});
+        let err = from_value_versioned::<Doc>(&value, &versions).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "#.title: invalid type: boolean `true`, expected a string"
+        );
+
+        let value = serde_json::json!({"openapi": "2.0"});
+        let err = from_value_versioned::<Doc>(&value, &versions).unwrap_err();
+        assert!(
+            matches!(err, Error::UnsupportedVersion(Some(ref x)) if x == "2.0"),
+            "{}",
+            err
+        );
+
+        let value = serde_json::json!({"swagger": 2});
+        let err = from_value_versioned::<Doc>(&value, &versions).unwrap_err();
+        assert!(
+            matches!(err, Error::UnsupportedVersion(Some(ref x)) if x == "2"),
+            "{}",
+            err
+        );
+
+        let value = serde_json::json!({"openapi": "1.0", "title": "Pets"});
+        let doc = from_value_versioned::<Doc>(&value, &versions).unwrap();
+        assert_eq!(doc.title, "Pets");
+    }
+
+    #[test]
+    #[cfg(feature = "v3_0")]
+    fn test_convert_from_error() {
+        let document = std::collections::BTreeMap::from([((1, 2), "pair")]);
+        let err = crate::v3_0::spec::Spec::convert_from(&document).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)), "{:?}", err);
+        assert_eq!(
+            err.to_string(),
+            "cannot serialize the document: key must be a string"
+        );
+    }
+
+    #[cfg(feature = "v3_0")]
+    #[derive(Debug, serde::Deserialize)]
+    struct Doc {
+        title: String,
+    }
+}
@@ -1,10 +1,18 @@
 //! OpenAPI Specification
 
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod common;
+pub mod error;
 pub mod validation;
 
+pub use error::Error;
+
 #[cfg(feature = "v2")]
 pub mod v2;
 
 #[cfg(feature = "v3_0")]
 pub mod v3_0;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,11 +1,13 @@
 //! Header Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::formats::{CollectionFormat, IntegerFormat, NumberFormat, StringFormat};
-use crate::common::helpers::{validate_pattern, Context, ValidateWithContext};
+use crate::common::helpers::{
+    validate_extensions, validate_format, validate_pattern, Context, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::v2::items::Items;
 use crate::v2::spec::Spec;
 
@@ -69,7 +71,7 @@ pub struct StringHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -95,7 +97,7 @@ pub struct IntegerHeader {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<i64>,
+    pub minimum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly greater than the value of `minimum`
     #[serde(rename = "exclusiveMinimum")]
@@ -104,7 +106,7 @@ pub struct IntegerHeader {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<i64>,
+    pub maximum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly less than the value of `maximum`
     #[serde(rename = "exclusiveMaximum")]
@@ -122,7 +124,7 @@ pub struct IntegerHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -139,12 +141,12 @@ pub struct NumberHeader {
     ///
     /// **Note**: "default" has no meaning for required headers.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<f64>,
+    pub default: Option<serde_json::Number>,
 
     /// The list of strings that defines the possible values of this parameter.
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<f64>>,
+    pub enum_values: Option<Vec<serde_json::Number>>,
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -175,7 +177,7 @@ pub struct NumberHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -196,7 +198,7 @@ pub struct BooleanHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -237,11 +239,11 @@ pub struct ArrayHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for Header {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             Header::String(header) => header.validate_with_context(ctx, path),
             Header::Integer(header) => header.validate_with_context(ctx, path),
@@ -253,34 +255,64 @@ impl ValidateWithContext<Spec> for Header {
 }
 
 impl ValidateWithContext<Spec> for StringHeader {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_format(&self.format, ctx, path.field("format"));
         if let Some(pattern) = &self.pattern {
-            validate_pattern(pattern, ctx, format!("{}.pattern", path));
+            validate_pattern(pattern, ctx, path.field("pattern"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for IntegerHeader {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
 }
 
 impl ValidateWithContext<Spec> for NumberHeader {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
 }
 
 impl ValidateWithContext<Spec> for BooleanHeader {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
+}
+
+impl ArrayHeader {
+    /// Splits the raw values of the header according to the collection format,
+    /// see [CollectionFormat::split] for details.
+    pub fn split_values<'a>(&self, raw: &[&'a str]) -> Vec<&'a str> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .split(raw)
+    }
+
+    /// Joins the values of the header according to the collection format,
+    /// see [CollectionFormat::join] for details.
+    pub fn join_values<S: AsRef<str>>(&self, values: &[S]) -> Vec<String> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .join(values)
+    }
 }
 
 impl ValidateWithContext<Spec> for ArrayHeader {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        self.items
-            .validate_with_context(ctx, format!("{}.items", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        self.items.validate_with_context(ctx, path.field("items"));
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use crate::v2::items::StringItem;
 
     use super::*;
@@ -336,9 +368,9 @@ mod tests {
                 format: Some(IntegerFormat::Int32),
                 default: Some(5.to_owned()),
                 enum_values: Some(vec![5.to_owned()]),
-                maximum: Some(10),
+                maximum: Some(10.into()),
                 exclusive_maximum: Some(true),
-                minimum: Some(1),
+                minimum: Some(1.into()),
                 exclusive_minimum: Some(true),
                 multiple_of: Some(1.0),
                 extensions: Some({
@@ -367,8 +399,8 @@ mod tests {
             Header::Number(NumberHeader {
                 description: Some("A short description of the header.".to_owned()),
                 format: Some(NumberFormat::Double),
-                default: Some(5.0.to_owned()),
-                enum_values: Some(vec![5.0.to_owned()]),
+                default: serde_json::Number::from_f64(5.0),
+                enum_values: Some(vec![serde_json::Number::from_f64(5.0).unwrap()]),
                 maximum: Some(10.0),
                 exclusive_maximum: Some(true),
                 minimum: Some(1.0),
@@ -471,9 +503,9 @@ mod tests {
                 format: Some(IntegerFormat::Int32),
                 default: Some(5.to_owned()),
                 enum_values: Some(vec![5.to_owned()]),
-                maximum: Some(10),
+                maximum: Some(10.into()),
                 exclusive_maximum: Some(true),
-                minimum: Some(1),
+                minimum: Some(1.into()),
                 exclusive_minimum: Some(true),
                 multiple_of: Some(1.0),
                 extensions: Some({
@@ -502,8 +534,8 @@ mod tests {
             serde_json::to_value(Header::Number(NumberHeader {
                 description: Some("A short description of the header.".to_owned()),
                 format: Some(NumberFormat::Double),
-                default: Some(5.0.to_owned()),
-                enum_values: Some(vec![5.0.to_owned()]),
+                default: serde_json::Number::from_f64(5.0),
+                enum_values: Some(vec![serde_json::Number::from_f64(5.0).unwrap()]),
                 maximum: Some(10.0),
                 exclusive_maximum: Some(true),
                 minimum: Some(1.0),
@@ -1,12 +1,14 @@
 //! Metadata about the API.
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::helpers::{
-    validate_email, validate_optional_url, validate_required_string, Context, ValidateWithContext,
+    validate_email, validate_extensions, validate_optional_url, validate_required_string, Context,
+    ValidateWithContext,
 };
+use crate::common::path::Path;
+use crate::common::url::{parse_optional, Url, UrlError};
 use crate::v2::spec::Spec;
 
 /// The object provides metadata about the API.
@@ -58,7 +60,7 @@ pub struct Info {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Contact information for the exposed API.
@@ -92,7 +94,7 @@ pub struct Contact {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// License information for the exposed API.
@@ -121,35 +123,53 @@ pub struct License {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for Info {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.title, ctx, format!("{}.title", path));
-        validate_required_string(&self.version, ctx, format!("{}.version", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.title, ctx, path.field("title"));
+        validate_required_string(&self.version, ctx, path.field("version"));
+        validate_optional_url(&self.terms_of_service, ctx, path.field("termsOfService"));
 
         if let Some(contact) = &self.contact {
-            contact.validate_with_context(ctx, format!("{}.contact", path));
+            contact.validate_with_context(ctx, path.field("contact"));
         }
 
         if let Some(license) = &self.license {
-            license.validate_with_context(ctx, format!("{}.license", path));
+            license.validate_with_context(ctx, path.field("license"));
         }
     }
 }
 
+impl Contact {
+    /// Returns the URL of the contact information, an error if it is not a valid URL.
+    pub fn parsed_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.url)
+    }
+}
+
 impl ValidateWithContext<Spec> for Contact {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_optional_url(&self.url, ctx, format!("{}.url", path));
-        validate_email(&self.email, ctx, format!("{}.email", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_optional_url(&self.url, ctx, path.field("url"));
+        validate_email(&self.email, ctx, path.field("email"));
+    }
+}
+
+impl License {
+    /// Returns the URL of the license, an error if it is not a valid URL.
+    pub fn parsed_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.url)
     }
 }
 
 impl ValidateWithContext<Spec> for License {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
-        validate_optional_url(&self.url, ctx, format!("{}.url", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
+        validate_optional_url(&self.url, ctx, path.field("url"));
     }
 }
 
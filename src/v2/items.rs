@@ -1,11 +1,13 @@
 //! Item Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::formats::{CollectionFormat, IntegerFormat, NumberFormat, StringFormat};
-use crate::common::helpers::{validate_pattern, Context, ValidateWithContext};
+use crate::common::helpers::{
+    validate_extensions, validate_format, validate_pattern, Context, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::v2::spec::Spec;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -70,7 +72,7 @@ pub struct StringItem {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -92,7 +94,7 @@ pub struct IntegerItem {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<i64>,
+    pub minimum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly greater than the value of `minimum`
     #[serde(rename = "exclusiveMinimum")]
@@ -101,7 +103,7 @@ pub struct IntegerItem {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<i64>,
+    pub maximum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly less than the value of `maximum`
     #[serde(rename = "exclusiveMaximum")]
@@ -119,7 +121,7 @@ pub struct IntegerItem {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -132,12 +134,12 @@ pub struct NumberItem {
     ///
     /// **Note**: "default" has no meaning for required headers.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<f64>,
+    pub default: Option<serde_json::Number>,
 
     /// The list of strings that defines the possible values of this parameter.
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<f64>>,
+    pub enum_values: Option<Vec<serde_json::Number>>,
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -168,7 +170,7 @@ pub struct NumberItem {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -185,7 +187,7 @@ pub struct BooleanItem {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -223,11 +225,11 @@ pub struct ArrayItem {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for Items {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             Items::String(item) => item.validate_with_context(ctx, path),
             Items::Integer(item) => item.validate_with_context(ctx, path),
@@ -239,34 +241,64 @@ impl ValidateWithContext<Spec> for Items {
 }
 
 impl ValidateWithContext<Spec> for StringItem {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_format(&self.format, ctx, path.field("format"));
         if let Some(pattern) = &self.pattern {
-            validate_pattern(pattern, ctx, format!("{}.pattern", path));
+            validate_pattern(pattern, ctx, path.field("pattern"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for IntegerItem {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
 }
 
 impl ValidateWithContext<Spec> for NumberItem {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
 }
 
 impl ValidateWithContext<Spec> for BooleanItem {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path);
+    }
+}
+
+impl ArrayItem {
+    /// Splits the raw values of the item according to the collection format,
+    /// see [CollectionFormat::split] for details.
+    pub fn split_values<'a>(&self, raw: &[&'a str]) -> Vec<&'a str> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .split(raw)
+    }
+
+    /// Joins the values of the item according to the collection format,
+    /// see [CollectionFormat::join] for details.
+    pub fn join_values<S: AsRef<str>>(&self, values: &[S]) -> Vec<String> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .join(values)
+    }
 }
 
 impl ValidateWithContext<Spec> for ArrayItem {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        self.items
-            .validate_with_context(ctx, format!("{}.items", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        self.items.validate_with_context(ctx, path.field("items"));
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
 
     #[test]
@@ -303,7 +335,7 @@ mod tests {
     #[test]
     fn test_string_items_serialize() {
         assert_eq!(
-            serde_json::to_value(&Items::String(StringItem {
+            serde_json::to_value(Items::String(StringItem {
                 format: Some(StringFormat::Byte),
                 default: Some(String::from("default")),
                 enum_values: Some(vec![String::from("enum1"), String::from("enum2")]),
@@ -351,9 +383,9 @@ mod tests {
                 format: Some(IntegerFormat::Int64),
                 default: Some(42),
                 enum_values: Some(vec![42, 105]),
-                minimum: Some(1),
+                minimum: Some(1.into()),
                 exclusive_minimum: Some(true),
-                maximum: Some(10),
+                maximum: Some(10.into()),
                 exclusive_maximum: Some(true),
                 multiple_of: Some(2.0),
                 extensions: Some({
@@ -369,13 +401,13 @@ mod tests {
     #[test]
     fn test_integer_items_serialize() {
         assert_eq!(
-            serde_json::to_value(&Items::Integer(IntegerItem {
+            serde_json::to_value(Items::Integer(IntegerItem {
                 format: Some(IntegerFormat::Int64),
                 default: Some(42),
                 enum_values: Some(vec![42, 105]),
-                minimum: Some(1),
+                minimum: Some(1.into()),
                 exclusive_minimum: Some(true),
-                maximum: Some(10),
+                maximum: Some(10.into()),
                 exclusive_maximum: Some(true),
                 multiple_of: Some(2.0),
                 extensions: Some({
@@ -419,8 +451,11 @@ mod tests {
             .unwrap(),
             Items::Number(NumberItem {
                 format: Some(NumberFormat::Double),
-                default: Some(42.0),
-                enum_values: Some(vec![42.0, 105.0]),
+                default: serde_json::Number::from_f64(42.0),
+                enum_values: Some(vec![
+                    serde_json::Number::from_f64(42.0).unwrap(),
+                    serde_json::Number::from_f64(105.0).unwrap()
+                ]),
                 minimum: Some(1.0),
                 exclusive_minimum: Some(true),
                 maximum: Some(10.0),
@@ -439,10 +474,13 @@ mod tests {
     #[test]
     fn test_number_items_serialize() {
         assert_eq!(
-            serde_json::to_value(&Items::Number(NumberItem {
+            serde_json::to_value(Items::Number(NumberItem {
                 format: Some(NumberFormat::Double),
-                default: Some(42.0),
-                enum_values: Some(vec![42.0, 105.0]),
+                default: serde_json::Number::from_f64(42.0),
+                enum_values: Some(vec![
+                    serde_json::Number::from_f64(42.0).unwrap(),
+                    serde_json::Number::from_f64(105.0).unwrap()
+                ]),
                 minimum: Some(1.0),
                 exclusive_minimum: Some(true),
                 maximum: Some(10.0),
@@ -495,7 +533,7 @@ mod tests {
     #[test]
     fn test_boolean_items_serialize() {
         assert_eq!(
-            serde_json::to_value(&Items::Boolean(BooleanItem {
+            serde_json::to_value(Items::Boolean(BooleanItem {
                 default: Some(true),
                 extensions: Some({
                     let mut map = BTreeMap::new();
@@ -553,7 +591,7 @@ mod tests {
     #[test]
     fn test_array_items_serialize() {
         assert_eq!(
-            serde_json::to_value(&Items::Array(ArrayItem {
+            serde_json::to_value(Items::Array(ArrayItem {
                 items: Box::new(Items::Number(NumberItem {
                     format: Some(NumberFormat::Double),
                     ..Default::default()
@@ -586,4 +624,21 @@ mod tests {
             "serialize",
         );
     }
+
+    #[test]
+    fn test_array_item_split_join() {
+        let item = serde_json::from_value::<ArrayItem>(serde_json::json!({
+            "items": {"type": "string"},
+            "collectionFormat": "pipes",
+        }))
+        .unwrap();
+        assert_eq!(item.split_values(&["a|b"]), vec!["a", "b"], "split pipes");
+        assert_eq!(item.join_values(&["a", "b"]), vec!["a|b"], "join pipes");
+
+        let item = ArrayItem {
+            collection_format: None,
+            ..item
+        };
+        assert_eq!(item.split_values(&["a,b"]), vec!["a", "b"], "split default");
+    }
 }
@@ -6,12 +6,15 @@ pub mod external_documentation;
 pub mod header;
 pub mod info;
 pub mod items;
+pub mod normalize;
 pub mod operation;
 pub mod parameter;
 pub mod path_item;
+pub mod rename;
 pub mod response;
 pub mod schema;
 pub mod security_scheme;
 pub mod spec;
 pub mod tag;
+pub mod visit;
 pub mod xml;
@@ -0,0 +1,121 @@
+//! Normalization of the v2 documents, see [crate::common::normalize] for details.
+
+use crate::common::extensions::Extensions;
+use crate::common::http::Method;
+use crate::common::normalize::{
+    drop_empty, lowercase_methods, normalize_paths, NormalizeError, NormalizeOptions,
+};
+use crate::common::visit::{VisitMut, WalkMut};
+use crate::v2::operation::Operation;
+use crate::v2::path_item::PathItem;
+use crate::v2::response::Response;
+use crate::v2::schema::Schema;
+use crate::v2::spec::Spec;
+
+impl Spec {
+    /// Rewrites the specification into the canonical form,
+    /// the specification is not changed if the normalization fails.
+    pub fn normalize(&mut self, options: NormalizeOptions) -> Result<(), NormalizeError> {
+        normalize_paths(&mut self.paths, &options.trailing_slash)?;
+        self.walk_mut(&mut Normalizer);
+        drop_empty(&mut self.schemes);
+        drop_empty(&mut self.consumes);
+        drop_empty(&mut self.produces);
+        drop_empty(&mut self.definitions);
+        drop_empty(&mut self.parameters);
+        drop_empty(&mut self.responses);
+        drop_empty(&mut self.security_definitions);
+        drop_empty(&mut self.tags);
+        Ok(())
+    }
+}
+
+struct Normalizer;
+
+impl VisitMut<Spec> for Normalizer {
+    fn visit_path_item_mut(&mut self, _path: &str, path_item: &mut PathItem) {
+        lowercase_methods(&mut path_item.operations);
+        drop_empty(&mut path_item.operations);
+        drop_empty(&mut path_item.parameters);
+    }
+
+    fn visit_operation_mut(&mut self, _path: &str, _method: &Method, operation: &mut Operation) {
+        drop_empty(&mut operation.tags);
+        drop_empty(&mut operation.consumes);
+        drop_empty(&mut operation.produces);
+        drop_empty(&mut operation.parameters);
+        drop_empty(&mut operation.schemes);
+        drop_empty(&mut operation.responses.responses);
+    }
+
+    fn visit_response_mut(&mut self, _path: &str, response: &mut Response) {
+        drop_empty(&mut response.headers);
+        drop_empty(&mut response.examples);
+    }
+
+    fn visit_schema_mut(&mut self, _path: &str, schema: &mut Schema) {
+        if let Schema::Object(s) = schema {
+            drop_empty(&mut s.properties);
+            drop_empty(&mut s.required);
+        }
+    }
+
+    fn visit_extensions_mut(&mut self, _path: &str, extensions: &mut Option<Extensions>) {
+        drop_empty(extensions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::normalize::TrailingSlash;
+
+    #[test]
+    fn test_normalize() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "consumes": [],
+            "paths": {
+                "/pets/": {
+                    "get": {
+                        "produces": [],
+                        "security": [],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "headers": {},
+                                "schema": {"type": "object", "properties": {}, "required": []},
+                            },
+                        },
+                    },
+                },
+            },
+            "definitions": {},
+        }))
+        .unwrap();
+        spec.normalize(NormalizeOptions {
+            trailing_slash: TrailingSlash::Remove,
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap(),
+            json!({
+                "swagger": "2.0",
+                "info": {"title": "foo", "version": "1"},
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "security": [],
+                            "responses": {
+                                "200": {"description": "pets", "schema": {"type": "object"}},
+                            },
+                        },
+                    },
+                },
+            }),
+        );
+    }
+}
@@ -4,10 +4,14 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, PushError, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v2::external_documentation::ExternalDocumentation;
-use crate::v2::parameter::Parameter;
+use crate::v2::parameter::{InFormData, Parameter};
 use crate::v2::response::Responses;
 use crate::v2::spec::{Scheme, Spec};
 use crate::v2::tag::Tag;
@@ -95,25 +99,84 @@ pub struct Operation {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
-impl ValidateWithContext<Spec> for Operation {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        if let Some(operation_id) = &self.operation_id {
-            if !ctx
-                .visited
-                .insert(format!("#/paths/operations/{}", operation_id))
-            {
-                ctx.error(
-                    path.clone(),
-                    format_args!("operationId `{}` already exists", operation_id),
-                );
+impl Operation {
+    /// Returns `true` if the operation, or the specification when the operation does not override it,
+    /// consumes a form data media type.
+    fn consumes_form_data(&self, spec: &Spec) -> bool {
+        self.consumes
+            .as_ref()
+            .or(spec.consumes.as_ref())
+            .into_iter()
+            .flatten()
+            .any(|x| {
+                let essence = x.split(';').next().unwrap_or_default().trim();
+                essence.eq_ignore_ascii_case("multipart/form-data")
+                    || essence.eq_ignore_ascii_case("application/x-www-form-urlencoded")
+            })
+    }
+
+    /// Validates the body and formData parameters of the operation together with the parameters
+    /// of the path item, the operation parameters override the path item ones by location and name.
+    pub(crate) fn validate_parameters(
+        &self,
+        common: &Option<Vec<RefOr<Parameter>>>,
+        ctx: &mut Context<Spec>,
+        path: Path,
+    ) {
+        let parameters: BTreeMap<_, _> = common
+            .iter()
+            .flatten()
+            .chain(self.parameters.iter().flatten())
+            .filter_map(|x| x.get_item(ctx.spec).ok())
+            .map(|x| ((x.location(), x.name()), x))
+            .collect();
+        let mut body_count = 0;
+        let mut form_data = false;
+        let mut file = false;
+        for parameter in parameters.values() {
+            match parameter {
+                Parameter::Body(_) => body_count += 1,
+                Parameter::FormData(p) => {
+                    form_data = true;
+                    file |= matches!(p, InFormData::File(_));
+                }
+                _ => {}
             }
         }
+        if body_count > 1 {
+            ctx.error(
+                path.clone(),
+                format_args!(
+                    ".parameters: only one body parameter allowed, found {}",
+                    body_count,
+                ),
+            );
+        }
+        if body_count > 0 && form_data {
+            ctx.error(
+                path.clone(),
+                ".parameters: body and formData parameters must not be used together",
+            );
+        }
+        if file && !self.consumes_form_data(ctx.spec) {
+            ctx.error(
+                path,
+                ".consumes: must include `multipart/form-data` or `application/x-www-form-urlencoded` for the file parameters",
+            );
+        }
+    }
+}
+
+impl ValidateWithContext<Spec> for Operation {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        // do not validate operation_id, it is already validated in Spec
         if let Some(tags) = &self.tags {
             for (i, tag) in tags.iter().enumerate() {
-                validate_required_string(tag, ctx, format!("{}.tags[{}]", path, i));
+                validate_required_string(tag, ctx, path.field("tags").index(i));
                 if tag.is_empty() {
                     continue;
                 }
@@ -121,7 +184,7 @@ impl ValidateWithContext<Spec> for Operation {
                 let reference = format!("#/tags/{}", tag);
                 if let Ok(spec_tag) = RefOr::<Tag>::new_ref(reference.clone()).get_item(ctx.spec) {
                     if ctx.visit(reference.clone()) {
-                        spec_tag.validate_with_context(ctx, reference);
+                        spec_tag.validate_with_context(ctx, reference.into());
                     }
                 } else if !ctx.is_option(Options::IgnoreMissingTags) {
                     ctx.error(
@@ -133,26 +196,13 @@ impl ValidateWithContext<Spec> for Operation {
         }
 
         if let Some(parameters) = &self.parameters {
-            let mut body_count = 0;
-            for (i, parameter) in parameters.clone().iter().enumerate() {
-                parameter.validate_with_context(ctx, format!("{}.parameters[{}]", path, i));
-                if let RefOr::Item(Parameter::Body(_)) = parameter {
-                    body_count += 1;
-                }
-            }
-            if body_count > 1 {
-                ctx.error(
-                    path.clone(),
-                    format_args!(
-                        ".parameters: only one body parameter allowed, found {}",
-                        body_count,
-                    ),
-                );
+            for (i, parameter) in parameters.iter().enumerate() {
+                parameter.validate_with_context(ctx, path.field("parameters").index(i));
             }
         }
 
         self.responses
-            .validate_with_context(ctx, format!("{}.responses", path));
+            .validate_with_context(ctx, path.field("responses"));
     }
 }
 
@@ -410,4 +460,54 @@ mod tests {
             "serialization"
         );
     }
+
+    #[test]
+    fn validate_form_data() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "consumes": ["multipart/form-data; charset=utf-8"],
+            "paths": {},
+            "parameters": {
+                "file": {"name": "file", "in": "formData", "type": "file"},
+            },
+        }))
+        .unwrap();
+        let validate = |operation: serde_json::Value| {
+            let mut ctx = Context::new(&spec, Options::new());
+            let operation = serde_json::from_value::<Operation>(operation).unwrap();
+            operation.validate_with_context(&mut ctx, "op".into());
+            operation.validate_parameters(&None, &mut ctx, "op".into());
+            ctx.errors
+        };
+
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [{"$ref": "#/parameters/file"}],
+                "responses": {"200": {"description": "ok"}},
+            })),
+            Vec::<String>::new(),
+            "global consumes",
+        );
+        assert_eq!(
+            validate(serde_json::json!({
+                "consumes": ["application/json"],
+                "parameters": [{"$ref": "#/parameters/file"}],
+                "responses": {"200": {"description": "ok"}},
+            })),
+            vec!["op.consumes: must include `multipart/form-data` or `application/x-www-form-urlencoded` for the file parameters"],
+            "overridden consumes",
+        );
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [
+                    {"name": "name", "in": "formData", "type": "string"},
+                    {"name": "pet", "in": "body", "schema": {"type": "object"}},
+                ],
+                "responses": {"200": {"description": "ok"}},
+            })),
+            vec!["op.parameters: body and formData parameters must not be used together"],
+            "body and formData",
+        );
+    }
 }
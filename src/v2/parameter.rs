@@ -1,19 +1,21 @@
 //! Parameter Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::formats::{CollectionFormat, IntegerFormat, NumberFormat, StringFormat};
 use crate::common::helpers::{
-    validate_pattern, validate_required_string, Context, ValidateWithContext,
+    validate_extensions, validate_format, validate_pattern, validate_required_string, Context,
+    ValidateWithContext,
 };
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v2::items::Items;
 use crate::v2::schema::Schema;
 use crate::v2::spec::Spec;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 #[serde(tag = "in")]
 pub enum Parameter {
     #[serde(rename = "body")]
@@ -57,7 +59,7 @@ pub struct InBody {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -197,7 +199,7 @@ pub struct StringParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -240,7 +242,7 @@ pub struct IntegerParameter {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<i64>,
+    pub minimum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly greater than the value of `minimum`
     #[serde(rename = "exclusiveMinimum")]
@@ -249,7 +251,7 @@ pub struct IntegerParameter {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<i64>,
+    pub maximum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly less than the value of `maximum`
     #[serde(rename = "exclusiveMaximum")]
@@ -267,7 +269,7 @@ pub struct IntegerParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -301,12 +303,12 @@ pub struct NumberParameter {
     ///
     /// **Note**: "default" has no meaning for required parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<f64>,
+    pub default: Option<serde_json::Number>,
 
     /// The list of strings that defines the possible values of this parameter.
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<f64>>,
+    pub enum_values: Option<Vec<serde_json::Number>>,
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -337,7 +339,7 @@ pub struct NumberParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -375,7 +377,7 @@ pub struct BooleanParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -434,7 +436,7 @@ pub struct ArrayParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -465,11 +467,108 @@ pub struct FileParameter {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Parameter {
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &str {
+        match self {
+            Parameter::Body(p) => &p.name,
+            Parameter::Header(InHeader::String(p))
+            | Parameter::Query(InQuery::String(p))
+            | Parameter::Path(InPath::String(p))
+            | Parameter::FormData(InFormData::String(p)) => &p.name,
+            Parameter::Header(InHeader::Integer(p))
+            | Parameter::Query(InQuery::Integer(p))
+            | Parameter::Path(InPath::Integer(p))
+            | Parameter::FormData(InFormData::Integer(p)) => &p.name,
+            Parameter::Header(InHeader::Number(p))
+            | Parameter::Query(InQuery::Number(p))
+            | Parameter::Path(InPath::Number(p))
+            | Parameter::FormData(InFormData::Number(p)) => &p.name,
+            Parameter::Header(InHeader::Boolean(p))
+            | Parameter::Query(InQuery::Boolean(p))
+            | Parameter::Path(InPath::Boolean(p))
+            | Parameter::FormData(InFormData::Boolean(p)) => &p.name,
+            Parameter::Header(InHeader::Array(p))
+            | Parameter::Query(InQuery::Array(p))
+            | Parameter::Path(InPath::Array(p))
+            | Parameter::FormData(InFormData::Array(p)) => &p.name,
+            Parameter::FormData(InFormData::File(p)) => &p.name,
+        }
+    }
+
+    /// Returns the location of the parameter, the value of the `in` field.
+    pub fn location(&self) -> &str {
+        match self {
+            Parameter::Body(_) => "body",
+            Parameter::Header(_) => "header",
+            Parameter::Query(_) => "query",
+            Parameter::Path(_) => "path",
+            Parameter::FormData(_) => "formData",
+        }
+    }
+
+    /// Returns the specification extensions of the parameter.
+    pub fn extensions(&self) -> Option<&Extensions> {
+        match self {
+            Parameter::Body(p) => p.extensions.as_ref(),
+            Parameter::Header(InHeader::String(p))
+            | Parameter::Query(InQuery::String(p))
+            | Parameter::Path(InPath::String(p))
+            | Parameter::FormData(InFormData::String(p)) => p.extensions.as_ref(),
+            Parameter::Header(InHeader::Integer(p))
+            | Parameter::Query(InQuery::Integer(p))
+            | Parameter::Path(InPath::Integer(p))
+            | Parameter::FormData(InFormData::Integer(p)) => p.extensions.as_ref(),
+            Parameter::Header(InHeader::Number(p))
+            | Parameter::Query(InQuery::Number(p))
+            | Parameter::Path(InPath::Number(p))
+            | Parameter::FormData(InFormData::Number(p)) => p.extensions.as_ref(),
+            Parameter::Header(InHeader::Boolean(p))
+            | Parameter::Query(InQuery::Boolean(p))
+            | Parameter::Path(InPath::Boolean(p))
+            | Parameter::FormData(InFormData::Boolean(p)) => p.extensions.as_ref(),
+            Parameter::Header(InHeader::Array(p))
+            | Parameter::Query(InQuery::Array(p))
+            | Parameter::Path(InPath::Array(p))
+            | Parameter::FormData(InFormData::Array(p)) => p.extensions.as_ref(),
+            Parameter::FormData(InFormData::File(p)) => p.extensions.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the specification extensions of the parameter.
+    pub fn extensions_mut(&mut self) -> &mut Option<Extensions> {
+        match self {
+            Parameter::Body(p) => &mut p.extensions,
+            Parameter::Header(InHeader::String(p))
+            | Parameter::Query(InQuery::String(p))
+            | Parameter::Path(InPath::String(p))
+            | Parameter::FormData(InFormData::String(p)) => &mut p.extensions,
+            Parameter::Header(InHeader::Integer(p))
+            | Parameter::Query(InQuery::Integer(p))
+            | Parameter::Path(InPath::Integer(p))
+            | Parameter::FormData(InFormData::Integer(p)) => &mut p.extensions,
+            Parameter::Header(InHeader::Number(p))
+            | Parameter::Query(InQuery::Number(p))
+            | Parameter::Path(InPath::Number(p))
+            | Parameter::FormData(InFormData::Number(p)) => &mut p.extensions,
+            Parameter::Header(InHeader::Boolean(p))
+            | Parameter::Query(InQuery::Boolean(p))
+            | Parameter::Path(InPath::Boolean(p))
+            | Parameter::FormData(InFormData::Boolean(p)) => &mut p.extensions,
+            Parameter::Header(InHeader::Array(p))
+            | Parameter::Query(InQuery::Array(p))
+            | Parameter::Path(InPath::Array(p))
+            | Parameter::FormData(InFormData::Array(p)) => &mut p.extensions,
+            Parameter::FormData(InFormData::File(p)) => &mut p.extensions,
+        }
+    }
 }
 
 impl ValidateWithContext<Spec> for Parameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             Parameter::Body(p) => p.validate_with_context(ctx, path),
             Parameter::Header(p) => p.validate_with_context(ctx, path),
@@ -481,21 +580,21 @@ impl ValidateWithContext<Spec> for Parameter {
 }
 
 impl ValidateWithContext<Spec> for InBody {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}name", path));
-        self.schema
-            .validate_with_context(ctx, format!("{}.schema", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
+        self.schema.validate_with_context(ctx, path.field("schema"));
     }
 }
 
 impl ValidateWithContext<Spec> for InHeader {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             InHeader::String(p) => {
                 p.validate_with_context(ctx, path.clone());
                 must_not_allow_empty_value(&p.allow_empty_value, ctx, path.clone(), p.name.clone());
                 if let Some(pattern) = &p.pattern {
-                    validate_pattern(pattern, ctx, format!("{}.pattern", path));
+                    validate_pattern(pattern, ctx, path.field("pattern"));
                 }
             }
             InHeader::Integer(p) => {
@@ -519,7 +618,7 @@ impl ValidateWithContext<Spec> for InHeader {
 }
 
 impl ValidateWithContext<Spec> for InQuery {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             InQuery::String(p) => {
                 p.validate_with_context(ctx, path);
@@ -541,7 +640,7 @@ impl ValidateWithContext<Spec> for InQuery {
 }
 
 impl ValidateWithContext<Spec> for InPath {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             InPath::String(p) => {
                 must_be_required(&p.required, ctx, path.clone(), p.name.clone());
@@ -568,7 +667,7 @@ impl ValidateWithContext<Spec> for InPath {
 }
 
 impl ValidateWithContext<Spec> for InFormData {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             InFormData::String(p) => {
                 p.validate_with_context(ctx, path);
@@ -593,54 +692,76 @@ impl ValidateWithContext<Spec> for InFormData {
 }
 
 impl ValidateWithContext<Spec> for StringParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
+        validate_format(&self.format, ctx, path.field("format"));
     }
 }
 
 impl ValidateWithContext<Spec> for IntegerParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
 impl ValidateWithContext<Spec> for NumberParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
 impl ValidateWithContext<Spec> for BooleanParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
+    }
+}
+
+impl ArrayParameter {
+    /// Splits the raw values of the parameter according to the collection format,
+    /// see [CollectionFormat::split] for details.
+    pub fn split_values<'a>(&self, raw: &[&'a str]) -> Vec<&'a str> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .split(raw)
+    }
+
+    /// Joins the values of the parameter according to the collection format,
+    /// see [CollectionFormat::join] for details.
+    pub fn join_values<S: AsRef<str>>(&self, values: &[S]) -> Vec<String> {
+        self.collection_format
+            .clone()
+            .unwrap_or_default()
+            .join(values)
     }
 }
 
 impl ValidateWithContext<Spec> for ArrayParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
 impl ValidateWithContext<Spec> for FileParameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
-fn must_be_required(p: &Option<bool>, ctx: &mut Context<Spec>, path: String, name: String) {
+fn must_be_required(p: &Option<bool>, ctx: &mut Context<Spec>, path: Path, name: String) {
     if !p.is_some_and(|x| x) {
         ctx.errors
             .push(format!("{}.{}: must be required", path, name));
     }
 }
 
-fn must_not_allow_empty_value(
-    p: &Option<bool>,
-    ctx: &mut Context<Spec>,
-    path: String,
-    name: String,
-) {
+fn must_not_allow_empty_value(p: &Option<bool>, ctx: &mut Context<Spec>, path: Path, name: String) {
     if p.is_some_and(|x| x) {
         ctx.errors
             .push(format!("{}.{}: must not allow empty value", path, name));
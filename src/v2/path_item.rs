@@ -1,5 +1,6 @@
 //! Path Items
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt;
 
@@ -7,7 +8,10 @@ use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, Context, ValidateWithContext};
+use crate::common::http::Method;
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v2::operation::Operation;
 use crate::v2::parameter::Parameter;
@@ -88,7 +92,99 @@ pub struct PathItem {
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl PathItem {
+    /// Returns the operation for the given HTTP method.
+    pub fn operation(&self, method: &Method) -> Option<&Operation> {
+        self.operations
+            .as_ref()
+            .and_then(|x| x.get(method.as_str()))
+    }
+
+    /// Returns the mutable operation for the given HTTP method.
+    pub fn operation_mut(&mut self, method: &Method) -> Option<&mut Operation> {
+        self.operations
+            .as_mut()
+            .and_then(|x| x.get_mut(method.as_str()))
+    }
+
+    /// Inserts the operation for the given HTTP method and returns the previous one.
+    pub fn insert(&mut self, method: Method, operation: Operation) -> Option<Operation> {
+        self.operations
+            .get_or_insert_with(BTreeMap::new)
+            .insert(method.to_string(), operation)
+    }
+
+    /// Returns the entry of the operation for the given HTTP method for in-place manipulation.
+    pub fn entry(&mut self, method: Method) -> Entry<'_, String, Operation> {
+        self.operations
+            .get_or_insert_with(BTreeMap::new)
+            .entry(method.to_string())
+    }
+
+    /// Returns an iterator over the HTTP methods and operations, ordered by the method key.
+    pub fn iter(&self) -> impl Iterator<Item = (Method, &Operation)> {
+        self.operations
+            .iter()
+            .flatten()
+            .map(|(method, operation)| (Method::from(method.as_str()), operation))
+    }
+
+    /// Returns an iterator over the HTTP methods and mutable operations,
+    /// ordered by the method key.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Method, &mut Operation)> {
+        self.operations
+            .iter_mut()
+            .flatten()
+            .map(|(method, operation)| (Method::from(method.as_str()), operation))
+    }
+
+    /// Removes the operation for the given HTTP method and returns it.
+    pub fn remove(&mut self, method: &Method) -> Option<Operation> {
+        let operations = self.operations.as_mut()?;
+        let res = operations.remove(method.as_str());
+        if operations.is_empty() {
+            self.operations = None;
+        }
+        res
+    }
+
+    /// Returns the `GET` operation.
+    pub fn get(&self) -> Option<&Operation> {
+        self.operation(&Method::Get)
+    }
+
+    /// Returns the `PUT` operation.
+    pub fn put(&self) -> Option<&Operation> {
+        self.operation(&Method::Put)
+    }
+
+    /// Returns the `POST` operation.
+    pub fn post(&self) -> Option<&Operation> {
+        self.operation(&Method::Post)
+    }
+
+    /// Returns the `DELETE` operation.
+    pub fn delete(&self) -> Option<&Operation> {
+        self.operation(&Method::Delete)
+    }
+
+    /// Returns the `OPTIONS` operation.
+    pub fn options(&self) -> Option<&Operation> {
+        self.operation(&Method::Options)
+    }
+
+    /// Returns the `HEAD` operation.
+    pub fn head(&self) -> Option<&Operation> {
+        self.operation(&Method::Head)
+    }
+
+    /// Returns the `PATCH` operation.
+    pub fn patch(&self) -> Option<&Operation> {
+        self.operation(&Method::Patch)
+    }
 }
 
 impl Serialize for PathItem {
@@ -154,7 +250,7 @@ impl<'de> Deserialize<'de> for PathItem {
             {
                 let mut res = PathItem::default();
                 let mut operations: BTreeMap<String, Operation> = BTreeMap::new();
-                let mut extensions: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+                let mut extensions: Extensions = BTreeMap::new();
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "parameters" {
                         if res.parameters.is_some() {
@@ -189,16 +285,18 @@ impl<'de> Deserialize<'de> for PathItem {
 }
 
 impl ValidateWithContext<Spec> for PathItem {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(other) = &self.operations {
             for (method, operation) in other.iter() {
-                operation.validate_with_context(ctx, format!("{}.{}", path, method));
+                operation.validate_with_context(ctx, path.field(method.to_owned()));
+                operation.validate_parameters(&self.parameters, ctx, path.field(method.to_owned()));
             }
         }
 
         if let Some(parameters) = &self.parameters {
             for (i, parameter) in parameters.iter().enumerate() {
-                parameter.validate_with_context(ctx, format!("{}.parameters[{}]", path, i));
+                parameter.validate_with_context(ctx, path.field("parameters").index(i));
             }
         }
     }
@@ -645,4 +743,120 @@ mod tests {
             "deserialize",
         );
     }
+
+    #[test]
+    fn test_path_item_methods() {
+        let mut path_item = PathItem::default();
+        assert!(path_item.get().is_none(), "no get operation");
+
+        let operation = Operation {
+            operation_id: Some("getPets".to_owned()),
+            ..Default::default()
+        };
+        assert!(path_item.insert(Method::Get, operation.clone()).is_none());
+        path_item.insert(Method::from("SEARCH"), operation.clone());
+        assert_eq!(path_item.get(), Some(&operation), "get operation");
+        assert_eq!(
+            path_item.operation(&Method::Custom("search".to_owned())),
+            Some(&operation),
+            "custom operation",
+        );
+        assert_eq!(
+            serde_json::to_value(&path_item).unwrap(),
+            serde_json::json!({
+                "get": {"operationId": "getPets", "responses": {}},
+                "search": {"operationId": "getPets", "responses": {}},
+            }),
+            "serialized operations",
+        );
+
+        path_item.operation_mut(&Method::Get).unwrap().operation_id = Some("listPets".to_owned());
+        assert_eq!(
+            path_item.remove(&Method::Get).unwrap().operation_id,
+            Some("listPets".to_owned()),
+            "removed operation",
+        );
+        path_item.remove(&Method::from("search"));
+        assert_eq!(path_item, PathItem::default(), "no operations");
+
+        path_item.entry(Method::Post).or_default().operation_id = Some("createPet".to_owned());
+        for (_, operation) in path_item.iter_mut() {
+            operation.summary = Some("Create a pet".to_owned());
+        }
+        assert_eq!(
+            path_item
+                .iter()
+                .map(|(method, operation)| (method, operation.operation_id.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![(Method::Post, Some("createPet"))],
+            "entry and iter",
+        );
+        assert_eq!(
+            path_item.post().unwrap().summary,
+            Some("Create a pet".to_owned()),
+            "iter_mut",
+        );
+    }
+
+    #[test]
+    fn test_validate_parameters() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+        }))
+        .unwrap();
+        let validate = |path_item: serde_json::Value| {
+            let mut ctx = Context::new(&spec, Default::default());
+            serde_json::from_value::<PathItem>(path_item)
+                .unwrap()
+                .validate_with_context(&mut ctx, "path".into());
+            ctx.errors
+        };
+
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [{"name": "name", "in": "formData", "type": "string"}],
+                "post": {
+                    "parameters": [{"name": "pet", "in": "body", "schema": {"type": "object"}}],
+                    "responses": {"200": {"description": "ok"}},
+                },
+            })),
+            vec!["path.post.parameters: body and formData parameters must not be used together"],
+            "body and path item formData",
+        );
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [{"name": "file", "in": "formData", "type": "file"}],
+                "post": {
+                    "consumes": ["application/json"],
+                    "responses": {"200": {"description": "ok"}},
+                },
+            })),
+            vec!["path.post.consumes: must include `multipart/form-data` or `application/x-www-form-urlencoded` for the file parameters"],
+            "path item file",
+        );
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [{"name": "pet", "in": "body", "schema": {"type": "object"}}],
+                "post": {
+                    "parameters": [{"name": "pet", "in": "body", "schema": {"type": "string"}}],
+                    "responses": {"200": {"description": "ok"}},
+                },
+            })),
+            Vec::<String>::new(),
+            "overridden body",
+        );
+        assert_eq!(
+            validate(serde_json::json!({
+                "parameters": [{"name": "pet", "in": "body", "schema": {"type": "object"}}],
+                "post": {
+                    "parameters": [{"name": "cat", "in": "body", "schema": {"type": "object"}}],
+                    "responses": {"200": {"description": "ok"}},
+                },
+            })),
+            vec!["path.post.parameters: only one body parameter allowed, found 2"],
+            "two bodies",
+        );
+    }
 }
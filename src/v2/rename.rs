@@ -0,0 +1,144 @@
+//! Renaming of the definitions
+//!
+//! Renames a definition, a parameter or a response of the specification
+//! and rewrites every reference to it.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v2::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "swagger": "2.0",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//!     "definitions": {
+//!         "Pet": {"type": "object"},
+//!         "Pets": {"type": "array", "items": {"$ref": "#/definitions/Pet"}},
+//!     },
+//! })).unwrap();
+//!
+//! assert!(spec.rename_definition("Pet", "Animal"));
+//! assert_eq!(
+//!     serde_json::to_value(spec.definitions.unwrap()).unwrap(),
+//!     serde_json::json!({
+//!         "Animal": {"type": "object"},
+//!         "Pets": {"type": "array", "items": {"$ref": "#/definitions/Animal"}},
+//!     }),
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::common::visit::{pointer, RenameReference, WalkMut};
+use crate::v2::spec::Spec;
+
+impl Spec {
+    /// Renames the definition and rewrites all references to it.
+    ///
+    /// Returns `false` and leaves the specification unchanged
+    /// if there is no such definition or the new name is already taken.
+    pub fn rename_definition(&mut self, from: &str, to: &str) -> bool {
+        self.rename_item("#/definitions", from, to, |x| &mut x.definitions)
+    }
+
+    /// Renames the parameter and rewrites all references to it,
+    /// see [Spec::rename_definition] for details.
+    pub fn rename_parameter(&mut self, from: &str, to: &str) -> bool {
+        self.rename_item("#/parameters", from, to, |x| &mut x.parameters)
+    }
+
+    /// Renames the response and rewrites all references to it,
+    /// see [Spec::rename_definition] for details.
+    pub fn rename_response(&mut self, from: &str, to: &str) -> bool {
+        self.rename_item("#/responses", from, to, |x| &mut x.responses)
+    }
+
+    fn rename_item<T>(
+        &mut self,
+        section: &str,
+        from: &str,
+        to: &str,
+        items: impl FnOnce(&mut Spec) -> &mut Option<BTreeMap<String, T>>,
+    ) -> bool {
+        let Some(items) = items(self).as_mut() else {
+            return false;
+        };
+        if items.contains_key(to) {
+            return false;
+        }
+        let Some(item) = items.remove(from) else {
+            return false;
+        };
+        items.insert(to.to_owned(), item);
+        self.walk_mut(&mut RenameReference {
+            from: pointer(section, from),
+            to: pointer(section, to),
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_rename() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "parameters": [{"$ref": "#/parameters/Limit"}],
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "schema": {"type": "array", "items": {"$ref": "#/definitions/Pet"}},
+                            },
+                            "default": {"$ref": "#/responses/Error"},
+                        },
+                    },
+                },
+            },
+            "definitions": {"Pet": {"type": "object"}},
+            "parameters": {"Limit": {"in": "query", "name": "limit", "type": "integer"}},
+            "responses": {"Error": {"description": "error"}},
+        }))
+        .unwrap();
+        assert!(spec.rename_definition("Pet", "Animal"));
+        assert!(spec.rename_parameter("Limit", "PageSize"));
+        assert!(spec.rename_response("Error", "Failure"));
+        assert!(!spec.rename_definition("Pet", "Cat"), "unknown definition");
+        assert!(
+            !spec.rename_parameter("PageSize", "PageSize"),
+            "name is taken"
+        );
+
+        let value = serde_json::to_value(&spec).unwrap();
+        let path = &value["paths"]["/pets"];
+        assert_eq!(
+            path["parameters"],
+            json!([{"$ref": "#/parameters/PageSize"}])
+        );
+        assert_eq!(
+            path["get"]["responses"]["200"]["schema"]["items"],
+            json!({"$ref": "#/definitions/Animal"}),
+        );
+        assert_eq!(
+            path["get"]["responses"]["default"],
+            json!({"$ref": "#/responses/Failure"}),
+        );
+        assert_eq!(
+            value["definitions"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["Animal"],
+        );
+    }
+}
@@ -7,11 +7,18 @@ use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, PushError, ValidateWithContext,
+};
+use crate::common::http::{StatusCode, StatusCodeError};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
+use crate::common::status::is_registered;
 use crate::v2::header::Header;
 use crate::v2::schema::Schema;
 use crate::v2::spec::Spec;
+use crate::validation::Lints;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct Responses {
@@ -30,7 +37,7 @@ pub struct Responses {
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -61,7 +68,7 @@ pub struct Response {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl Serialize for Responses {
@@ -115,7 +122,7 @@ impl<'de> Deserialize<'de> for Responses {
             {
                 let mut res = Responses::default();
                 let mut responses: BTreeMap<String, RefOr<Response>> = BTreeMap::new();
-                let mut extensions: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+                let mut extensions: Extensions = BTreeMap::new();
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "default" {
                         if res.default.is_some() {
@@ -157,28 +164,139 @@ impl<'de> Deserialize<'de> for Responses {
 }
 
 impl ValidateWithContext<Spec> for Response {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.description, ctx, format!("{}.description", path));
-        if let Some(schema) = &self.schema {
-            schema.validate_with_context(ctx, format!("{}.schema", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.description, ctx, path.field("description"));
+        match &self.schema {
+            Some(RefOr::Item(Schema::File(schema))) => {
+                schema.validate_with_context(ctx, path.field("schema"));
+            }
+            Some(schema) => schema.validate_with_context(ctx, path.field("schema")),
+            None => {}
         }
         if let Some(headers) = &self.headers {
             for (name, header) in headers {
-                header.validate_with_context(ctx, format!("{}.headers.{}", path, name));
+                header.validate_with_context(ctx, path.field("headers").field(name.to_owned()));
             }
         }
     }
 }
 
+impl Responses {
+    /// Returns the response for the numeric status code, e.g. `http::StatusCode::as_u16`,
+    /// the `default` response is returned if the code is not defined.
+    pub fn get_response(&self, status: u16) -> Option<&RefOr<Response>> {
+        self.responses
+            .as_ref()
+            .and_then(|x| x.get(&status.to_string()))
+            .or(self.default.as_ref())
+    }
+
+    /// Returns `true` if neither the default response nor any response code is defined.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.responses.as_ref().is_none_or(BTreeMap::is_empty)
+    }
+
+    /// Returns a builder of the responses.
+    pub fn builder() -> ResponsesBuilder {
+        ResponsesBuilder {
+            responses: Responses::default(),
+            error: None,
+        }
+    }
+
+    /// Inserts the response for the status code,
+    /// returns the previous response of the code.
+    ///
+    /// The code is validated before the insertion, see [StatusCode::validate],
+    /// the ranges like `2XX` are not supported by v2.0.
+    pub fn insert(
+        &mut self,
+        status: impl Into<StatusCode>,
+        response: RefOr<Response>,
+    ) -> Result<Option<RefOr<Response>>, StatusCodeError> {
+        let status = status.into().validate()?;
+        if let StatusCode::Range(_) = status {
+            return Err(StatusCodeError::UnsupportedRange(status));
+        }
+        Ok(self
+            .responses
+            .get_or_insert_with(BTreeMap::new)
+            .insert(status.to_string(), response))
+    }
+}
+
+/// Builds the [Responses] keyed by the status codes.
+///
+/// Example:
+///
+/// ```rust
+/// use roas::common::reference::RefOr;
+/// use roas::v2::response::{Response, Responses};
+///
+/// let responses = Responses::builder()
+///     .ok(RefOr::new_item(Response {
+///         description: "OK".to_owned(),
+///         ..Default::default()
+///     }))
+///     .status(404, RefOr::new_ref("#/responses/NotFound".to_owned()))
+///     .default(RefOr::new_ref("#/responses/Error".to_owned()))
+///     .build()
+///     .unwrap();
+/// assert!(responses.get_response(404).is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponsesBuilder {
+    responses: Responses,
+    error: Option<StatusCodeError>,
+}
+
+impl ResponsesBuilder {
+    /// Sets the response for the `200` status code.
+    pub fn ok(self, response: RefOr<Response>) -> Self {
+        self.status(200, response)
+    }
+
+    /// Sets the response for the status code, an invalid code is reported by [ResponsesBuilder::build].
+    pub fn status(mut self, status: impl Into<StatusCode>, response: RefOr<Response>) -> Self {
+        if self.error.is_none() {
+            self.error = self.responses.insert(status, response).err();
+        }
+        self
+    }
+
+    /// Sets the `default` response.
+    pub fn default(mut self, response: RefOr<Response>) -> Self {
+        self.responses.default = Some(response);
+        self
+    }
+
+    /// Returns the responses or the first error of the status codes.
+    pub fn build(self) -> Result<Responses, StatusCodeError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.responses),
+        }
+    }
+}
+
 impl ValidateWithContext<Spec> for Responses {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if self.is_empty() {
+            ctx.error(path.clone(), "must contain at least one response");
+        }
         if let Some(response) = &self.default {
-            response.validate_with_context(ctx, format!("{}.default", path));
+            response.validate_with_context(ctx, path.field("default"));
         }
         if let Some(responses) = &self.responses {
             for (name, response) in responses {
                 match name.parse::<u16>() {
-                    Ok(100..=599) => {}
+                    Ok(100..=599) => {
+                        if ctx.is_lint(Lints::UnregisteredStatusCodes) && !is_registered(name) {
+                            ctx.error(path.field(name.to_owned()), "status code is not registered");
+                        }
+                    }
                     _ => {
                         ctx.error(
                             path.clone(),
@@ -189,7 +307,7 @@ impl ValidateWithContext<Spec> for Responses {
                         );
                     }
                 }
-                response.validate_with_context(ctx, format!("{}.{}", path, name));
+                response.validate_with_context(ctx, path.field(name.to_owned()));
             }
         }
     }
@@ -198,6 +316,7 @@ impl ValidateWithContext<Spec> for Responses {
 #[cfg(test)]
 mod tests {
     use crate::v2::header::{IntegerHeader, StringHeader};
+    use crate::validation::Options;
 
     use super::*;
 
@@ -675,4 +794,125 @@ mod tests {
             "responses serialization",
         );
     }
+
+    #[test]
+    fn test_validate_file_response() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        serde_json::from_value::<Response>(serde_json::json!({
+            "description": "The image",
+            "schema": {"type": "file"},
+        }))
+        .unwrap()
+        .validate_with_context(&mut ctx, "response".into());
+        assert!(ctx.errors.is_empty(), "root file schema: {:?}", ctx.errors);
+
+        serde_json::from_value::<Response>(serde_json::json!({
+            "description": "The images",
+            "schema": {"type": "array", "items": {"type": "file"}},
+        }))
+        .unwrap()
+        .validate_with_context(&mut ctx, "response".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["response.schema.items: type `file` is allowed only for the root schema of a response"],
+        );
+    }
+
+    #[test]
+    fn test_validate_empty_responses() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        Responses::default().validate_with_context(&mut ctx, "responses".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["responses: must contain at least one response"]
+        );
+
+        let mut ctx = Context::new(&spec, Default::default());
+        Responses {
+            default: Some(RefOr::new_item(Response {
+                description: "error".to_owned(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .validate_with_context(&mut ctx, "responses".into());
+        assert!(ctx.errors.is_empty(), "default only: {:?}", ctx.errors);
+    }
+
+    #[test]
+    fn test_get_response() {
+        let responses = serde_json::from_value::<Responses>(serde_json::json!({
+            "200": {"description": "ok"},
+            "default": {"description": "error"},
+        }))
+        .unwrap();
+        let description = |status| match responses.get_response(status) {
+            Some(RefOr::Item(response)) => response.description.as_str(),
+            _ => "",
+        };
+        assert_eq!(description(200), "ok", "explicit code");
+        assert_eq!(description(404), "error", "default");
+    }
+
+    #[test]
+    fn test_responses_builder() {
+        let ok = RefOr::new_item(Response {
+            description: "ok".to_owned(),
+            ..Default::default()
+        });
+        let responses = Responses::builder()
+            .ok(ok.clone())
+            .status(404, RefOr::new_ref("#/responses/NotFound".to_owned()))
+            .default(ok.clone())
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&responses).unwrap(),
+            serde_json::json!({
+                "200": {"description": "ok"},
+                "404": {"$ref": "#/responses/NotFound"},
+                "default": {"description": "ok"},
+            })
+        );
+
+        assert_eq!(
+            Responses::builder().status(600, ok.clone()).build(),
+            Err(StatusCodeError::Invalid("600".to_owned()))
+        );
+
+        let mut responses = Responses::default();
+        assert_eq!(responses.insert(201, ok.clone()), Ok(None));
+        assert_eq!(
+            responses.insert(StatusCode::Range(2), ok),
+            Err(StatusCodeError::UnsupportedRange(StatusCode::Range(2))),
+            "ranges are not supported"
+        );
+    }
+
+    #[test]
+    fn test_validate_unregistered_status_codes() {
+        let responses = serde_json::from_value::<Responses>(serde_json::json!({
+            "200": {"description": "ok"},
+            "299": {"description": "custom"},
+        }))
+        .unwrap();
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        responses.validate_with_context(&mut ctx, "responses".into());
+        assert!(
+            ctx.errors.is_empty(),
+            "allowed by default: {:?}",
+            ctx.errors
+        );
+
+        let mut ctx =
+            Context::new(&spec, Options::new()).with_lints(Lints::UnregisteredStatusCodes.only());
+        responses.validate_with_context(&mut ctx, "responses".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["responses.299: status code is not registered"]
+        );
+    }
 }
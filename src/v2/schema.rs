@@ -4,17 +4,27 @@ use monostate::MustBe;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::common::bool_or::BoolOr;
+use crate::common::extensions::Extensions;
 use crate::common::formats::{IntegerFormat, NumberFormat, StringFormat};
-use crate::common::helpers::{validate_pattern, Context, ValidateWithContext};
+use crate::common::helpers::{
+    validate_extensions, validate_format, validate_pattern, Context, PushError, ValidateWithContext,
+};
+use crate::common::parse::from_map;
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
+use crate::common::schema::{
+    enum_values, properties, Constraints, SchemaKind, SchemaLike, SchemaRef,
+};
 use crate::v2::external_documentation::ExternalDocumentation;
 use crate::v2::spec::Spec;
 use crate::v2::xml::XML;
+use crate::validation::Options;
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum Schema {
     #[serde(rename = "string")]
@@ -32,11 +42,18 @@ pub enum Schema {
     #[serde(rename = "array")]
     Array(ArraySchema),
 
+    #[serde(rename = "file")]
+    File(FileSchema),
+
     #[serde(rename = "null")]
     Null(NullSchema),
 
     #[serde(rename = "object")]
-    Object(ObjectSchema), // must be last
+    Object(ObjectSchema),
+
+    /// A schema with an unknown or vendor specific type,
+    /// kept as is instead of failing the whole document.
+    Other(OtherSchema), // must be last
 }
 
 impl Default for Schema {
@@ -45,6 +62,33 @@ impl Default for Schema {
     }
 }
 
+const TYPES: &[&str] = &[
+    "string", "integer", "number", "boolean", "array", "file", "null", "object",
+];
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // the variant is chosen by the `type` field, so the errors of a known type are not lost
+        let map = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+        let schema = match map.get("type").and_then(serde_json::Value::as_str) {
+            Some("string") => Schema::String(from_map(map)?),
+            Some("integer") => Schema::Integer(from_map(map)?),
+            Some("number") => Schema::Number(from_map(map)?),
+            Some("boolean") => Schema::Boolean(from_map(map)?),
+            Some("array") => Schema::Array(from_map(map)?),
+            Some("file") => Schema::File(from_map(map)?),
+            Some("null") => Schema::Null(from_map(map)?),
+            Some("object") => Schema::Object(from_map(map)?),
+            None if !map.contains_key("type") => Schema::Object(from_map(map)?),
+            _ => Schema::Other(from_map(map)?),
+        };
+        Ok(schema)
+    }
+}
+
 impl Display for Schema {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -53,8 +97,14 @@ impl Display for Schema {
             Schema::Number(_) => write!(f, "number"),
             Schema::Boolean(_) => write!(f, "boolean"),
             Schema::Array(_) => write!(f, "array"),
+            Schema::File(_) => write!(f, "file"),
             Schema::Object(_) => write!(f, "object"),
             Schema::Null(_) => write!(f, "null"),
+            Schema::Other(s) => match s.fields.get("type") {
+                Some(serde_json::Value::String(t)) => write!(f, "{}", t),
+                Some(t) => write!(f, "{}", t),
+                None => write!(f, "unknown"),
+            },
         }
     }
 }
@@ -133,7 +183,7 @@ pub struct StringSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -166,7 +216,7 @@ pub struct IntegerSchema {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<i64>,
+    pub minimum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly greater than the value of `minimum`
     #[serde(rename = "exclusiveMinimum")]
@@ -175,7 +225,7 @@ pub struct IntegerSchema {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<i64>,
+    pub maximum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly less than the value of `maximum`
     #[serde(rename = "exclusiveMaximum")]
@@ -219,7 +269,7 @@ pub struct IntegerSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -243,12 +293,12 @@ pub struct NumberSchema {
     ///
     /// **Note**: "default" has no meaning for required headers.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<f64>,
+    pub default: Option<serde_json::Number>,
 
     /// The list of strings that defines the possible values of this parameter.
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<f64>>,
+    pub enum_values: Option<Vec<serde_json::Number>>,
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -305,7 +355,7 @@ pub struct NumberSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -359,7 +409,7 @@ pub struct BooleanSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -430,13 +480,14 @@ pub struct ArraySchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct ObjectSchema {
     #[serde(rename = "type")]
     #[serde(default)]
+    #[serde(deserialize_with = "deserialize_object_type")]
     _type: String,
 
     /// A title to explain the purpose of the schema.
@@ -523,7 +574,7 @@ pub struct ObjectSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl Default for ObjectSchema {
@@ -549,10 +600,42 @@ impl Default for ObjectSchema {
     }
 }
 
+/// The file returned by a response, allowed only for the root schema of a response.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct FileSchema {
+    #[serde(rename = "type")]
+    _type: MustBe!("file"),
+
+    /// A title to explain the purpose of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A short description of the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Additional external documentation for this schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "externalDocs")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    /// A free-form property to include an example of an instance for this schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+
+    /// Allows extensions to the Swagger Schema.
+    /// The field name MUST begin with `x-`, for example, `x-internal-id`.
+    /// The value can be null, a primitive, an array or an object.
+    #[serde(flatten)]
+    #[serde(with = "crate::common::extensions")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Extensions>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct NullSchema {
-    #[serde(rename = "null")]
-    _type: MustBe!("string"),
+    #[serde(rename = "type")]
+    _type: MustBe!("null"),
 
     /// A title to explain the purpose of the schema.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -594,97 +677,388 @@ pub struct NullSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+/// A schema whose type is not supported by the Swagger 2.0 specification.
+///
+/// The fields are kept unchanged, so the schema survives a round trip,
+/// and the specification extensions are separated as for the other schemas.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct OtherSchema {
+    /// The fields of the schema, including the `type` field.
+    pub fields: BTreeMap<String, serde_json::Value>,
+
+    /// Allows extensions to the Swagger Schema.
+    /// The field name MUST begin with `x-`, for example, `x-internal-id`.
+    /// The value can be null, a primitive, an array or an object.
+    pub extensions: Option<Extensions>,
+}
+
+impl Serialize for OtherSchema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for (k, v) in &self.fields {
+            map.serialize_entry(k, v)?;
+        }
+        if let Some(ref ext) = self.extensions {
+            for (k, v) in ext {
+                if k.starts_with("x-") {
+                    map.serialize_entry(k, v)?;
+                }
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for OtherSchema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (extensions, fields): (BTreeMap<_, _>, BTreeMap<_, _>) =
+            BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?
+                .into_iter()
+                .partition(|(k, _)| k.starts_with("x-"));
+        match fields.get("type") {
+            None => return Err(serde::de::Error::missing_field("type")),
+            Some(serde_json::Value::String(t)) if TYPES.contains(&t.as_str()) => {
+                return Err(serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(t),
+                    &"a type not supported by the specification",
+                ));
+            }
+            _ => {}
+        }
+        Ok(OtherSchema {
+            fields,
+            extensions: (!extensions.is_empty()).then_some(extensions),
+        })
+    }
+}
+
+/// Accepts only the `object` type.
+fn deserialize_object_type<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let t = String::deserialize(deserializer)?;
+    if t != "object" {
+        return Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(&t),
+            &"object",
+        ));
+    }
+    Ok(t)
+}
+
+impl Schema {
+    /// Returns the specification extensions of the schema.
+    pub fn extensions(&self) -> Option<&Extensions> {
+        match self {
+            Schema::String(s) => s.extensions.as_ref(),
+            Schema::Integer(s) => s.extensions.as_ref(),
+            Schema::Number(s) => s.extensions.as_ref(),
+            Schema::Boolean(s) => s.extensions.as_ref(),
+            Schema::Array(s) => s.extensions.as_ref(),
+            Schema::File(s) => s.extensions.as_ref(),
+            Schema::Object(s) => s.extensions.as_ref(),
+            Schema::Null(s) => s.extensions.as_ref(),
+            Schema::Other(s) => s.extensions.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the specification extensions of the schema.
+    pub fn extensions_mut(&mut self) -> &mut Option<Extensions> {
+        match self {
+            Schema::String(s) => &mut s.extensions,
+            Schema::Integer(s) => &mut s.extensions,
+            Schema::Number(s) => &mut s.extensions,
+            Schema::Boolean(s) => &mut s.extensions,
+            Schema::Array(s) => &mut s.extensions,
+            Schema::File(s) => &mut s.extensions,
+            Schema::Object(s) => &mut s.extensions,
+            Schema::Null(s) => &mut s.extensions,
+            Schema::Other(s) => &mut s.extensions,
+        }
+    }
+}
+
+impl SchemaLike for Schema {
+    fn kind(&self) -> SchemaKind {
+        match self {
+            Schema::String(_) => SchemaKind::String,
+            Schema::Integer(_) => SchemaKind::Integer,
+            Schema::Number(_) => SchemaKind::Number,
+            Schema::Boolean(_) => SchemaKind::Boolean,
+            Schema::Array(_) => SchemaKind::Array,
+            Schema::File(_) => SchemaKind::File,
+            Schema::Null(_) => SchemaKind::Null,
+            Schema::Object(s) => s.kind(),
+            Schema::Other(s) => match s.fields.get("type") {
+                Some(serde_json::Value::String(t)) => SchemaKind::Other(t.clone()),
+                _ => SchemaKind::Any,
+            },
+        }
+    }
+
+    fn title(&self) -> Option<&str> {
+        match self {
+            Schema::String(s) => s.title.as_deref(),
+            Schema::Integer(s) => s.title.as_deref(),
+            Schema::Number(s) => s.title.as_deref(),
+            Schema::Boolean(s) => s.title.as_deref(),
+            Schema::Array(s) => s.title.as_deref(),
+            Schema::File(s) => s.title.as_deref(),
+            Schema::Null(s) => s.title.as_deref(),
+            Schema::Object(s) => s.title.as_deref(),
+            Schema::Other(s) => s.fields.get("title").and_then(|x| x.as_str()),
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Schema::String(s) => s.description.as_deref(),
+            Schema::Integer(s) => s.description.as_deref(),
+            Schema::Number(s) => s.description.as_deref(),
+            Schema::Boolean(s) => s.description.as_deref(),
+            Schema::Array(s) => s.description.as_deref(),
+            Schema::File(s) => s.description.as_deref(),
+            Schema::Null(s) => s.description.as_deref(),
+            Schema::Object(s) => s.description.as_deref(),
+            Schema::Other(s) => s.fields.get("description").and_then(|x| x.as_str()),
+        }
+    }
+
+    fn constraints(&self) -> Constraints<'_> {
+        match self {
+            Schema::String(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                min_length: s.min_length,
+                max_length: s.max_length,
+                pattern: s.pattern.as_deref(),
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Integer(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                minimum: s.minimum.as_ref().and_then(|x| x.as_f64()),
+                exclusive_minimum: s.exclusive_minimum.unwrap_or(false),
+                maximum: s.maximum.as_ref().and_then(|x| x.as_f64()),
+                exclusive_maximum: s.exclusive_maximum.unwrap_or(false),
+                multiple_of: s.multiple_of,
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Number(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                minimum: s.minimum,
+                exclusive_minimum: s.exclusive_minimum.unwrap_or(false),
+                maximum: s.maximum,
+                exclusive_maximum: s.exclusive_maximum.unwrap_or(false),
+                multiple_of: s.multiple_of,
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Boolean(s) => Constraints {
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Array(s) => Constraints {
+                min_items: s.min_items,
+                max_items: s.max_items,
+                unique_items: s.unique_items.unwrap_or(false),
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Null(s) => Constraints {
+                read_only: s.read_only.unwrap_or(false),
+                ..Default::default()
+            },
+            Schema::Object(s) => s.constraints(),
+            Schema::File(_) | Schema::Other(_) => Constraints::default(),
+        }
+    }
+
+    fn properties(&self) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_> {
+        match self {
+            Schema::Object(s) => s.properties(),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn items(&self) -> Option<SchemaRef<'_>> {
+        match self {
+            Schema::Array(s) => s.items.as_ref().map(Into::into),
+            _ => None,
+        }
+    }
+
+    fn members(&self) -> Vec<SchemaRef<'_>> {
+        match self {
+            Schema::Object(s) => s.members(),
+            _ => vec![],
+        }
+    }
+}
+
+impl SchemaLike for ObjectSchema {
+    fn kind(&self) -> SchemaKind {
+        SchemaKind::Object
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn constraints(&self) -> Constraints<'_> {
+        Constraints {
+            min_properties: self.min_properties,
+            max_properties: self.max_properties,
+            required: self.required.as_deref().unwrap_or_default(),
+            read_only: self.read_only.unwrap_or(false),
+            ..Default::default()
+        }
+    }
+
+    fn properties(&self) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_> {
+        properties(&self.properties)
+    }
+
+    fn items(&self) -> Option<SchemaRef<'_>> {
+        None
+    }
+
+    fn members(&self) -> Vec<SchemaRef<'_>> {
+        self.all_of.iter().flatten().map(Into::into).collect()
+    }
 }
 
 impl ValidateWithContext<Spec> for Schema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             Schema::String(s) => s.validate_with_context(ctx, path),
             Schema::Integer(s) => s.validate_with_context(ctx, path),
             Schema::Number(s) => s.validate_with_context(ctx, path),
             Schema::Boolean(s) => s.validate_with_context(ctx, path),
             Schema::Array(s) => s.validate_with_context(ctx, path),
+            Schema::File(s) => {
+                ctx.error(
+                    path.clone(),
+                    "type `file` is allowed only for the root schema of a response",
+                );
+                s.validate_with_context(ctx, path);
+            }
             Schema::Object(s) => s.validate_with_context(ctx, path),
             Schema::Null(s) => s.validate_with_context(ctx, path),
+            Schema::Other(s) => {
+                validate_extensions(&s.extensions, ctx, path.clone());
+                if !ctx.is_option(Options::IgnoreUnsupportedSchemas) {
+                    ctx.error(
+                        path,
+                        format_args!("unsupported schema type `{}`, not validated", self),
+                    );
+                }
+            }
         }
     }
 }
 
 impl ValidateWithContext<Spec> for StringSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
+        validate_format(&self.format, ctx, path.field("format"));
         if let Some(pattern) = &self.pattern {
-            validate_pattern(pattern, ctx, format!("{}.pattern", path));
+            validate_pattern(pattern, ctx, path.field("pattern"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for IntegerSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for NumberSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for BooleanSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for ArraySchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
 
         if let Some(items) = &self.items {
-            items.validate_with_context_boxed(ctx, format!("{}.items", path));
+            items.validate_with_context_boxed(ctx, path.field("items"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for ObjectSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
 
         if let Some(properties) = &self.properties {
             for (name, schema) in properties {
-                schema.validate_with_context_boxed(ctx, format!("{}.properties.{}", path, name));
+                schema.validate_with_context_boxed(
+                    ctx,
+                    path.field("properties").field(name.to_owned()),
+                );
             }
         }
 
@@ -692,26 +1066,35 @@ impl ValidateWithContext<Spec> for ObjectSchema {
             match additional_properties {
                 BoolOr::Bool(_) => {}
                 BoolOr::Item(schema) => {
-                    schema
-                        .validate_with_context_boxed(ctx, format!("{}.additionalProperties", path));
+                    schema.validate_with_context_boxed(ctx, path.field("additionalProperties"));
                 }
             }
         }
         if let Some(all_of) = &self.all_of {
             for (i, schema) in all_of.iter().enumerate() {
-                schema.validate_with_context(ctx, format!("{}.allOf[{}]", path, i));
+                schema.validate_with_context(ctx, path.field("allOf").index(i));
             }
         }
     }
 }
 
+impl ValidateWithContext<Spec> for FileSchema {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if let Some(docs) = &self.external_docs {
+            docs.validate_with_context(ctx, path.field("externalDocs"));
+        }
+    }
+}
+
 impl ValidateWithContext<Spec> for NullSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
@@ -852,4 +1235,117 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn test_file_schema() {
+        let value = serde_json::json!({"type": "file", "description": "The image"});
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        assert!(
+            matches!(schema, Schema::File(_)),
+            "file schema: {:?}",
+            schema
+        );
+        assert_eq!(schema.to_string(), "file");
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value, "round trip");
+
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        schema.validate_with_context(&mut ctx, "schema".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["schema: type `file` is allowed only for the root schema of a response"],
+        );
+    }
+
+    #[test]
+    fn test_integer_bounds_round_trip() {
+        let value = serde_json::json!({
+            "type": "integer",
+            "minimum": i64::MIN,
+            "maximum": u64::MAX,
+        });
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        if let Schema::Integer(integer) = &schema {
+            assert_eq!(integer.minimum, Some(i64::MIN.into()), "minimum");
+            assert_eq!(integer.maximum, Some(u64::MAX.into()), "maximum");
+        } else {
+            panic!("expected IntegerSchema");
+        }
+        assert_eq!(serde_json::to_value(schema).unwrap(), value, "round trip");
+    }
+
+    #[test]
+    fn test_null_round_trip() {
+        let value = serde_json::json!({
+            "type": "null",
+            "title": "nothing",
+        });
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        assert!(matches!(schema, Schema::Null(_)), "{:?}", schema);
+        assert_eq!(schema.to_string(), "null");
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value, "round trip");
+    }
+
+    #[test]
+    fn test_other_schema() {
+        let value = serde_json::json!({
+            "type": "uuid",
+            "title": "id",
+            "x-vendor": true,
+        });
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        assert_eq!(
+            schema,
+            Schema::Other(OtherSchema {
+                fields: BTreeMap::from([
+                    ("type".to_owned(), "uuid".into()),
+                    ("title".to_owned(), "id".into()),
+                ]),
+                extensions: Some(BTreeMap::from([("x-vendor".to_owned(), true.into())])),
+            }),
+        );
+        assert_eq!(schema.to_string(), "uuid");
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value, "round trip");
+
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        schema.validate_with_context(&mut ctx, "schema".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["schema: unsupported schema type `uuid`, not validated"],
+        );
+
+        let mut ctx = Context::new(&spec, Options::IgnoreUnsupportedSchemas.only());
+        schema.validate_with_context(&mut ctx, "schema".into());
+        assert!(ctx.errors.is_empty(), "ignored: {:?}", ctx.errors);
+    }
+
+    #[test]
+    fn test_known_type_errors() {
+        let err = serde_json::from_str::<Schema>(r#"{"type": "string", "maxLength": "ten"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("invalid type: string \"ten\", expected u64"),
+            "error of the typed schema: {}",
+            err
+        );
+        let err = serde_json::from_str::<Schema>(r#"{"required": "id"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("invalid type: string \"id\""),
+            "error of the object schema without a type: {}",
+            err
+        );
+        assert!(
+            serde_json::from_str::<OtherSchema>(r#"{"type": "string"}"#).is_err(),
+            "known type",
+        );
+        assert!(
+            serde_json::from_str::<Schema>(r#"{"type": ["string", "null"]}"#)
+                .is_ok_and(|x| matches!(x, Schema::Other(_))),
+            "list of types",
+        );
+    }
 }
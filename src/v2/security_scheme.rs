@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use crate::common::helpers::{
     validate_optional_url, validate_required_string, Context, PushError, ValidateWithContext,
 };
+use crate::common::path::Path;
+use crate::common::url::{parse_optional, Url, UrlError};
 use crate::v2::spec::Spec;
 
 /// Allows the definition of a security scheme that can be used by the operations.
@@ -57,7 +59,7 @@ impl Display for SecurityScheme {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             SecurityScheme::Basic(_) => write!(f, "basic"),
-            SecurityScheme::ApiKey(_) => write!(f, "aoiKey"),
+            SecurityScheme::ApiKey(_) => write!(f, "apiKey"),
             SecurityScheme::OAuth2(_) => write!(f, "oauth2"),
         }
     }
@@ -106,28 +108,40 @@ impl Display for SecuritySchemeApiKeyLocation {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct OAuth2SecurityScheme {
     /// **Required** The flow used by the OAuth2 security scheme.
-    flow: SecuritySchemeOAuth2Flow,
+    pub flow: SecuritySchemeOAuth2Flow,
 
     /// The authorization URL to be used for this flow.
     /// Required for `implicit` and `accessCode` flows.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "authorizationUrl")]
-    authorization_url: Option<String>,
+    pub authorization_url: Option<String>,
 
     /// The token URL to be used for this flow.
     /// Required for `password`, `application` and `accessCode` flows.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "tokenUrl")]
-    token_url: Option<String>,
+    pub token_url: Option<String>,
 
     /// **Required** The available scopes for the OAuth2 security scheme.
     ///
     /// The extensions support is dropped for simplicity.
-    scopes: BTreeMap<String, String>,
+    pub scopes: BTreeMap<String, String>,
 
     /// A short description for security scheme.
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
+    pub description: Option<String>,
+}
+
+impl OAuth2SecurityScheme {
+    /// Returns the authorization URL, an error if it is not a valid URL.
+    pub fn parsed_authorization_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.authorization_url)
+    }
+
+    /// Returns the token URL, an error if it is not a valid URL.
+    pub fn parsed_token_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.token_url)
+    }
 }
 
 /// The flow used by the OAuth2 security scheme.
@@ -155,8 +169,23 @@ impl Display for SecuritySchemeOAuth2Flow {
     }
 }
 
+impl SecuritySchemeOAuth2Flow {
+    /// Returns `true` if the flow requires the `authorizationUrl` field.
+    pub fn requires_authorization_url(&self) -> bool {
+        matches!(
+            self,
+            SecuritySchemeOAuth2Flow::Implicit | SecuritySchemeOAuth2Flow::AccessCode
+        )
+    }
+
+    /// Returns `true` if the flow requires the `tokenUrl` field.
+    pub fn requires_token_url(&self) -> bool {
+        !matches!(self, SecuritySchemeOAuth2Flow::Implicit)
+    }
+}
+
 impl ValidateWithContext<Spec> for SecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             SecurityScheme::Basic(basic) => basic.validate_with_context(ctx, path),
             SecurityScheme::ApiKey(api_key) => api_key.validate_with_context(ctx, path),
@@ -166,38 +195,51 @@ impl ValidateWithContext<Spec> for SecurityScheme {
 }
 
 impl ValidateWithContext<Spec> for BasicSecurityScheme {
-    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: String) {}
+    fn validate_with_context(&self, _ctx: &mut Context<Spec>, _path: Path) {}
 }
 
 impl ValidateWithContext<Spec> for ApiKeySecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
 impl ValidateWithContext<Spec> for OAuth2SecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         if self.scopes.is_empty() {
             ctx.error(path.clone(), ".scopes: must not be empty");
         }
-        if self.authorization_url.is_none()
-            && (self.flow == SecuritySchemeOAuth2Flow::Implicit
-                || self.flow == SecuritySchemeOAuth2Flow::AccessCode)
-        {
-            ctx.error(
-                path,
-                format_args!(
-                    ".authorizationUrl: must be present for flow `{}`",
-                    self.flow,
-                ),
-            );
-        } else {
-            validate_optional_url(
-                &self.authorization_url,
-                ctx,
-                format!("{}.authorizationUrl", path),
-            );
-        }
+        validate_flow_url(
+            &self.authorization_url,
+            self.flow.requires_authorization_url(),
+            &self.flow,
+            ctx,
+            path.field("authorizationUrl"),
+        );
+        validate_flow_url(
+            &self.token_url,
+            self.flow.requires_token_url(),
+            &self.flow,
+            ctx,
+            path.field("tokenUrl"),
+        );
+    }
+}
+
+fn validate_flow_url(
+    url: &Option<String>,
+    required: bool,
+    flow: &SecuritySchemeOAuth2Flow,
+    ctx: &mut Context<Spec>,
+    path: Path,
+) {
+    match (url, required) {
+        (None, true) => ctx.error(path, format_args!("must be present for flow `{}`", flow)),
+        (Some(_), false) => ctx.error(
+            path,
+            format_args!("must not be present for flow `{}`", flow),
+        ),
+        _ => validate_optional_url(url, ctx, path),
     }
 }
 
@@ -528,4 +570,57 @@ mod tests {
             "serialize flow = application",
         );
     }
+
+    #[test]
+    fn test_security_scheme_oauth2_validate_urls() {
+        let spec = Spec::default();
+        let scheme = |flow, authorization_url: Option<&str>, token_url: Option<&str>| {
+            SecurityScheme::OAuth2(OAuth2SecurityScheme {
+                flow,
+                authorization_url: authorization_url.map(String::from),
+                token_url: token_url.map(String::from),
+                scopes: BTreeMap::from([(String::from("read"), String::from("read"))]),
+                description: None,
+            })
+        };
+        let url = Some("https://example.com/api/oauth");
+        for (flow, authorization_url, token_url, errors) in [
+            (SecuritySchemeOAuth2Flow::Implicit, url, None, vec![]),
+            (SecuritySchemeOAuth2Flow::Password, None, url, vec![]),
+            (SecuritySchemeOAuth2Flow::Application, None, url, vec![]),
+            (SecuritySchemeOAuth2Flow::AccessCode, url, url, vec![]),
+            (
+                SecuritySchemeOAuth2Flow::Implicit,
+                None,
+                url,
+                vec![
+                    "scheme.authorizationUrl: must be present for flow `implicit`",
+                    "scheme.tokenUrl: must not be present for flow `implicit`",
+                ],
+            ),
+            (
+                SecuritySchemeOAuth2Flow::Password,
+                url,
+                None,
+                vec![
+                    "scheme.authorizationUrl: must not be present for flow `password`",
+                    "scheme.tokenUrl: must be present for flow `password`",
+                ],
+            ),
+            (
+                SecuritySchemeOAuth2Flow::AccessCode,
+                Some("dialog"),
+                None,
+                vec![
+                    "scheme.authorizationUrl: must be a valid URL, found `dialog`",
+                    "scheme.tokenUrl: must be present for flow `accessCode`",
+                ],
+            ),
+        ] {
+            let mut ctx = Context::new(&spec, Default::default());
+            scheme(flow.clone(), authorization_url, token_url)
+                .validate_with_context(&mut ctx, "scheme".into());
+            assert_eq!(ctx.errors, errors, "flow = {}", flow);
+        }
+    }
 }
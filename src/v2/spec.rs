@@ -3,24 +3,41 @@
 use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::str::FromStr;
 
 use enumset::EnumSet;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::helpers::{
-    validate_optional_string_matches, Context, PushError, ValidateWithContext,
+    normalize_path_template, validate_each, validate_extensions, validate_tag_groups, Context,
+    PushError, ValidateWithContext,
 };
+use crate::common::http::Method;
+use crate::common::operation_id::{OperationIdStrategy, OperationIds};
+use crate::common::parse::from_reader;
+use crate::common::patch::{
+    apply_patch_typed, merge_patch_typed, split_subtree, PatchError, PatchOp,
+};
+use crate::common::path::Path;
+use crate::common::paths::Paths;
 use crate::common::reference::ResolveReference;
+use crate::common::semantic::{semantically_eq, EqOptions};
+use crate::common::spec::{OperationView, SpecLike};
+use crate::common::stats::{is_secured, Stats};
+use crate::common::strict::{from_value_strict, StrictError};
+use crate::error::parse_versioned;
 use crate::v2::external_documentation::ExternalDocumentation;
 use crate::v2::info::Info;
+use crate::v2::operation::Operation;
 use crate::v2::parameter::Parameter;
 use crate::v2::path_item::PathItem;
 use crate::v2::response::Response;
 use crate::v2::schema::{ObjectSchema, Schema};
 use crate::v2::security_scheme::SecurityScheme;
 use crate::v2::tag::Tag;
-use crate::validation::{Error, Options, Validate};
+use crate::validation::{Error, Lints, Options, Validate};
 
 /// This is the root document object for the API specification.
 /// It combines what previously was the Resource Listing and API Declaration (version 1.2 and earlier) together into one document.
@@ -126,7 +143,7 @@ pub struct Spec {
     /// [Path templating](https://swagger.io/specification/v2/#path-templating) is allowed.
     ///
     /// The extensions support is dropped for simplicity.
-    pub paths: BTreeMap<String, PathItem>,
+    pub paths: Paths<PathItem>,
 
     /// An object to hold data types produced and consumed by operations.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -180,7 +197,7 @@ pub struct Spec {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// The Swagger Specification version.
@@ -201,6 +218,26 @@ impl Display for Version {
     }
 }
 
+/// The supported versions of the specification.
+pub(crate) const VERSIONS: [&str; 1] = ["2.0"];
+
+impl FromStr for Spec {
+    type Err = crate::Error;
+
+    /// Parses the JSON document, the document of another version is reported as unsupported.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_versioned(s, &VERSIONS)
+    }
+}
+
+impl Display for Spec {
+    /// Writes the specification as the pretty printed JSON.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let raw = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&raw)
+    }
+}
+
 /// The possible values of the transfer protocol of the API
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub enum Scheme {
@@ -285,36 +322,314 @@ impl ResolveReference<Tag> for Spec {
     }
 }
 
+impl Spec {
+    /// Deserializes the JSON specification from the reader without buffering the whole document.
+    /// The reader is not buffered, wrap it in a [std::io::BufReader] when reading a file.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, crate::Error> {
+        Ok(from_reader(reader)?)
+    }
+
+    /// Serializes the specification as JSON to the writer.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Returns an iterator over all operations as `(path, method, operation)` tuples.
+    pub fn operations(&self) -> impl Iterator<Item = (&String, &String, &Operation)> {
+        self.paths.iter().flat_map(|(path, item)| {
+            item.operations
+                .iter()
+                .flatten()
+                .map(move |(method, operation)| (path, method, operation))
+        })
+    }
+
+    /// Returns an iterator over all operations as `(path, method, operation)` tuples,
+    /// allowing to modify the operations.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&String, &String, &mut Operation)> {
+        self.paths.iter_mut().flat_map(|(path, item)| {
+            item.operations
+                .iter_mut()
+                .flatten()
+                .map(move |(method, operation)| (path, method, operation))
+        })
+    }
+
+    /// Returns the operation with the given `operationId` as `(path, method, operation)` tuple.
+    pub fn operation_by_id(&self, operation_id: &str) -> Option<(&String, &String, &Operation)> {
+        self.operations()
+            .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(operation_id))
+    }
+
+    /// Sets the missing `operationId`s using the strategy
+    /// and returns the number of the generated ids,
+    /// see [crate::common::operation_id] for details.
+    pub fn ensure_operation_ids<S: OperationIdStrategy>(&mut self, strategy: S) -> usize {
+        let mut ids = OperationIds::new(
+            &strategy,
+            self.operations()
+                .filter_map(|(_, _, operation)| operation.operation_id.as_deref()),
+        );
+        let mut generated = 0;
+        for (path, method, operation) in self.operations_mut() {
+            if operation.operation_id.is_none() {
+                operation.operation_id = Some(ids.generate(&Method::from(method.as_str()), path));
+                generated += 1;
+            }
+        }
+        generated
+    }
+
+    /// Returns the statistics of the specification, see [Stats] for details.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            paths: self.paths.len(),
+            tags: self.tags.as_ref().map_or(0, |x| x.len()),
+            ..Default::default()
+        };
+        for (_, method, operation) in self.operations() {
+            stats.add_operation(
+                method,
+                operation.deprecated.unwrap_or(false),
+                operation.summary.is_some() || operation.description.is_some(),
+                operation.operation_id.is_some(),
+                is_secured(&operation.security, &self.security),
+            );
+        }
+        stats.schemas = self.definitions.as_ref().map_or(0, |x| x.len());
+        stats.parameters = self.parameters.as_ref().map_or(0, |x| x.len());
+        stats.responses = self.responses.as_ref().map_or(0, |x| x.len());
+        stats.security_schemes = self.security_definitions.as_ref().map_or(0, |x| x.len());
+        stats
+    }
+
+    /// Returns `true` if the specifications describe the same API,
+    /// see [crate::common::semantic] for the ignored differences.
+    pub fn semantically_eq(&self, other: &Self, options: EqOptions) -> bool {
+        semantically_eq(self, other, &options)
+    }
+
+    /// Deserializes the specification from the given value and fails on any field
+    /// that is not defined by the specification and does not start with `x-`.
+    pub fn from_value_strict(value: serde_json::Value) -> Result<Self, StrictError> {
+        from_value_strict(value)
+    }
+
+    /// Applies the JSON Patch to the specification.
+    ///
+    /// If all operations target a single path item, only that path item is re-deserialized.
+    /// The specification is not modified if the patch fails.
+    pub fn apply_json_patch(&mut self, ops: &[PatchOp]) -> Result<(), PatchError> {
+        if let Some((path, root, ops)) = split_subtree(ops, "/paths/") {
+            if let Some(item) = self.paths.get_mut(&path) {
+                return apply_patch_typed(item, &ops).map_err(|e| e.with_root(&root));
+            }
+        }
+        apply_patch_typed(self, ops)
+    }
+
+    /// Applies the JSON Merge Patch to the specification.
+    ///
+    /// The specification is not modified if the patch fails.
+    pub fn apply_merge_patch(&mut self, patch: serde_json::Value) -> Result<(), PatchError> {
+        merge_patch_typed(self, &patch)
+    }
+}
+
+/// The host must be a name or an IP address with an optional port, without a scheme and a path.
+fn validate_host(host: &str, ctx: &mut Context<Spec>, path: Path) {
+    if host.contains("://") {
+        ctx.error(
+            path,
+            format_args!("must not include a scheme, found `{}`", host),
+        );
+        return;
+    }
+    if host.contains('/') {
+        ctx.error(
+            path,
+            format_args!("must not include a path, found `{}`", host),
+        );
+        return;
+    }
+    // an IPv6 address is enclosed in the brackets, so its colons are not confused with the port
+    let (name, port) = match host.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((address, "")) => (address, None),
+            Some((address, port)) => (address, Some(port.strip_prefix(':').unwrap_or_default())),
+            None => ("", None),
+        },
+        None => match host.split_once(':') {
+            Some((name, port)) => (name, Some(port)),
+            None => (host, None),
+        },
+    };
+    let valid_name = !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || "{}\\?#@[]".contains(c));
+    let valid_port = port.is_none_or(|x| x.parse::<u16>().is_ok());
+    if !valid_name || !valid_port {
+        ctx.error(
+            path,
+            format_args!(
+                "must be a host name or an IP address with an optional port, found `{}`",
+                host
+            ),
+        );
+    }
+}
+
+/// The base path must start with a slash and must not contain templates, a query or a fragment.
+fn validate_base_path(base_path: &str, ctx: &mut Context<Spec>, path: Path) {
+    if !base_path.starts_with('/') {
+        ctx.error(
+            path.clone(),
+            format_args!("must start with `/`, found `{}`", base_path),
+        );
+    }
+    if base_path.contains(['{', '}']) {
+        ctx.error(
+            path.clone(),
+            format_args!("must not contain templates, found `{}`", base_path),
+        );
+    }
+    if base_path.contains(['?', '#']) {
+        ctx.error(
+            path,
+            format_args!(
+                "must not contain a query or a fragment, found `{}`",
+                base_path
+            ),
+        );
+    }
+}
+
+impl SpecLike for Spec {
+    fn spec_version(&self) -> String {
+        self.swagger.to_string()
+    }
+
+    fn title(&self) -> &str {
+        &self.info.title
+    }
+
+    fn api_version(&self) -> &str {
+        &self.info.version
+    }
+
+    fn operations(&self) -> Box<dyn Iterator<Item = OperationView<'_>> + '_> {
+        Box::new(
+            Spec::operations(self).map(|(path, method, operation)| OperationView {
+                path,
+                method: Method::from(method.as_str()),
+                operation_id: operation.operation_id.as_deref(),
+                summary: operation.summary.as_deref(),
+                description: operation.description.as_deref(),
+                tags: operation.tags.as_deref().unwrap_or_default(),
+                deprecated: operation.deprecated.unwrap_or(false),
+            }),
+        )
+    }
+
+    fn schema_names(&self) -> Vec<&str> {
+        self.definitions
+            .iter()
+            .flat_map(|x| x.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn schema_value(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.definitions.as_ref()?.get(name)?).ok()
+    }
+
+    fn stats(&self) -> Stats {
+        Spec::stats(self)
+    }
+}
+
 impl Validate for Spec {
-    fn validate(&self, options: EnumSet<Options>) -> Result<(), Error> {
-        let mut ctx = Context::new(self, options);
+    fn validate_with_lints(
+        &self,
+        options: EnumSet<Options>,
+        lints: EnumSet<Lints>,
+    ) -> Result<(), Error> {
+        let mut ctx = Context::new(self, options).with_lints(lints);
+
+        validate_extensions(&self.extensions, &mut ctx, Path::root());
+        validate_tag_groups(
+            &self.extensions,
+            &self
+                .tags
+                .iter()
+                .flatten()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>(),
+            &mut ctx,
+        );
 
         self.info
-            .validate_with_context(&mut ctx, "#.info".to_owned());
+            .validate_with_context(&mut ctx, Path::root().field("info"));
 
-        let re = Regex::new(r"^[^{}/ :\\]+(?::\d+)?$").unwrap();
-        validate_optional_string_matches(&self.host, &re, &mut ctx, "#.host".to_owned());
+        if let Some(host) = &self.host {
+            validate_host(host, &mut ctx, Path::root().field("host"));
+        }
 
         if let Some(base_path) = &self.base_path {
-            if !base_path.starts_with('/') {
-                ctx.error(
-                    "#.basePath".to_owned(),
-                    format_args!("must start with `/`, found `{}`", base_path),
-                );
+            validate_base_path(base_path, &mut ctx, Path::root().field("basePath"));
+        }
+
+        if let Some(schemes) = &self.schemes {
+            for (i, scheme) in schemes.iter().enumerate() {
+                if schemes[..i].contains(scheme) {
+                    ctx.error(
+                        Path::root().field("schemes").index(i),
+                        format_args!("duplicate scheme `{}`", scheme),
+                    );
+                }
             }
         }
 
         // validate paths operations
-        for (name, item) in self.paths.iter() {
-            let path = format!("#.paths[{}]", name);
+        let mut templates: BTreeMap<String, &String> = BTreeMap::new();
+        let items = self
+            .paths
+            .iter()
+            .map(|(name, item)| {
+                let equivalent = templates.insert(normalize_path_template(name), name);
+                (name, item, equivalent)
+            })
+            .collect::<Vec<_>>();
+        validate_each(&mut ctx, &items, |ctx, (name, item, equivalent)| {
+            let path = Path::root().field("paths").item(name);
             if !name.starts_with('/') {
                 ctx.error(path.clone(), "must start with `/`");
             }
-            item.validate_with_context(&mut ctx, path);
+            if let Some(other) = equivalent {
+                ctx.error(path.clone(), format_args!("is equivalent to `{}`", other));
+            }
+            item.validate_with_context(ctx, path);
+        });
+
+        // the operation ids are checked after the path items,
+        // because the path items can be validated by different threads
+        for (name, method, operation) in self.operations() {
+            if let Some(operation_id) = &operation.operation_id {
+                if !ctx.visit(format!("#/paths/operations/{}", operation_id)) {
+                    ctx.error(
+                        Path::root()
+                            .field("paths")
+                            .item(name)
+                            .field(method.to_owned()),
+                        format_args!("operationId `{}` already exists", operation_id),
+                    );
+                }
+            }
         }
 
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(&mut ctx, "#.externalDocs".to_owned())
+            docs.validate_with_context(&mut ctx, Path::root().field("externalDocs"))
         }
 
         // validate unused components
@@ -323,6 +638,7 @@ impl Validate for Spec {
                 for tag in tags.iter() {
                     let path = format!("#/tags/{}", tag.name);
                     if ctx.visit(path.clone()) {
+                        let path = Path::from(path);
                         ctx.error(path.clone(), "unused");
                         tag.validate_with_context(&mut ctx, path);
                     }
@@ -335,6 +651,7 @@ impl Validate for Spec {
                 for (name, definition) in definitions.iter() {
                     let path = format!("#/definitions/{}", name);
                     if ctx.visit(path.clone()) {
+                        let path = Path::from(path);
                         ctx.error(path.clone(), "unused");
                         definition.validate_with_context(&mut ctx, path);
                     }
@@ -347,6 +664,7 @@ impl Validate for Spec {
                 for (name, parameter) in parameters.iter() {
                     let path = format!("#/parameters/{}", name);
                     if ctx.visit(path.clone()) {
+                        let path = Path::from(path);
                         ctx.error(path.clone(), "unused");
                         parameter.validate_with_context(&mut ctx, path);
                     }
@@ -359,6 +677,7 @@ impl Validate for Spec {
                 for (name, response) in responses.iter() {
                     let path = format!("#/responses/{}", name);
                     if ctx.visit(path.clone()) {
+                        let path = Path::from(path);
                         ctx.error(path.clone(), "unused");
                         response.validate_with_context(&mut ctx, path);
                     }
@@ -589,4 +908,311 @@ mod tests {
             "foo string as scheme",
         );
     }
+
+    #[test]
+    fn test_validate_equivalent_paths() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {
+                "/pets/{id}": {},
+                "/pets/{petId}": {},
+                "/pets/{id}/photos": {},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.validate(Options::new()).unwrap_err().errors,
+            vec!["#.paths[/pets/{petId}]: is equivalent to `/pets/{id}`"],
+            "equivalent paths",
+        );
+    }
+
+    #[test]
+    fn test_validate_duplicate_operation_ids() {
+        let mut paths = serde_json::Map::new();
+        for i in 0..64 {
+            paths.insert(
+                format!("/pets{}", i),
+                serde_json::json!({
+                    "get": {"operationId": "getPet", "responses": {"200": {"description": "ok"}}},
+                }),
+            );
+        }
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "paths": paths,
+        }))
+        .unwrap();
+        let errors = spec.validate(Options::new()).unwrap_err().errors;
+        assert_eq!(errors.len(), 63, "{:?}", errors);
+        assert!(
+            !errors.iter().any(|x| x.starts_with("#.paths[/pets0].")),
+            "the first operation owns the id: {:?}",
+            errors,
+        );
+        assert!(
+            errors
+                .contains(&"#.paths[/pets63].get: operationId `getPet` already exists".to_owned()),
+            "{:?}",
+            errors,
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {
+                "/pets": {
+                    "get": {"responses": {"200": {"description": "ok"}}},
+                    "post": {"responses": {"201": {"description": "created"}}},
+                },
+                "/pets/{id}": {
+                    "delete": {"responses": {"204": {"description": "deleted"}}},
+                },
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.operations()
+                .map(|(path, method, _)| format!("{} {}", method, path))
+                .collect::<Vec<_>>(),
+            vec!["get /pets", "post /pets", "delete /pets/{id}"],
+            "all operations",
+        );
+
+        for (_, method, operation) in spec.operations_mut() {
+            operation.operation_id = Some(method.clone());
+        }
+        assert_eq!(
+            spec.operations()
+                .filter_map(|(_, _, operation)| operation.operation_id.clone())
+                .collect::<Vec<_>>(),
+            vec!["get", "post", "delete"],
+            "modified operations",
+        );
+
+        assert_eq!(
+            spec.operation_by_id("post")
+                .map(|(path, method, _)| format!("{} {}", method, path)),
+            Some("post /pets".to_owned()),
+            "existing operation",
+        );
+        assert!(spec.operation_by_id("put").is_none(), "missing operation");
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "swagger": "2.0",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {
+                "/pets": {
+                    "get": {"responses": {"200": {"description": "ok"}}},
+                },
+            },
+        }))
+        .unwrap();
+        spec.apply_json_patch(
+            &serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                {"op": "add", "path": "/paths/~1pets/get/summary", "value": "List pets"},
+            ]))
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            spec.paths["/pets"].get().unwrap().summary,
+            Some("List pets".to_owned()),
+            "path item patch",
+        );
+
+        let original = spec.clone();
+        assert_eq!(
+            spec.apply_json_patch(
+                &serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                    {"op": "remove", "path": "/paths/~1pets/post"},
+                ]))
+                .unwrap(),
+            )
+            .unwrap_err()
+            .to_string(),
+            "path `/paths/~1pets/post` not found",
+            "path item error",
+        );
+        assert_eq!(
+            spec.apply_json_patch(
+                &serde_json::from_value::<Vec<PatchOp>>(serde_json::json!([
+                    {"op": "remove", "path": "/info/title"},
+                ]))
+                .unwrap(),
+            )
+            .unwrap_err()
+            .to_string(),
+            "missing field `title`",
+            "invalid result",
+        );
+        assert_eq!(spec, original, "unchanged on error");
+
+        spec.apply_merge_patch(serde_json::json!({
+            "info": {"title": "bar"},
+            "paths": {"/pets": null},
+        }))
+        .unwrap();
+        assert_eq!(spec.info.title, "bar", "merge patch title");
+        assert!(spec.paths.is_empty(), "merge patch paths");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let spec = r#"{"swagger": "2.0", "info": {"title": "foo", "version": "1"}, "paths": {}}"#
+            .parse::<Spec>()
+            .unwrap();
+        assert_eq!(spec.info.title, "foo");
+
+        assert!(
+            matches!(
+                r#"{"openapi": "3.0.3", "info": {"title": "foo", "version": "1"}}"#.parse::<Spec>(),
+                Err(crate::Error::UnsupportedVersion(Some(ref x))) if x == "3.0.3"
+            ),
+            "another version",
+        );
+        assert!(
+            matches!(
+                r#"{"info": {"title": "foo", "version": "1"}}"#.parse::<Spec>(),
+                Err(crate::Error::UnsupportedVersion(None))
+            ),
+            "no version",
+        );
+        match r#"{"swagger": "2.0", "info": {"title": 1, "version": "1"}, "paths": {}}"#
+            .parse::<Spec>()
+        {
+            Err(crate::Error::Parse(err)) => assert_eq!(err.path.to_string(), "#.info.title"),
+            res => panic!("expected parse error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_reader_writer() {
+        let spec = Spec::from_reader(
+            &br#"{"swagger": "2.0", "info": {"title": "foo", "version": "1"}, "paths": {}}"#[..],
+        )
+        .unwrap();
+        let mut raw = Vec::new();
+        spec.to_writer(&mut raw).unwrap();
+        assert_eq!(
+            raw,
+            br#"{"swagger":"2.0","info":{"title":"foo","version":"1"},"paths":{}}"#,
+        );
+        assert_eq!(
+            spec.to_string().parse::<Spec>().unwrap(),
+            spec,
+            "display round trip"
+        );
+        assert!(
+            spec.to_string().contains("\n  \"swagger\": \"2.0\""),
+            "pretty"
+        );
+        assert!(matches!(
+            Spec::from_reader(&b"{}"[..]),
+            Err(crate::Error::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_host_base_path_schemes() {
+        let validate = |host: &str, base_path: &str, schemes: Vec<Scheme>| {
+            Spec {
+                info: Info {
+                    title: "foo".to_owned(),
+                    version: "1".to_owned(),
+                    ..Default::default()
+                },
+                host: Some(host.to_owned()),
+                base_path: Some(base_path.to_owned()),
+                schemes: Some(schemes),
+                ..Default::default()
+            }
+            .validate(Options::new())
+            .map_err(|e| e.errors)
+        };
+
+        for host in [
+            "example.com",
+            "example.com:8080",
+            "127.0.0.1:80",
+            "[::1]",
+            "[2001:db8::1]:443",
+        ] {
+            assert_eq!(
+                validate(host, "/v1", vec![Scheme::HTTPS]),
+                Ok(()),
+                "{}",
+                host
+            );
+        }
+
+        assert_eq!(
+            validate("https://example.com", "/v1", vec![]),
+            Err(vec![
+                "#.host: must not include a scheme, found `https://example.com`".to_owned()
+            ]),
+        );
+        assert_eq!(
+            validate("example.com/api", "/v1", vec![]),
+            Err(vec![
+                "#.host: must not include a path, found `example.com/api`".to_owned()
+            ]),
+        );
+        for host in [
+            "example.com:http",
+            "example.com:",
+            "[::1",
+            "[::1]x",
+            "{host}",
+            "",
+        ] {
+            assert_eq!(
+                validate(host, "/v1", vec![]),
+                Err(vec![format!(
+                    "#.host: must be a host name or an IP address with an optional port, found `{}`",
+                    host
+                )]),
+            );
+        }
+
+        assert_eq!(
+            validate("example.com", "v1/{version}?x", vec![]),
+            Err(vec![
+                "#.basePath: must start with `/`, found `v1/{version}?x`".to_owned(),
+                "#.basePath: must not contain templates, found `v1/{version}?x`".to_owned(),
+                "#.basePath: must not contain a query or a fragment, found `v1/{version}?x`"
+                    .to_owned(),
+            ]),
+        );
+
+        assert_eq!(
+            validate(
+                "example.com",
+                "/",
+                vec![Scheme::HTTP, Scheme::WS, Scheme::HTTP]
+            ),
+            Err(vec!["#.schemes[2]: duplicate scheme `http`".to_owned()]),
+        );
+        assert!(
+            serde_json::from_value::<Scheme>(serde_json::json!("ftp")).is_err(),
+            "unknown scheme",
+        );
+    }
 }
@@ -1,10 +1,12 @@
 //! Tag Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_required_string, Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::v2::external_documentation::ExternalDocumentation;
 use crate::v2::spec::Spec;
 
@@ -39,14 +41,15 @@ pub struct Tag {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for Tag {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
         if let Some(doc) = &self.external_docs {
-            doc.validate_with_context(ctx, format!("{}.externalDocs", path));
+            doc.validate_with_context(ctx, path.field("externalDocs"));
         }
     }
 }
@@ -103,7 +106,7 @@ mod tests {
             }),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("tag"));
+        .validate_with_context(&mut ctx, "tag".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         Tag {
@@ -111,20 +114,20 @@ mod tests {
             description: Some(String::from("Pets operations")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("tag"));
+        .validate_with_context(&mut ctx, "tag".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         Tag {
             name: String::from("pet"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("tag"));
+        .validate_with_context(&mut ctx, "tag".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         Tag {
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("tag"));
+        .validate_with_context(&mut ctx, "tag".into());
         assert_eq!(
             ctx.errors,
             vec!["tag.name: must not be empty"],
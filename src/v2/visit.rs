@@ -0,0 +1,790 @@
+//! Traversal of the v2 documents, see [crate::common::visit] for details.
+//!
+//! The items of `allOf` are the object schemas, not the [Schema] objects,
+//! so they are not passed to [Visit::visit_schema] and [VisitMut::visit_schema_mut],
+//! but their nested schemas are.
+
+use crate::common::bool_or::BoolOr;
+use crate::common::extensions::Extensions;
+use crate::common::reference::RefOr;
+use std::collections::BTreeMap;
+
+use crate::common::visit::{pointer, Document, Visit, VisitMut, Walk, WalkMut};
+use crate::v2::external_documentation::ExternalDocumentation;
+use crate::v2::header::Header;
+use crate::v2::info::Info;
+use crate::v2::items::Items;
+use crate::v2::operation::Operation;
+use crate::v2::parameter::{InFormData, InHeader, InPath, InQuery, Parameter};
+use crate::v2::path_item::PathItem;
+use crate::v2::response::{Response, Responses};
+use crate::v2::schema::{ObjectSchema, Schema};
+use crate::v2::spec::Spec;
+use crate::v2::tag::Tag;
+use crate::v2::xml::XML;
+
+impl Document for Spec {
+    type PathItem = PathItem;
+    type Operation = Operation;
+    type Parameter = Parameter;
+    type Response = Response;
+    type Schema = Schema;
+}
+
+impl Walk for Spec {
+    fn walk<V: Visit<Self>>(&self, visitor: &mut V) {
+        let mut walker = Walker { visitor };
+        walker.extensions("#", &self.extensions);
+        walker.info("#/info".to_owned(), &self.info);
+        for (name, path_item) in &self.paths {
+            walker.path_item(pointer("#/paths", name), path_item);
+        }
+        for (name, schema) in self.definitions.iter().flatten() {
+            walker.schema(pointer("#/definitions", name), schema);
+        }
+        for (name, parameter) in self.parameters.iter().flatten() {
+            walker.parameter(pointer("#/parameters", name), parameter);
+        }
+        for (name, response) in self.responses.iter().flatten() {
+            walker.response(pointer("#/responses", name), response);
+        }
+        for (i, tag) in self.tags.iter().flatten().enumerate() {
+            walker.tag(pointer("#/tags", &i.to_string()), tag);
+        }
+        walker.external_docs("#", &self.external_docs);
+    }
+}
+
+struct Walker<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<V: Visit<Spec>> Walker<'_, V> {
+    fn extensions(&mut self, path: &str, extensions: &Option<Extensions>) {
+        if let Some(extensions) = extensions {
+            self.visitor.visit_extensions(path, extensions);
+        }
+    }
+
+    fn info(&mut self, path: String, info: &Info) {
+        self.extensions(&path, &info.extensions);
+        if let Some(contact) = &info.contact {
+            self.extensions(&pointer(&path, "contact"), &contact.extensions);
+        }
+        if let Some(license) = &info.license {
+            self.extensions(&pointer(&path, "license"), &license.extensions);
+        }
+    }
+
+    fn tag(&mut self, path: String, tag: &Tag) {
+        self.extensions(&path, &tag.extensions);
+        self.external_docs(&path, &tag.external_docs);
+    }
+
+    fn external_docs(&mut self, path: &str, external_docs: &Option<ExternalDocumentation>) {
+        if let Some(external_docs) = external_docs {
+            self.extensions(&pointer(path, "externalDocs"), &external_docs.extensions);
+        }
+    }
+
+    fn xml(&mut self, path: &str, xml: &Option<XML>) {
+        if let Some(xml) = xml {
+            self.extensions(&pointer(path, "xml"), &xml.extensions);
+        }
+    }
+
+    fn items(&mut self, path: String, items: &Items) {
+        let (extensions, nested) = items_parts(items);
+        self.extensions(&path, extensions);
+        if let Some(nested) = nested {
+            self.items(pointer(&path, "items"), nested);
+        }
+    }
+
+    fn path_item(&mut self, path: String, path_item: &PathItem) {
+        self.visitor.visit_path_item(&path, path_item);
+        self.extensions(&path, &path_item.extensions);
+        self.parameters(&path, &path_item.parameters);
+        for (method, operation) in path_item.iter() {
+            let path = pointer(&path, method.as_str());
+            self.visitor.visit_operation(&path, &method, operation);
+            self.operation(path, operation);
+        }
+    }
+
+    fn operation(&mut self, path: String, operation: &Operation) {
+        self.extensions(&path, &operation.extensions);
+        self.external_docs(&path, &operation.external_docs);
+        self.parameters(&path, &operation.parameters);
+        self.responses(pointer(&path, "responses"), &operation.responses);
+    }
+
+    fn parameters(&mut self, path: &str, parameters: &Option<Vec<RefOr<Parameter>>>) {
+        let path = pointer(path, "parameters");
+        for (i, parameter) in parameters.iter().flatten().enumerate() {
+            let path = pointer(&path, &i.to_string());
+            match parameter {
+                RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+                RefOr::Item(parameter) => self.parameter(path, parameter),
+            }
+        }
+    }
+
+    fn parameter(&mut self, path: String, parameter: &Parameter) {
+        self.visitor.visit_parameter(&path, parameter);
+        if let Some(extensions) = parameter.extensions() {
+            self.visitor.visit_extensions(&path, extensions);
+        }
+        if let Some(items) = parameter_items(parameter) {
+            self.items(pointer(&path, "items"), items);
+        }
+        if let Parameter::Body(body) = parameter {
+            let path = pointer(&path, "schema");
+            match &body.schema {
+                RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+                RefOr::Item(schema) => self.schema(path, schema),
+            }
+        }
+    }
+
+    fn responses(&mut self, path: String, responses: &Responses) {
+        self.extensions(&path, &responses.extensions);
+        let default = responses.default.iter().map(|x| ("default", x));
+        let codes = responses.responses.iter().flatten();
+        for (status, response) in default.chain(codes.map(|(k, v)| (k.as_str(), v))) {
+            let path = pointer(&path, status);
+            match response {
+                RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+                RefOr::Item(response) => self.response(path, response),
+            }
+        }
+    }
+
+    fn response(&mut self, path: String, response: &Response) {
+        self.visitor.visit_response(&path, response);
+        self.extensions(&path, &response.extensions);
+        if let Some(schema) = &response.schema {
+            let path = pointer(&path, "schema");
+            match schema {
+                RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+                RefOr::Item(schema) => self.schema(path, schema),
+            }
+        }
+        self.headers(&path, response.headers.as_ref());
+    }
+
+    fn headers(&mut self, path: &str, headers: Option<&BTreeMap<String, Header>>) {
+        let path = pointer(path, "headers");
+        for (name, header) in headers.into_iter().flatten() {
+            let path = pointer(&path, name);
+            let (extensions, items) = header_parts(header);
+            self.extensions(&path, extensions);
+            if let Some(items) = items {
+                self.items(pointer(&path, "items"), items);
+            }
+        }
+    }
+
+    fn schema(&mut self, path: String, schema: &Schema) {
+        self.visitor.visit_schema(&path, schema);
+        if let Some(extensions) = schema.extensions() {
+            self.visitor.visit_extensions(&path, extensions);
+        }
+        if let Some((xml, external_docs)) = documentation(schema) {
+            self.xml(&path, xml);
+            self.external_docs(&path, external_docs);
+        }
+        match schema {
+            Schema::Array(s) => {
+                if let Some(items) = &s.items {
+                    self.nested(pointer(&path, "items"), items);
+                }
+            }
+            Schema::Object(s) => self.object(path, s),
+            _ => {}
+        }
+    }
+
+    fn nested(&mut self, path: String, schema: &RefOr<Box<Schema>>) {
+        match schema {
+            RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+            RefOr::Item(schema) => self.schema(path, schema),
+        }
+    }
+
+    fn object(&mut self, path: String, schema: &ObjectSchema) {
+        self.xml(&path, &schema.xml);
+        self.external_docs(&path, &schema.external_docs);
+        let properties = pointer(&path, "properties");
+        for (name, schema) in schema.properties.iter().flatten() {
+            self.nested(pointer(&properties, name), schema);
+        }
+        if let Some(BoolOr::Item(schema)) = &schema.additional_properties {
+            self.nested(pointer(&path, "additionalProperties"), schema);
+        }
+        let all_of = pointer(&path, "allOf");
+        for (i, schema) in schema.all_of.iter().flatten().enumerate() {
+            let path = pointer(&all_of, &i.to_string());
+            match schema {
+                RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+                RefOr::Item(schema) => {
+                    self.extensions(&path, &schema.extensions);
+                    self.object(path, schema);
+                }
+            }
+        }
+    }
+}
+
+impl WalkMut for Spec {
+    fn walk_mut<V: VisitMut<Self>>(&mut self, visitor: &mut V) {
+        let mut walker = WalkerMut { visitor };
+        walker
+            .visitor
+            .visit_extensions_mut("#", &mut self.extensions);
+        walker.info("#/info".to_owned(), &mut self.info);
+        for (name, path_item) in &mut self.paths {
+            walker.path_item(pointer("#/paths", name), path_item);
+        }
+        for (name, schema) in self.definitions.iter_mut().flatten() {
+            walker.schema(pointer("#/definitions", name), schema);
+        }
+        for (name, parameter) in self.parameters.iter_mut().flatten() {
+            walker.parameter(pointer("#/parameters", name), parameter);
+        }
+        for (name, response) in self.responses.iter_mut().flatten() {
+            walker.response(pointer("#/responses", name), response);
+        }
+        for (i, tag) in self.tags.iter_mut().flatten().enumerate() {
+            walker.tag(pointer("#/tags", &i.to_string()), tag);
+        }
+        walker.external_docs("#", &mut self.external_docs);
+    }
+}
+
+struct WalkerMut<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<V: VisitMut<Spec>> WalkerMut<'_, V> {
+    fn info(&mut self, path: String, info: &mut Info) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut info.extensions);
+        if let Some(contact) = &mut info.contact {
+            self.visitor
+                .visit_extensions_mut(&pointer(&path, "contact"), &mut contact.extensions);
+        }
+        if let Some(license) = &mut info.license {
+            self.visitor
+                .visit_extensions_mut(&pointer(&path, "license"), &mut license.extensions);
+        }
+    }
+
+    fn tag(&mut self, path: String, tag: &mut Tag) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut tag.extensions);
+        self.external_docs(&path, &mut tag.external_docs);
+    }
+
+    fn external_docs(&mut self, path: &str, external_docs: &mut Option<ExternalDocumentation>) {
+        if let Some(external_docs) = external_docs {
+            self.visitor.visit_extensions_mut(
+                &pointer(path, "externalDocs"),
+                &mut external_docs.extensions,
+            );
+        }
+    }
+
+    fn xml(&mut self, path: &str, xml: &mut Option<XML>) {
+        if let Some(xml) = xml {
+            self.visitor
+                .visit_extensions_mut(&pointer(path, "xml"), &mut xml.extensions);
+        }
+    }
+
+    fn items(&mut self, path: String, items: &mut Items) {
+        let (extensions, nested) = items_parts_mut(items);
+        self.visitor.visit_extensions_mut(&path, extensions);
+        if let Some(nested) = nested {
+            self.items(pointer(&path, "items"), nested);
+        }
+    }
+
+    fn path_item(&mut self, path: String, path_item: &mut PathItem) {
+        self.visitor.visit_path_item_mut(&path, path_item);
+        self.visitor
+            .visit_extensions_mut(&path, &mut path_item.extensions);
+        self.parameters(&path, &mut path_item.parameters);
+        for (method, operation) in path_item.iter_mut() {
+            let path = pointer(&path, method.as_str());
+            self.visitor.visit_operation_mut(&path, &method, operation);
+            self.operation(path, operation);
+        }
+    }
+
+    fn operation(&mut self, path: String, operation: &mut Operation) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut operation.extensions);
+        self.external_docs(&path, &mut operation.external_docs);
+        self.parameters(&path, &mut operation.parameters);
+        self.responses(pointer(&path, "responses"), &mut operation.responses);
+    }
+
+    fn parameters(&mut self, path: &str, parameters: &mut Option<Vec<RefOr<Parameter>>>) {
+        let path = pointer(path, "parameters");
+        for (i, parameter) in parameters.iter_mut().flatten().enumerate() {
+            let path = pointer(&path, &i.to_string());
+            match parameter {
+                RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+                RefOr::Item(parameter) => self.parameter(path, parameter),
+            }
+        }
+    }
+
+    fn parameter(&mut self, path: String, parameter: &mut Parameter) {
+        self.visitor.visit_parameter_mut(&path, parameter);
+        self.visitor
+            .visit_extensions_mut(&path, parameter.extensions_mut());
+        if let Some(items) = parameter_items_mut(parameter) {
+            self.items(pointer(&path, "items"), items);
+        }
+        if let Parameter::Body(body) = parameter {
+            let path = pointer(&path, "schema");
+            match &mut body.schema {
+                RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+                RefOr::Item(schema) => self.schema(path, schema),
+            }
+        }
+    }
+
+    fn responses(&mut self, path: String, responses: &mut Responses) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut responses.extensions);
+        let default = responses.default.iter_mut().map(|x| ("default", x));
+        let codes = responses.responses.iter_mut().flatten();
+        for (status, response) in default.chain(codes.map(|(k, v)| (k.as_str(), v))) {
+            let path = pointer(&path, status);
+            match response {
+                RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+                RefOr::Item(response) => self.response(path, response),
+            }
+        }
+    }
+
+    fn response(&mut self, path: String, response: &mut Response) {
+        self.visitor.visit_response_mut(&path, response);
+        self.visitor
+            .visit_extensions_mut(&path, &mut response.extensions);
+        if let Some(schema) = &mut response.schema {
+            let path = pointer(&path, "schema");
+            match schema {
+                RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+                RefOr::Item(schema) => self.schema(path, schema),
+            }
+        }
+        self.headers(&path, response.headers.as_mut());
+    }
+
+    fn headers(&mut self, path: &str, headers: Option<&mut BTreeMap<String, Header>>) {
+        let path = pointer(path, "headers");
+        for (name, header) in headers.into_iter().flatten() {
+            let path = pointer(&path, name);
+            let (extensions, items) = header_parts_mut(header);
+            self.visitor.visit_extensions_mut(&path, extensions);
+            if let Some(items) = items {
+                self.items(pointer(&path, "items"), items);
+            }
+        }
+    }
+
+    fn schema(&mut self, path: String, schema: &mut Schema) {
+        self.visitor.visit_schema_mut(&path, schema);
+        self.visitor
+            .visit_extensions_mut(&path, schema.extensions_mut());
+        if let Some((xml, external_docs)) = documentation_mut(schema) {
+            self.xml(&path, xml);
+            self.external_docs(&path, external_docs);
+        }
+        match schema {
+            Schema::Array(s) => {
+                if let Some(items) = &mut s.items {
+                    self.nested(pointer(&path, "items"), items);
+                }
+            }
+            Schema::Object(s) => self.object(path, s),
+            _ => {}
+        }
+    }
+
+    fn nested(&mut self, path: String, schema: &mut RefOr<Box<Schema>>) {
+        match schema {
+            RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+            RefOr::Item(schema) => self.schema(path, schema),
+        }
+    }
+
+    fn object(&mut self, path: String, schema: &mut ObjectSchema) {
+        self.xml(&path, &mut schema.xml);
+        self.external_docs(&path, &mut schema.external_docs);
+        let properties = pointer(&path, "properties");
+        for (name, schema) in schema.properties.iter_mut().flatten() {
+            self.nested(pointer(&properties, name), schema);
+        }
+        if let Some(BoolOr::Item(schema)) = &mut schema.additional_properties {
+            self.nested(pointer(&path, "additionalProperties"), schema);
+        }
+        let all_of = pointer(&path, "allOf");
+        for (i, schema) in schema.all_of.iter_mut().flatten().enumerate() {
+            let path = pointer(&all_of, &i.to_string());
+            match schema {
+                RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+                RefOr::Item(schema) => {
+                    self.visitor
+                        .visit_extensions_mut(&path, &mut schema.extensions);
+                    self.object(path, schema);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the items of an array parameter.
+fn parameter_items(parameter: &Parameter) -> Option<&Items> {
+    match parameter {
+        Parameter::Header(InHeader::Array(p))
+        | Parameter::Query(InQuery::Array(p))
+        | Parameter::Path(InPath::Array(p))
+        | Parameter::FormData(InFormData::Array(p)) => Some(&p.items),
+        _ => None,
+    }
+}
+
+fn parameter_items_mut(parameter: &mut Parameter) -> Option<&mut Items> {
+    match parameter {
+        Parameter::Header(InHeader::Array(p))
+        | Parameter::Query(InQuery::Array(p))
+        | Parameter::Path(InPath::Array(p))
+        | Parameter::FormData(InFormData::Array(p)) => Some(&mut p.items),
+        _ => None,
+    }
+}
+
+/// Returns the extensions of the header and the items of an array header.
+fn header_parts(header: &Header) -> (&Option<Extensions>, Option<&Items>) {
+    match header {
+        Header::String(h) => (&h.extensions, None),
+        Header::Integer(h) => (&h.extensions, None),
+        Header::Number(h) => (&h.extensions, None),
+        Header::Boolean(h) => (&h.extensions, None),
+        Header::Array(h) => (&h.extensions, Some(&h.items)),
+    }
+}
+
+fn header_parts_mut(header: &mut Header) -> (&mut Option<Extensions>, Option<&mut Items>) {
+    match header {
+        Header::String(h) => (&mut h.extensions, None),
+        Header::Integer(h) => (&mut h.extensions, None),
+        Header::Number(h) => (&mut h.extensions, None),
+        Header::Boolean(h) => (&mut h.extensions, None),
+        Header::Array(h) => (&mut h.extensions, Some(&mut h.items)),
+    }
+}
+
+/// Returns the extensions of the items and the nested items of an array.
+fn items_parts(items: &Items) -> (&Option<Extensions>, Option<&Items>) {
+    match items {
+        Items::String(i) => (&i.extensions, None),
+        Items::Integer(i) => (&i.extensions, None),
+        Items::Number(i) => (&i.extensions, None),
+        Items::Boolean(i) => (&i.extensions, None),
+        Items::Array(i) => (&i.extensions, Some(&i.items)),
+    }
+}
+
+fn items_parts_mut(items: &mut Items) -> (&mut Option<Extensions>, Option<&mut Items>) {
+    match items {
+        Items::String(i) => (&mut i.extensions, None),
+        Items::Integer(i) => (&mut i.extensions, None),
+        Items::Number(i) => (&mut i.extensions, None),
+        Items::Boolean(i) => (&mut i.extensions, None),
+        Items::Array(i) => (&mut i.extensions, Some(&mut i.items)),
+    }
+}
+
+/// Returns the XML object and the external documentation of a schema,
+/// the object schemas are handled with their `allOf` items.
+fn documentation(schema: &Schema) -> Option<(&Option<XML>, &Option<ExternalDocumentation>)> {
+    match schema {
+        Schema::String(s) => Some((&s.xml, &s.external_docs)),
+        Schema::Integer(s) => Some((&s.xml, &s.external_docs)),
+        Schema::Number(s) => Some((&s.xml, &s.external_docs)),
+        Schema::Boolean(s) => Some((&s.xml, &s.external_docs)),
+        Schema::Array(s) => Some((&s.xml, &s.external_docs)),
+        Schema::Null(s) => Some((&s.xml, &s.external_docs)),
+        Schema::File(s) => Some((&None, &s.external_docs)),
+        Schema::Object(_) | Schema::Other(_) => None,
+    }
+}
+
+fn documentation_mut(
+    schema: &mut Schema,
+) -> Option<(&mut Option<XML>, &mut Option<ExternalDocumentation>)> {
+    match schema {
+        Schema::String(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::Integer(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::Number(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::Boolean(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::Array(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::Null(s) => Some((&mut s.xml, &mut s.external_docs)),
+        Schema::File(_) | Schema::Object(_) | Schema::Other(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::http::Method;
+    use crate::common::reference::Ref;
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Visit<Spec> for Recorder {
+        fn visit_path_item(&mut self, path: &str, _: &PathItem) {
+            self.0.push(format!("path_item {}", path));
+        }
+
+        fn visit_operation(&mut self, path: &str, method: &Method, _: &Operation) {
+            self.0.push(format!("operation {} {}", method, path));
+        }
+
+        fn visit_parameter(&mut self, path: &str, _: &Parameter) {
+            self.0.push(format!("parameter {}", path));
+        }
+
+        fn visit_response(&mut self, path: &str, _: &Response) {
+            self.0.push(format!("response {}", path));
+        }
+
+        fn visit_schema(&mut self, path: &str, _: &Schema) {
+            self.0.push(format!("schema {}", path));
+        }
+
+        fn visit_reference(&mut self, path: &str, reference: &Ref) {
+            self.0
+                .push(format!("reference {} {}", path, reference.reference));
+        }
+
+        fn visit_extensions(&mut self, path: &str, extensions: &Extensions) {
+            for name in extensions.keys() {
+                self.0.push(format!("extension {} {}", path, name));
+            }
+        }
+    }
+
+    #[test]
+    fn test_walk() {
+        let spec = serde_json::from_value::<Spec>(json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "parameters": [
+                            {"in": "body", "name": "pet", "schema": {"$ref": "#/definitions/Pet"}},
+                            {"in": "query", "name": "dry", "type": "boolean", "x-hidden": true},
+                        ],
+                        "responses": {
+                            "default": {"$ref": "#/responses/Error"},
+                            "201": {"description": "created"},
+                        },
+                    },
+                },
+            },
+            "definitions": {
+                "Pet": {
+                    "allOf": [
+                        {"$ref": "#/definitions/Base"},
+                        {"properties": {"tags": {"type": "array", "items": {"type": "string"}}}},
+                    ],
+                },
+            },
+            "responses": {
+                "Error": {"description": "error", "schema": {"type": "string"}},
+            },
+        }))
+        .unwrap();
+        let mut recorder = Recorder::default();
+        spec.walk(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            vec![
+                "path_item #/paths/~1pets",
+                "operation post #/paths/~1pets/post",
+                "parameter #/paths/~1pets/post/parameters/0",
+                "reference #/paths/~1pets/post/parameters/0/schema #/definitions/Pet",
+                "parameter #/paths/~1pets/post/parameters/1",
+                "extension #/paths/~1pets/post/parameters/1 x-hidden",
+                "reference #/paths/~1pets/post/responses/default #/responses/Error",
+                "response #/paths/~1pets/post/responses/201",
+                "schema #/definitions/Pet",
+                "reference #/definitions/Pet/allOf/0 #/definitions/Base",
+                "schema #/definitions/Pet/allOf/1/properties/tags",
+                "schema #/definitions/Pet/allOf/1/properties/tags/items",
+                "response #/responses/Error",
+                "schema #/responses/Error/schema",
+            ],
+        );
+    }
+
+    struct Rewriter;
+
+    impl VisitMut<Spec> for Rewriter {
+        fn visit_reference_mut(&mut self, _: &str, reference: &mut Ref) {
+            if let Some(name) = reference.reference.strip_prefix("#/definitions/") {
+                reference.reference = format!("#/definitions/v1.{}", name);
+            }
+        }
+
+        fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Option<Extensions>) {
+            *extensions = None;
+        }
+    }
+
+    #[test]
+    fn test_walk_mut() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "swagger": "2.0",
+            "info": {"title": "foo", "version": "1"},
+            "x-root": true,
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "parameters": [
+                            {"in": "body", "name": "pet", "schema": {"$ref": "#/definitions/Pet"}},
+                            {"in": "query", "name": "dry", "type": "boolean", "x-hidden": true},
+                        ],
+                        "responses": {"201": {"description": "created"}},
+                    },
+                },
+            },
+            "definitions": {
+                "Pet": {
+                    "allOf": [
+                        {"$ref": "#/definitions/Base"},
+                        {"type": "object", "properties": {"tags": {"type": "array", "items": {"$ref": "#/definitions/Tag"}}}},
+                    ],
+                },
+            },
+        }))
+        .unwrap();
+        spec.walk_mut(&mut Rewriter);
+        let value = serde_json::to_value(&spec).unwrap();
+        assert_eq!(value.get("x-root"), None);
+        assert_eq!(
+            value["paths"]["/pets"]["post"]["parameters"],
+            json!([
+                {"in": "body", "name": "pet", "schema": {"$ref": "#/definitions/v1.Pet"}},
+                {"in": "query", "name": "dry", "type": "boolean"},
+            ]),
+        );
+        assert_eq!(
+            value["definitions"]["Pet"]["allOf"],
+            json!([
+                {"$ref": "#/definitions/v1.Base"},
+                {"type": "object", "properties": {"tags": {"type": "array", "items": {"$ref": "#/definitions/v1.Tag"}}}},
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_walk_all_extensions() {
+        let spec = serde_json::from_value::<Spec>(json!({
+            "swagger": "2.0",
+            "info": {
+                "title": "foo",
+                "version": "1",
+                "x-info": 1,
+                "contact": {"x-contact": 1},
+                "license": {"name": "MIT", "x-license": 1},
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "externalDocs": {"url": "https://example.com", "x-docs": 1},
+                        "parameters": [{
+                            "in": "query",
+                            "name": "ids",
+                            "type": "array",
+                            "items": {"type": "array", "items": {"type": "string", "x-nested": 1}, "x-items": 1},
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "headers": {
+                                    "X-Rate": {"type": "integer", "x-header": 1},
+                                    "X-Tags": {"type": "array", "items": {"type": "string", "x-header-items": 1}},
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "xml": {"name": "pet", "x-xml": 1},
+                    "properties": {
+                        "name": {"type": "string", "externalDocs": {"url": "https://example.com", "x-name-docs": 1}},
+                    },
+                },
+            },
+            "tags": [{
+                "name": "pets",
+                "externalDocs": {"url": "https://example.com", "x-tag-docs": 1},
+                "x-tag": 1,
+            }],
+            "externalDocs": {"url": "https://example.com", "x-root-docs": 1},
+        }))
+        .unwrap();
+        let mut recorder = Recorder::default();
+        spec.walk(&mut recorder);
+        assert_eq!(
+            recorder
+                .0
+                .iter()
+                .filter(|x| x.starts_with("extension "))
+                .collect::<Vec<_>>(),
+            vec![
+                "extension #/info x-info",
+                "extension #/info/contact x-contact",
+                "extension #/info/license x-license",
+                "extension #/paths/~1pets/get/externalDocs x-docs",
+                "extension #/paths/~1pets/get/parameters/0/items x-items",
+                "extension #/paths/~1pets/get/parameters/0/items/items x-nested",
+                "extension #/paths/~1pets/get/responses/200/headers/X-Rate x-header",
+                "extension #/paths/~1pets/get/responses/200/headers/X-Tags/items x-header-items",
+                "extension #/definitions/Pet/xml x-xml",
+                "extension #/definitions/Pet/properties/name/externalDocs x-name-docs",
+                "extension #/tags/0 x-tag",
+                "extension #/tags/0/externalDocs x-tag-docs",
+                "extension #/externalDocs x-root-docs",
+            ],
+        );
+
+        struct Strip;
+
+        impl VisitMut<Spec> for Strip {
+            fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Option<Extensions>) {
+                *extensions = None;
+            }
+        }
+
+        let mut spec = spec;
+        spec.walk_mut(&mut Strip);
+        let value = serde_json::to_string(&spec).unwrap();
+        assert!(!value.contains("\"x-"), "all extensions removed: {}", value);
+    }
+}
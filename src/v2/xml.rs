@@ -1,12 +1,12 @@
 //! XML Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::helpers::{
-    validate_optional_url, validate_required_string, Context, ValidateWithContext,
+    validate_extensions, validate_required_string, validate_uri, Context, ValidateWithContext,
 };
+use crate::common::path::Path;
 use crate::v2::spec::Spec;
 
 /// A metadata object that allows for more fine-tuned XML model definitions.
@@ -114,20 +114,25 @@ pub struct XML {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for XML {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(name) = &self.name {
-            validate_required_string(name, ctx, format!("{}.name", path));
+            validate_required_string(name, ctx, path.field("name"));
+        }
+        if let Some(namespace) = &self.namespace {
+            validate_uri(namespace, ctx, path.field("namespace"));
         }
-        validate_optional_url(&self.namespace, ctx, format!("{}.namespace", path));
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use crate::validation::Options;
 
     use super::*;
@@ -212,14 +217,14 @@ mod tests {
             wrapped: Some(true),
             extensions: None,
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         XML {
             namespace: Some("https://example.com/schema/sample".to_owned()),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         XML {
@@ -227,12 +232,12 @@ mod tests {
             namespace: Some("foo-bar".to_owned()),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert_eq!(
             ctx.errors,
             vec![
                 "xml.name: must not be empty",
-                "xml.namespace: must be a valid URL, found `foo-bar`",
+                "xml.namespace: must be a valid URI, found `foo-bar`",
             ],
             "invalid URL and empty name: {:?}",
             ctx.errors
@@ -0,0 +1,239 @@
+//! Borrowed view of a specification
+//!
+//! [SpecRef] holds the parts of a specification needed by the read-only workflows,
+//! like indexing a registry of specifications: the info, the tags, the operations
+//! and the names of the component schemas.
+//! The string fields borrow from the input buffer, unless they contain escape sequences,
+//! the keys of the maps and the items of the lists are copied as serde does not borrow them,
+//! and the rest of the document is skipped without being materialized.
+//!
+//! Example:
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! use roas::v3_0::borrowed::SpecRef;
+//!
+//! let raw = r#"{
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "Pets", "version": "1"},
+//!     "paths": {"/pets": {"get": {"operationId": "listPets", "responses": {}}}}
+//! }"#;
+//! let spec = serde_json::from_str::<SpecRef>(raw).unwrap();
+//! assert!(matches!(spec.info.title, Cow::Borrowed("Pets")));
+//! assert_eq!(
+//!     spec.operations().map(|(_, _, x)| x.operation_id.as_deref()).collect::<Vec<_>>(),
+//!     vec![Some("listPets")],
+//! );
+//! ```
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+/// The borrowed view of the [crate::v3_0::spec::Spec].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SpecRef<'a> {
+    /// The version of the OpenAPI Specification.
+    #[serde(borrow)]
+    pub openapi: Cow<'a, str>,
+
+    /// The metadata about the API.
+    #[serde(borrow)]
+    pub info: InfoRef<'a>,
+
+    /// The paths and their operations.
+    #[serde(borrow, default)]
+    pub paths: BTreeMap<Cow<'a, str>, PathItemRef<'a>>,
+
+    /// The names of the component schemas.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<ComponentsRef<'a>>,
+
+    /// The tags.
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagRef<'a>>,
+}
+
+/// The borrowed view of the [crate::v3_0::info::Info].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct InfoRef<'a> {
+    /// The title of the API.
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+
+    /// The version of the API.
+    #[serde(borrow)]
+    pub version: Cow<'a, str>,
+
+    /// A short description of the API.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Cow<'a, str>>,
+}
+
+/// The borrowed view of the [crate::v3_0::path_item::PathItem], the operations only.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PathItemRef<'a> {
+    /// The GET operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub get: Option<OperationRef<'a>>,
+
+    /// The PUT operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub put: Option<OperationRef<'a>>,
+
+    /// The POST operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub post: Option<OperationRef<'a>>,
+
+    /// The DELETE operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<OperationRef<'a>>,
+
+    /// The OPTIONS operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<OperationRef<'a>>,
+
+    /// The HEAD operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<OperationRef<'a>>,
+
+    /// The PATCH operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub patch: Option<OperationRef<'a>>,
+
+    /// The TRACE operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<OperationRef<'a>>,
+}
+
+impl<'a> PathItemRef<'a> {
+    /// Returns an iterator over the methods and the operations.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &OperationRef<'a>)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|x| (method, x)))
+    }
+}
+
+/// The borrowed view of the [crate::v3_0::operation::Operation].
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationRef<'a> {
+    /// The unique identifier of the operation.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<Cow<'a, str>>,
+
+    /// A short summary of what the operation does.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Cow<'a, str>>,
+
+    /// A verbose explanation of the operation behavior.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Cow<'a, str>>,
+
+    /// The tags of the operation.
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Cow<'a, str>>,
+
+    /// Declares the operation to be deprecated.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+}
+
+/// The borrowed view of the [crate::v3_0::components::Components], the names of the schemas only.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ComponentsRef<'a> {
+    /// The names of the schemas, the schemas themselves are skipped.
+    #[serde(borrow, default, skip_serializing)]
+    pub schemas: BTreeMap<Cow<'a, str>, IgnoredAny>,
+}
+
+/// The borrowed view of the [crate::v3_0::tag::Tag].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct TagRef<'a> {
+    /// The name of the tag.
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+
+    /// A short description of the tag.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Cow<'a, str>>,
+}
+
+impl<'a> SpecRef<'a> {
+    /// Returns an iterator over the operations as `(path, method, operation)` tuples.
+    pub fn operations(&self) -> impl Iterator<Item = (&str, &'static str, &OperationRef<'a>)> {
+        self.paths.iter().flat_map(|(path, item)| {
+            item.iter()
+                .map(move |(method, operation)| (path.as_ref(), method, operation))
+        })
+    }
+
+    /// Returns the names of the component schemas.
+    pub fn schema_names(&self) -> impl Iterator<Item = &str> {
+        self.components
+            .iter()
+            .flat_map(|x| x.schemas.keys())
+            .map(|x| x.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_ref() {
+        let raw = r#"{
+            "openapi": "3.0.3",
+            "info": {"title": "Pets \"API\"", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "parameters": [{"name": "limit", "in": "query"}],
+                    "get": {"operationId": "listPets", "tags": ["pets"], "responses": {}},
+                    "post": {"deprecated": true, "responses": {}},
+                    "x-internal": true
+                }
+            },
+            "components": {"schemas": {"Pet": {"type": "object"}, "Error": {"type": "string"}}},
+            "tags": [{"name": "pets"}]
+        }"#;
+        let spec = serde_json::from_str::<SpecRef>(raw).unwrap();
+        assert!(
+            matches!(spec.info.title, Cow::Owned(ref x) if x == "Pets \"API\""),
+            "escaped strings are owned"
+        );
+        assert!(matches!(spec.openapi, Cow::Borrowed("3.0.3")));
+        assert_eq!(
+            spec.operations()
+                .map(|(path, method, operation)| (
+                    path,
+                    method,
+                    operation.operation_id.as_deref(),
+                    operation.deprecated
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/pets", "get", Some("listPets"), false),
+                ("/pets", "post", None, true),
+            ],
+        );
+        assert_eq!(
+            spec.schema_names().collect::<Vec<_>>(),
+            vec!["Error", "Pet"]
+        );
+        assert_eq!(spec.tags[0].name, "pets");
+    }
+}
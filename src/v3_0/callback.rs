@@ -5,7 +5,12 @@ use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, Context, ValidateWithContext};
+use crate::common::path::Path;
+use crate::common::reference::RefOr;
+use crate::v3_0::link::validate_expression_template;
+use crate::v3_0::operation::Operation;
 use crate::v3_0::path_item::PathItem;
 use crate::v3_0::spec::Spec;
 
@@ -53,7 +58,78 @@ pub struct Callback {
     /// This object MAY be extended with Specification Extensions.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Callback {
+    /// Returns an iterator over the operations of the callback
+    /// as `(expression, method, operation)` tuples.
+    pub fn operations(&self) -> impl Iterator<Item = (&String, &String, &Operation)> {
+        self.paths.iter().flat_map(|(expression, item)| {
+            item.operations
+                .iter()
+                .flatten()
+                .map(move |(method, operation)| (expression, method, operation))
+        })
+    }
+}
+
+/// Collects the operations of the inline callbacks of the operation,
+/// including the nested callbacks, as `(expression, method, operation)` tuples.
+pub(crate) fn collect_nested_operations<'a>(
+    operation: &'a Operation,
+    operations: &mut Vec<(&'a String, &'a String, &'a Operation)>,
+) {
+    for callback in operation.callbacks.iter().flat_map(|x| x.values()) {
+        if let RefOr::Item(callback) = callback {
+            collect_callback_operations(callback, operations);
+        }
+    }
+}
+
+/// Collects the operations of the callback, including the nested callbacks.
+pub(crate) fn collect_callback_operations<'a>(
+    callback: &'a Callback,
+    operations: &mut Vec<(&'a String, &'a String, &'a Operation)>,
+) {
+    for (expression, method, operation) in callback.operations() {
+        operations.push((expression, method, operation));
+        collect_nested_operations(operation, operations);
+    }
+}
+
+/// Collects the operation and the operations of its inline callbacks,
+/// including the nested callbacks, with their locations.
+pub(crate) fn collect_located_operations<'a>(
+    operation: &'a Operation,
+    path: Path,
+    operations: &mut Vec<(Path, &'a Operation)>,
+) {
+    operations.push((path.clone(), operation));
+    for (name, callback) in operation.callbacks.iter().flatten() {
+        if let RefOr::Item(callback) = callback {
+            collect_located_callback_operations(
+                callback,
+                path.field("callbacks").item(name),
+                operations,
+            );
+        }
+    }
+}
+
+/// Collects the operations of the callback, including the nested callbacks, with their locations.
+pub(crate) fn collect_located_callback_operations<'a>(
+    callback: &'a Callback,
+    path: Path,
+    operations: &mut Vec<(Path, &'a Operation)>,
+) {
+    for (expression, method, operation) in callback.operations() {
+        collect_located_operations(
+            operation,
+            path.item(expression).field(method.clone()),
+            operations,
+        );
+    }
 }
 
 impl Serialize for Callback {
@@ -96,7 +172,7 @@ impl<'de> Deserialize<'de> for Callback {
             type Value = Callback;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct Callback")
+                formatter.write_str("a callback object")
             }
 
             fn visit_map<V>(self, mut map: V) -> Result<Callback, V::Error>
@@ -107,7 +183,7 @@ impl<'de> Deserialize<'de> for Callback {
                     paths: BTreeMap::new(),
                     ..Default::default()
                 };
-                let mut extensions: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+                let mut extensions: Extensions = BTreeMap::new();
                 while let Some(key) = map.next_key::<String>()? {
                     if key.starts_with("x-") {
                         if extensions.contains_key(key.as_str()) {
@@ -133,9 +209,113 @@ impl<'de> Deserialize<'de> for Callback {
 }
 
 impl ValidateWithContext<Spec> for Callback {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         for (name, path_item) in &self.paths {
-            path_item.validate_with_context(ctx, format!("{}[{}]", path, name));
+            validate_expression_template(name, ctx, path.item(name));
+            path_item.validate_with_context(ctx, path.item(name));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::validation::{Options, Validate};
+
+    #[test]
+    fn test_validate_expressions() {
+        let callback = serde_json::from_value::<Callback>(json!({
+            "{$request.query.callbackUrl}/data": {},
+            "https://example.com?id={$request.body#/id}": {},
+            "$request.header.X-Callback": {},
+            "{$request.foo}": {},
+            "{$response.body#/url": {},
+        }))
+        .unwrap();
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        callback.validate_with_context(&mut ctx, "callback".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "callback[{$request.foo}]: invalid runtime expression `$request.foo`",
+                "callback[{$response.body#/url]: unclosed runtime expression in `{$response.body#/url`",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_callback_operations() {
+        let spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "callbacks", "version": "1.0"},
+            "paths": {
+                "/subscribe": {
+                    "post": {
+                        "operationId": "subscribe",
+                        "responses": {"201": {"description": "subscribed"}},
+                        "callbacks": {
+                            "onData": {
+                                "{$request.body#/url}": {
+                                    "post": {
+                                        "operationId": "onData",
+                                        "responses": {"200": {"description": "received"}},
+                                        "callbacks": {
+                                            "onAck": {
+                                                "{$request.body#/ack}": {
+                                                    "post": {
+                                                        "operationId": "subscribe",
+                                                        "responses": {"200": {"description": "ok"}},
+                                                    },
+                                                },
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "callbacks": {
+                    "onDone": {
+                        "{$request.body#/done}": {
+                            "post": {
+                                "operationId": "onDone",
+                                "responses": {"200": {"description": "done"}},
+                            },
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.operations_with_callbacks()
+                .map(|(path, method, operation)| (
+                    path.as_str(),
+                    method.as_str(),
+                    operation.operation_id.as_deref().unwrap()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/subscribe", "post", "subscribe"),
+                ("{$request.body#/url}", "post", "onData"),
+                ("{$request.body#/ack}", "post", "subscribe"),
+                ("{$request.body#/done}", "post", "onDone"),
+            ],
+        );
+
+        let err = spec
+            .validate(Options::IgnoreUnusedCallbacks.only())
+            .unwrap_err();
+        assert_eq!(
+            err.errors,
+            vec!["#.paths[/subscribe].post.callbacks[onData][{$request.body#/url}].post.callbacks[onAck][{$request.body#/ack}].post.operationId: `subscribe` already in use"],
+        );
+    }
+}
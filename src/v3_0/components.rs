@@ -4,9 +4,15 @@ use std::collections::BTreeMap;
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::common::helpers::{validate_string_matches, Context, PushError, ValidateWithContext};
-use crate::common::reference::RefOr;
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_string_matches, Context, PushError, ValidateWithContext,
+};
+use crate::common::parse::{from_value, ParseError};
+use crate::common::path::Path;
+use crate::common::reference::{Ref, RefOr};
 use crate::v3_0::callback::Callback;
 use crate::v3_0::example::Example;
 use crate::v3_0::header::Header;
@@ -65,21 +71,274 @@ pub struct Components {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Components {
+    /// Adds the schema with the given name and returns a reference to it,
+    /// fails if a schema with the same name already exists.
+    pub fn add_schema(&mut self, name: String, schema: Schema) -> Result<Ref, ComponentError> {
+        add_component(&mut self.schemas, "#/components/schemas/", name, schema)
+    }
+
+    /// Adds the response with the given name and returns a reference to it,
+    /// fails if a response with the same name already exists.
+    pub fn add_response(
+        &mut self,
+        name: String,
+        response: Response,
+    ) -> Result<Ref, ComponentError> {
+        add_component(
+            &mut self.responses,
+            "#/components/responses/",
+            name,
+            response,
+        )
+    }
+
+    /// Adds the parameter with the given name and returns a reference to it,
+    /// fails if a parameter with the same name already exists.
+    pub fn add_parameter(
+        &mut self,
+        name: String,
+        parameter: Parameter,
+    ) -> Result<Ref, ComponentError> {
+        add_component(
+            &mut self.parameters,
+            "#/components/parameters/",
+            name,
+            parameter,
+        )
+    }
+
+    /// Adds the example with the given name and returns a reference to it,
+    /// fails if an example with the same name already exists.
+    pub fn add_example(&mut self, name: String, example: Example) -> Result<Ref, ComponentError> {
+        add_component(&mut self.examples, "#/components/examples/", name, example)
+    }
+
+    /// Adds the request body with the given name and returns a reference to it,
+    /// fails if a request body with the same name already exists.
+    pub fn add_request_body(
+        &mut self,
+        name: String,
+        request_body: RequestBody,
+    ) -> Result<Ref, ComponentError> {
+        add_component(
+            &mut self.request_bodies,
+            "#/components/requestBodies/",
+            name,
+            request_body,
+        )
+    }
+
+    /// Adds the header with the given name and returns a reference to it,
+    /// fails if a header with the same name already exists.
+    pub fn add_header(&mut self, name: String, header: Header) -> Result<Ref, ComponentError> {
+        add_component(&mut self.headers, "#/components/headers/", name, header)
+    }
+
+    /// Adds the security scheme with the given name and returns a reference to it,
+    /// fails if a security scheme with the same name already exists.
+    pub fn add_security_scheme(
+        &mut self,
+        name: String,
+        security_scheme: SecurityScheme,
+    ) -> Result<Ref, ComponentError> {
+        add_component(
+            &mut self.security_schemes,
+            "#/components/securitySchemes/",
+            name,
+            security_scheme,
+        )
+    }
+
+    /// Adds the link with the given name and returns a reference to it,
+    /// fails if a link with the same name already exists.
+    pub fn add_link(&mut self, name: String, link: Link) -> Result<Ref, ComponentError> {
+        add_component(&mut self.links, "#/components/links/", name, link)
+    }
+
+    /// Adds the callback with the given name and returns a reference to it,
+    /// fails if a callback with the same name already exists.
+    pub fn add_callback(
+        &mut self,
+        name: String,
+        callback: Callback,
+    ) -> Result<Ref, ComponentError> {
+        add_component(
+            &mut self.callbacks,
+            "#/components/callbacks/",
+            name,
+            callback,
+        )
+    }
+
+    /// Adds the JSON schema with the given name and returns a reference to it,
+    /// e.g. the schema generated by `schemars` for a type deriving `JsonSchema`.
+    ///
+    /// The definitions of the schema (`$defs` or `definitions`) are added as separate schemas
+    /// and the references to them are rewritten to point to the components.
+    /// The JSON Schema constructs that are not supported by v3.0 are converted:
+    /// the type arrays like `["string", "null"]` become `nullable` types
+    /// and the integer formats other than `int32` and `int64` are removed.
+    /// A definition equal to the existing schema with the same name is shared,
+    /// e.g. a type used by several added schemas.
+    /// Nothing is added if any of the schemas cannot be parsed
+    /// or differs from the existing schema with the same name.
+    pub fn add_json_schema(
+        &mut self,
+        name: String,
+        mut schema: serde_json::Value,
+    ) -> Result<Ref, ComponentError> {
+        let mut schemas = Vec::new();
+        if let serde_json::Value::Object(map) = &mut schema {
+            map.remove("$schema");
+            for key in ["$defs", "definitions"] {
+                if let Some(serde_json::Value::Object(defs)) = map.remove(key) {
+                    schemas.extend(defs);
+                }
+            }
+        }
+        let reference = Ref::new(format!("#/components/schemas/{}", name));
+        schemas.insert(0, (name, schema));
+        let schemas = schemas
+            .into_iter()
+            .map(|(name, mut value)| {
+                convert_json_schema(&mut value);
+                from_value::<Schema>(&value).map(|schema| (name, schema))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let existing = self.schemas.get_or_insert_with(BTreeMap::new);
+        let mut added = Vec::new();
+        for (name, schema) in schemas {
+            let schema = RefOr::new_item(schema);
+            match existing.get(&name) {
+                Some(x) if x == &schema => {}
+                Some(_) => return Err(ComponentError::Exists(name)),
+                None => added.push((name, schema)),
+            }
+        }
+        existing.extend(added);
+        Ok(reference)
+    }
+}
+
+/// The error of adding a component.
+#[derive(Debug, Error)]
+pub enum ComponentError {
+    /// Exists is returned when a different component with the same name already exists.
+    #[error("component `{0}` already exists")]
+    Exists(String),
+
+    /// Parse is returned when an added JSON schema cannot be parsed.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+fn add_component<T>(
+    map: &mut Option<BTreeMap<String, RefOr<T>>>,
+    prefix: &str,
+    name: String,
+    item: T,
+) -> Result<Ref, ComponentError> {
+    let map = map.get_or_insert_with(BTreeMap::new);
+    if map.contains_key(&name) {
+        return Err(ComponentError::Exists(name));
+    }
+    let reference = Ref::new(format!("{}{}", prefix, name));
+    map.insert(name, RefOr::new_item(item));
+    Ok(reference)
+}
+
+/// The keywords holding a schema, e.g. `not`, or an array of schemas, e.g. `allOf`.
+const SCHEMA_KEYWORDS: [&str; 16] = [
+    "additionalItems",
+    "additionalProperties",
+    "allOf",
+    "anyOf",
+    "contains",
+    "contentSchema",
+    "else",
+    "if",
+    "items",
+    "not",
+    "oneOf",
+    "prefixItems",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// The keywords holding a map of schemas, e.g. `properties`.
+const SCHEMA_MAP_KEYWORDS: [&str; 5] = [
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// Converts the JSON schema in place to the form supported by v3.0, see [Components::add_json_schema].
+fn convert_json_schema(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(serde_json::Value::String(reference)) = map.get_mut("$ref") {
+                for prefix in ["#/$defs/", "#/definitions/"] {
+                    if let Some(name) = reference.strip_prefix(prefix) {
+                        *reference = format!("#/components/schemas/{}", name);
+                        break;
+                    }
+                }
+            }
+            if let Some(serde_json::Value::Array(types)) = map.get("type") {
+                let mut types = types.clone();
+                let len = types.len();
+                types.retain(|x| x != "null");
+                if types.len() == 1 {
+                    map.insert("type".to_owned(), types.remove(0));
+                    if len > 1 {
+                        map.insert("nullable".to_owned(), true.into());
+                    }
+                }
+            }
+            if map.get("type").is_some_and(|x| x == "integer")
+                && map
+                    .get("format")
+                    .is_some_and(|x| x != "int32" && x != "int64")
+            {
+                map.remove("format");
+            }
+            // the payloads, like `default` or `enum`, are kept as they are
+            for (key, value) in map.iter_mut() {
+                if SCHEMA_KEYWORDS.contains(&key.as_str()) {
+                    convert_json_schema(value);
+                } else if SCHEMA_MAP_KEYWORDS.contains(&key.as_str()) {
+                    if let serde_json::Value::Object(schemas) = value {
+                        schemas.values_mut().for_each(convert_json_schema);
+                    }
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(convert_json_schema),
+        _ => {}
+    }
 }
 
 impl ValidateWithContext<Spec> for Components {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         let re = Regex::new(r"^[a-zA-Z0-9.\-_]+$").unwrap();
 
         if let Some(objs) = &self.schemas {
             for (name, obj) in objs {
                 let reference = format!("#/components/schemas/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedSchemas) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.schemas[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.schemas[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("schemas").item("<name>"));
+                obj.validate_with_context(ctx, path.field("schemas").item(name));
             }
         }
 
@@ -87,10 +346,10 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/responses/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedResponses) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.responses[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.responses[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("responses").item("<name>"));
+                obj.validate_with_context(ctx, path.field("responses").item(name));
             }
         }
 
@@ -98,10 +357,10 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/parameters/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedParameters) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.parameters[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.parameters[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("parameters").item("<name>"));
+                obj.validate_with_context(ctx, path.field("parameters").item(name));
             }
         }
 
@@ -109,10 +368,10 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/examples/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedExamples) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.examples[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.examples[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("examples").item("<name>"));
+                obj.validate_with_context(ctx, path.field("examples").item(name));
             }
         }
 
@@ -121,10 +380,10 @@ impl ValidateWithContext<Spec> for Components {
                 let reference = format!("#/components/requestBodies/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedRequestBodies)
                 {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.requestBodies[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.requestBodies[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("requestBodies").item("<name>"));
+                obj.validate_with_context(ctx, path.field("requestBodies").item(name));
             }
         }
 
@@ -132,10 +391,10 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/headers/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedHeaders) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.headers[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.headers[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("headers").item("<name>"));
+                obj.validate_with_context(ctx, path.field("headers").item(name));
             }
         }
 
@@ -145,24 +404,22 @@ impl ValidateWithContext<Spec> for Components {
                 if !ctx.is_visited(&reference)
                     && !ctx.is_option(Options::IgnoreUnusedSecuritySchemes)
                 {
-                    ctx.error(reference.clone(), "unused");
+                    ctx.error(reference.clone().into(), "unused");
                 }
                 validate_string_matches(
                     name,
                     &re,
                     ctx,
-                    format!("{}.securitySchemes[<name>]", path),
+                    path.field("securitySchemes").item("<name>"),
                 );
-                obj.validate_with_context(ctx, format!("{}.securitySchemes[{}]", path, name));
+                obj.validate_with_context(ctx, path.field("securitySchemes").item(name));
                 if let Ok(SecurityScheme::OAuth2(oauth2)) = obj.get_item(ctx.spec) {
-                    if let Some(flow) = &oauth2.flows.implicit {
-                        for scope in flow.scopes.keys() {
-                            let reference = format!("{}/{}", reference, scope);
-                            if !ctx.is_visited(&reference)
-                                && !ctx.is_option(Options::IgnoreUnusedSecuritySchemes)
-                            {
-                                ctx.error(reference, "unused");
-                            }
+                    for scope in oauth2.flows.all_scopes().keys() {
+                        let reference = format!("{}/{}", reference, scope);
+                        if !ctx.is_visited(&reference)
+                            && !ctx.is_option(Options::IgnoreUnusedSecuritySchemes)
+                        {
+                            ctx.error(reference.into(), "unused");
                         }
                     }
                 }
@@ -173,10 +430,10 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/links/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedLinks) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.links[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.links[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("links").item("<name>"));
+                obj.validate_with_context(ctx, path.field("links").item(name));
             }
         }
 
@@ -184,11 +441,170 @@ impl ValidateWithContext<Spec> for Components {
             for (name, obj) in objs {
                 let reference = format!("#/components/callbacks/{}", name);
                 if !ctx.is_visited(&reference) && !ctx.is_option(Options::IgnoreUnusedCallbacks) {
-                    ctx.error(reference, "unused");
+                    ctx.error(reference.into(), "unused");
                 }
-                validate_string_matches(name, &re, ctx, format!("{}.callbacks[<name>]", path));
-                obj.validate_with_context(ctx, format!("{}.callbacks[{}]", path, name));
+                validate_string_matches(name, &re, ctx, path.field("callbacks").item("<name>"));
+                obj.validate_with_context(ctx, path.field("callbacks").item(name));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_component() {
+        let mut components = Components::default();
+        assert_eq!(
+            components
+                .add_schema("Pet".to_owned(), Schema::default())
+                .unwrap(),
+            Ref::new("#/components/schemas/Pet".to_owned()),
+            "schema reference",
+        );
+        assert_eq!(
+            components
+                .add_request_body("Pet".to_owned(), RequestBody::default())
+                .unwrap(),
+            Ref::new("#/components/requestBodies/Pet".to_owned()),
+            "request body reference",
+        );
+        assert!(
+            matches!(
+                components.add_schema("Pet".to_owned(), Schema::default()),
+                Err(ComponentError::Exists(ref x)) if x == "Pet"
+            ),
+            "name conflict",
+        );
+        assert_eq!(
+            components.schemas,
+            Some(BTreeMap::from([(
+                "Pet".to_owned(),
+                RefOr::new_item(Schema::default())
+            )])),
+            "schema added",
+        );
+
+        let spec = Spec {
+            components: Some(components),
+            ..Default::default()
+        };
+        let reference =
+            RefOr::<RequestBody>::Ref(Ref::new("#/components/requestBodies/Pet".to_owned()));
+        assert!(reference.get_item(&spec).is_ok(), "reference resolved");
+    }
+
+    #[test]
+    fn test_add_json_schema() {
+        let mut components = Components::default();
+        let reference = components
+            .add_json_schema(
+                "Pet".to_owned(),
+                serde_json::json!({
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "title": "Pet",
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "tag": {"type": ["string", "null"]},
+                        "age": {"type": "integer", "format": "uint32", "minimum": 0},
+                        "owner": {"$ref": "#/$defs/Owner"},
+                    },
+                    "required": ["name", "owner"],
+                    "$defs": {
+                        "Owner": {
+                            "type": "object",
+                            "properties": {"id": {"type": "integer", "format": "int64"}},
+                        },
+                    },
+                }),
+            )
+            .unwrap();
+        assert_eq!(reference.reference, "#/components/schemas/Pet");
+        assert_eq!(
+            serde_json::to_value(&components).unwrap(),
+            serde_json::json!({
+                "schemas": {
+                    "Pet": {
+                        "title": "Pet",
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "tag": {"type": "string", "nullable": true},
+                            "age": {"type": "integer", "minimum": 0},
+                            "owner": {"$ref": "#/components/schemas/Owner"},
+                        },
+                        "required": ["name", "owner"],
+                    },
+                    "Owner": {
+                        "type": "object",
+                        "properties": {"id": {"type": "integer", "format": "int64"}},
+                    },
+                },
+            }),
+        );
+
+        let err = Components::default()
+            .add_json_schema(
+                "Pet".to_owned(),
+                serde_json::json!({
+                    "type": "object",
+                    "$defs": {"Owner": {"type": "object", "required": "id"}},
+                }),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, ComponentError::Parse(ref x) if x.path.to_string() == "#.required"),
+            "{:?}",
+            err
+        );
+
+        let reference = components
+            .add_json_schema(
+                "Pets".to_owned(),
+                serde_json::json!({
+                    "type": "array",
+                    "items": {"$ref": "#/definitions/Owner"},
+                    "default": [{"type": ["string", "null"], "$ref": "#/$defs/Owner"}],
+                    "definitions": {
+                        "Owner": {
+                            "type": "object",
+                            "properties": {"id": {"type": "integer", "format": "int64"}},
+                        },
+                    },
+                }),
+            )
+            .unwrap();
+        assert_eq!(reference.reference, "#/components/schemas/Pets");
+        assert_eq!(
+            serde_json::to_value(&components.schemas.as_ref().unwrap()["Pets"]).unwrap(),
+            serde_json::json!({
+                "type": "array",
+                "items": {"$ref": "#/components/schemas/Owner"},
+                "default": [{"type": ["string", "null"], "$ref": "#/$defs/Owner"}],
+            }),
+            "the payloads are not converted, the equal definition is shared",
+        );
+
+        let err = components
+            .add_json_schema(
+                "Owners".to_owned(),
+                serde_json::json!({
+                    "type": "array",
+                    "$defs": {"Owner": {"type": "string"}},
+                }),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, ComponentError::Exists(ref x) if x == "Owner"),
+            "{:?}",
+            err
+        );
+        assert!(
+            !components.schemas.as_ref().unwrap().contains_key("Owners"),
+            "nothing added"
+        );
+    }
+}
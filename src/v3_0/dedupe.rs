@@ -0,0 +1,271 @@
+//! Deduplication of the schemas
+//!
+//! The specifications generated by the code-first tools often define the same anonymous model
+//! under several names.
+//! The deduplication finds the structurally identical schemas, keeps the first one by the name
+//! and rewrites the references to the others.
+//! The schemas selected by a discriminator are never removed,
+//! because the discriminator values are the names of the schemas.
+//! The schemas are compared semantically, see [crate::common::semantic] for details.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets": {
+//!             "get": {
+//!                 "responses": {
+//!                     "200": {
+//!                         "description": "pets",
+//!                         "content": {
+//!                             "application/json": {
+//!                                 "schema": {"$ref": "#/components/schemas/Pet2"},
+//!                             },
+//!                         },
+//!                     },
+//!                 },
+//!             },
+//!         },
+//!     },
+//!     "components": {
+//!         "schemas": {
+//!             "Pet1": {"type": "object", "properties": {"name": {"type": "string"}}},
+//!             "Pet2": {"type": "object", "properties": {"name": {"type": "string"}}},
+//!         },
+//!     },
+//! })).unwrap();
+//!
+//! let removed = spec.dedupe_schemas();
+//! assert_eq!(removed.get("Pet2").map(String::as_str), Some("Pet1"));
+//! assert_eq!(
+//!     serde_json::to_value(&spec.paths).unwrap()["/pets"]["get"]["responses"]["200"]["content"]
+//!         ["application/json"]["schema"],
+//!     serde_json::json!({"$ref": "#/components/schemas/Pet1"}),
+//! );
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::common::semantic::{canonical_value, semantic_hash, EqOptions};
+use crate::common::visit::{Visit, Walk, WalkMut};
+use crate::v3_0::components::Components;
+use crate::v3_0::rename::RenameSchema;
+use crate::v3_0::schema::Schema;
+use crate::v3_0::spec::Spec;
+use crate::v3_0::visit::{walk_components, walk_components_mut};
+
+impl Schema {
+    /// Returns the hash of the structure of the schema.
+    ///
+    /// The semantically equal schemas, see [crate::common::semantic], have the same hash.
+    /// The hash is stable within a build only, so it must not be persisted.
+    pub fn semantic_hash(&self) -> u64 {
+        semantic_hash(self)
+    }
+}
+
+impl Components {
+    /// Removes the structurally identical schemas, keeping the first one by the name,
+    /// and rewrites the references to the removed schemas within the components.
+    ///
+    /// The deduplication is repeated while the rewritten references make more schemas identical.
+    /// The schemas selected by the discriminators of the components are kept.
+    /// Returns the names of the removed schemas mapped to the names of the kept ones.
+    /// Use [Spec::dedupe_schemas] to rewrite the references of the whole specification.
+    pub fn dedupe_schemas(&mut self) -> BTreeMap<String, String> {
+        let mut selected = Selected::default();
+        walk_components(self, &mut selected);
+        self.dedupe(&selected.0)
+    }
+
+    fn dedupe(&mut self, selected: &BTreeSet<String>) -> BTreeMap<String, String> {
+        let mut removed = BTreeMap::new();
+        loop {
+            let duplicates = self.find_duplicates(selected);
+            if duplicates.is_empty() {
+                break;
+            }
+            if let Some(schemas) = &mut self.schemas {
+                for name in duplicates.keys() {
+                    schemas.remove(name);
+                }
+            }
+            for (from, to) in &duplicates {
+                walk_components_mut(self, &mut RenameSchema::new(from, to));
+            }
+            for to in removed.values_mut() {
+                if let Some(kept) = duplicates.get(to) {
+                    *to = kept.clone();
+                }
+            }
+            removed.extend(duplicates);
+        }
+        removed
+    }
+
+    fn find_duplicates(&self, selected: &BTreeSet<String>) -> BTreeMap<String, String> {
+        let options = EqOptions::default();
+        let mut kept = BTreeMap::<u64, Vec<(&String, serde_json::Value)>>::new();
+        let mut duplicates = BTreeMap::new();
+        for (name, schema) in self.schemas.iter().flatten() {
+            let Ok(value) = canonical_value(schema, &options) else {
+                continue;
+            };
+            let candidates = kept.entry(semantic_hash(schema)).or_default();
+            match candidates.iter().find(|(_, x)| x == &value) {
+                Some(_) if selected.contains(name) => {}
+                Some((original, _)) => {
+                    duplicates.insert(name.clone(), String::clone(original));
+                }
+                None => candidates.push((name, value)),
+            }
+        }
+        duplicates
+    }
+}
+
+impl Spec {
+    /// Removes the structurally identical schemas and rewrites all references to them,
+    /// see [Components::dedupe_schemas] for details.
+    ///
+    /// The schemas selected by any discriminator of the specification are kept.
+    pub fn dedupe_schemas(&mut self) -> BTreeMap<String, String> {
+        let mut selected = Selected::default();
+        self.walk(&mut selected);
+        let removed = match &mut self.components {
+            Some(components) => components.dedupe(&selected.0),
+            None => return BTreeMap::new(),
+        };
+        for (from, to) in &removed {
+            self.walk_mut(&mut RenameSchema::new(from, to));
+        }
+        removed
+    }
+}
+
+/// Collects the names of the schemas selected by the discriminators.
+#[derive(Default)]
+struct Selected(BTreeSet<String>);
+
+impl Visit<Spec> for Selected {
+    fn visit_schema(&mut self, _path: &str, schema: &Schema) {
+        let (discriminator, members) = match schema {
+            Schema::AllOf(s) => (&s.discriminator, &s.all_of),
+            Schema::AnyOf(s) => (&s.discriminator, &s.any_of),
+            Schema::OneOf(s) => (&s.discriminator, &s.one_of),
+            _ => return,
+        };
+        if let Some(discriminator) = discriminator {
+            self.0.extend(discriminator.schema_names(members));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_semantic_hash() {
+        let schema = |value| serde_json::from_value::<Schema>(value).unwrap();
+        assert_eq!(
+            schema(json!({"type": "object", "properties": {"a": {"type": "string"}, "b": {"type": "integer"}}}))
+                .semantic_hash(),
+            schema(json!({"properties": {"b": {"type": "integer"}, "a": {"type": "string"}}, "type": "object"}))
+                .semantic_hash(),
+        );
+        assert_ne!(
+            schema(json!({"type": "string"})).semantic_hash(),
+            schema(json!({"type": "integer"})).semantic_hash(),
+        );
+    }
+
+    #[test]
+    fn test_dedupe_schemas() {
+        let mut components = serde_json::from_value::<Components>(json!({
+            "schemas": {
+                "A": {"type": "object", "properties": {"id": {"type": "integer"}}},
+                "B": {"type": "object", "properties": {"id": {"type": "integer"}}},
+                "ListA": {"type": "array", "items": {"$ref": "#/components/schemas/A"}},
+                "ListB": {"type": "array", "items": {"$ref": "#/components/schemas/B"}},
+                "Owner": {
+                    "type": "object",
+                    "properties": {"pets": {"$ref": "#/components/schemas/ListB"}},
+                },
+                "Text": {"type": "string"},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            components.dedupe_schemas(),
+            BTreeMap::from([
+                ("B".to_owned(), "A".to_owned()),
+                ("ListB".to_owned(), "ListA".to_owned()),
+            ]),
+        );
+        assert_eq!(
+            serde_json::to_value(&components).unwrap(),
+            json!({
+                "schemas": {
+                    "A": {"type": "object", "properties": {"id": {"type": "integer"}}},
+                    "ListA": {"type": "array", "items": {"$ref": "#/components/schemas/A"}},
+                    "Owner": {
+                        "type": "object",
+                        "properties": {"pets": {"$ref": "#/components/schemas/ListA"}},
+                    },
+                    "Text": {"type": "string"},
+                },
+            }),
+        );
+    }
+    #[test]
+    fn test_dedupe_schemas_discriminator() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Cat": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "Dog": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "Fish": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "Pet": {
+                        "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+                        "discriminator": {"propertyName": "kind", "mapping": {"fish": "Fish"}},
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        assert_eq!(spec.dedupe_schemas(), BTreeMap::new());
+
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Cat": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "Kitten": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "Pet": {
+                        "oneOf": [{"$ref": "#/components/schemas/Cat"}],
+                        "discriminator": {"propertyName": "kind"},
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            spec.dedupe_schemas(),
+            BTreeMap::from([("Kitten".to_owned(), "Cat".to_owned())]),
+            "the selected schema is kept",
+        );
+    }
+}
@@ -0,0 +1,1351 @@
+//! Semantic diff between two specifications.
+//!
+//! The diff walks the paths, operations and component schemas of two specifications
+//! and produces a list of structured changes, so API review tooling does not need to compare
+//! raw JSON documents.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::diff::{diff, Change};
+//! use roas::v3_0::spec::Spec;
+//!
+//! let old = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {"/pets": {}},
+//! })).unwrap();
+//! let new = Spec::default();
+//!
+//! assert_eq!(
+//!     diff(&old, &new).changes,
+//!     vec![Change::PathRemoved { path: "/pets".to_owned() }],
+//! );
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::common::reference::RefOr;
+use crate::v3_0::media_type::MediaType;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::parameter::Parameter;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::response::Response;
+use crate::v3_0::schema::{Schema, SingleSchema};
+use crate::v3_0::spec::Spec;
+
+/// A single change between two specifications.
+///
+/// The `location` fields use the same format as the validation errors,
+/// e.g. `#.paths[/pets].get` or `#/components/schemas/Pet.properties.name`.
+/// The `direction` fields tell whether the changed data is sent or received by the clients,
+/// which defines the compatibility of the change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    PathAdded {
+        path: String,
+    },
+    PathRemoved {
+        path: String,
+    },
+    OperationAdded {
+        path: String,
+        method: String,
+    },
+    OperationRemoved {
+        path: String,
+        method: String,
+    },
+    ParameterAdded {
+        location: String,
+        name: String,
+        parameter_in: String,
+        required: bool,
+    },
+    ParameterRemoved {
+        location: String,
+        name: String,
+        parameter_in: String,
+    },
+    ParameterRequiredChanged {
+        location: String,
+        name: String,
+        parameter_in: String,
+        required: bool,
+    },
+    RequestBodyAdded {
+        location: String,
+        required: bool,
+    },
+    RequestBodyRemoved {
+        location: String,
+    },
+    RequestBodyRequiredChanged {
+        location: String,
+        required: bool,
+    },
+    MediaTypeAdded {
+        location: String,
+        direction: Direction,
+        media_type: String,
+    },
+    MediaTypeRemoved {
+        location: String,
+        media_type: String,
+    },
+    ResponseAdded {
+        location: String,
+        status: String,
+    },
+    ResponseRemoved {
+        location: String,
+        status: String,
+    },
+    SchemaAdded {
+        name: String,
+    },
+    SchemaRemoved {
+        name: String,
+    },
+    SchemaTypeChanged {
+        location: String,
+        from: String,
+        to: String,
+    },
+    SchemaPropertyAdded {
+        location: String,
+        property: String,
+    },
+    SchemaPropertyRemoved {
+        location: String,
+        direction: Direction,
+        property: String,
+    },
+    RequiredPropertyAdded {
+        location: String,
+        direction: Direction,
+        property: String,
+    },
+    RequiredPropertyRemoved {
+        location: String,
+        direction: Direction,
+        property: String,
+    },
+    EnumValueAdded {
+        location: String,
+        direction: Direction,
+        value: String,
+    },
+    EnumValueRemoved {
+        location: String,
+        direction: Direction,
+        value: String,
+    },
+    /// The `enum` added to a schema without the allowed values.
+    EnumAdded {
+        location: String,
+        direction: Direction,
+    },
+    /// The `enum` removed, so the schema allows any value of its type.
+    EnumRemoved {
+        location: String,
+        direction: Direction,
+    },
+    /// A member of the `allOf`, `anyOf` or `oneOf` composition added at the `index`.
+    SchemaMemberAdded {
+        location: String,
+        direction: Direction,
+        keyword: String,
+        index: usize,
+    },
+    /// A member of the `allOf`, `anyOf` or `oneOf` composition removed from the `index`.
+    SchemaMemberRemoved {
+        location: String,
+        direction: Direction,
+        keyword: String,
+        index: usize,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::PathAdded { path } => write!(f, "#.paths[{}]: path added", path),
+            Change::PathRemoved { path } => write!(f, "#.paths[{}]: path removed", path),
+            Change::OperationAdded { path, method } => {
+                write!(f, "#.paths[{}].{}: operation added", path, method)
+            }
+            Change::OperationRemoved { path, method } => {
+                write!(f, "#.paths[{}].{}: operation removed", path, method)
+            }
+            Change::ParameterAdded {
+                location,
+                name,
+                parameter_in,
+                required,
+            } => write!(
+                f,
+                "{}.parameters[{}.{}]: {} parameter added",
+                location,
+                parameter_in,
+                name,
+                if *required { "required" } else { "optional" }
+            ),
+            Change::ParameterRemoved {
+                location,
+                name,
+                parameter_in,
+            } => write!(
+                f,
+                "{}.parameters[{}.{}]: parameter removed",
+                location, parameter_in, name
+            ),
+            Change::ParameterRequiredChanged {
+                location,
+                name,
+                parameter_in,
+                required,
+            } => write!(
+                f,
+                "{}.parameters[{}.{}]: parameter became {}",
+                location,
+                parameter_in,
+                name,
+                if *required { "required" } else { "optional" }
+            ),
+            Change::RequestBodyAdded { location, required } => write!(
+                f,
+                "{}.requestBody: {} request body added",
+                location,
+                if *required { "required" } else { "optional" }
+            ),
+            Change::RequestBodyRemoved { location } => {
+                write!(f, "{}.requestBody: request body removed", location)
+            }
+            Change::RequestBodyRequiredChanged { location, required } => write!(
+                f,
+                "{}.requestBody: request body became {}",
+                location,
+                if *required { "required" } else { "optional" }
+            ),
+            Change::MediaTypeAdded {
+                location,
+                media_type,
+                ..
+            } => write!(f, "{}.content[{}]: media type added", location, media_type),
+            Change::MediaTypeRemoved {
+                location,
+                media_type,
+            } => write!(
+                f,
+                "{}.content[{}]: media type removed",
+                location, media_type
+            ),
+            Change::ResponseAdded { location, status } => {
+                write!(f, "{}.responses[{}]: response added", location, status)
+            }
+            Change::ResponseRemoved { location, status } => {
+                write!(f, "{}.responses[{}]: response removed", location, status)
+            }
+            Change::SchemaAdded { name } => {
+                write!(f, "#/components/schemas/{}: schema added", name)
+            }
+            Change::SchemaRemoved { name } => {
+                write!(f, "#/components/schemas/{}: schema removed", name)
+            }
+            Change::SchemaTypeChanged { location, from, to } => {
+                write!(f, "{}: type changed from `{}` to `{}`", location, from, to)
+            }
+            Change::SchemaPropertyAdded { location, property } => {
+                write!(f, "{}.properties.{}: property added", location, property)
+            }
+            Change::SchemaPropertyRemoved {
+                location, property, ..
+            } => {
+                write!(f, "{}.properties.{}: property removed", location, property)
+            }
+            Change::RequiredPropertyAdded {
+                location, property, ..
+            } => {
+                write!(f, "{}.required: `{}` added", location, property)
+            }
+            Change::RequiredPropertyRemoved {
+                location, property, ..
+            } => {
+                write!(f, "{}.required: `{}` removed", location, property)
+            }
+            Change::EnumValueAdded {
+                location, value, ..
+            } => {
+                write!(f, "{}.enum: `{}` added", location, value)
+            }
+            Change::EnumValueRemoved {
+                location, value, ..
+            } => {
+                write!(f, "{}.enum: `{}` removed", location, value)
+            }
+            Change::EnumAdded { location, .. } => write!(f, "{}.enum: enum added", location),
+            Change::EnumRemoved { location, .. } => write!(f, "{}.enum: enum removed", location),
+            Change::SchemaMemberAdded {
+                location,
+                keyword,
+                index,
+                ..
+            } => write!(f, "{}.{}[{}]: member added", location, keyword, index),
+            Change::SchemaMemberRemoved {
+                location,
+                keyword,
+                index,
+                ..
+            } => write!(f, "{}.{}[{}]: member removed", location, keyword, index),
+        }
+    }
+}
+
+/// The compatibility of a change for the existing clients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// The existing clients keep working.
+    NonBreaking,
+
+    /// The existing clients may break, depending on how they use the API.
+    PotentiallyBreaking,
+
+    /// The existing clients break.
+    Breaking,
+}
+
+/// The direction of the data described by the location of a change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// The data sent by the clients, the parameters and the request bodies.
+    Request,
+
+    /// The data received by the clients, the responses.
+    Response,
+
+    /// The component schemas can be used in both requests and responses.
+    Unknown,
+}
+
+impl Change {
+    /// Classifies the change following the common rules,
+    /// e.g. a removed operation or a narrowed request enum is breaking,
+    /// while a new optional property is not.
+    pub fn compatibility(&self) -> Compatibility {
+        use Compatibility::*;
+
+        let required = |required: bool| if required { Breaking } else { NonBreaking };
+        let by_direction = |direction: &Direction, request, response, unknown| match direction {
+            Direction::Request => request,
+            Direction::Response => response,
+            Direction::Unknown => unknown,
+        };
+        match self {
+            Change::PathAdded { .. } | Change::OperationAdded { .. } => NonBreaking,
+            Change::PathRemoved { .. } | Change::OperationRemoved { .. } => Breaking,
+            Change::ParameterAdded { required: r, .. } => required(*r),
+            Change::ParameterRemoved { .. } => PotentiallyBreaking,
+            Change::ParameterRequiredChanged { required: r, .. } => required(*r),
+            Change::RequestBodyAdded { required: r, .. } => required(*r),
+            Change::RequestBodyRemoved { .. } => PotentiallyBreaking,
+            Change::RequestBodyRequiredChanged { required: r, .. } => required(*r),
+            Change::MediaTypeAdded { direction, .. } => by_direction(
+                direction,
+                NonBreaking,
+                PotentiallyBreaking,
+                PotentiallyBreaking,
+            ),
+            Change::MediaTypeRemoved { .. } => Breaking,
+            Change::ResponseAdded { .. } | Change::ResponseRemoved { .. } => PotentiallyBreaking,
+            Change::SchemaAdded { .. } => NonBreaking,
+            Change::SchemaRemoved { .. } => PotentiallyBreaking,
+            Change::SchemaTypeChanged { .. } => Breaking,
+            Change::SchemaPropertyAdded { .. } => NonBreaking,
+            Change::SchemaPropertyRemoved { direction, .. } => {
+                by_direction(direction, PotentiallyBreaking, Breaking, Breaking)
+            }
+            Change::RequiredPropertyAdded { direction, .. } => {
+                by_direction(direction, Breaking, NonBreaking, PotentiallyBreaking)
+            }
+            Change::RequiredPropertyRemoved { direction, .. } => {
+                by_direction(direction, NonBreaking, Breaking, PotentiallyBreaking)
+            }
+            Change::EnumValueAdded { direction, .. } => by_direction(
+                direction,
+                NonBreaking,
+                PotentiallyBreaking,
+                PotentiallyBreaking,
+            ),
+            Change::EnumValueRemoved { direction, .. } => {
+                by_direction(direction, Breaking, NonBreaking, Breaking)
+            }
+            Change::EnumAdded { direction, .. } => {
+                by_direction(direction, Breaking, NonBreaking, Breaking)
+            }
+            Change::EnumRemoved { direction, .. } => by_direction(
+                direction,
+                NonBreaking,
+                PotentiallyBreaking,
+                PotentiallyBreaking,
+            ),
+            // a new `allOf` member narrows the accepted values, a new `anyOf` or `oneOf` one widens them
+            Change::SchemaMemberAdded {
+                direction, keyword, ..
+            } if keyword == "allOf" => by_direction(direction, Breaking, NonBreaking, Breaking),
+            Change::SchemaMemberAdded { direction, .. } => by_direction(
+                direction,
+                NonBreaking,
+                PotentiallyBreaking,
+                PotentiallyBreaking,
+            ),
+            Change::SchemaMemberRemoved {
+                direction, keyword, ..
+            } if keyword == "allOf" => by_direction(
+                direction,
+                NonBreaking,
+                PotentiallyBreaking,
+                PotentiallyBreaking,
+            ),
+            Change::SchemaMemberRemoved { direction, .. } => {
+                by_direction(direction, Breaking, NonBreaking, Breaking)
+            }
+        }
+    }
+}
+
+/// The list of changes between two specifications.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Diff {
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Returns `true` if the specifications are semantically equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the overall compatibility, which is the worst compatibility of all changes.
+    pub fn compatibility(&self) -> Compatibility {
+        self.changes
+            .iter()
+            .map(|x| x.compatibility())
+            .max()
+            .unwrap_or(Compatibility::NonBreaking)
+    }
+
+    /// Returns `true` if any of the changes is breaking.
+    pub fn has_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+
+    /// Returns an iterator over the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|x| x.compatibility() == Compatibility::Breaking)
+    }
+}
+
+/// Computes the changes needed to turn the `old` specification into the `new` one.
+pub fn diff(old: &Spec, new: &Spec) -> Diff {
+    let mut d = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+    };
+    d.paths();
+    d.components();
+    Diff { changes: d.changes }
+}
+
+struct Differ<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    fn paths(&mut self) {
+        for path in self.old.paths.keys() {
+            if !self.new.paths.contains_key(path) {
+                self.changes
+                    .push(Change::PathRemoved { path: path.clone() });
+            }
+        }
+        for (path, new) in &self.new.paths {
+            match self.old.paths.get(path) {
+                Some(old) => self.path_item(path, old, new),
+                None => self.changes.push(Change::PathAdded { path: path.clone() }),
+            }
+        }
+    }
+
+    fn path_item(&mut self, path: &str, old: &'a PathItem, new: &'a PathItem) {
+        let (removed, entries) = compare(old.operations.as_ref(), new.operations.as_ref());
+        for method in removed {
+            self.changes.push(Change::OperationRemoved {
+                path: path.to_owned(),
+                method: method.clone(),
+            });
+        }
+        for (method, old_operation, new_operation) in entries {
+            match old_operation {
+                Some(old_operation) => {
+                    let location = format!("#.paths[{}].{}", path, method);
+                    self.parameters(
+                        &location,
+                        parameters(self.old, old, old_operation),
+                        parameters(self.new, new, new_operation),
+                    );
+                    self.request_body(&location, old_operation, new_operation);
+                    self.responses(&location, old_operation, new_operation);
+                }
+                None => self.changes.push(Change::OperationAdded {
+                    path: path.to_owned(),
+                    method: method.clone(),
+                }),
+            }
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        location: &str,
+        old: BTreeMap<(&'a str, &'a str), &'a Parameter>,
+        new: BTreeMap<(&'a str, &'a str), &'a Parameter>,
+    ) {
+        for &(parameter_in, name) in old.keys() {
+            if !new.contains_key(&(parameter_in, name)) {
+                self.changes.push(Change::ParameterRemoved {
+                    location: location.to_owned(),
+                    name: name.to_string(),
+                    parameter_in: parameter_in.to_string(),
+                });
+            }
+        }
+        for (&(parameter_in, name), new_parameter) in new.iter() {
+            match old.get(&(parameter_in, name)) {
+                Some(old_parameter) => {
+                    if old_parameter.is_required() != new_parameter.is_required() {
+                        self.changes.push(Change::ParameterRequiredChanged {
+                            location: location.to_owned(),
+                            name: name.to_string(),
+                            parameter_in: parameter_in.to_string(),
+                            required: new_parameter.is_required(),
+                        });
+                    }
+                    if let (Some(old_schema), Some(new_schema)) =
+                        (old_parameter.schema(), new_parameter.schema())
+                    {
+                        self.schema(
+                            format!("{}.parameters[{}.{}].schema", location, parameter_in, name),
+                            Direction::Request,
+                            old_schema,
+                            new_schema,
+                        );
+                    }
+                }
+                None => self.changes.push(Change::ParameterAdded {
+                    location: location.to_owned(),
+                    name: name.to_string(),
+                    parameter_in: parameter_in.to_string(),
+                    required: new_parameter.is_required(),
+                }),
+            }
+        }
+    }
+
+    fn request_body(&mut self, location: &str, old: &'a Operation, new: &'a Operation) {
+        let old_body = old
+            .request_body
+            .as_ref()
+            .and_then(|x| x.get_item(self.old).ok());
+        let new_body = new
+            .request_body
+            .as_ref()
+            .and_then(|x| x.get_item(self.new).ok());
+        match (old_body, new_body) {
+            (None, None) => {}
+            (Some(_), None) => self.changes.push(Change::RequestBodyRemoved {
+                location: location.to_owned(),
+            }),
+            (None, Some(new_body)) => self.changes.push(Change::RequestBodyAdded {
+                location: location.to_owned(),
+                required: new_body.required.unwrap_or(false),
+            }),
+            (Some(old_body), Some(new_body)) => {
+                let required = new_body.required.unwrap_or(false);
+                if old_body.required.unwrap_or(false) != required {
+                    self.changes.push(Change::RequestBodyRequiredChanged {
+                        location: location.to_owned(),
+                        required,
+                    });
+                }
+                self.content(
+                    format!("{}.requestBody", location),
+                    Direction::Request,
+                    Some(&old_body.content),
+                    Some(&new_body.content),
+                );
+            }
+        }
+    }
+
+    fn responses(&mut self, location: &str, old: &'a Operation, new: &'a Operation) {
+        let old_responses = responses(self.old, old);
+        let new_responses = responses(self.new, new);
+        for &status in old_responses.keys() {
+            if !new_responses.contains_key(status) {
+                self.changes.push(Change::ResponseRemoved {
+                    location: location.to_owned(),
+                    status: status.to_string(),
+                });
+            }
+        }
+        for (&status, new_response) in new_responses.iter() {
+            match old_responses.get(status) {
+                Some(old_response) => self.content(
+                    format!("{}.responses[{}]", location, status),
+                    Direction::Response,
+                    old_response.content.as_ref(),
+                    new_response.content.as_ref(),
+                ),
+                None => self.changes.push(Change::ResponseAdded {
+                    location: location.to_owned(),
+                    status: status.to_string(),
+                }),
+            }
+        }
+    }
+
+    fn content(
+        &mut self,
+        location: String,
+        direction: Direction,
+        old: Option<&'a BTreeMap<String, MediaType>>,
+        new: Option<&'a BTreeMap<String, MediaType>>,
+    ) {
+        let (removed, entries) = compare(old, new);
+        for media_type in removed {
+            self.changes.push(Change::MediaTypeRemoved {
+                location: location.clone(),
+                media_type: media_type.clone(),
+            });
+        }
+        for (media_type, old_media_type, new_media_type) in entries {
+            match old_media_type {
+                Some(old_media_type) => {
+                    if let (Some(old_schema), Some(new_schema)) =
+                        (&old_media_type.schema, &new_media_type.schema)
+                    {
+                        self.schema(
+                            format!("{}.content[{}].schema", location, media_type),
+                            direction,
+                            old_schema,
+                            new_schema,
+                        );
+                    }
+                }
+                None => self.changes.push(Change::MediaTypeAdded {
+                    location: location.clone(),
+                    media_type: media_type.clone(),
+                    direction,
+                }),
+            }
+        }
+    }
+
+    fn components(&mut self) {
+        let (removed, entries) = compare(
+            self.old
+                .components
+                .as_ref()
+                .and_then(|x| x.schemas.as_ref()),
+            self.new
+                .components
+                .as_ref()
+                .and_then(|x| x.schemas.as_ref()),
+        );
+        for name in removed {
+            self.changes
+                .push(Change::SchemaRemoved { name: name.clone() });
+        }
+        for (name, old_schema, new_schema) in entries {
+            match old_schema {
+                Some(old_schema) => {
+                    self.schema(
+                        format!("#/components/schemas/{}", name),
+                        Direction::Unknown,
+                        old_schema,
+                        new_schema,
+                    );
+                }
+                None => self
+                    .changes
+                    .push(Change::SchemaAdded { name: name.clone() }),
+            }
+        }
+    }
+
+    /// Compares two schemas.
+    /// The referenced schemas are not followed, since they are compared as components.
+    fn schema<L, R>(
+        &mut self,
+        location: String,
+        direction: Direction,
+        old: &'a RefOr<L>,
+        new: &'a RefOr<R>,
+    ) where
+        L: Borrow<Schema>,
+        R: Borrow<Schema>,
+    {
+        let (old, new) = match (old, new) {
+            (RefOr::Item(old), RefOr::Item(new)) => (old.borrow(), new.borrow()),
+            (RefOr::Ref(old), RefOr::Ref(new)) if old.reference == new.reference => return,
+            _ => {
+                self.changes.push(Change::SchemaTypeChanged {
+                    location,
+                    from: schema_type(old),
+                    to: schema_type(new),
+                });
+                return;
+            }
+        };
+        match (old, new) {
+            (Schema::AllOf(old), Schema::AllOf(new)) => {
+                self.members(&location, direction, "allOf", &old.all_of, &new.all_of);
+            }
+            (Schema::AnyOf(old), Schema::AnyOf(new)) => {
+                self.members(&location, direction, "anyOf", &old.any_of, &new.any_of);
+            }
+            (Schema::OneOf(old), Schema::OneOf(new)) => {
+                self.members(&location, direction, "oneOf", &old.one_of, &new.one_of);
+            }
+            (Schema::Not(old), Schema::Not(new)) => {
+                self.schema(format!("{}.not", location), direction, &old.not, &new.not);
+            }
+            (
+                Schema::Single(SingleSchema::String(old)),
+                Schema::Single(SingleSchema::String(new)),
+            ) => {
+                self.enum_values(&location, direction, &old.enum_values, &new.enum_values);
+            }
+            (
+                Schema::Single(SingleSchema::Integer(old)),
+                Schema::Single(SingleSchema::Integer(new)),
+            ) => {
+                self.enum_values(&location, direction, &old.enum_values, &new.enum_values);
+            }
+            (
+                Schema::Single(SingleSchema::Number(old)),
+                Schema::Single(SingleSchema::Number(new)),
+            ) => {
+                self.enum_values(&location, direction, &old.enum_values, &new.enum_values);
+            }
+            (
+                Schema::Single(SingleSchema::Array(old)),
+                Schema::Single(SingleSchema::Array(new)),
+            ) => {
+                if let (Some(old), Some(new)) = (&old.items, &new.items) {
+                    self.schema(format!("{}.items", location), direction, old, new);
+                }
+            }
+            (
+                Schema::Single(SingleSchema::Object(old)),
+                Schema::Single(SingleSchema::Object(new)),
+            ) => {
+                let (removed, entries) = compare(old.properties.as_ref(), new.properties.as_ref());
+                for property in removed {
+                    self.changes.push(Change::SchemaPropertyRemoved {
+                        location: location.clone(),
+                        property: property.clone(),
+                        direction,
+                    });
+                }
+                for (property, old_property, new_property) in entries {
+                    match old_property {
+                        Some(old_property) => self.schema(
+                            format!("{}.properties.{}", location, property),
+                            direction,
+                            old_property,
+                            new_property,
+                        ),
+                        None => self.changes.push(Change::SchemaPropertyAdded {
+                            location: location.clone(),
+                            property: property.clone(),
+                        }),
+                    }
+                }
+
+                let old_required: BTreeSet<&String> = old.required.iter().flatten().collect();
+                let new_required: BTreeSet<&String> = new.required.iter().flatten().collect();
+                for property in old_required.difference(&new_required) {
+                    self.changes.push(Change::RequiredPropertyRemoved {
+                        location: location.clone(),
+                        property: property.to_string(),
+                        direction,
+                    });
+                }
+                for property in new_required.difference(&old_required) {
+                    self.changes.push(Change::RequiredPropertyAdded {
+                        location: location.clone(),
+                        property: property.to_string(),
+                        direction,
+                    });
+                }
+            }
+            (Schema::Single(old), Schema::Single(new))
+                if std::mem::discriminant(old) == std::mem::discriminant(new) => {}
+            (old, new) => self.changes.push(Change::SchemaTypeChanged {
+                location,
+                from: item_type(old),
+                to: item_type(new),
+            }),
+        }
+    }
+
+    /// Compares the members of a composition by their position,
+    /// the unmatched tail is reported as added or removed members.
+    fn members(
+        &mut self,
+        location: &str,
+        direction: Direction,
+        keyword: &str,
+        old: &'a [RefOr<Box<Schema>>],
+        new: &'a [RefOr<Box<Schema>>],
+    ) {
+        for (i, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+            self.schema(
+                format!("{}.{}[{}]", location, keyword, i),
+                direction,
+                old,
+                new,
+            );
+        }
+        for index in new.len()..old.len() {
+            self.changes.push(Change::SchemaMemberRemoved {
+                location: location.to_owned(),
+                keyword: keyword.to_owned(),
+                index,
+                direction,
+            });
+        }
+        for index in old.len()..new.len() {
+            self.changes.push(Change::SchemaMemberAdded {
+                location: location.to_owned(),
+                keyword: keyword.to_owned(),
+                index,
+                direction,
+            });
+        }
+    }
+
+    fn enum_values<T: Display>(
+        &mut self,
+        location: &str,
+        direction: Direction,
+        old: &Option<Vec<T>>,
+        new: &Option<Vec<T>>,
+    ) {
+        let old: BTreeSet<String> = old.iter().flatten().map(|x| x.to_string()).collect();
+        let new: BTreeSet<String> = new.iter().flatten().map(|x| x.to_string()).collect();
+        // an empty list means no restrictions, so adding or removing it narrows or widens the values
+        match (old.is_empty(), new.is_empty()) {
+            (true, true) => return,
+            (true, false) => {
+                self.changes.push(Change::EnumAdded {
+                    location: location.to_owned(),
+                    direction,
+                });
+                return;
+            }
+            (false, true) => {
+                self.changes.push(Change::EnumRemoved {
+                    location: location.to_owned(),
+                    direction,
+                });
+                return;
+            }
+            (false, false) => {}
+        }
+        for value in old.difference(&new) {
+            self.changes.push(Change::EnumValueRemoved {
+                location: location.to_owned(),
+                value: value.clone(),
+                direction,
+            });
+        }
+        for value in new.difference(&old) {
+            self.changes.push(Change::EnumValueAdded {
+                location: location.to_owned(),
+                value: value.clone(),
+                direction,
+            });
+        }
+    }
+}
+
+/// Splits the keys of two maps into the removed keys and the keys of the new map
+/// with the corresponding old values, if any.
+#[allow(clippy::type_complexity)]
+fn compare<'m, V>(
+    old: Option<&'m BTreeMap<String, V>>,
+    new: Option<&'m BTreeMap<String, V>>,
+) -> (Vec<&'m String>, Vec<(&'m String, Option<&'m V>, &'m V)>) {
+    let removed = old
+        .into_iter()
+        .flatten()
+        .filter(|(k, _)| !new.is_some_and(|x| x.contains_key(*k)))
+        .map(|(k, _)| k)
+        .collect();
+    let entries = new
+        .into_iter()
+        .flatten()
+        .map(|(k, v)| (k, old.and_then(|x| x.get(k)), v))
+        .collect();
+    (removed, entries)
+}
+
+/// Returns the effective parameters of the operation, keyed by location and name.
+fn parameters<'a>(
+    spec: &'a Spec,
+    path_item: &'a PathItem,
+    operation: &'a Operation,
+) -> BTreeMap<(&'a str, &'a str), &'a Parameter> {
+    path_item
+        .parameters
+        .iter()
+        .flatten()
+        .chain(operation.parameters.iter().flatten())
+        .filter_map(|x| x.get_item(spec).ok())
+        .map(|x| ((x.location(), x.name()), x))
+        .collect()
+}
+
+/// Returns the responses of the operation, keyed by status code or `default`.
+fn responses<'a>(spec: &'a Spec, operation: &'a Operation) -> BTreeMap<&'a str, &'a Response> {
+    operation
+        .responses
+        .default
+        .iter()
+        .map(|x| ("default", x))
+        .chain(
+            operation
+                .responses
+                .responses
+                .iter()
+                .flatten()
+                .map(|(k, v)| (k.as_str(), v)),
+        )
+        .filter_map(|(k, v)| v.get_item(spec).ok().map(|v| (k, v)))
+        .collect()
+}
+
+fn schema_type<T: Borrow<Schema>>(schema: &RefOr<T>) -> String {
+    match schema {
+        RefOr::Ref(r) => r.reference.clone(),
+        RefOr::Item(schema) => item_type(schema.borrow()),
+    }
+}
+
+fn item_type(schema: &Schema) -> String {
+    match schema {
+        Schema::AllOf(_) => "allOf".to_owned(),
+        Schema::AnyOf(_) => "anyOf".to_owned(),
+        Schema::OneOf(_) => "oneOf".to_owned(),
+        Schema::Not(_) => "not".to_owned(),
+        Schema::Single(s) => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(paths: serde_json::Value, schemas: serde_json::Value) -> Spec {
+        serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": paths,
+            "components": {"schemas": schemas},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_equal() {
+        let old = spec(
+            serde_json::json!({"/pets": {"get": {"responses": {"200": {"description": "ok"}}}}}),
+            serde_json::json!({"Pet": {"type": "object"}}),
+        );
+        assert!(diff(&old, &old.clone()).is_empty(), "no changes");
+    }
+
+    #[test]
+    fn test_diff_paths() {
+        let old = spec(
+            serde_json::json!({
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            {"name": "limit", "in": "query"},
+                            {"name": "offset", "in": "query"},
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {"schema": {"type": "array"}},
+                                },
+                            },
+                            "default": {"description": "error"},
+                        },
+                    },
+                    "delete": {"responses": {}},
+                },
+                "/users": {},
+            }),
+            serde_json::json!({}),
+        );
+        let new = spec(
+            serde_json::json!({
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            {"name": "limit", "in": "query", "required": true},
+                            {"name": "X-Id", "in": "header"},
+                        ],
+                        "requestBody": {
+                            "content": {"application/json": {}},
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {"schema": {"type": "object"}},
+                                    "application/xml": {},
+                                },
+                            },
+                            "404": {"description": "not found"},
+                        },
+                    },
+                    "post": {"responses": {}},
+                },
+                "/stores": {},
+            }),
+            serde_json::json!({}),
+        );
+        assert_eq!(
+            diff(&old, &new)
+                .changes
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "#.paths[/users]: path removed",
+                "#.paths[/pets].delete: operation removed",
+                "#.paths[/pets].get.parameters[query.offset]: parameter removed",
+                "#.paths[/pets].get.parameters[header.X-Id]: optional parameter added",
+                "#.paths[/pets].get.parameters[query.limit]: parameter became required",
+                "#.paths[/pets].get.requestBody: optional request body added",
+                "#.paths[/pets].get.responses[default]: response removed",
+                "#.paths[/pets].get.responses[200].content[application/json].schema: type changed from `array` to `object`",
+                "#.paths[/pets].get.responses[200].content[application/xml]: media type added",
+                "#.paths[/pets].get.responses[404]: response added",
+                "#.paths[/pets].post: operation added",
+                "#.paths[/stores]: path added",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_diff_schemas() {
+        let old = spec(
+            serde_json::json!({}),
+            serde_json::json!({
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "kind": {"type": "string", "enum": ["cat", "dog"]},
+                        "age": {"type": "integer"},
+                        "owner": {"$ref": "#/components/schemas/User"},
+                        "size": {"type": "integer"},
+                    },
+                    "required": ["name"],
+                },
+                "User": {"type": "object"},
+            }),
+        );
+        let new = spec(
+            serde_json::json!({}),
+            serde_json::json!({
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "kind": {"type": "string", "enum": ["cat", "bird"]},
+                        "age": {"type": "string"},
+                        "owner": {"$ref": "#/components/schemas/Owner"},
+                        "size": {"type": "integer", "enum": [1, 2]},
+                        "tags": {"type": "array"},
+                    },
+                    "required": ["kind"],
+                },
+                "Owner": {"type": "object"},
+            }),
+        );
+        assert_eq!(
+            diff(&old, &new).changes,
+            vec![
+                Change::SchemaRemoved {
+                    name: "User".to_owned()
+                },
+                Change::SchemaAdded {
+                    name: "Owner".to_owned()
+                },
+                Change::SchemaTypeChanged {
+                    location: "#/components/schemas/Pet.properties.age".to_owned(),
+                    from: "integer".to_owned(),
+                    to: "string".to_owned(),
+                },
+                Change::EnumValueRemoved {
+                    location: "#/components/schemas/Pet.properties.kind".to_owned(),
+                    value: "dog".to_owned(),
+                    direction: Direction::Unknown,
+                },
+                Change::EnumValueAdded {
+                    location: "#/components/schemas/Pet.properties.kind".to_owned(),
+                    value: "bird".to_owned(),
+                    direction: Direction::Unknown,
+                },
+                Change::SchemaTypeChanged {
+                    location: "#/components/schemas/Pet.properties.owner".to_owned(),
+                    from: "#/components/schemas/User".to_owned(),
+                    to: "#/components/schemas/Owner".to_owned(),
+                },
+                Change::EnumAdded {
+                    location: "#/components/schemas/Pet.properties.size".to_owned(),
+                    direction: Direction::Unknown,
+                },
+                Change::SchemaPropertyAdded {
+                    location: "#/components/schemas/Pet".to_owned(),
+                    property: "tags".to_owned(),
+                },
+                Change::RequiredPropertyRemoved {
+                    location: "#/components/schemas/Pet".to_owned(),
+                    property: "name".to_owned(),
+                    direction: Direction::Unknown,
+                },
+                Change::RequiredPropertyAdded {
+                    location: "#/components/schemas/Pet".to_owned(),
+                    property: "kind".to_owned(),
+                    direction: Direction::Unknown,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_diff_members() {
+        let old = spec(
+            serde_json::json!({}),
+            serde_json::json!({
+                "Pet": {
+                    "oneOf": [
+                        {"$ref": "#/components/schemas/Cat"},
+                        {"$ref": "#/components/schemas/Dog"},
+                    ],
+                },
+                "Named": {"allOf": [{"$ref": "#/components/schemas/Pet"}]},
+            }),
+        );
+        let new = spec(
+            serde_json::json!({}),
+            serde_json::json!({
+                "Pet": {"oneOf": [{"$ref": "#/components/schemas/Cat"}]},
+                "Named": {
+                    "allOf": [
+                        {"$ref": "#/components/schemas/Pet"},
+                        {"type": "object", "required": ["name"]},
+                    ],
+                },
+            }),
+        );
+        assert_eq!(
+            diff(&old, &new)
+                .changes
+                .iter()
+                .map(|x| (x.to_string(), x.compatibility()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "#/components/schemas/Named.allOf[1]: member added".to_owned(),
+                    Compatibility::Breaking,
+                ),
+                (
+                    "#/components/schemas/Pet.oneOf[1]: member removed".to_owned(),
+                    Compatibility::Breaking,
+                ),
+            ],
+        );
+        assert_eq!(
+            diff(&new, &old)
+                .changes
+                .iter()
+                .map(|x| x.compatibility())
+                .collect::<Vec<_>>(),
+            vec![
+                Compatibility::PotentiallyBreaking,
+                Compatibility::PotentiallyBreaking,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_compatibility() {
+        let old = spec(
+            serde_json::json!({
+                "/pets": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "kind": {"type": "string", "enum": ["cat", "dog"]},
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "required": ["id"],
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            }),
+            serde_json::json!({}),
+        );
+        let new = spec(
+            serde_json::json!({
+                "/pets": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "kind": {"type": "string", "enum": ["cat"]},
+                                            "name": {"type": "string"},
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"type": "object"},
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            }),
+            serde_json::json!({}),
+        );
+        let d = diff(&old, &new);
+        assert_eq!(
+            d.changes
+                .iter()
+                .map(|x| (x.to_string(), x.compatibility()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "#.paths[/pets].post.requestBody.content[application/json].schema.properties.kind.enum: `dog` removed".to_owned(),
+                    Compatibility::Breaking,
+                ),
+                (
+                    "#.paths[/pets].post.requestBody.content[application/json].schema.properties.name: property added".to_owned(),
+                    Compatibility::NonBreaking,
+                ),
+                (
+                    "#.paths[/pets].post.responses[200].content[application/json].schema.required: `id` removed".to_owned(),
+                    Compatibility::Breaking,
+                ),
+            ],
+        );
+        assert!(d.has_breaking(), "breaking changes");
+        assert_eq!(
+            Change::EnumAdded {
+                location: "#.paths[/pets].post.requestBody.content[application/json].schema"
+                    .to_owned(),
+                direction: Direction::Request,
+            }
+            .compatibility(),
+            Compatibility::Breaking,
+            "narrowed request values",
+        );
+        assert_eq!(d.breaking().count(), 2, "two breaking changes");
+
+        let d = diff(
+            &spec(
+                serde_json::json!({}),
+                serde_json::json!({
+                    "Pet": {"properties": {"requestBody": {"type": "object"}}},
+                }),
+            ),
+            &spec(
+                serde_json::json!({}),
+                serde_json::json!({
+                    "Pet": {"properties": {"requestBody": {"type": "object", "required": ["id"]}}},
+                }),
+            ),
+        );
+        assert_eq!(
+            d.changes
+                .iter()
+                .map(|x| (x.to_string(), x.compatibility()))
+                .collect::<Vec<_>>(),
+            vec![(
+                "#/components/schemas/Pet.properties.requestBody.required: `id` added".to_owned(),
+                Compatibility::PotentiallyBreaking,
+            )],
+            "the property names do not define the direction",
+        );
+
+        assert_eq!(
+            diff(
+                &new,
+                &Spec {
+                    paths: new.paths.clone(),
+                    ..Default::default()
+                }
+            )
+            .compatibility(),
+            Compatibility::NonBreaking,
+            "no changes in paths",
+        );
+    }
+}
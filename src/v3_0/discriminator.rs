@@ -1,11 +1,13 @@
 //! Discriminator Object
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::common::helpers::PushError;
 use crate::common::helpers::{validate_required_string, Context, ValidateWithContext};
-use crate::common::reference::RefOr;
+use crate::common::path::{unescape_token, Path};
+use crate::common::reference::{Ref, RefOr, ResolveReference};
 use crate::v3_0::schema::Schema;
 use crate::v3_0::spec::Spec;
 
@@ -27,14 +29,278 @@ pub struct Discriminator {
 }
 
 impl ValidateWithContext<Spec> for Discriminator {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.property_name, ctx, format!("{}.propertyName", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_required_string(&self.property_name, ctx, path.field("propertyName"));
 
         if let Some(mapping) = &self.mapping {
             for (k, v) in mapping {
-                let schema_ref = RefOr::<Schema>::new_ref(format!("#/components/schemas/{}", v));
-                schema_ref.validate_with_context(ctx, format!("{}.mapping[{}]", path, k));
+                let schema_ref = RefOr::<Schema>::new_ref(mapping_reference(v));
+                schema_ref.validate_with_context(ctx, path.field("mapping").item(k));
             }
         }
     }
 }
+
+impl Discriminator {
+    /// Returns the reference to the concrete schema of the payload.
+    ///
+    /// Reads the discriminator property of the payload and looks its value up in the `mapping`,
+    /// otherwise the value is used as a name of a schema in the components,
+    /// which must exist in the specification.
+    /// Returns `None` if the payload is not an object or has no string discriminator property.
+    pub fn resolve(&self, value: &serde_json::Value, spec: &Spec) -> Option<Ref> {
+        let name = value.get(&self.property_name)?.as_str()?;
+        if let Some(mapped) = self.mapping.as_ref().and_then(|x| x.get(name)) {
+            return Some(Ref::new(mapping_reference(mapped)));
+        }
+        let reference = format!("#/components/schemas/{}", name);
+        ResolveReference::<Schema>::resolve_reference(spec, &reference)?;
+        Some(Ref::new(reference))
+    }
+
+    /// Validates that the discriminator property is defined and required
+    /// in each of the `oneOf` or `anyOf` schemas.
+    pub(crate) fn validate_schemas(
+        &self,
+        schemas: &[RefOr<Box<Schema>>],
+        ctx: &mut Context<Spec>,
+        path: Path,
+    ) {
+        if self.property_name.is_empty() {
+            return;
+        }
+        for (i, schema) in schemas.iter().enumerate() {
+            let schema = match schema {
+                RefOr::Ref(r) => match ctx.spec.resolve_reference(&r.reference) {
+                    Some(schema) => schema,
+                    // not found references are reported by the schema validation
+                    None => continue,
+                },
+                RefOr::Item(schema) => schema.as_ref(),
+            };
+            let (defined, required) =
+                schema.lookup_property(&self.property_name, ctx.spec, &mut HashSet::new());
+            if !defined {
+                ctx.error(
+                    path.index(i),
+                    format_args!(
+                        "discriminator property `{}` is not defined",
+                        self.property_name
+                    ),
+                );
+            } else if !required {
+                ctx.error(
+                    path.index(i),
+                    format_args!(
+                        "discriminator property `{}` is not required",
+                        self.property_name
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Returns the names of the component schemas the discriminator can select:
+    /// the mapping values and the referenced `members`, which are mapped implicitly.
+    pub(crate) fn schema_names(&self, members: &[RefOr<Box<Schema>>]) -> Vec<String> {
+        let mapped = self
+            .mapping
+            .iter()
+            .flatten()
+            .map(|(_, v)| mapping_reference(v));
+        let referenced = members.iter().filter_map(|x| match x {
+            RefOr::Ref(r) => Some(r.reference.clone()),
+            RefOr::Item(_) => None,
+        });
+        mapped
+            .chain(referenced)
+            .filter_map(|x| {
+                let name = x.strip_prefix("#/components/schemas/")?;
+                (!name.contains('/')).then(|| unescape_token(name).into_owned())
+            })
+            .collect()
+    }
+
+    /// Rewrites the mapping after the schema `from` has been renamed to `to`.
+    ///
+    /// The mapping values pointing to the old schema are rewritten, keeping the name form.
+    /// If one of the `members` references the renamed schema and the old name is not mapped,
+    /// the old name is mapped explicitly, because the implicit mapping uses the schema name.
+    pub(crate) fn rename_schema(&mut self, from: &str, to: &str, members: &[RefOr<Box<Schema>>]) {
+        let from_ref = mapping_reference(from);
+        let to_ref = mapping_reference(to);
+        for value in self.mapping.iter_mut().flatten().map(|(_, v)| v) {
+            if value == from {
+                *value = to.to_owned();
+            } else if let Some(rest) = value.strip_prefix(&from_ref) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    *value = format!("{}{}", to_ref, rest);
+                }
+            }
+        }
+        let referenced = members.iter().any(|x| match x {
+            RefOr::Ref(r) => r.reference == from_ref || r.reference == to_ref,
+            RefOr::Item(_) => false,
+        });
+        if referenced {
+            self.mapping
+                .get_or_insert_with(BTreeMap::new)
+                .entry(from.to_owned())
+                .or_insert(to_ref);
+        }
+    }
+}
+
+/// Converts a mapping value, which can be a schema name or a reference, to a reference.
+fn mapping_reference(value: &str) -> String {
+    if value.starts_with('#') || value.contains('/') {
+        value.to_owned()
+    } else {
+        format!("#/components/schemas/{}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::Options;
+
+    use super::*;
+
+    #[test]
+    fn test_mapping_reference() {
+        assert_eq!(
+            mapping_reference("Dog"),
+            "#/components/schemas/Dog",
+            "schema name"
+        );
+        assert_eq!(
+            mapping_reference("#/components/schemas/Dog"),
+            "#/components/schemas/Dog",
+            "reference"
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Cat": {"type": "object"},
+                    "Dog": {"type": "object"},
+                },
+            },
+        }))
+        .unwrap();
+        let discriminator = Discriminator {
+            property_name: "petType".to_owned(),
+            mapping: Some(BTreeMap::from([
+                ("dog".to_owned(), "Dog".to_owned()),
+                ("hound".to_owned(), "#/components/schemas/Dog".to_owned()),
+            ])),
+        };
+        for (value, expected) in [
+            (
+                serde_json::json!({"petType": "dog"}),
+                Some("#/components/schemas/Dog"),
+            ),
+            (
+                serde_json::json!({"petType": "hound"}),
+                Some("#/components/schemas/Dog"),
+            ),
+            (
+                serde_json::json!({"petType": "Cat"}),
+                Some("#/components/schemas/Cat"),
+            ),
+            (serde_json::json!({"petType": "Fish"}), None),
+            (serde_json::json!({"petType": 1}), None),
+            (serde_json::json!({"name": "Cat"}), None),
+            (serde_json::json!("Cat"), None),
+        ] {
+            assert_eq!(
+                discriminator.resolve(&value, &spec),
+                expected.map(|x| Ref::new(x.to_owned())),
+                "{}",
+                value,
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_schemas() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "petType": {"type": "string"},
+                        },
+                        "required": ["petType"],
+                    },
+                    "Cat": {
+                        "allOf": [
+                            {"$ref": "#/components/schemas/Pet"},
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "name": {"type": "string"},
+                                },
+                            },
+                        ],
+                    },
+                    "Dog": {
+                        "type": "object",
+                        "properties": {
+                            "petType": {"type": "string"},
+                        },
+                    },
+                    "Lizard": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        let mut ctx = Context::new(&spec, Options::new());
+        let schema = serde_json::from_value::<Schema>(serde_json::json!({
+            "oneOf": [
+                {"$ref": "#/components/schemas/Pet"},
+                {"$ref": "#/components/schemas/Cat"},
+                {"$ref": "#/components/schemas/Dog"},
+                {"$ref": "#/components/schemas/Lizard"},
+            ],
+            "discriminator": {
+                "propertyName": "petType",
+                "mapping": {
+                    "cat": "Cat",
+                    "dog": "#/components/schemas/Dog",
+                    "fish": "Fish",
+                },
+            },
+        }))
+        .unwrap();
+        schema.validate_with_context(&mut ctx, "schema".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "schema.discriminator.mapping[fish].$ref: `#/components/schemas/Fish` not found",
+                "schema.oneOf[2]: discriminator property `petType` is not required",
+                "schema.oneOf[3]: discriminator property `petType` is not defined",
+            ],
+            "discriminator errors: {:?}",
+            ctx.errors
+        );
+    }
+}
@@ -1,10 +1,13 @@
 //! Example object.
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_optional_url, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_uri_reference, Context, PushError, ValidateWithContext,
+};
+use crate::common::path::Path;
+use crate::common::reference::{DocumentLoader, ResolveError};
 use crate::v3_0::spec::Spec;
 
 /// Example object.
@@ -40,17 +43,103 @@ pub struct Example {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Example {
+    /// Returns the example value,
+    /// the `externalValue` is loaded with the loader if the `value` is not set.
+    pub fn load_value<L>(&self, loader: &L) -> Result<Option<serde_json::Value>, ResolveError>
+    where
+        L: DocumentLoader + ?Sized,
+    {
+        match (&self.value, &self.external_value) {
+            (Some(value), _) => Ok(Some(value.clone())),
+            (None, Some(uri)) => loader.load(uri).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
 }
 
 impl ValidateWithContext<Spec> for Example {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if self.value.is_some() && self.external_value.is_some() {
             ctx.error(
                 path.clone(),
                 "value and externalValue are mutually exclusive",
             );
         }
-        validate_optional_url(&self.external_value, ctx, format!("{}.externalValue", path));
+        if let Some(uri) = &self.external_value {
+            validate_uri_reference(uri, ctx, path.field("externalValue"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let example = Example {
+            value: Some(json!({"id": 1})),
+            external_value: Some("pet.json".to_owned()),
+            ..Default::default()
+        };
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        example.validate_with_context(&mut ctx, "example".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["example: value and externalValue are mutually exclusive"],
+        );
+
+        let example = Example {
+            external_value: Some("pets/<id>.json".to_owned()),
+            ..Default::default()
+        };
+        let mut ctx = Context::new(&spec, Default::default());
+        example.validate_with_context(&mut ctx, "example".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["example.externalValue: must be a valid URI reference, found `pets/<id>.json`"],
+        );
+    }
+
+    #[test]
+    fn test_load_value() {
+        let loader = |uri: &str| match uri {
+            "https://example.com/pet.json" => Ok(json!({"id": 2})),
+            _ => Err(ResolveError::Load {
+                uri: uri.to_owned(),
+                reason: "not found".to_owned(),
+            }),
+        };
+
+        let example = Example {
+            value: Some(json!({"id": 1})),
+            ..Default::default()
+        };
+        assert_eq!(example.load_value(&loader).unwrap(), Some(json!({"id": 1})));
+
+        let example = Example {
+            external_value: Some("https://example.com/pet.json".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(example.load_value(&loader).unwrap(), Some(json!({"id": 2})));
+
+        let example = Example {
+            external_value: Some("https://example.com/cat.json".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            example.load_value(&loader).unwrap_err().to_string(),
+            "loading of `https://example.com/cat.json` failed: not found",
+        );
+
+        assert_eq!(Example::default().load_value(&loader).unwrap(), None);
     }
 }
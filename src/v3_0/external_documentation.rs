@@ -1,10 +1,13 @@
 //! References an external resource for extended documentation.
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_optional_url, Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_uri_reference, Context, ValidateWithContext,
+};
+use crate::common::path::Path;
+use crate::common::url::{Url, UrlError};
 use crate::v3_0::spec::Spec;
 
 /// Allows referencing an external resource for extended documentation.
@@ -32,12 +35,20 @@ pub struct ExternalDocumentation {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl ExternalDocumentation {
+    /// Returns the URL of the documentation, an error if it is not a valid URL.
+    pub fn parsed_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.url)
+    }
 }
 
 impl ValidateWithContext<Spec> for ExternalDocumentation {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_optional_url(&Some(self.url.clone()), ctx, format!("{}.url", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(&self.url, ctx, path.field("url"));
     }
 }
 
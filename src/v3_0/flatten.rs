@@ -0,0 +1,331 @@
+//! Flattening of the `allOf` compositions
+//!
+//! Merges the members of an `allOf` schema into a single object schema,
+//! the view needed by the client generators and the form builders.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::schema::Schema;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//!     "components": {
+//!         "schemas": {
+//!             "Base": {
+//!                 "type": "object",
+//!                 "properties": {"id": {"type": "integer"}},
+//!                 "required": ["id"],
+//!             },
+//!         },
+//!     },
+//! })).unwrap();
+//! let schema = serde_json::from_value::<Schema>(serde_json::json!({
+//!     "allOf": [
+//!         {"$ref": "#/components/schemas/Base"},
+//!         {"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]},
+//!     ],
+//! })).unwrap();
+//!
+//! let flat = schema.flatten_all_of(&spec).unwrap();
+//! assert_eq!(flat.required, Some(vec!["id".to_owned(), "name".to_owned()]));
+//! assert_eq!(flat.properties.unwrap().len(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::common::reference::{RefOr, ResolveReference};
+use crate::v3_0::schema::{ObjectSchema, Schema, SingleSchema};
+
+/// An error of the flattening of a schema.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum FlattenError {
+    /// The reference cannot be resolved.
+    #[error("reference `{0}` cannot be resolved")]
+    Reference(String),
+
+    /// The schema references itself through the `allOf` members.
+    #[error("reference `{0}` is recursive")]
+    Recursion(String),
+
+    /// The schema or the member of `allOf` at the location is not an object schema.
+    #[error("`{0}` is not an object schema")]
+    NotObject(String),
+
+    /// The members define the listed fields differently, e.g. `properties.name`.
+    #[error("conflicting definitions of {}", .0.join(", "))]
+    Conflicts(Vec<String>),
+}
+
+impl Schema {
+    /// Merges the members of the `allOf` schema, resolving the references,
+    /// into a single object schema.
+    ///
+    /// The nested `allOf` members are flattened as well, an object schema is returned as is.
+    /// The `properties`, the `required` lists and the extensions are united,
+    /// the stricter of the `minProperties` and `maxProperties` is used,
+    /// the `readOnly`, `writeOnly` and `deprecated` flags are set if any member sets them,
+    /// and the result is `nullable` only if all the members are.
+    /// The annotations, like `title` or `example`, are taken from the first member defining them.
+    ///
+    /// A property or `additionalProperties` defined differently by several members
+    /// is reported as a conflict, all the conflicts are reported at once.
+    pub fn flatten_all_of<R: ResolveReference<Schema>>(
+        &self,
+        resolver: &R,
+    ) -> Result<ObjectSchema, FlattenError> {
+        let mut flattener = Flattener {
+            resolver,
+            stack: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        let schema = flattener.flatten(self, "#")?;
+        if flattener.conflicts.is_empty() {
+            Ok(schema)
+        } else {
+            Err(FlattenError::Conflicts(flattener.conflicts))
+        }
+    }
+}
+
+struct Flattener<'a, R> {
+    resolver: &'a R,
+    stack: Vec<&'a str>,
+    conflicts: Vec<String>,
+}
+
+impl<'a, R: ResolveReference<Schema>> Flattener<'a, R> {
+    fn flatten(
+        &mut self,
+        schema: &'a Schema,
+        location: &str,
+    ) -> Result<ObjectSchema, FlattenError> {
+        match schema {
+            Schema::Single(SingleSchema::Object(s)) => Ok(s.clone()),
+            Schema::AllOf(s) => {
+                let mut merged = ObjectSchema::default();
+                let mut nullable = Vec::with_capacity(s.all_of.len());
+                for (i, member) in s.all_of.iter().enumerate() {
+                    let member = match member {
+                        RefOr::Item(schema) => {
+                            self.flatten(schema, &format!("{}/allOf/{}", location, i))?
+                        }
+                        RefOr::Ref(r) => self.reference(&r.reference)?,
+                    };
+                    nullable.push(member.nullable);
+                    merged = self.merge(merged, member);
+                }
+                merged.nullable = if nullable.iter().all(|x| x.is_none()) {
+                    None
+                } else {
+                    Some(nullable.iter().all(|x| *x == Some(true)))
+                };
+                Ok(merged)
+            }
+            _ => Err(FlattenError::NotObject(location.to_owned())),
+        }
+    }
+
+    fn reference(&mut self, reference: &'a str) -> Result<ObjectSchema, FlattenError> {
+        if self.stack.contains(&reference) {
+            return Err(FlattenError::Recursion(reference.to_owned()));
+        }
+        let schema = self
+            .resolver
+            .resolve_reference(reference)
+            .ok_or_else(|| FlattenError::Reference(reference.to_owned()))?;
+        self.stack.push(reference);
+        let schema = self.flatten(schema, reference);
+        self.stack.pop();
+        schema
+    }
+
+    fn merge(&mut self, mut left: ObjectSchema, right: ObjectSchema) -> ObjectSchema {
+        if let Some(properties) = right.properties {
+            let merged = left.properties.get_or_insert_with(BTreeMap::new);
+            for (name, schema) in properties {
+                match merged.get(&name) {
+                    Some(existing) if existing != &schema => {
+                        self.conflicts.push(format!("properties.{}", name));
+                    }
+                    Some(_) => {}
+                    None => {
+                        merged.insert(name, schema);
+                    }
+                }
+            }
+        }
+        if let Some(required) = right.required {
+            let merged = left.required.get_or_insert_with(Vec::new);
+            for name in required {
+                if !merged.contains(&name) {
+                    merged.push(name);
+                }
+            }
+        }
+        left.additional_properties = match (left.additional_properties, right.additional_properties)
+        {
+            (Some(l), Some(r)) if l != r => {
+                self.conflicts.push("additionalProperties".to_owned());
+                Some(l)
+            }
+            (l, r) => l.or(r),
+        };
+        left.min_properties = match (left.min_properties, right.min_properties) {
+            (Some(l), Some(r)) => Some(l.max(r)),
+            (l, r) => l.or(r),
+        };
+        left.max_properties = match (left.max_properties, right.max_properties) {
+            (Some(l), Some(r)) => Some(l.min(r)),
+            (l, r) => l.or(r),
+        };
+        let any = |l: Option<bool>, r: Option<bool>| match (l, r) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (l, r) => l.or(r),
+        };
+        left.read_only = any(left.read_only, right.read_only);
+        left.write_only = any(left.write_only, right.write_only);
+        left.deprecated = any(left.deprecated, right.deprecated);
+        left.title = left.title.or(right.title);
+        left.description = left.description.or(right.description);
+        left.default = left.default.or(right.default);
+        left.xml = left.xml.or(right.xml);
+        left.external_docs = left.external_docs.or(right.external_docs);
+        left.example = left.example.or(right.example);
+        if let Some(extensions) = right.extensions {
+            let merged = left.extensions.get_or_insert_with(BTreeMap::new);
+            for (name, value) in extensions {
+                merged.entry(name).or_insert(value);
+            }
+        }
+        left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::v3_0::spec::Spec;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Base": {
+                        "type": "object",
+                        "title": "Base",
+                        "properties": {"id": {"type": "integer"}},
+                        "required": ["id"],
+                        "minProperties": 1,
+                        "nullable": true,
+                    },
+                    "Named": {
+                        "allOf": [
+                            {"$ref": "#/components/schemas/Base"},
+                            {"properties": {"name": {"type": "string"}}, "required": ["name", "id"]},
+                        ],
+                    },
+                    "Loop": {"allOf": [{"$ref": "#/components/schemas/Loop"}]},
+                    "Text": {"type": "string"},
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn schema(value: serde_json::Value) -> Schema {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_flatten_all_of() {
+        let spec = spec();
+        let flat = schema(json!({
+            "allOf": [
+                {"$ref": "#/components/schemas/Named"},
+                {
+                    "type": "object",
+                    "title": "Pet",
+                    "properties": {"id": {"type": "integer"}, "tag": {"type": "string"}},
+                    "minProperties": 2,
+                    "maxProperties": 5,
+                    "readOnly": true,
+                    "x-model": "Pet",
+                },
+            ],
+        }))
+        .flatten_all_of(&spec)
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(flat).unwrap(),
+            json!({
+                "type": "object",
+                "title": "Base",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "name": {"type": "string"},
+                    "tag": {"type": "string"},
+                },
+                "required": ["id", "name"],
+                "minProperties": 2,
+                "maxProperties": 5,
+                "readOnly": true,
+                "nullable": false,
+                "x-model": "Pet",
+            }),
+        );
+    }
+
+    #[test]
+    fn test_flatten_all_of_errors() {
+        let spec = spec();
+        assert_eq!(
+            schema(json!({
+                "allOf": [
+                    {"$ref": "#/components/schemas/Base"},
+                    {"properties": {"id": {"type": "string"}}, "additionalProperties": false},
+                    {"additionalProperties": true},
+                ],
+            }))
+            .flatten_all_of(&spec),
+            Err(FlattenError::Conflicts(vec![
+                "properties.id".to_owned(),
+                "additionalProperties".to_owned(),
+            ])),
+        );
+        assert_eq!(
+            schema(json!({"allOf": [{"$ref": "#/components/schemas/Loop"}]})).flatten_all_of(&spec),
+            Err(FlattenError::Recursion(
+                "#/components/schemas/Loop".to_owned()
+            )),
+        );
+        assert_eq!(
+            schema(json!({"allOf": [{"$ref": "#/components/schemas/Unknown"}]}))
+                .flatten_all_of(&spec),
+            Err(FlattenError::Reference(
+                "#/components/schemas/Unknown".to_owned()
+            )),
+        );
+        assert_eq!(
+            schema(json!({"allOf": [{"$ref": "#/components/schemas/Text"}]})).flatten_all_of(&spec),
+            Err(FlattenError::NotObject(
+                "#/components/schemas/Text".to_owned()
+            )),
+        );
+        assert_eq!(
+            schema(json!({"allOf": [{"type": "object"}, {"type": "integer"}]}))
+                .flatten_all_of(&spec),
+            Err(FlattenError::NotObject("#/allOf/1".to_owned())),
+        );
+    }
+}
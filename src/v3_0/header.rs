@@ -4,13 +4,17 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, Context, PushError, ValidateWithContext};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v3_0::example::Example;
 use crate::v3_0::media_type::MediaType;
 use crate::v3_0::parameter::InHeaderStyle;
 use crate::v3_0::schema::Schema;
 use crate::v3_0::spec::Spec;
+use crate::v3_0::style::{encode_value, parse_value, Style};
+use crate::validation::Lints;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Header {
@@ -72,17 +76,78 @@ pub struct Header {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<BTreeMap<String, MediaType>>,
 
+    /// The `name` MUST NOT be specified, it is given in the corresponding `headers` map.
+    /// The field is kept only to report it by the validation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The `in` MUST NOT be specified, it is implicitly in `header`.
+    /// The field is kept only to report it by the validation.
+    #[serde(rename = "in")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
     /// This object MAY be extended with Specification Extensions.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Header {
+    /// Returns the serialization style of the header.
+    pub fn style(&self) -> Style {
+        self.style.as_ref().map_or(Style::Simple, Style::from)
+    }
+
+    /// Parses the raw value of the header according to its style and schema,
+    /// see [parse_value](crate::v3_0::style::parse_value) for details.
+    pub fn parse_value(&self, raw: &str) -> serde_json::Value {
+        let style = self.style();
+        let explode = self.explode.unwrap_or_else(|| style.default_explode());
+        parse_value(&style, explode, "", raw, self.schema.as_ref())
+    }
+
+    /// Serializes the value of the header according to its style,
+    /// see [encode_value](crate::v3_0::style::encode_value) for details.
+    pub fn encode_value(&self, value: &serde_json::Value) -> String {
+        let style = self.style();
+        let explode = self.explode.unwrap_or_else(|| style.default_explode());
+        encode_value(&style, explode, "", value)
+    }
+}
+
+/// The reserved headers that SHALL be ignored when declared in responses or encodings.
+const RESERVED_HEADERS: [&str; 3] = ["Content-Type", "Accept", "Authorization"];
+
+/// Reports the header names that are ignored by the specification, if [Lints::ReservedHeaders] is enabled,
+/// used for the `headers` maps of the responses and encodings.
+pub(crate) fn validate_header_name(name: &str, ctx: &mut Context<Spec>, path: Path) {
+    if !ctx.is_lint(Lints::ReservedHeaders) {
+        return;
+    }
+    if let Some(header) = RESERVED_HEADERS
+        .iter()
+        .find(|x| x.eq_ignore_ascii_case(name))
+    {
+        ctx.error(
+            path,
+            format_args!("the `{}` header is ignored by the specification", header),
+        );
+    }
 }
 
 impl ValidateWithContext<Spec> for Header {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if self.name.is_some() {
+            ctx.error(path.clone(), ".name: must not be specified");
+        }
+        if self.location.is_some() {
+            ctx.error(path.clone(), ".in: must not be specified");
+        }
         if self.example.is_some() && self.examples.is_some() {
             ctx.error(path.clone(), "example and examples are mutually exclusive");
         }
@@ -91,12 +156,12 @@ impl ValidateWithContext<Spec> for Header {
         }
         if let Some(examples) = &self.examples {
             for (k, v) in examples {
-                v.validate_with_context(ctx, format!("{}.examples[{}]", path, k));
+                v.validate_with_context(ctx, path.field("examples").item(k));
             }
         }
         if let Some(content) = &self.content {
             for (k, v) in content {
-                v.validate_with_context(ctx, format!("{}.content[{}]", path, k));
+                v.validate_with_context(ctx, path.field("content").item(k));
             }
         }
     }
@@ -163,4 +228,46 @@ mod tests {
             "serialize string",
         );
     }
+
+    #[test]
+    fn test_header_validate() {
+        let header = serde_json::from_value::<Header>(serde_json::json!({
+            "name": "X-Rate-Limit",
+            "in": "header",
+            "schema": {"type": "integer"},
+        }))
+        .unwrap();
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        header.validate_with_context(&mut ctx, "header".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "header.name: must not be specified",
+                "header.in: must not be specified",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_header_name() {
+        let spec = Spec::default();
+        let mut ctx =
+            Context::new(&spec, Default::default()).with_lints(Lints::ReservedHeaders.only());
+        for name in ["content-type", "Accept", "AUTHORIZATION", "X-Rate-Limit"] {
+            validate_header_name(name, &mut ctx, Path::from("headers").item(name));
+        }
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "headers[content-type]: the `Content-Type` header is ignored by the specification",
+                "headers[Accept]: the `Accept` header is ignored by the specification",
+                "headers[AUTHORIZATION]: the `Authorization` header is ignored by the specification",
+            ],
+        );
+
+        let mut ctx = Context::new(&spec, Default::default());
+        validate_header_name("Content-Type", &mut ctx, "headers".into());
+        assert!(ctx.errors.is_empty(), "disabled lint: {:?}", ctx.errors);
+    }
 }
@@ -1,12 +1,14 @@
 //! Provides metadata about the API.
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::common::extensions::Extensions;
 use crate::common::helpers::{
-    validate_email, validate_optional_url, validate_required_string, Context, ValidateWithContext,
+    validate_email, validate_extensions, validate_required_string, validate_uri_reference, Context,
+    ValidateWithContext,
 };
+use crate::common::path::Path;
+use crate::common::url::{parse_optional, Url, UrlError};
 use crate::v3_0::spec::Spec;
 
 /// The object provides metadata about the API.
@@ -60,7 +62,7 @@ pub struct Info {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Contact information for the exposed API.
@@ -94,7 +96,7 @@ pub struct Contact {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// License information for the exposed API.
@@ -123,35 +125,59 @@ pub struct License {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for Info {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.title, ctx, format!("{}.title", path));
-        validate_required_string(&self.version, ctx, format!("{}.version", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.title, ctx, path.field("title"));
+        validate_required_string(&self.version, ctx, path.field("version"));
+        if let Some(url) = &self.terms_of_service {
+            validate_uri_reference(url, ctx, path.field("termsOfService"));
+        }
 
         if let Some(contact) = &self.contact {
-            contact.validate_with_context(ctx, format!("{}.contact", path));
+            contact.validate_with_context(ctx, path.field("contact"));
         }
 
         if let Some(license) = &self.license {
-            license.validate_with_context(ctx, format!("{}.license", path));
+            license.validate_with_context(ctx, path.field("license"));
         }
     }
 }
 
+impl Contact {
+    /// Returns the URL of the contact information, an error if it is not a valid URL.
+    pub fn parsed_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.url)
+    }
+}
+
 impl ValidateWithContext<Spec> for Contact {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_optional_url(&self.url, ctx, format!("{}.url", path));
-        validate_email(&self.email, ctx, format!("{}.email", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if let Some(url) = &self.url {
+            validate_uri_reference(url, ctx, path.field("url"));
+        }
+        validate_email(&self.email, ctx, path.field("email"));
+    }
+}
+
+impl License {
+    /// Returns the URL of the license, an error if it is not a valid URL.
+    pub fn parsed_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.url)
     }
 }
 
 impl ValidateWithContext<Spec> for License {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
-        validate_optional_url(&self.url, ctx, format!("{}.url", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
+        if let Some(url) = &self.url {
+            validate_uri_reference(url, ctx, path.field("url"));
+        }
     }
 }
 
@@ -397,7 +423,7 @@ mod tests {
             email: Some(String::from("support@example.com")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("contact"));
+        .validate_with_context(&mut ctx, "contact".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Contact {
@@ -405,14 +431,14 @@ mod tests {
             email: Some(String::from("support@example.com")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("contact"));
+        .validate_with_context(&mut ctx, "contact".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Contact {
             url: Some(String::from("foo - bar")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("contact"));
+        .validate_with_context(&mut ctx, "contact".into());
         assert_eq!(ctx.errors.len(), 1, "incorrect url: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Default::default());
@@ -420,7 +446,7 @@ mod tests {
             email: Some(String::from("foo - bar")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("contact"));
+        .validate_with_context(&mut ctx, "contact".into());
         assert_eq!(ctx.errors.len(), 1, "incorrect email: {:?}", ctx.errors);
     }
 
@@ -435,14 +461,14 @@ mod tests {
             )),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("license"));
+        .validate_with_context(&mut ctx, "license".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         License {
             name: String::from("Apache 2.0"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("license"));
+        .validate_with_context(&mut ctx, "license".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Default::default());
@@ -450,7 +476,7 @@ mod tests {
             name: String::from(""),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("license"));
+        .validate_with_context(&mut ctx, "license".into());
         assert_eq!(ctx.errors.len(), 1, "empty name: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Default::default());
@@ -459,7 +485,7 @@ mod tests {
             url: Some(String::from("foo - bar")),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("license"));
+        .validate_with_context(&mut ctx, "license".into());
         assert_eq!(ctx.errors.len(), 1, "incorrect url: {:?}", ctx.errors);
     }
 
@@ -487,7 +513,7 @@ mod tests {
             version: "1.0.1".to_owned(),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("info"));
+        .validate_with_context(&mut ctx, "info".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Info {
@@ -497,7 +523,7 @@ mod tests {
             version: "1.0.1".to_owned(),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("info"));
+        .validate_with_context(&mut ctx, "info".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Info {
@@ -505,7 +531,7 @@ mod tests {
             version: "1.0.1".to_owned(),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("info"));
+        .validate_with_context(&mut ctx, "info".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Info {
@@ -513,7 +539,7 @@ mod tests {
             version: String::from(""),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("info"));
+        .validate_with_context(&mut ctx, "info".into());
         assert_eq!(ctx.errors.len(), 1, "empty version: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Default::default());
@@ -522,7 +548,59 @@ mod tests {
             version: String::from("1.0.1"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("info"));
+        .validate_with_context(&mut ctx, "info".into());
         assert_eq!(ctx.errors.len(), 1, "empty title: {:?}", ctx.errors);
     }
+
+    #[test]
+    fn test_info_validate_terms_of_service() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        Info {
+            title: String::from("Swagger Sample App"),
+            version: String::from("1.0.1"),
+            terms_of_service: Some(String::from("terms of service")),
+            contact: Some(Contact {
+                email: Some(String::from("support@")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .validate_with_context(&mut ctx, "info".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "info.termsOfService: must be a valid URI reference, found `terms of service`",
+                "info.contact.email: must be a valid email address, found `support@`",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_info_validate_extensions() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        Info {
+            title: String::from("Swagger Sample App"),
+            version: String::from("1.0.1"),
+            extensions: Some(Extensions::from([
+                ("x-internal".to_owned(), true.into()),
+                ("internal".to_owned(), true.into()),
+            ])),
+            license: Some(License {
+                name: String::from("MIT"),
+                extensions: Some(Extensions::from([("id".to_owned(), "MIT".into())])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .validate_with_context(&mut ctx, "#.info".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "#.info.internal: extension name must start with `x-`",
+                "#.info.license.id: extension name must start with `x-`",
+            ],
+        );
+    }
 }
@@ -0,0 +1,592 @@
+//! Instance validation
+//!
+//! Checks a JSON value against a schema, e.g. a request or a response body.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::schema::Schema;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//!     "components": {
+//!         "schemas": {
+//!             "Pet": {
+//!                 "type": "object",
+//!                 "required": ["name"],
+//!                 "properties": {"name": {"type": "string", "minLength": 1}},
+//!             },
+//!         },
+//!     },
+//! })).unwrap();
+//! let schema = serde_json::from_value::<Schema>(serde_json::json!({
+//!     "type": "array",
+//!     "items": {"$ref": "#/components/schemas/Pet"},
+//! })).unwrap();
+//!
+//! assert!(schema.validate_instance(&serde_json::json!([{"name": "Rex"}]), &spec).is_ok());
+//!
+//! let errors = schema
+//!     .validate_instance(&serde_json::json!([{"name": ""}, {}]), &spec)
+//!     .unwrap_err();
+//! assert_eq!(
+//!     errors.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+//!     vec![
+//!         "#/0/name: length must be at least 1",
+//!         "#/1: property `name` is required",
+//!     ],
+//! );
+//! ```
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::common::bool_or::BoolOr;
+use crate::common::datetime::check_format;
+use crate::common::helpers::Patterns;
+use crate::common::path::escape_token;
+use crate::common::reference::{RefOr, ResolveError, ResolveReference};
+use crate::v3_0::schema::{
+    ArraySchema, IntegerSchema, NumberSchema, ObjectSchema, Schema, SingleSchema, StringSchema,
+};
+
+/// InstanceError describes a value that does not match the schema.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("{path}: {message}")]
+pub struct InstanceError {
+    /// The location of the invalid value as a JSON Pointer fragment, e.g. `#/items/0`.
+    pub path: String,
+
+    /// The description of the violated rule.
+    pub message: String,
+}
+
+impl Schema {
+    /// Validates the value against the schema.
+    ///
+    /// Checks the types, `nullable`, `enum`, `pattern`, the lengths and the bounds,
+    /// the properties, the items and the composition keywords,
+    /// the references are resolved by the resolver, usually the specification.
+    /// The `format` keyword is checked for `date`, `date-time`, `time` and `duration` only,
+    /// the `discriminator` keyword is not checked.
+    pub fn validate_instance<R>(
+        &self,
+        value: &Value,
+        resolver: &R,
+    ) -> Result<(), Vec<InstanceError>>
+    where
+        R: ResolveReference<Schema>,
+    {
+        let mut validator = Validator {
+            resolver,
+            errors: Vec::new(),
+            stack: Vec::new(),
+            patterns: Patterns::default(),
+        };
+        validator.schema(self, value, "#".to_owned());
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+struct Validator<'a, R> {
+    resolver: &'a R,
+    errors: Vec<InstanceError>,
+    /// The references being resolved together with the paths of the values,
+    /// prevents the infinite recursion of the self-referencing compositions.
+    stack: Vec<(String, String)>,
+    /// The patterns compiled once per validation, not once per value.
+    patterns: Patterns,
+}
+
+impl<'a, R> Validator<'a, R>
+where
+    R: ResolveReference<Schema>,
+{
+    fn error(&mut self, path: &str, message: impl Into<String>) {
+        self.errors.push(InstanceError {
+            path: path.to_owned(),
+            message: message.into(),
+        });
+    }
+
+    /// Runs the validation and returns the errors instead of collecting them.
+    fn check(
+        &mut self,
+        schema: &'a RefOr<Box<Schema>>,
+        value: &Value,
+        path: &str,
+    ) -> Vec<InstanceError> {
+        let errors = std::mem::take(&mut self.errors);
+        self.reference(schema, value, path.to_owned());
+        std::mem::replace(&mut self.errors, errors)
+    }
+
+    fn reference(&mut self, schema: &'a RefOr<Box<Schema>>, value: &Value, path: String) {
+        match schema {
+            RefOr::Item(schema) => self.schema(schema, value, path),
+            RefOr::Ref(r) => {
+                let key = (r.reference.clone(), path);
+                if self.stack.contains(&key) {
+                    return;
+                }
+                let path = key.1.clone();
+                let resolved = if r.reference.starts_with("#/") {
+                    self.resolver
+                        .resolve_reference(&r.reference)
+                        .ok_or_else(|| ResolveError::NotFound(r.reference.clone()))
+                } else {
+                    Err(ResolveError::ExternalUnsupported(r.reference.clone()))
+                };
+                match resolved {
+                    Ok(schema) => {
+                        self.stack.push(key);
+                        self.schema(schema, value, path);
+                        self.stack.pop();
+                    }
+                    Err(e) => self.error(&path, e.to_string()),
+                }
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &'a Schema, value: &Value, path: String) {
+        match schema {
+            Schema::AllOf(s) => {
+                for schema in s.all_of.iter() {
+                    self.reference(schema, value, path.clone());
+                }
+            }
+            Schema::AnyOf(s) => {
+                if !s
+                    .any_of
+                    .iter()
+                    .any(|x| self.check(x, value, &path).is_empty())
+                {
+                    self.error(&path, "must match at least one schema of `anyOf`");
+                }
+            }
+            Schema::OneOf(s) => {
+                let matched = s
+                    .one_of
+                    .iter()
+                    .filter(|x| self.check(x, value, &path).is_empty())
+                    .count();
+                if matched != 1 {
+                    self.error(
+                        &path,
+                        format!(
+                            "must match exactly one schema of `oneOf`, matched {}",
+                            matched
+                        ),
+                    );
+                }
+            }
+            Schema::Not(s) => {
+                if self.check(&s.not, value, &path).is_empty() {
+                    self.error(&path, "must not match the schema of `not`");
+                }
+            }
+            Schema::Single(s) => self.single(s, value, path),
+        }
+    }
+
+    fn single(&mut self, schema: &'a SingleSchema, value: &Value, path: String) {
+        if value.is_null() && schema.is_nullable() {
+            return;
+        }
+        match (schema, value) {
+            (SingleSchema::String(s), Value::String(v)) => self.string(s, v, &path),
+            (SingleSchema::Integer(s), Value::Number(v)) if is_integer(v) => {
+                self.integer(s, v, &path)
+            }
+            (SingleSchema::Number(s), Value::Number(v)) => self.number(s, v, &path),
+            (SingleSchema::Boolean(_), Value::Bool(_)) => {}
+            (SingleSchema::Array(s), Value::Array(v)) => self.array(s, v, path),
+            (SingleSchema::Object(s), Value::Object(v)) => self.object(s, v, path),
+            (SingleSchema::Object(s), _) if !s.has_type() => {}
+            _ => self.error(
+                &path,
+                format!("expected `{}`, found `{}`", schema, type_name(value)),
+            ),
+        }
+    }
+
+    fn string(&mut self, schema: &StringSchema, value: &str, path: &str) {
+        if let Some(values) = &schema.enum_values {
+            if !values.iter().any(|x| x == value) {
+                self.error(path, "must be one of the enum values");
+            }
+        }
+        let length = value.chars().count() as u64;
+        if let Some(min) = schema.min_length {
+            if length < min {
+                self.error(path, format!("length must be at least {}", min));
+            }
+        }
+        if let Some(max) = schema.max_length {
+            if length > max {
+                self.error(path, format!("length must be at most {}", max));
+            }
+        }
+        if let Some(pattern) = &schema.pattern {
+            let error = match self.patterns.get(pattern) {
+                Ok(re) if !re.is_match(value) => Some(format!("must match pattern `{}`", pattern)),
+                Ok(_) => None,
+                Err(e) => Some(format!("invalid pattern `{}`: {}", pattern, e)),
+            };
+            if let Some(error) = error {
+                self.error(path, error);
+            }
+        }
+        if let Some(format) = &schema.format {
+            if check_format(format, value).is_err() {
+                self.error(path, format!("must be a valid `{}`", format));
+            }
+        }
+    }
+
+    fn integer(&mut self, schema: &IntegerSchema, value: &serde_json::Number, path: &str) {
+        if let Some(values) = &schema.enum_values {
+            // an integral float, e.g. `5.0`, matches the same integer
+            let matches = |x: i64| match value.as_i64() {
+                Some(v) => v == x,
+                None => value.as_f64() == Some(x as f64),
+            };
+            if !values.iter().copied().any(matches) {
+                self.error(path, "must be one of the enum values");
+            }
+        }
+        let value = value.as_f64().unwrap_or_default();
+        self.bounds(
+            value,
+            schema.minimum.as_ref().and_then(|x| x.as_f64()),
+            schema.exclusive_minimum,
+            schema.maximum.as_ref().and_then(|x| x.as_f64()),
+            schema.exclusive_maximum,
+            schema.multiple_of,
+            path,
+        );
+    }
+
+    fn number(&mut self, schema: &NumberSchema, value: &serde_json::Number, path: &str) {
+        let value = value.as_f64().unwrap_or_default();
+        if let Some(values) = &schema.enum_values {
+            if !values.iter().any(|x| x.as_f64() == Some(value)) {
+                self.error(path, "must be one of the enum values");
+            }
+        }
+        self.bounds(
+            value,
+            schema.minimum,
+            schema.exclusive_minimum,
+            schema.maximum,
+            schema.exclusive_maximum,
+            schema.multiple_of,
+            path,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn bounds(
+        &mut self,
+        value: f64,
+        minimum: Option<f64>,
+        exclusive_minimum: Option<bool>,
+        maximum: Option<f64>,
+        exclusive_maximum: Option<bool>,
+        multiple_of: Option<f64>,
+        path: &str,
+    ) {
+        if let Some(min) = minimum {
+            if exclusive_minimum.unwrap_or(false) {
+                if value <= min {
+                    self.error(path, format!("must be greater than {}", min));
+                }
+            } else if value < min {
+                self.error(path, format!("must be greater than or equal to {}", min));
+            }
+        }
+        if let Some(max) = maximum {
+            if exclusive_maximum.unwrap_or(false) {
+                if value >= max {
+                    self.error(path, format!("must be less than {}", max));
+                }
+            } else if value > max {
+                self.error(path, format!("must be less than or equal to {}", max));
+            }
+        }
+        if let Some(multiple_of) = multiple_of {
+            if multiple_of > 0.0 && !is_multiple_of(value, multiple_of) {
+                self.error(path, format!("must be a multiple of {}", multiple_of));
+            }
+        }
+    }
+
+    fn array(&mut self, schema: &'a ArraySchema, value: &[Value], path: String) {
+        let length = value.len() as u64;
+        if let Some(min) = schema.min_items {
+            if length < min {
+                self.error(&path, format!("must have at least {} items", min));
+            }
+        }
+        if let Some(max) = schema.max_items {
+            if length > max {
+                self.error(&path, format!("must have at most {} items", max));
+            }
+        }
+        if schema.unique_items.unwrap_or(false)
+            && value
+                .iter()
+                .enumerate()
+                .any(|(i, x)| value[..i].contains(x))
+        {
+            self.error(&path, "items must be unique");
+        }
+        if let Some(items) = &schema.items {
+            for (i, item) in value.iter().enumerate() {
+                self.reference(items, item, format!("{}/{}", path, i));
+            }
+        }
+    }
+
+    fn object(&mut self, schema: &'a ObjectSchema, value: &Map<String, Value>, path: String) {
+        let length = value.len() as u64;
+        if let Some(min) = schema.min_properties {
+            if length < min {
+                self.error(&path, format!("must have at least {} properties", min));
+            }
+        }
+        if let Some(max) = schema.max_properties {
+            if length > max {
+                self.error(&path, format!("must have at most {} properties", max));
+            }
+        }
+        for name in schema.required.iter().flatten() {
+            if !value.contains_key(name) {
+                self.error(&path, format!("property `{}` is required", name));
+            }
+        }
+        for (name, item) in value {
+            let item_path = format!("{}/{}", path, escape_token(name));
+            match schema.properties.as_ref().and_then(|x| x.get(name)) {
+                Some(property) => self.reference(property, item, item_path),
+                None => match &schema.additional_properties {
+                    Some(BoolOr::Bool(false)) => {
+                        self.error(&path, format!("property `{}` is not allowed", name));
+                    }
+                    Some(BoolOr::Item(additional)) => self.reference(additional, item, item_path),
+                    _ => {}
+                },
+            }
+        }
+    }
+}
+
+fn is_integer(value: &serde_json::Number) -> bool {
+    value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|x| x.fract() == 0.0)
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Returns `true` if the value is a multiple of the divisor,
+/// the quotient is compared with a relative tolerance, so `0.3` is a multiple of `0.1`.
+fn is_multiple_of(value: f64, divisor: f64) -> bool {
+    let quotient = value / divisor;
+    (quotient - quotient.round()).abs() <= quotient.abs().max(1.0) * f64::EPSILON * 4.0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::v3_0::spec::Spec;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "value": {"type": "integer"},
+                            "next": {"$ref": "#/components/schemas/Node"},
+                        },
+                    },
+                    "Loop": {"allOf": [{"$ref": "#/components/schemas/Loop"}]},
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn errors(schema: Value, value: Value) -> Vec<String> {
+        let schema = serde_json::from_value::<Schema>(schema).unwrap();
+        match schema.validate_instance(&value, &spec()) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_instance() {
+        for (schema, value, expected) in [
+            (json!({"type": "string"}), json!("foo"), vec![]),
+            (
+                json!({"type": "string"}),
+                json!(1),
+                vec!["#: expected `string`, found `integer`"],
+            ),
+            (
+                json!({"type": "string", "nullable": true}),
+                json!(null),
+                vec![],
+            ),
+            (
+                json!({"type": "string", "enum": ["a", "b"]}),
+                json!("c"),
+                vec!["#: must be one of the enum values"],
+            ),
+            (
+                json!({"type": "string", "pattern": "^[a-z]+$", "maxLength": 2}),
+                json!("abC"),
+                vec![
+                    "#: length must be at most 2",
+                    "#: must match pattern `^[a-z]+$`",
+                ],
+            ),
+            (
+                json!({"type": "integer", "minimum": 1, "exclusiveMinimum": true, "multipleOf": 2}),
+                json!(1),
+                vec!["#: must be greater than 1", "#: must be a multiple of 2"],
+            ),
+            (json!({"type": "integer", "enum": [5]}), json!(5.0), vec![]),
+            (
+                json!({"type": "integer", "enum": [5]}),
+                json!(6.0),
+                vec!["#: must be one of the enum values"],
+            ),
+            (
+                json!({"type": "integer"}),
+                json!(1.5),
+                vec!["#: expected `integer`, found `number`"],
+            ),
+            (
+                json!({"type": "number", "multipleOf": 0.1}),
+                json!(0.3),
+                vec![],
+            ),
+            (
+                json!({"type": "number", "multipleOf": 0.1}),
+                json!(0.35),
+                vec!["#: must be a multiple of 0.1"],
+            ),
+            (
+                json!({"type": "array", "items": {"type": "string", "pattern": "^[a-z]+$"}}),
+                json!(["a", "B", "c"]),
+                vec!["#/1: must match pattern `^[a-z]+$`"],
+            ),
+            (
+                json!({"type": "number", "maximum": 1.5}),
+                json!(2),
+                vec!["#: must be less than or equal to 1.5"],
+            ),
+            (
+                json!({"type": "array", "items": {"type": "boolean"}, "uniqueItems": true}),
+                json!([true, "x", true]),
+                vec![
+                    "#: items must be unique",
+                    "#/1: expected `boolean`, found `string`",
+                ],
+            ),
+            (
+                json!({
+                    "type": "object",
+                    "required": ["a"],
+                    "properties": {"b/c": {"type": "string"}},
+                    "additionalProperties": false,
+                }),
+                json!({"b/c": 1, "d": 2}),
+                vec![
+                    "#: property `a` is required",
+                    "#/b~1c: expected `string`, found `integer`",
+                    "#: property `d` is not allowed",
+                ],
+            ),
+            (
+                json!({"type": "object", "additionalProperties": {"type": "integer"}}),
+                json!({"a": 1, "b": "2"}),
+                vec!["#/b: expected `integer`, found `string`"],
+            ),
+            (
+                json!({"type": "string", "format": "date-time"}),
+                json!("2024-02-29T10:30:00Z"),
+                vec![],
+            ),
+            (
+                json!({"type": "string", "format": "date"}),
+                json!("2023-02-29"),
+                vec!["#: must be a valid `date`"],
+            ),
+            (
+                json!({"type": "string", "format": "duration"}),
+                json!("1 day"),
+                vec!["#: must be a valid `duration`"],
+            ),
+            (json!({}), json!("any"), vec![]),
+            (
+                json!({"anyOf": [{"type": "string"}, {"type": "integer"}]}),
+                json!(true),
+                vec!["#: must match at least one schema of `anyOf`"],
+            ),
+            (
+                json!({"oneOf": [{"type": "number"}, {"type": "integer"}]}),
+                json!(1),
+                vec!["#: must match exactly one schema of `oneOf`, matched 2"],
+            ),
+            (
+                json!({"allOf": [{"$ref": "#/components/schemas/Node"}]}),
+                json!({"value": 1, "next": {"value": "2"}}),
+                vec!["#/next/value: expected `integer`, found `string`"],
+            ),
+            (
+                json!({"allOf": [{"$ref": "#/components/schemas/Loop"}]}),
+                json!(1),
+                vec![],
+            ),
+            (
+                json!({"allOf": [{"$ref": "#/components/schemas/Missing"}]}),
+                json!(1),
+                vec!["#: reference `#/components/schemas/Missing` not found"],
+            ),
+        ] {
+            assert_eq!(
+                errors(schema.clone(), value.clone()),
+                expected,
+                "{} {}",
+                schema,
+                value
+            );
+        }
+    }
+}
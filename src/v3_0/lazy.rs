@@ -0,0 +1,166 @@
+//! Lazily parsed component schemas
+//!
+//! [LazySchemas] keeps the raw JSON of the component schemas and parses a schema
+//! only when it is requested for the first time, the parsed schema is cached.
+//! Most tools touch a fraction of the component schemas of a large specification,
+//! so the time to parse the rest of them is saved.
+//!
+//! Example:
+//!
+//! ```rust
+//! use serde::Deserialize;
+//!
+//! use roas::v3_0::lazy::LazySchemas;
+//!
+//! #[derive(Deserialize)]
+//! struct Components {
+//!     schemas: LazySchemas,
+//! }
+//!
+//! let raw = r#"{
+//!     "schemas": {
+//!         "Pet": {"type": "object"},
+//!         "Broken": {"type": "object", "required": 1}
+//!     }
+//! }"#;
+//! let components = serde_json::from_str::<Components>(raw).unwrap();
+//! assert_eq!(components.schemas.names().collect::<Vec<_>>(), vec!["Broken", "Pet"]);
+//! assert!(components.schemas.get("Pet").unwrap().is_ok());
+//! assert!(components.schemas.get("Broken").unwrap().is_err());
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::common::reference::RefOr;
+use crate::v3_0::schema::Schema;
+
+/// The component schemas parsed on demand.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct LazySchemas(BTreeMap<String, LazySchema>);
+
+impl LazySchemas {
+    /// Returns the names of the schemas, without parsing them.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Returns the schema with the given name, parsing it on the first call.
+    /// `None` if the schema is not defined.
+    pub fn get(&self, name: &str) -> Option<Result<&RefOr<Schema>, serde_json::Error>> {
+        self.0.get(name).map(LazySchema::get)
+    }
+
+    /// Returns the raw JSON of the schema with the given name.
+    pub fn get_raw(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(LazySchema::raw)
+    }
+
+    /// Parses all the schemas, like the `schemas` field of the [crate::v3_0::components::Components].
+    pub fn into_schemas(self) -> Result<BTreeMap<String, RefOr<Schema>>, serde_json::Error> {
+        self.0
+            .into_iter()
+            .map(|(name, schema)| Ok((name, schema.into_schema()?)))
+            .collect()
+    }
+}
+
+/// A single schema, kept as the raw JSON until it is requested.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct LazySchema {
+    raw: Box<RawValue>,
+
+    #[serde(skip)]
+    parsed: OnceLock<RefOr<Schema>>,
+}
+
+impl LazySchema {
+    /// Returns the raw JSON of the schema.
+    pub fn raw(&self) -> &str {
+        self.raw.get()
+    }
+
+    /// Returns the schema, parsing it on the first call.
+    /// The errors are not cached, so the failed schema is parsed on every call.
+    pub fn get(&self) -> Result<&RefOr<Schema>, serde_json::Error> {
+        if let Some(schema) = self.parsed.get() {
+            return Ok(schema);
+        }
+        let schema = serde_json::from_str(self.raw.get())?;
+        Ok(self.parsed.get_or_init(|| schema))
+    }
+
+    /// Returns the parsed schema, reusing the cached one.
+    pub fn into_schema(self) -> Result<RefOr<Schema>, serde_json::Error> {
+        match self.parsed.into_inner() {
+            Some(schema) => Ok(schema),
+            None => serde_json::from_str(self.raw.get()),
+        }
+    }
+}
+
+impl PartialEq for LazySchema {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw.get() == other.raw.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_schemas() {
+        let schemas = serde_json::from_str::<LazySchemas>(
+            r##"{
+                "Pet": {"type": "object", "properties": {"name": {"type": "string"}}},
+                "Pets": {"$ref": "#/components/schemas/Pet"}
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(schemas.names().collect::<Vec<_>>(), vec!["Pet", "Pets"]);
+        assert!(schemas.0["Pet"].parsed.get().is_none(), "not parsed yet");
+
+        let pet = schemas.get("Pet").unwrap().unwrap();
+        assert!(matches!(pet, RefOr::Item(Schema::Single(_))));
+        assert!(
+            std::ptr::eq(pet, schemas.get("Pet").unwrap().unwrap()),
+            "cached"
+        );
+        assert!(schemas.0["Pets"].parsed.get().is_none(), "parsed on demand");
+        assert!(schemas.get("Missing").is_none());
+        assert_eq!(
+            schemas.get_raw("Pets"),
+            Some(r##"{"$ref": "#/components/schemas/Pet"}"##)
+        );
+
+        assert_eq!(
+            serde_json::to_value(&schemas).unwrap(),
+            serde_json::json!({
+                "Pet": {"type": "object", "properties": {"name": {"type": "string"}}},
+                "Pets": {"$ref": "#/components/schemas/Pet"},
+            }),
+        );
+        let parsed = schemas.into_schemas().unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(matches!(parsed["Pets"], RefOr::Ref(_)));
+    }
+
+    #[test]
+    fn test_lazy_schema_error() {
+        let schemas =
+            serde_json::from_str::<LazySchemas>(r#"{"Broken": {"type": "object", "required": 1}}"#)
+                .unwrap();
+        assert!(schemas.get("Broken").unwrap().is_err());
+        assert!(
+            schemas.get("Broken").unwrap().is_err(),
+            "errors are not cached"
+        );
+        assert!(schemas.into_schemas().is_err());
+    }
+}
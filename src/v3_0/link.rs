@@ -1,10 +1,19 @@
 //! Represents a possible design-time link for a response
 
 use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::common::helpers::{Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_uri_reference, Context, PushError, ValidateWithContext,
+};
+use crate::common::path::Path;
+use crate::v3_0::operation::Operation;
 use crate::v3_0::server::Server;
 use crate::v3_0::spec::Spec;
 
@@ -63,11 +72,184 @@ pub struct Link {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+/// A runtime expression allows defining values based on information that will only be available
+/// within the HTTP message in an actual API call.
+///
+/// ABNF syntax:
+///
+/// ```text
+/// expression = ( "$url" / "$method" / "$statusCode" / "$request." source / "$response." source )
+/// source = ( header-reference / query-reference / path-reference / body-reference )
+/// header-reference = "header." token
+/// query-reference = "query." name
+/// path-reference = "path." name
+/// body-reference = "body" ["#" json-pointer ]
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuntimeExpression {
+    /// `$url`
+    Url,
+
+    /// `$method`
+    Method,
+
+    /// `$statusCode`
+    StatusCode,
+
+    /// `$request.{source}`
+    Request(RuntimeExpressionSource),
+
+    /// `$response.{source}`
+    Response(RuntimeExpressionSource),
+}
+
+/// The source of a request or response runtime expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuntimeExpressionSource {
+    /// `header.{token}`
+    Header(String),
+
+    /// `query.{name}`
+    Query(String),
+
+    /// `path.{name}`
+    Path(String),
+
+    /// `body` with an optional JSON pointer, e.g. `body#/id`
+    Body(Option<String>),
+}
+
+/// InvalidRuntimeExpression is returned when the runtime expression cannot be parsed.
+#[derive(Debug, Error, PartialEq)]
+#[error("invalid runtime expression `{0}`")]
+pub struct InvalidRuntimeExpression(pub String);
+
+impl FromStr for RuntimeExpression {
+    type Err = InvalidRuntimeExpression;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidRuntimeExpression(s.to_owned());
+        match s {
+            "$url" => return Ok(RuntimeExpression::Url),
+            "$method" => return Ok(RuntimeExpression::Method),
+            "$statusCode" => return Ok(RuntimeExpression::StatusCode),
+            _ => {}
+        }
+        let (kind, source) = if let Some(source) = s.strip_prefix("$request.") {
+            (RuntimeExpression::Request as fn(_) -> _, source)
+        } else if let Some(source) = s.strip_prefix("$response.") {
+            (RuntimeExpression::Response as fn(_) -> _, source)
+        } else {
+            return Err(err());
+        };
+        let source = if let Some(token) = source.strip_prefix("header.") {
+            let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+            if token.is_empty() || !token.chars().all(is_tchar) {
+                return Err(err());
+            }
+            RuntimeExpressionSource::Header(token.to_owned())
+        } else if let Some(name) = source.strip_prefix("query.") {
+            if name.is_empty() {
+                return Err(err());
+            }
+            RuntimeExpressionSource::Query(name.to_owned())
+        } else if let Some(name) = source.strip_prefix("path.") {
+            if name.is_empty() {
+                return Err(err());
+            }
+            RuntimeExpressionSource::Path(name.to_owned())
+        } else if source == "body" {
+            RuntimeExpressionSource::Body(None)
+        } else if let Some(pointer) = source.strip_prefix("body#") {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(err());
+            }
+            RuntimeExpressionSource::Body(Some(pointer.to_owned()))
+        } else {
+            return Err(err());
+        };
+        Ok(kind(source))
+    }
+}
+
+impl Display for RuntimeExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeExpression::Url => write!(f, "$url"),
+            RuntimeExpression::Method => write!(f, "$method"),
+            RuntimeExpression::StatusCode => write!(f, "$statusCode"),
+            RuntimeExpression::Request(source) => write!(f, "$request.{}", source),
+            RuntimeExpression::Response(source) => write!(f, "$response.{}", source),
+        }
+    }
+}
+
+impl Display for RuntimeExpressionSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeExpressionSource::Header(token) => write!(f, "header.{}", token),
+            RuntimeExpressionSource::Query(name) => write!(f, "query.{}", name),
+            RuntimeExpressionSource::Path(name) => write!(f, "path.{}", name),
+            RuntimeExpressionSource::Body(None) => write!(f, "body"),
+            RuntimeExpressionSource::Body(Some(pointer)) => write!(f, "body#{}", pointer),
+        }
+    }
+}
+
+/// Validates a link value, which can be a constant, an expression (`$request.path.id`)
+/// or a string with embedded expressions (`{$request.path.id}`).
+fn validate_expression(value: &serde_json::Value, ctx: &mut Context<Spec>, path: Path) {
+    if let serde_json::Value::String(value) = value {
+        validate_expression_template(value, ctx, path);
+    }
+}
+
+/// Validates a string that is either an expression (`$request.path.id`)
+/// or a template with embedded expressions (`{$request.query.url}/data`).
+pub(crate) fn validate_expression_template(value: &str, ctx: &mut Context<Spec>, path: Path) {
+    if value.starts_with('$') {
+        if let Err(e) = value.parse::<RuntimeExpression>() {
+            ctx.error(path, e.to_string());
+        }
+        return;
+    }
+    let mut rest = value;
+    while let Some(start) = rest.find("{$") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => {
+                ctx.error(
+                    path,
+                    format_args!("unclosed runtime expression in `{}`", value),
+                );
+                return;
+            }
+        };
+        if let Err(e) = rest[start + 1..end].parse::<RuntimeExpression>() {
+            ctx.error(path.clone(), e.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+}
+
+impl Link {
+    /// Returns the operation referenced by `operationId` as `(path, method, operation)` tuple.
+    pub fn operation<'a>(&self, spec: &'a Spec) -> Option<(&'a String, &'a String, &'a Operation)> {
+        self.operation_id
+            .as_ref()
+            .and_then(|operation_id| spec.operation_by_id(operation_id))
+    }
 }
 
 impl ValidateWithContext<Spec> for Link {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if let Some(operation_ref) = &self.operation_ref {
+            validate_uri_reference(operation_ref, ctx, path.field("operationRef"));
+        }
         if let Some(operation_id) = &self.operation_id {
             if !ctx
                 .visited
@@ -79,8 +261,143 @@ impl ValidateWithContext<Spec> for Link {
                 );
             }
         }
+        if let Some(parameters) = &self.parameters {
+            for (name, value) in parameters {
+                validate_expression(value, ctx, path.field("parameters").item(name));
+            }
+        }
+        if let Some(request_body) = &self.request_body {
+            validate_expression(request_body, ctx, path.field("requestBody"));
+        }
         if let Some(server) = &self.server {
-            server.validate_with_context(ctx, format!("{}.server", path));
+            server.validate_with_context(ctx, path.field("server"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_expression_parse() {
+        for (s, expected) in [
+            ("$url", RuntimeExpression::Url),
+            ("$method", RuntimeExpression::Method),
+            ("$statusCode", RuntimeExpression::StatusCode),
+            (
+                "$request.path.id",
+                RuntimeExpression::Request(RuntimeExpressionSource::Path("id".to_owned())),
+            ),
+            (
+                "$request.query.queryUrl",
+                RuntimeExpression::Request(RuntimeExpressionSource::Query("queryUrl".to_owned())),
+            ),
+            (
+                "$request.header.accept",
+                RuntimeExpression::Request(RuntimeExpressionSource::Header("accept".to_owned())),
+            ),
+            (
+                "$request.body",
+                RuntimeExpression::Request(RuntimeExpressionSource::Body(None)),
+            ),
+            (
+                "$response.body#/id",
+                RuntimeExpression::Response(RuntimeExpressionSource::Body(Some("/id".to_owned()))),
+            ),
+        ] {
+            assert_eq!(
+                s.parse::<RuntimeExpression>(),
+                Ok(expected.clone()),
+                "{}",
+                s
+            );
+            assert_eq!(expected.to_string(), s, "{}", s);
+        }
+
+        for s in [
+            "$foo",
+            "$request",
+            "$request.path.",
+            "$request.header.a b",
+            "$response.body#id",
+            "$response.cookie.id",
+        ] {
+            assert_eq!(
+                s.parse::<RuntimeExpression>(),
+                Err(InvalidRuntimeExpression(s.to_owned())),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_expressions() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        Link {
+            parameters: Some(BTreeMap::from([
+                ("id".to_owned(), serde_json::json!("$request.path.id")),
+                ("constant".to_owned(), serde_json::json!(42)),
+                (
+                    "url".to_owned(),
+                    serde_json::json!("/users/{$request.path.id}"),
+                ),
+                ("bad".to_owned(), serde_json::json!("$request.foo")),
+            ])),
+            request_body: Some(serde_json::json!("{$response.body#/id")),
+            ..Default::default()
         }
+        .validate_with_context(&mut ctx, "link".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "link.parameters[bad]: invalid runtime expression `$request.foo`",
+                "link.requestBody: unclosed runtime expression in `{$response.body#/id`",
+            ],
+            "invalid expressions: {:?}",
+            ctx.errors
+        );
+    }
+
+    #[test]
+    fn test_operation() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "operationId": "getUser",
+                        "responses": {"200": {"description": "ok"}},
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            Link {
+                operation_id: Some("getUser".to_owned()),
+                ..Default::default()
+            }
+            .operation(&spec)
+            .map(|(path, method, _)| format!("{} {}", method, path)),
+            Some("get /users/{id}".to_owned()),
+            "existing operation",
+        );
+        assert!(
+            Link {
+                operation_id: Some("getUsers".to_owned()),
+                ..Default::default()
+            }
+            .operation(&spec)
+            .is_none(),
+            "missing operation",
+        );
+        assert!(Link::default().operation(&spec).is_none(), "no operationId");
     }
 }
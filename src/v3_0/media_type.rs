@@ -1,16 +1,19 @@
 //! Provides schema and examples for the media type
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, Context, PushError, ValidateWithContext};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v3_0::example::Example;
-use crate::v3_0::header::Header;
+use crate::v3_0::header::{validate_header_name, Header};
 use crate::v3_0::parameter::InQueryStyle;
-use crate::v3_0::schema::Schema;
+use crate::v3_0::schema::{Schema, SingleSchema};
 use crate::v3_0::spec::Spec;
+use crate::v3_0::style::{encode_value, parse_value, Style};
 
 /// Each Media Type Object provides schema and examples for the media type identified by its key.
 ///
@@ -39,7 +42,7 @@ use crate::v3_0::spec::Spec;
 ///     frog:
 ///       $ref: "#/components/examples/frog-example"
 /// ```
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct MediaType {
     /// The schema defining the content of the request, response, or parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -76,7 +79,7 @@ pub struct MediaType {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// A single encoding definition applied to a single schema property.
@@ -127,9 +130,9 @@ pub struct Encoding {
     /// - for other primitive types – `text/plain`;
     /// - for `object` - `application/json`;
     /// - for `array` – the default is defined based on the inner type.
-    /// The value can be a specific media type (e.g. `application/json`),
-    /// a wildcard media type (e.g. `image/*`),
-    /// or a comma-separated list of the two types.
+    ///   The value can be a specific media type (e.g. `application/json`),
+    ///   a wildcard media type (e.g. `image/*`),
+    ///   or a comma-separated list of the two types.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "contentType")]
     pub content_type: Option<String>,
@@ -171,33 +174,112 @@ pub struct Encoding {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for MediaType {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(schema) = &self.schema {
-            schema.validate_with_context(ctx, format!("{}.schema", path));
+            schema.validate_with_context(ctx, path.field("schema"));
         }
         if let Some(examples) = &self.examples {
             for (name, example) in examples {
-                example.validate_with_context(ctx, format!("{}.examples[{}]", path, name));
+                example.validate_with_context(ctx, path.field("examples").item(name));
             }
         }
         if let Some(encoding) = &self.encoding {
+            let schema = self.schema.as_ref().and_then(|x| x.get_item(ctx.spec).ok());
             for (name, encoding) in encoding {
-                encoding.validate_with_context(ctx, format!("{}.encoding[{}]", path, name));
+                let path = path.field("encoding").item(name);
+                if let Some(schema @ (Schema::Single(SingleSchema::Object(_)) | Schema::AllOf(_))) =
+                    schema
+                {
+                    if !schema
+                        .lookup_property(name, ctx.spec, &mut HashSet::new())
+                        .0
+                    {
+                        ctx.error(
+                            path.clone(),
+                            format_args!("property `{}` is not defined in the schema", name),
+                        );
+                    }
+                }
+                encoding.validate_with_context(ctx, path);
             }
         }
     }
 }
 
+impl Encoding {
+    /// Returns the serialization style of the property, the default is `form`.
+    pub fn style(&self) -> Style {
+        self.style.as_ref().map_or(Style::Form, Style::from)
+    }
+
+    /// Parses the raw form data of the property according to its style and schema,
+    /// see [parse_value](crate::v3_0::style::parse_value) for details.
+    pub fn parse_value(
+        &self,
+        name: &str,
+        raw: &str,
+        schema: Option<&RefOr<Schema>>,
+    ) -> serde_json::Value {
+        let style = self.style();
+        let explode = self.explode.unwrap_or_else(|| style.default_explode());
+        parse_value(&style, explode, name, raw, schema)
+    }
+
+    /// Serializes the value of the property according to its style,
+    /// see [encode_value](crate::v3_0::style::encode_value) for details.
+    pub fn encode_value(&self, name: &str, value: &serde_json::Value) -> String {
+        let style = self.style();
+        let explode = self.explode.unwrap_or_else(|| style.default_explode());
+        encode_value(&style, explode, name, value)
+    }
+}
+
 impl ValidateWithContext<Spec> for Encoding {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(headers) = &self.headers {
             for (name, header) in headers {
-                header.validate_with_context(ctx, format!("{}.headers[{}]", path, name));
+                validate_header_name(name, ctx, path.field("headers").item(name));
+                header.validate_with_context(ctx, path.field("headers").item(name));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_encoding_properties() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        serde_json::from_value::<MediaType>(serde_json::json!({
+            "schema": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string"},
+                    "profileImage": {"type": "string", "format": "binary"},
+                },
+            },
+            "encoding": {
+                "profileImage": {"contentType": "image/png, image/jpeg"},
+                "avatar": {"contentType": "image/png"},
+            },
+        }))
+        .unwrap()
+        .validate_with_context(&mut ctx, "#.content[multipart/form-data]".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "#.content[multipart/form-data].encoding[avatar]: property `avatar` is not defined in the schema"
+            ],
+            "undefined property",
+        );
+    }
+}
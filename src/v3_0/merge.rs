@@ -0,0 +1,726 @@
+//! Merging of specifications.
+//!
+//! The merge unions the paths, components, tags and security requirements of two specifications,
+//! so several specifications, e.g. per-team ones, can be stitched into a single one.
+//!
+//! Identical definitions are never a conflict.
+//! Different definitions with the same name are resolved by the [MergeStrategy]:
+//!
+//! * `Error` fails the merge.
+//! * `PreferLeft` keeps the definition of the specification being merged into.
+//! * `RenameWithPrefix` keeps both components by renaming the conflicting components
+//!   of the other specification and rewriting all references to them.
+//!   The paths and operations cannot be renamed, so a conflicting operation fails the merge,
+//!   and a conflicting tag keeps the left definition.
+//!   A prefixed name already used by a component of either specification fails the merge.
+//!
+//! The path-level `parameters` and `servers` of the other specification apply to its operations only,
+//! so they are moved into the operations when the path is already defined.
+//! A path equivalent to a defined one, e.g. `/pets/{petId}` and `/pets/{id}`,
+//! is a conflict, because the paths would match the same requests.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::merge::{MergeOptions, MergeStrategy};
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut left = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//!     "components": {"schemas": {"Pet": {"type": "object"}}},
+//! })).unwrap();
+//! let right = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "bar", "version": "1"},
+//!     "paths": {},
+//!     "components": {"schemas": {"Pet": {"type": "string"}}},
+//! })).unwrap();
+//!
+//! left.merge(right, MergeOptions {
+//!     strategy: MergeStrategy::RenameWithPrefix("Bar".to_owned()),
+//! }).unwrap();
+//! let schemas = left.components.unwrap().schemas.unwrap();
+//! assert_eq!(schemas.keys().collect::<Vec<_>>(), vec!["BarPet", "Pet"]);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::helpers::normalize_path_template;
+use crate::common::reference::RefOr;
+use crate::v3_0::components::Components;
+use crate::v3_0::parameter::Parameter;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::spec::Spec;
+
+/// The way to resolve the conflicting definitions.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum MergeStrategy {
+    /// Fail the merge.
+    #[default]
+    Error,
+
+    /// Keep the definition of the specification being merged into.
+    PreferLeft,
+
+    /// Rename the conflicting components of the other specification by adding the prefix.
+    RenameWithPrefix(String),
+}
+
+/// The options of the merge.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MergeOptions {
+    /// The way to resolve the conflicting definitions.
+    pub strategy: MergeStrategy,
+}
+
+/// MergeError is an error type for the merge of specifications.
+#[derive(Debug, Error)]
+pub enum MergeError {
+    /// Conflict is returned when the definitions at the location cannot be merged.
+    #[error("{0}: conflicting definitions")]
+    Conflict(String),
+
+    /// Json is returned when the references cannot be rewritten.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Spec {
+    /// Merges the other specification into this one.
+    ///
+    /// The specification is not modified if the merge fails.
+    pub fn merge(&mut self, other: Spec, options: MergeOptions) -> Result<(), MergeError> {
+        let mut merged = self.clone();
+        let mut other = other;
+        let mut renames = BTreeMap::new();
+        if let MergeStrategy::RenameWithPrefix(prefix) = &options.strategy {
+            renames = component_renames(&merged, &other, prefix)?;
+            if !renames.is_empty() {
+                other = rename_components(other, &renames)?;
+            }
+        }
+
+        let mut templates = merged
+            .paths
+            .keys()
+            .map(|x| (normalize_path_template(x), x.clone()))
+            .collect::<BTreeMap<_, _>>();
+        for (path, mut item) in std::mem::take(&mut other.paths) {
+            let left = match merged.paths.get_mut(&path) {
+                Some(left) => left,
+                None => {
+                    if templates.contains_key(&normalize_path_template(&path)) {
+                        if options.strategy == MergeStrategy::PreferLeft {
+                            continue;
+                        }
+                        return Err(MergeError::Conflict(format!("#.paths[{}]", path)));
+                    }
+                    templates.insert(normalize_path_template(&path), path.clone());
+                    merged.paths.insert(path, item);
+                    continue;
+                }
+            };
+            // the equal fields of the left path item apply to the merged operations as well
+            if item.parameters == left.parameters {
+                item.parameters = None;
+            }
+            if item.servers == left.servers {
+                item.servers = None;
+            }
+            move_path_fields(&mut item, &other);
+            for (method, operation) in item.operations.unwrap_or_default() {
+                let operations = left.operations.get_or_insert_with(BTreeMap::new);
+                match operations.get(&method) {
+                    None => {
+                        operations.insert(method, operation);
+                    }
+                    Some(x) if *x == operation => {}
+                    Some(_) => {
+                        if options.strategy != MergeStrategy::PreferLeft {
+                            return Err(MergeError::Conflict(format!(
+                                "#.paths[{}].{}",
+                                path, method
+                            )));
+                        }
+                    }
+                }
+            }
+            if left.extensions.is_none() {
+                left.extensions = item.extensions;
+            }
+        }
+
+        if let Some(components) = other.components {
+            merge_components(
+                merged.components.get_or_insert_with(Default::default),
+                components,
+                &options.strategy,
+                &renames,
+            )?;
+        }
+
+        for tag in other.tags.unwrap_or_default() {
+            let tags = merged.tags.get_or_insert_with(Vec::new);
+            match tags.iter().find(|x| x.name == tag.name) {
+                None => tags.push(tag),
+                Some(x) if *x == tag => {}
+                Some(_) => {
+                    if options.strategy == MergeStrategy::Error {
+                        return Err(MergeError::Conflict(format!("#.tags[{}]", tag.name)));
+                    }
+                }
+            }
+        }
+
+        for requirement in other.security.unwrap_or_default() {
+            let security = merged.security.get_or_insert_with(Vec::new);
+            if !security.contains(&requirement) {
+                security.push(requirement);
+            }
+        }
+
+        *self = merged;
+        Ok(())
+    }
+}
+
+/// Moves the path-level parameters and servers of the path item into its operations,
+/// the parameters overridden by an operation are skipped.
+fn move_path_fields(item: &mut PathItem, spec: &Spec) {
+    let parameters = item.parameters.take().unwrap_or_default();
+    let servers = item.servers.take();
+    let key = |x: &RefOr<Parameter>| {
+        x.get_item(spec)
+            .ok()
+            .map(|x| format!("{}:{}", x.location(), x.name()))
+    };
+    for operation in item.operations.iter_mut().flat_map(|x| x.values_mut()) {
+        let own = operation.parameters.take().unwrap_or_default();
+        let defined = own.iter().filter_map(key).collect::<Vec<_>>();
+        let mut merged = parameters
+            .iter()
+            .filter(|x| !own.contains(x) && key(x).is_none_or(|x| !defined.contains(&x)))
+            .cloned()
+            .collect::<Vec<_>>();
+        merged.extend(own);
+        if !merged.is_empty() {
+            operation.parameters = Some(merged);
+        }
+        if operation.servers.is_none() {
+            operation.servers.clone_from(&servers);
+        }
+    }
+}
+
+/// Returns the new names of the conflicting components of the right specification,
+/// keyed by the kind of the component and its old name.
+///
+/// Renaming a component changes the components referencing it,
+/// so the conflicts are searched again until no new conflict is found.
+fn component_renames(
+    left: &Spec,
+    right: &Spec,
+    prefix: &str,
+) -> Result<BTreeMap<(String, String), String>, MergeError> {
+    let left = serde_json::to_value(&left.components)?;
+    let mut renames = BTreeMap::new();
+    loop {
+        let mut right = match &right.components {
+            Some(components) => serde_json::to_value(components)?,
+            None => return Ok(renames),
+        };
+        rewrite_references(&mut right, &renames);
+        let mut found = false;
+        if let (Value::Object(left), Value::Object(right)) = (&left, &right) {
+            for (kind, items) in right {
+                if kind.starts_with("x-") {
+                    continue;
+                }
+                let (Some(Value::Object(left)), Value::Object(items)) = (left.get(kind), items)
+                else {
+                    continue;
+                };
+                for (name, item) in items {
+                    let key = (kind.clone(), name.clone());
+                    if left.get(name).is_some_and(|x| x != item) && !renames.contains_key(&key) {
+                        let renamed = format!("{}{}", prefix, name);
+                        if left.contains_key(&renamed) || items.contains_key(&renamed) {
+                            return Err(MergeError::Conflict(format!(
+                                "#/components/{}/{}",
+                                kind, renamed
+                            )));
+                        }
+                        renames.insert(key, renamed);
+                        found = true;
+                    }
+                }
+            }
+        }
+        if !found {
+            return Ok(renames);
+        }
+    }
+}
+
+/// Rewrites all references to the renamed components
+/// and renames the security schemes used by the security requirements.
+fn rename_components(
+    spec: Spec,
+    renames: &BTreeMap<(String, String), String>,
+) -> Result<Spec, MergeError> {
+    let mut value = serde_json::to_value(spec)?;
+    rewrite_references(&mut value, renames);
+    let mut spec = Spec::deserialize(value)?;
+
+    let schemes = renames
+        .iter()
+        .filter(|((kind, _), _)| kind == "securitySchemes")
+        .map(|((_, name), new)| (name, new))
+        .collect::<BTreeMap<_, _>>();
+    if !schemes.is_empty() {
+        let rename = |security: &mut Option<Vec<BTreeMap<String, Vec<String>>>>| {
+            for requirement in security.iter_mut().flatten() {
+                *requirement = std::mem::take(requirement)
+                    .into_iter()
+                    .map(|(name, scopes)| match schemes.get(&name) {
+                        Some(new) => ((*new).clone(), scopes),
+                        None => (name, scopes),
+                    })
+                    .collect();
+            }
+        };
+        rename(&mut spec.security);
+        for (_, _, operation) in spec.operations_mut() {
+            rename(&mut operation.security);
+        }
+    }
+    Ok(spec)
+}
+
+fn rewrite_references(value: &mut Value, renames: &BTreeMap<(String, String), String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        for ((kind, name), new) in renames {
+                            let old = format!("#/components/{}/{}", kind, name);
+                            if let Some(rest) = reference.strip_prefix(&old) {
+                                if rest.is_empty() || rest.starts_with('/') {
+                                    *reference = format!("#/components/{}/{}{}", kind, new, rest);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    _ => rewrite_references(value, renames),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for value in items {
+                rewrite_references(value, renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn merge_components(
+    left: &mut Components,
+    right: Components,
+    strategy: &MergeStrategy,
+    renames: &BTreeMap<(String, String), String>,
+) -> Result<(), MergeError> {
+    merge_map(
+        &mut left.schemas,
+        right.schemas,
+        "schemas",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.responses,
+        right.responses,
+        "responses",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.parameters,
+        right.parameters,
+        "parameters",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.examples,
+        right.examples,
+        "examples",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.request_bodies,
+        right.request_bodies,
+        "requestBodies",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.headers,
+        right.headers,
+        "headers",
+        strategy,
+        renames,
+    )?;
+    merge_map(
+        &mut left.security_schemes,
+        right.security_schemes,
+        "securitySchemes",
+        strategy,
+        renames,
+    )?;
+    merge_map(&mut left.links, right.links, "links", strategy, renames)?;
+    merge_map(
+        &mut left.callbacks,
+        right.callbacks,
+        "callbacks",
+        strategy,
+        renames,
+    )?;
+    if left.extensions.is_none() {
+        left.extensions = right.extensions;
+    }
+    Ok(())
+}
+
+fn merge_map<T: PartialEq>(
+    left: &mut Option<BTreeMap<String, T>>,
+    right: Option<BTreeMap<String, T>>,
+    kind: &str,
+    strategy: &MergeStrategy,
+    renames: &BTreeMap<(String, String), String>,
+) -> Result<(), MergeError> {
+    for (name, item) in right.unwrap_or_default() {
+        let renamed = renames.get(&(kind.to_owned(), name.clone()));
+        let name = renamed.cloned().unwrap_or(name);
+        let left = left.get_or_insert_with(BTreeMap::new);
+        match left.get(&name) {
+            None => {
+                left.insert(name, item);
+            }
+            Some(x) if *x == item => {}
+            Some(_) => {
+                if renamed.is_some() || *strategy == MergeStrategy::Error {
+                    return Err(MergeError::Conflict(format!(
+                        "#/components/{}/{}",
+                        kind, name
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(value: Value) -> Spec {
+        let mut spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+        });
+        spec.as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(spec).unwrap()
+    }
+
+    #[test]
+    fn test_merge_union() {
+        let mut left = spec(serde_json::json!({
+            "paths": {
+                "/pets": {"get": {"responses": {"200": {"description": "ok"}}}},
+            },
+            "components": {"schemas": {"Pet": {"type": "object"}}},
+            "tags": [{"name": "pets"}],
+            "security": [{"api_key": []}],
+        }));
+        let right = spec(serde_json::json!({
+            "paths": {
+                "/pets": {"post": {"responses": {"201": {"description": "ok"}}}},
+                "/users": {"get": {"responses": {"200": {"description": "ok"}}}},
+            },
+            "components": {"schemas": {"Pet": {"type": "object"}, "User": {"type": "object"}}},
+            "tags": [{"name": "pets"}, {"name": "users"}],
+            "security": [{"api_key": []}, {"oauth": ["read"]}],
+        }));
+        left.merge(right, MergeOptions::default()).unwrap();
+        assert_eq!(
+            left,
+            spec(serde_json::json!({
+                "paths": {
+                    "/pets": {
+                        "get": {"responses": {"200": {"description": "ok"}}},
+                        "post": {"responses": {"201": {"description": "ok"}}},
+                    },
+                    "/users": {"get": {"responses": {"200": {"description": "ok"}}}},
+                },
+                "components": {"schemas": {"Pet": {"type": "object"}, "User": {"type": "object"}}},
+                "tags": [{"name": "pets"}, {"name": "users"}],
+                "security": [{"api_key": []}, {"oauth": ["read"]}],
+            })),
+        );
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let left = spec(serde_json::json!({
+            "paths": {
+                "/pets": {"get": {"responses": {"200": {"description": "ok"}}}},
+            },
+            "components": {"schemas": {"Pet": {"type": "object"}}},
+        }));
+        let right = spec(serde_json::json!({
+            "components": {"schemas": {"Pet": {"type": "string"}}},
+        }));
+
+        let mut merged = left.clone();
+        assert_eq!(
+            merged
+                .merge(right.clone(), MergeOptions::default())
+                .unwrap_err()
+                .to_string(),
+            "#/components/schemas/Pet: conflicting definitions",
+        );
+        assert_eq!(merged, left, "unchanged on error");
+
+        merged
+            .merge(
+                right,
+                MergeOptions {
+                    strategy: MergeStrategy::PreferLeft,
+                },
+            )
+            .unwrap();
+        assert_eq!(merged, left, "prefer left");
+
+        let right = spec(serde_json::json!({
+            "paths": {
+                "/pets": {"get": {"responses": {"201": {"description": "ok"}}}},
+            },
+        }));
+        assert_eq!(
+            merged
+                .merge(
+                    right,
+                    MergeOptions {
+                        strategy: MergeStrategy::RenameWithPrefix("Bar".to_owned()),
+                    },
+                )
+                .unwrap_err()
+                .to_string(),
+            "#.paths[/pets].get: conflicting definitions",
+        );
+    }
+
+    #[test]
+    fn test_merge_rename_with_prefix() {
+        let mut left = spec(serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Category": {"type": "string"},
+                    "Pet": {
+                        "type": "object",
+                        "properties": {"category": {"$ref": "#/components/schemas/Category"}},
+                    },
+                },
+                "securitySchemes": {"auth": {"type": "http", "scheme": "basic"}},
+            },
+        }));
+        let right = spec(serde_json::json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "security": [{"auth": []}],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"},
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Category": {"type": "integer"},
+                    "Pet": {
+                        "type": "object",
+                        "properties": {"category": {"$ref": "#/components/schemas/Category"}},
+                    },
+                },
+                "securitySchemes": {"auth": {"type": "http", "scheme": "bearer"}},
+            },
+        }));
+        left.merge(
+            right,
+            MergeOptions {
+                strategy: MergeStrategy::RenameWithPrefix("Bar".to_owned()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            left,
+            spec(serde_json::json!({
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "security": [{"Barauth": []}],
+                            "responses": {
+                                "200": {
+                                    "description": "ok",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {"$ref": "#/components/schemas/BarPet"},
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "BarCategory": {"type": "integer"},
+                        "BarPet": {
+                            "type": "object",
+                            "properties": {
+                                "category": {"$ref": "#/components/schemas/BarCategory"},
+                            },
+                        },
+                        "Category": {"type": "string"},
+                        "Pet": {
+                            "type": "object",
+                            "properties": {"category": {"$ref": "#/components/schemas/Category"}},
+                        },
+                    },
+                    "securitySchemes": {
+                        "Barauth": {"type": "http", "scheme": "bearer"},
+                        "auth": {"type": "http", "scheme": "basic"},
+                    },
+                },
+            })),
+        );
+    }
+
+    #[test]
+    fn test_merge_path_fields() {
+        let mut left = spec(serde_json::json!({
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [{"in": "path", "name": "id", "required": true, "schema": {"type": "integer"}}],
+                    "get": {"responses": {"200": {"description": "ok"}}},
+                },
+            },
+        }));
+        let right = spec(serde_json::json!({
+            "paths": {
+                "/pets/{id}": {
+                    "servers": [{"url": "https://v2.example.com"}],
+                    "parameters": [
+                        {"in": "path", "name": "id", "required": true, "schema": {"type": "string"}},
+                        {"in": "header", "name": "X-Trace", "schema": {"type": "string"}},
+                    ],
+                    "delete": {
+                        "parameters": [{"in": "header", "name": "X-Trace", "required": true, "schema": {"type": "string"}}],
+                        "responses": {"204": {"description": "ok"}},
+                    },
+                },
+            },
+        }));
+        left.merge(right, MergeOptions::default()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&left.paths).unwrap(),
+            serde_json::json!({
+                "/pets/{id}": {
+                    "parameters": [{"in": "path", "name": "id", "required": true, "schema": {"type": "integer"}}],
+                    "get": {"responses": {"200": {"description": "ok"}}},
+                    "delete": {
+                        "servers": [{"url": "https://v2.example.com"}],
+                        "parameters": [
+                            {"in": "path", "name": "id", "required": true, "schema": {"type": "string"}},
+                            {"in": "header", "name": "X-Trace", "required": true, "schema": {"type": "string"}},
+                        ],
+                        "responses": {"204": {"description": "ok"}},
+                    },
+                },
+            }),
+            "the right path-level fields are moved into the right operations",
+        );
+    }
+
+    #[test]
+    fn test_merge_collisions() {
+        let left = spec(serde_json::json!({
+            "paths": {
+                "/pets/{id}": {"get": {"responses": {"200": {"description": "ok"}}}},
+            },
+            "components": {"schemas": {"Pet": {"type": "object"}, "BarPet": {"type": "integer"}}},
+        }));
+        let right = spec(serde_json::json!({
+            "paths": {
+                "/pets/{petId}": {"delete": {"responses": {"204": {"description": "ok"}}}},
+            },
+        }));
+        let mut merged = left.clone();
+        assert_eq!(
+            merged
+                .merge(right.clone(), MergeOptions::default())
+                .unwrap_err()
+                .to_string(),
+            "#.paths[/pets/{petId}]: conflicting definitions",
+            "equivalent paths",
+        );
+        merged
+            .merge(
+                right,
+                MergeOptions {
+                    strategy: MergeStrategy::PreferLeft,
+                },
+            )
+            .unwrap();
+        assert_eq!(merged, left, "prefer left");
+
+        let right = spec(serde_json::json!({
+            "components": {"schemas": {"Pet": {"type": "string"}}},
+        }));
+        assert_eq!(
+            merged
+                .merge(
+                    right,
+                    MergeOptions {
+                        strategy: MergeStrategy::RenameWithPrefix("Bar".to_owned()),
+                    },
+                )
+                .unwrap_err()
+                .to_string(),
+            "#/components/schemas/BarPet: conflicting definitions",
+            "prefixed name is taken",
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! Example generation
+//!
+//! Synthesizes the example values from the schemas
+//! and picks the example responses of the operations for the documentation renderers.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets": {
+//!             "get": {
+//!                 "responses": {
+//!                     "200": {
+//!                         "description": "pets",
+//!                         "content": {
+//!                             "application/json": {
+//!                                 "schema": {
+//!                                     "type": "array",
+//!                                     "items": {"$ref": "#/components/schemas/Pet"},
+//!                                 },
+//!                             },
+//!                         },
+//!                     },
+//!                 },
+//!             },
+//!         },
+//!     },
+//!     "components": {
+//!         "schemas": {
+//!             "Pet": {
+//!                 "type": "object",
+//!                 "properties": {
+//!                     "id": {"type": "integer", "minimum": 1},
+//!                     "name": {"type": "string", "example": "Rex"},
+//!                 },
+//!             },
+//!         },
+//!     },
+//! })).unwrap();
+//!
+//! let operation = spec.paths["/pets"].get().unwrap();
+//! assert_eq!(
+//!     operation.example_response(&spec, "200", "application/json"),
+//!     Some(serde_json::json!([{"id": 1, "name": "Rex"}])),
+//! );
+//! ```
+
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use crate::common::bool_or::BoolOr;
+use crate::common::formats::StringFormat;
+use crate::common::reference::{RefOr, ResolveReference};
+use crate::v3_0::media_type::MediaType;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::schema::{Schema, SingleSchema};
+use crate::v3_0::spec::Spec;
+
+impl Schema {
+    /// Synthesizes an example value of the schema.
+    ///
+    /// Uses the `example`, the `default` or the first `enum` value when they are declared,
+    /// otherwise builds a value from the type, the format and the bounds:
+    /// the numbers honor `minimum`, `maximum`, their exclusive variants and `multipleOf`,
+    /// the strings honor `minLength` and `maxLength`, the arrays honor `minItems`.
+    /// The `pattern` keyword is not supported, so the generated string may not match it.
+    /// `allOf` merges the objects of all the schemas, `anyOf` and `oneOf` use the first schema.
+    /// A recursive reference produces `null`.
+    pub fn example_value(&self, spec: &Spec) -> Value {
+        example(self, spec, &mut HashSet::new())
+    }
+}
+
+impl MediaType {
+    /// Returns the declared `example`, the value of the first `examples` entry
+    /// or an example synthesized from the schema.
+    pub fn example_value(&self, spec: &Spec) -> Option<Value> {
+        if let Some(example) = &self.example {
+            return Some(example.clone());
+        }
+        let declared = self
+            .examples
+            .iter()
+            .flatten()
+            .filter_map(|(_, x)| x.get_item(spec).ok())
+            .find_map(|x| x.value.clone());
+        if declared.is_some() {
+            return declared;
+        }
+        let mut visited = HashSet::new();
+        let schema = match self.schema.as_ref()? {
+            RefOr::Item(schema) => schema,
+            RefOr::Ref(r) => {
+                visited.insert(r.reference.as_str());
+                ResolveReference::<Schema>::resolve_reference(spec, &r.reference)?
+            }
+        };
+        Some(example(schema, spec, &mut visited))
+    }
+}
+
+impl Operation {
+    /// Returns an example body of the response for the status code and the media type.
+    ///
+    /// The response is looked up by the exact status code, then by the range like `2XX`
+    /// and then the `default` response is used,
+    /// the media type is matched by [Response::best_match](crate::v3_0::response::Response::best_match).
+    /// Returns `None` if the response or the content is not found or cannot be resolved.
+    pub fn example_response(&self, spec: &Spec, status: &str, media_type: &str) -> Option<Value> {
+        let response = self.responses.get(status)?.get_item(spec).ok()?;
+        let (_, content) = response.best_match(media_type)?;
+        content.example_value(spec)
+    }
+}
+
+fn example<'a>(schema: &'a Schema, spec: &'a Spec, visited: &mut HashSet<&'a str>) -> Value {
+    match schema {
+        Schema::AllOf(s) => {
+            let mut merged = Map::new();
+            for schema in s.all_of.iter() {
+                match reference(schema, spec, visited) {
+                    Value::Object(o) => merged.extend(o),
+                    value if merged.is_empty() => return value,
+                    _ => {}
+                }
+            }
+            Value::Object(merged)
+        }
+        Schema::AnyOf(s) => s
+            .any_of
+            .first()
+            .map_or(Value::Null, |x| reference(x, spec, visited)),
+        Schema::OneOf(s) => s
+            .one_of
+            .first()
+            .map_or(Value::Null, |x| reference(x, spec, visited)),
+        Schema::Not(_) => Value::Null,
+        Schema::Single(s) => single(s, spec, visited),
+    }
+}
+
+fn reference<'a>(
+    schema: &'a RefOr<Box<Schema>>,
+    spec: &'a Spec,
+    visited: &mut HashSet<&'a str>,
+) -> Value {
+    match schema {
+        RefOr::Item(schema) => example(schema, spec, visited),
+        RefOr::Ref(r) => {
+            if !visited.insert(r.reference.as_str()) {
+                return Value::Null;
+            }
+            let value = match ResolveReference::<Schema>::resolve_reference(spec, &r.reference) {
+                Some(schema) => example(schema, spec, visited),
+                None => Value::Null,
+            };
+            visited.remove(r.reference.as_str());
+            value
+        }
+    }
+}
+
+fn single<'a>(schema: &'a SingleSchema, spec: &'a Spec, visited: &mut HashSet<&'a str>) -> Value {
+    match schema {
+        SingleSchema::String(s) => s
+            .example
+            .clone()
+            .or_else(|| s.default.clone().map(Value::String))
+            .or_else(|| {
+                s.enum_values
+                    .iter()
+                    .flatten()
+                    .next()
+                    .cloned()
+                    .map(Value::String)
+            })
+            .unwrap_or_else(|| {
+                let value = match &s.format {
+                    Some(StringFormat::Date) => "2024-01-01",
+                    Some(StringFormat::DateTime) => "2024-01-01T00:00:00Z",
+                    Some(StringFormat::UUID) => "00000000-0000-0000-0000-000000000000",
+                    Some(StringFormat::Byte) => "",
+                    Some(StringFormat::Email) => "user@example.com",
+                    Some(StringFormat::URI) => "https://example.com",
+                    _ => "string",
+                };
+                let length = value.chars().count() as u64;
+                match (s.min_length, s.max_length) {
+                    (Some(min), _) if length < min => Value::String("x".repeat(min as usize)),
+                    (_, Some(max)) if length > max => {
+                        Value::String(value.chars().take(max as usize).collect())
+                    }
+                    _ => Value::String(value.to_owned()),
+                }
+            }),
+        SingleSchema::Integer(s) => s
+            .example
+            .clone()
+            .or_else(|| s.default.map(Value::from))
+            .or_else(|| {
+                s.enum_values
+                    .iter()
+                    .flatten()
+                    .next()
+                    .copied()
+                    .map(Value::from)
+            })
+            .unwrap_or_else(|| {
+                // the integers are the multiples of one, a fractional `multipleOf` is ignored
+                let step = s.multiple_of.filter(|x| x.fract() == 0.0).unwrap_or(1.0);
+                let value = bounded(
+                    (
+                        s.minimum.as_ref().and_then(|x| x.as_f64()),
+                        s.exclusive_minimum,
+                    ),
+                    (
+                        s.maximum.as_ref().and_then(|x| x.as_f64()),
+                        s.exclusive_maximum,
+                    ),
+                    Some(step),
+                );
+                Value::from(value as i64)
+            }),
+        SingleSchema::Number(s) => s
+            .example
+            .clone()
+            .or_else(|| s.default.clone().map(Value::Number))
+            .or_else(|| {
+                s.enum_values
+                    .iter()
+                    .flatten()
+                    .next()
+                    .cloned()
+                    .map(Value::Number)
+            })
+            .unwrap_or_else(|| {
+                Value::from(bounded(
+                    (s.minimum, s.exclusive_minimum),
+                    (s.maximum, s.exclusive_maximum),
+                    s.multiple_of,
+                ))
+            }),
+        SingleSchema::Boolean(s) => s
+            .example
+            .clone()
+            .or_else(|| s.default.map(Value::Bool))
+            .unwrap_or(Value::Bool(false)),
+        SingleSchema::Array(s) => s
+            .example
+            .clone()
+            .or_else(|| s.default.clone().map(Value::Array))
+            .unwrap_or_else(|| match &s.items {
+                Some(items) => {
+                    let count = s.min_items.unwrap_or(1).max(1) as usize;
+                    let item = reference(items, spec, visited);
+                    Value::Array(vec![item; count])
+                }
+                None => Value::Array(Vec::new()),
+            }),
+        SingleSchema::Object(s) => s
+            .example
+            .clone()
+            .or_else(|| {
+                s.default
+                    .clone()
+                    .map(|x| Value::Object(x.into_iter().collect()))
+            })
+            .unwrap_or_else(|| {
+                let mut map = Map::new();
+                for (name, schema) in s.properties.iter().flatten() {
+                    map.insert(name.clone(), reference(schema, spec, visited));
+                }
+                if map.is_empty() {
+                    if let Some(BoolOr::Item(schema)) = &s.additional_properties {
+                        map.insert("key".to_owned(), reference(schema, spec, visited));
+                    }
+                }
+                Value::Object(map)
+            }),
+        SingleSchema::Null(s) => s.example.clone().unwrap_or(Value::Null),
+    }
+}
+
+/// Returns a number within the bounds, given as the limit and whether it is exclusive,
+/// and a multiple of the step, if any.
+/// The number closest to the minimum is preferred, then zero, then the number closest to the maximum.
+fn bounded(
+    min: (Option<f64>, Option<bool>),
+    max: (Option<f64>, Option<bool>),
+    step: Option<f64>,
+) -> f64 {
+    let above = |x: f64| match min {
+        (Some(min), Some(true)) => x > min,
+        (Some(min), _) => x >= min,
+        (None, _) => true,
+    };
+    let below = |x: f64| match max {
+        (Some(max), Some(true)) => x < max,
+        (Some(max), _) => x <= max,
+        (None, _) => true,
+    };
+    match (step.filter(|x| *x > 0.0), min.0) {
+        (Some(step), Some(min)) => {
+            let value = (min / step).ceil() * step;
+            if above(value) {
+                value
+            } else {
+                value + step
+            }
+        }
+        (Some(step), None) => match max.0 {
+            _ if below(0.0) => 0.0,
+            Some(max) => {
+                let value = (max / step).floor() * step;
+                if below(value) {
+                    value
+                } else {
+                    value - step
+                }
+            }
+            None => 0.0,
+        },
+        (None, Some(value)) if above(value) => value,
+        (None, Some(value)) => match max.0 {
+            Some(max) if !below(value + 1.0) => (value + max) / 2.0,
+            _ => value + 1.0,
+        },
+        (None, None) => match max.0 {
+            _ if below(0.0) => 0.0,
+            Some(max) if below(max) => max,
+            Some(max) => max - 1.0,
+            None => 0.0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": {"$ref": "#/components/responses/Pet"},
+                            "404": {
+                                "description": "not found",
+                                "content": {
+                                    "application/json": {
+                                        "examples": {
+                                            "missing": {"value": {"message": "not found"}},
+                                        },
+                                    },
+                                },
+                            },
+                            "default": {
+                                "description": "error",
+                                "content": {
+                                    "*/*": {"example": "error"},
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "responses": {
+                    "Pet": {
+                        "description": "pet",
+                        "content": {
+                            "application/json": {
+                                "schema": {"$ref": "#/components/schemas/Pet"},
+                            },
+                        },
+                    },
+                },
+                "schemas": {
+                    "Pet": {
+                        "allOf": [
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "id": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                                    "born": {"type": "string", "format": "date"},
+                                    "kind": {"type": "string", "enum": ["cat", "dog"]},
+                                },
+                            },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "tags": {"type": "array", "items": {"type": "string"}, "minItems": 2},
+                                    "parent": {"$ref": "#/components/schemas/Pet"},
+                                    "vaccinated": {"type": "boolean", "default": true},
+                                },
+                            },
+                        ],
+                    },
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_example_response() {
+        let spec = spec();
+        let operation = spec.paths["/pets/{id}"].get().unwrap();
+        assert_eq!(
+            operation.example_response(&spec, "200", "application/json"),
+            Some(json!({
+                "id": 1,
+                "born": "2024-01-01",
+                "kind": "cat",
+                "tags": ["string", "string"],
+                "parent": null,
+                "vaccinated": true,
+            })),
+            "synthesized from the schema",
+        );
+        assert_eq!(
+            operation.example_response(&spec, "404", "application/json"),
+            Some(json!({"message": "not found"})),
+            "declared examples",
+        );
+        assert_eq!(
+            operation.example_response(&spec, "500", "text/plain"),
+            Some(json!("error")),
+            "default response",
+        );
+        assert_eq!(
+            operation.example_response(&spec, "200", "text/plain"),
+            None,
+            "unknown media type",
+        );
+    }
+
+    #[test]
+    fn test_example_bounds() {
+        let spec = spec();
+        for (schema, expected) in [
+            (
+                json!({"type": "integer", "minimum": 1, "multipleOf": 5}),
+                json!(5),
+            ),
+            (
+                json!({"type": "integer", "maximum": -1, "exclusiveMaximum": true}),
+                json!(-2),
+            ),
+            (
+                json!({"type": "integer", "maximum": -3, "multipleOf": 2}),
+                json!(-4),
+            ),
+            (
+                json!({"type": "number", "minimum": 0, "exclusiveMinimum": true, "maximum": 1, "exclusiveMaximum": true}),
+                json!(0.5),
+            ),
+            (
+                json!({"type": "number", "minimum": 1, "exclusiveMinimum": true, "multipleOf": 0.5}),
+                json!(1.5),
+            ),
+            (json!({"type": "number", "maximum": 10}), json!(0.0)),
+            (
+                json!({"type": "string", "format": "email", "maxLength": 4}),
+                json!("user"),
+            ),
+            (json!({"type": "string", "minLength": 8}), json!("xxxxxxxx")),
+        ] {
+            let value = serde_json::from_value::<Schema>(schema.clone())
+                .unwrap()
+                .example_value(&spec);
+            assert_eq!(value, expected, "{}", schema);
+        }
+    }
+}
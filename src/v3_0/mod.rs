@@ -1,23 +1,42 @@
 //! Implementation of OpenAPI v3.0.X Specification
 //!
 //! Full specification can be found [here](https://spec.openapis.org/oas/v3.0.3).
+pub mod borrowed;
 pub mod callback;
 pub mod components;
+pub mod dedupe;
+pub mod diff;
 pub mod discriminator;
 pub mod example;
 pub mod external_documentation;
+pub mod flatten;
 pub mod header;
 pub mod info;
+#[cfg(feature = "schema_validation")]
+pub mod instance;
+#[cfg(feature = "lazy_schemas")]
+pub mod lazy;
 pub mod link;
 pub mod media_type;
+pub mod merge;
+pub mod mock;
+pub mod normalize;
 pub mod operation;
 pub mod parameter;
 pub mod path_item;
+pub mod rename;
+#[cfg(feature = "schema_validation")]
+pub mod request;
 pub mod request_body;
 pub mod response;
+pub mod router;
 pub mod schema;
 pub mod security_scheme;
 pub mod server;
 pub mod spec;
+pub mod style;
 pub mod tag;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod visit;
 pub mod xml;
@@ -0,0 +1,213 @@
+//! Normalization of the v3.0 documents, see [crate::common::normalize] for details.
+
+use std::collections::BTreeMap;
+
+use crate::common::extensions::Extensions;
+use crate::common::http::Method;
+use crate::common::normalize::{
+    drop_empty, lowercase_methods, normalize_paths, NormalizeError, NormalizeOptions,
+};
+use crate::common::reference::RefOr;
+use crate::common::visit::{VisitMut, WalkMut};
+use crate::v3_0::components::Components;
+use crate::v3_0::header::Header;
+use crate::v3_0::media_type::MediaType;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::parameter::Parameter;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::response::Response;
+use crate::v3_0::schema::{Schema, SingleSchema};
+use crate::v3_0::spec::Spec;
+
+impl Spec {
+    /// Rewrites the specification into the canonical form,
+    /// the specification is not changed if the normalization fails.
+    pub fn normalize(&mut self, options: NormalizeOptions) -> Result<(), NormalizeError> {
+        normalize_paths(&mut self.paths, &options.trailing_slash)?;
+        self.walk_mut(&mut Normalizer);
+        drop_empty(&mut self.servers);
+        drop_empty(&mut self.tags);
+        if let Some(components) = &mut self.components {
+            drop_empty(&mut components.schemas);
+            drop_empty(&mut components.responses);
+            drop_empty(&mut components.parameters);
+            drop_empty(&mut components.examples);
+            drop_empty(&mut components.request_bodies);
+            drop_empty(&mut components.headers);
+            drop_empty(&mut components.security_schemes);
+            drop_empty(&mut components.links);
+            drop_empty(&mut components.callbacks);
+            if *components == Components::default() {
+                self.components = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Normalizer;
+
+impl VisitMut<Spec> for Normalizer {
+    fn visit_path_item_mut(&mut self, _path: &str, path_item: &mut PathItem) {
+        lowercase_methods(&mut path_item.operations);
+        drop_empty(&mut path_item.operations);
+        drop_empty(&mut path_item.parameters);
+        drop_empty(&mut path_item.servers);
+    }
+
+    fn visit_operation_mut(&mut self, _path: &str, _method: &Method, operation: &mut Operation) {
+        drop_empty(&mut operation.tags);
+        drop_empty(&mut operation.parameters);
+        drop_empty(&mut operation.callbacks);
+        drop_empty(&mut operation.servers);
+        if let Some(RefOr::Item(request_body)) = &mut operation.request_body {
+            for media_type in request_body.content.values_mut() {
+                normalize_media_type(media_type);
+            }
+        }
+        drop_empty(&mut operation.responses.responses);
+    }
+
+    fn visit_parameter_mut(&mut self, _path: &str, parameter: &mut Parameter) {
+        let (examples, content) = match parameter {
+            Parameter::Path(p) => (&mut p.examples, &mut p.content),
+            Parameter::Query(p) => (&mut p.examples, &mut p.content),
+            Parameter::Header(p) => (&mut p.examples, &mut p.content),
+            Parameter::Cookie(p) => (&mut p.examples, &mut p.content),
+        };
+        drop_empty(examples);
+        normalize_content(content);
+    }
+
+    fn visit_response_mut(&mut self, _path: &str, response: &mut Response) {
+        for header in response.headers.iter_mut().flatten().map(|(_, x)| x) {
+            if let RefOr::Item(header) = header {
+                normalize_header(header);
+            }
+        }
+        drop_empty(&mut response.headers);
+        normalize_content(&mut response.content);
+        drop_empty(&mut response.links);
+    }
+
+    fn visit_schema_mut(&mut self, _path: &str, schema: &mut Schema) {
+        if let Schema::Single(SingleSchema::Object(s)) = schema {
+            drop_empty(&mut s.properties);
+            drop_empty(&mut s.required);
+        }
+    }
+
+    fn visit_extensions_mut(&mut self, _path: &str, extensions: &mut Option<Extensions>) {
+        drop_empty(extensions);
+    }
+}
+
+fn normalize_content(content: &mut Option<BTreeMap<String, MediaType>>) {
+    for media_type in content.iter_mut().flat_map(|x| x.values_mut()) {
+        normalize_media_type(media_type);
+    }
+    drop_empty(content);
+}
+
+fn normalize_media_type(media_type: &mut MediaType) {
+    drop_empty(&mut media_type.examples);
+    for encoding in media_type.encoding.iter_mut().flat_map(|x| x.values_mut()) {
+        drop_empty(&mut encoding.headers);
+    }
+    drop_empty(&mut media_type.encoding);
+}
+
+fn normalize_header(header: &mut Header) {
+    drop_empty(&mut header.examples);
+    normalize_content(&mut header.content);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::normalize::TrailingSlash;
+
+    #[test]
+    fn test_normalize() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "servers": [],
+            "security": [],
+            "paths": {
+                "/pets": {
+                    "parameters": [],
+                    "get": {
+                        "tags": [],
+                        "security": [],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "headers": {"X-Rate": {"examples": {}, "schema": {"type": "integer"}}},
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"type": "object", "properties": {}, "required": []},
+                                        "encoding": {},
+                                    },
+                                },
+                                "links": {},
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {"schemas": {}, "responses": {}},
+        }))
+        .unwrap();
+        spec.normalize(NormalizeOptions {
+            trailing_slash: TrailingSlash::Add,
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap(),
+            json!({
+                "openapi": "3.0.3",
+                "info": {"title": "foo", "version": "1"},
+                "security": [],
+                "paths": {
+                    "/pets/": {
+                        "get": {
+                            "security": [],
+                            "responses": {
+                                "200": {
+                                    "description": "pets",
+                                    "headers": {"X-Rate": {"schema": {"type": "integer"}}},
+                                    "content": {
+                                        "application/json": {"schema": {"type": "object"}},
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            }),
+        );
+
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "tags": [],
+            "paths": {"/pets": {"get": {"responses": {}}}, "/pets/": {"post": {"responses": {}}}},
+        }))
+        .unwrap();
+        let original = spec.clone();
+        assert_eq!(
+            spec.normalize(NormalizeOptions {
+                trailing_slash: TrailingSlash::Add,
+            }),
+            Err(NormalizeError::PathCollision {
+                path: "/pets".to_owned(),
+                other: "/pets/".to_owned(),
+                normalized: "/pets/".to_owned(),
+            }),
+        );
+        assert_eq!(spec, original, "not changed");
+    }
+}
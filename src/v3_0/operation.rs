@@ -4,13 +4,18 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, PushError, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v3_0::callback::Callback;
 use crate::v3_0::external_documentation::ExternalDocumentation;
 use crate::v3_0::parameter::Parameter;
 use crate::v3_0::request_body::RequestBody;
 use crate::v3_0::response::Responses;
+use crate::v3_0::schema::Schema;
 use crate::v3_0::security_scheme::SecurityScheme;
 use crate::v3_0::server::Server;
 use crate::v3_0::spec::Spec;
@@ -102,16 +107,24 @@ pub struct Operation {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Operation {
+    /// Sets the request body with the `application/json` media type described by the schema.
+    pub fn set_json_body(&mut self, schema: RefOr<Schema>) {
+        self.request_body = Some(RefOr::new_item(RequestBody::json(schema)));
+    }
 }
 
 impl ValidateWithContext<Spec> for Operation {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         // do not validate operation_id, it is already validated in PathItem
 
         if let Some(tags) = &self.tags {
             for (i, tag) in tags.iter().enumerate() {
-                let path = format!("{}.tags[{}]", path, i);
+                let path = path.field("tags").index(i);
                 validate_required_string(tag, ctx, path.clone());
                 if tag.is_empty() {
                     continue;
@@ -119,7 +132,7 @@ impl ValidateWithContext<Spec> for Operation {
                 let reference = format!("#/tags/{}", tag);
                 if let Ok(spec_tag) = RefOr::<Tag>::new_ref(reference.clone()).get_item(ctx.spec) {
                     if ctx.visit(reference.clone()) {
-                        spec_tag.validate_with_context(ctx, reference);
+                        spec_tag.validate_with_context(ctx, reference.into());
                     }
                 } else if !ctx.is_option(Options::IgnoreMissingTags) {
                     ctx.error(
@@ -132,37 +145,37 @@ impl ValidateWithContext<Spec> for Operation {
 
         if let Some(parameters) = &self.parameters {
             for (i, parameter) in parameters.clone().iter().enumerate() {
-                parameter.validate_with_context(ctx, format!("{}.parameters[{}]", path, i));
+                parameter.validate_with_context(ctx, path.field("parameters").index(i));
             }
         }
 
         if let Some(request_body) = &self.request_body {
-            request_body.validate_with_context(ctx, format!("{}.requestBody", path));
+            request_body.validate_with_context(ctx, path.field("requestBody"));
         }
 
         if let Some(servers) = &self.servers {
             for (i, server) in servers.iter().enumerate() {
-                server.validate_with_context(ctx, format!("{}.servers[{}]", path, i));
+                server.validate_with_context(ctx, path.field("servers").index(i));
             }
         }
 
         if let Some(callbacks) = &self.callbacks {
             for (k, v) in callbacks {
-                v.validate_with_context(ctx, format!("{}.callbacks[{}]", path, k));
+                v.validate_with_context(ctx, path.field("callbacks").item(k));
             }
         }
 
         self.responses
-            .validate_with_context(ctx, format!("{}.responses", path));
+            .validate_with_context(ctx, path.field("responses"));
 
         if let Some(external_doc) = &self.external_docs {
-            external_doc.validate_with_context(ctx, format!("{}.externalDocs", path));
+            external_doc.validate_with_context(ctx, path.field("externalDocs"));
         }
 
         if let Some(security) = &self.security {
             for (i, security) in security.iter().enumerate() {
                 for (name, scopes) in security {
-                    let path = format!("{}.security[{}][{}]", path, i, name);
+                    let path = path.field("security").index(i).item(name);
                     let reference = format!("#/components/securitySchemes/{}", name);
                     let spec_ref = RefOr::<SecurityScheme>::new_ref(reference.clone());
                     spec_ref.validate_with_context(ctx, path.clone());
@@ -170,26 +183,7 @@ impl ValidateWithContext<Spec> for Operation {
                         if let Ok(SecurityScheme::OAuth2(oauth2)) = spec_ref.get_item(ctx.spec) {
                             for scope in scopes {
                                 ctx.visit(format!("{}/{}", reference, scope));
-                                let mut found = false;
-                                if let Some(flow) = &oauth2.flows.implicit {
-                                    found = found || flow.scopes.contains_key(scope)
-                                }
-                                if !found {
-                                    if let Some(flow) = &oauth2.flows.password {
-                                        found = found || flow.scopes.contains_key(scope)
-                                    }
-                                }
-                                if !found {
-                                    if let Some(flow) = &oauth2.flows.client_credentials {
-                                        found = found || flow.scopes.contains_key(scope)
-                                    }
-                                }
-                                if !found {
-                                    if let Some(flow) = &oauth2.flows.authorization_code {
-                                        found = found || flow.scopes.contains_key(scope)
-                                    }
-                                }
-                                if !found {
+                                if !oauth2.flows.has_scope(scope) {
                                     ctx.error(
                                         path.clone(),
                                         format_args!(
@@ -4,12 +4,17 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, PushError, ValidateWithContext,
+};
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v3_0::example::Example;
 use crate::v3_0::media_type::MediaType;
 use crate::v3_0::schema::Schema;
 use crate::v3_0::spec::Spec;
+use crate::v3_0::style::{encode_value, parse_value, Style};
 
 /// Describes a single operation parameter.
 ///
@@ -109,7 +114,7 @@ pub struct InPath {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Holds the style information for a parameter with `in: path` property.
@@ -218,7 +223,7 @@ pub struct InQuery {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Holds the style information for a parameter with `in: query` property.
@@ -316,7 +321,7 @@ pub struct InHeader {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Holds the style information for a parameter with `in: header` property.
@@ -399,7 +404,7 @@ pub struct InCookie {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Holds the style information for a parameter with `in: cookie` property.
@@ -412,8 +417,109 @@ pub enum InCookieStyle {
     Form,
 }
 
+impl Parameter {
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &str {
+        match self {
+            Parameter::Path(p) => &p.name,
+            Parameter::Query(p) => &p.name,
+            Parameter::Header(p) => &p.name,
+            Parameter::Cookie(p) => &p.name,
+        }
+    }
+
+    /// Returns the location of the parameter, the value of the `in` field.
+    pub fn location(&self) -> &str {
+        match self {
+            Parameter::Path(_) => "path",
+            Parameter::Query(_) => "query",
+            Parameter::Header(_) => "header",
+            Parameter::Cookie(_) => "cookie",
+        }
+    }
+
+    /// Returns `true` if the parameter is mandatory.
+    pub fn is_required(&self) -> bool {
+        match self {
+            Parameter::Path(p) => p.required,
+            Parameter::Query(p) => p.required.unwrap_or(false),
+            Parameter::Header(p) => p.required.unwrap_or(false),
+            Parameter::Cookie(p) => p.required.unwrap_or(false),
+        }
+    }
+
+    /// Returns the schema of the parameter.
+    pub fn schema(&self) -> Option<&RefOr<Schema>> {
+        match self {
+            Parameter::Path(p) => p.schema.as_ref(),
+            Parameter::Query(p) => p.schema.as_ref(),
+            Parameter::Header(p) => p.schema.as_ref(),
+            Parameter::Cookie(p) => p.schema.as_ref(),
+        }
+    }
+
+    /// Returns the specification extensions of the parameter.
+    pub fn extensions(&self) -> Option<&Extensions> {
+        match self {
+            Parameter::Path(p) => p.extensions.as_ref(),
+            Parameter::Query(p) => p.extensions.as_ref(),
+            Parameter::Header(p) => p.extensions.as_ref(),
+            Parameter::Cookie(p) => p.extensions.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the specification extensions of the parameter.
+    pub fn extensions_mut(&mut self) -> &mut Option<Extensions> {
+        match self {
+            Parameter::Path(p) => &mut p.extensions,
+            Parameter::Query(p) => &mut p.extensions,
+            Parameter::Header(p) => &mut p.extensions,
+            Parameter::Cookie(p) => &mut p.extensions,
+        }
+    }
+
+    /// Returns the serialization style of the parameter, the default depends on the location.
+    pub fn style(&self) -> Style {
+        match self {
+            Parameter::Path(p) => p.style.as_ref().map_or(Style::Simple, Style::from),
+            Parameter::Query(p) => p.style.as_ref().map_or(Style::Form, Style::from),
+            Parameter::Header(p) => p.style.as_ref().map_or(Style::Simple, Style::from),
+            Parameter::Cookie(p) => p.style.as_ref().map_or(Style::Form, Style::from),
+        }
+    }
+
+    /// Returns the value of the `explode` field, the default depends on the style.
+    pub fn explode(&self) -> bool {
+        let explode = match self {
+            Parameter::Path(p) => p.explode,
+            Parameter::Query(p) => p.explode,
+            Parameter::Header(p) => p.explode,
+            Parameter::Cookie(p) => p.explode,
+        };
+        explode.unwrap_or_else(|| self.style().default_explode())
+    }
+
+    /// Parses the raw value of the parameter according to its style and schema,
+    /// see [parse_value](crate::v3_0::style::parse_value) for details.
+    pub fn parse_value(&self, raw: &str) -> serde_json::Value {
+        parse_value(
+            &self.style(),
+            self.explode(),
+            self.name(),
+            raw,
+            self.schema(),
+        )
+    }
+
+    /// Serializes the value of the parameter according to its style,
+    /// see [encode_value](crate::v3_0::style::encode_value) for details.
+    pub fn encode_value(&self, value: &serde_json::Value) -> String {
+        encode_value(&self.style(), self.explode(), self.name(), value)
+    }
+}
+
 impl ValidateWithContext<Spec> for Parameter {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             Parameter::Path(p) => p.validate_with_context(ctx, path),
             Parameter::Query(p) => p.validate_with_context(ctx, path),
@@ -424,8 +530,9 @@ impl ValidateWithContext<Spec> for Parameter {
 }
 
 impl ValidateWithContext<Spec> for InPath {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
         must_be_required(&Some(self.required), ctx, path.clone(), self.name.clone());
         either_example_or_examples(ctx, &self.example, &self.examples, path.clone());
         either_schema_or_content(ctx, &self.schema, &self.content, path.clone());
@@ -433,30 +540,33 @@ impl ValidateWithContext<Spec> for InPath {
 }
 
 impl ValidateWithContext<Spec> for InQuery {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
         either_example_or_examples(ctx, &self.example, &self.examples, path.clone());
         either_schema_or_content(ctx, &self.schema, &self.content, path.clone());
     }
 }
 
 impl ValidateWithContext<Spec> for InHeader {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
         either_example_or_examples(ctx, &self.example, &self.examples, path.clone());
         either_schema_or_content(ctx, &self.schema, &self.content, path.clone());
     }
 }
 
 impl ValidateWithContext<Spec> for InCookie {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
         either_example_or_examples(ctx, &self.example, &self.examples, path.clone());
         either_schema_or_content(ctx, &self.schema, &self.content, path.clone());
     }
 }
 
-fn must_be_required(p: &Option<bool>, ctx: &mut Context<Spec>, path: String, name: String) {
+fn must_be_required(p: &Option<bool>, ctx: &mut Context<Spec>, path: Path, name: String) {
     if !p.is_some_and(|x| x) {
         ctx.error(path, format_args!(".{}: must be required", name));
     }
@@ -466,7 +576,7 @@ fn either_example_or_examples(
     ctx: &mut Context<Spec>,
     example: &Option<serde_json::Value>,
     examples: &Option<BTreeMap<String, RefOr<Example>>>,
-    path: String,
+    path: Path,
 ) {
     if example.is_some() && examples.is_some() {
         ctx.error(path, "example and examples are mutually exclusive");
@@ -477,9 +587,124 @@ fn either_schema_or_content(
     ctx: &mut Context<Spec>,
     schema: &Option<RefOr<Schema>>,
     content: &Option<BTreeMap<String, MediaType>>,
-    path: String,
+    path: Path,
 ) {
-    if schema.is_some() && content.is_some() {
-        ctx.error(path, "schema and content are mutually exclusive");
+    match (schema, content) {
+        (Some(_), Some(_)) => ctx.error(path, "schema and content are mutually exclusive"),
+        (None, None) => ctx.error(path, "either schema or content is required"),
+        (None, Some(content)) if content.len() != 1 => ctx.error(
+            path,
+            format_args!(
+                ".content: must contain exactly one media type, found {}",
+                content.len()
+            ),
+        ),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parameter_value() {
+        for (parameter, raw, value) in [
+            (
+                json!({"in": "path", "name": "id", "required": true, "schema": {"type": "integer"}}),
+                "5",
+                json!(5),
+            ),
+            (
+                json!({
+                    "in": "query",
+                    "name": "tag",
+                    "schema": {"type": "array", "items": {"type": "string"}},
+                }),
+                "tag=a&tag=b",
+                json!(["a", "b"]),
+            ),
+            (
+                json!({
+                    "in": "query",
+                    "name": "tag",
+                    "style": "pipeDelimited",
+                    "explode": false,
+                    "schema": {"type": "array", "items": {"type": "string"}},
+                }),
+                "a|b",
+                json!(["a", "b"]),
+            ),
+            (
+                json!({
+                    "in": "path",
+                    "name": "point",
+                    "required": true,
+                    "style": "label",
+                    "explode": true,
+                    "schema": {
+                        "type": "object",
+                        "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    },
+                }),
+                ".x=1.y=2",
+                json!({"x": 1, "y": 2}),
+            ),
+        ] {
+            let parameter = serde_json::from_value::<Parameter>(parameter).unwrap();
+            assert_eq!(parameter.parse_value(raw), value, "parse {}", raw);
+            assert_eq!(parameter.encode_value(&value), raw, "encode {}", raw);
+        }
+    }
+
+    #[test]
+    fn test_validate_schema_or_content() {
+        let spec = Spec::default();
+        for (parameter, errors) in [
+            (
+                json!({"in": "query", "name": "id", "schema": {"type": "integer"}}),
+                vec![],
+            ),
+            (
+                json!({
+                    "in": "query",
+                    "name": "id",
+                    "content": {"application/json": {"schema": {"type": "integer"}}},
+                }),
+                vec![],
+            ),
+            (
+                json!({
+                    "in": "header",
+                    "name": "id",
+                    "schema": {"type": "integer"},
+                    "content": {"application/json": {}},
+                }),
+                vec!["parameter: schema and content are mutually exclusive"],
+            ),
+            (
+                json!({"in": "cookie", "name": "id"}),
+                vec!["parameter: either schema or content is required"],
+            ),
+            (
+                json!({"in": "path", "name": "id", "required": true, "content": {}}),
+                vec!["parameter.content: must contain exactly one media type, found 0"],
+            ),
+            (
+                json!({
+                    "in": "query",
+                    "name": "id",
+                    "content": {"application/json": {}, "application/xml": {}},
+                }),
+                vec!["parameter.content: must contain exactly one media type, found 2"],
+            ),
+        ] {
+            let parameter = serde_json::from_value::<Parameter>(parameter).unwrap();
+            let mut ctx = Context::new(&spec, Default::default());
+            parameter.validate_with_context(&mut ctx, "parameter".into());
+            assert_eq!(ctx.errors, errors, "{:?}", parameter);
+        }
     }
 }
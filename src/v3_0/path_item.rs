@@ -1,5 +1,6 @@
 //! Path Items
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt;
 
@@ -7,7 +8,10 @@ use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, Context, ValidateWithContext};
+use crate::common::http::Method;
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
 use crate::v3_0::operation::Operation;
 use crate::v3_0::parameter::Parameter;
@@ -76,7 +80,104 @@ pub struct PathItem {
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl PathItem {
+    /// Returns the operation for the given HTTP method.
+    pub fn operation(&self, method: &Method) -> Option<&Operation> {
+        self.operations
+            .as_ref()
+            .and_then(|x| x.get(method.as_str()))
+    }
+
+    /// Returns the mutable operation for the given HTTP method.
+    pub fn operation_mut(&mut self, method: &Method) -> Option<&mut Operation> {
+        self.operations
+            .as_mut()
+            .and_then(|x| x.get_mut(method.as_str()))
+    }
+
+    /// Inserts the operation for the given HTTP method and returns the previous one.
+    pub fn insert(&mut self, method: Method, operation: Operation) -> Option<Operation> {
+        self.operations
+            .get_or_insert_with(BTreeMap::new)
+            .insert(method.to_string(), operation)
+    }
+
+    /// Returns the entry of the operation for the given HTTP method for in-place manipulation.
+    pub fn entry(&mut self, method: Method) -> Entry<'_, String, Operation> {
+        self.operations
+            .get_or_insert_with(BTreeMap::new)
+            .entry(method.to_string())
+    }
+
+    /// Returns an iterator over the HTTP methods and operations, ordered by the method key.
+    pub fn iter(&self) -> impl Iterator<Item = (Method, &Operation)> {
+        self.operations
+            .iter()
+            .flatten()
+            .map(|(method, operation)| (Method::from(method.as_str()), operation))
+    }
+
+    /// Returns an iterator over the HTTP methods and mutable operations,
+    /// ordered by the method key.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Method, &mut Operation)> {
+        self.operations
+            .iter_mut()
+            .flatten()
+            .map(|(method, operation)| (Method::from(method.as_str()), operation))
+    }
+
+    /// Removes the operation for the given HTTP method and returns it.
+    pub fn remove(&mut self, method: &Method) -> Option<Operation> {
+        let operations = self.operations.as_mut()?;
+        let res = operations.remove(method.as_str());
+        if operations.is_empty() {
+            self.operations = None;
+        }
+        res
+    }
+
+    /// Returns the `GET` operation.
+    pub fn get(&self) -> Option<&Operation> {
+        self.operation(&Method::Get)
+    }
+
+    /// Returns the `PUT` operation.
+    pub fn put(&self) -> Option<&Operation> {
+        self.operation(&Method::Put)
+    }
+
+    /// Returns the `POST` operation.
+    pub fn post(&self) -> Option<&Operation> {
+        self.operation(&Method::Post)
+    }
+
+    /// Returns the `DELETE` operation.
+    pub fn delete(&self) -> Option<&Operation> {
+        self.operation(&Method::Delete)
+    }
+
+    /// Returns the `OPTIONS` operation.
+    pub fn options(&self) -> Option<&Operation> {
+        self.operation(&Method::Options)
+    }
+
+    /// Returns the `HEAD` operation.
+    pub fn head(&self) -> Option<&Operation> {
+        self.operation(&Method::Head)
+    }
+
+    /// Returns the `PATCH` operation.
+    pub fn patch(&self) -> Option<&Operation> {
+        self.operation(&Method::Patch)
+    }
+
+    /// Returns the `TRACE` operation.
+    pub fn trace(&self) -> Option<&Operation> {
+        self.operation(&Method::Trace)
+    }
 }
 
 impl Serialize for PathItem {
@@ -147,7 +248,7 @@ impl<'de> Deserialize<'de> for PathItem {
             {
                 let mut res = PathItem::default();
                 let mut operations: BTreeMap<String, Operation> = BTreeMap::new();
-                let mut extensions: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+                let mut extensions: Extensions = BTreeMap::new();
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "parameters" {
                         if res.parameters.is_some() {
@@ -158,7 +259,7 @@ impl<'de> Deserialize<'de> for PathItem {
                         if res.servers.is_some() {
                             return Err(Error::duplicate_field("servers"));
                         }
-                        res.parameters = Some(map.next_value()?);
+                        res.servers = Some(map.next_value()?);
                     } else if key.starts_with("x-") {
                         if extensions.contains_key(key.clone().as_str()) {
                             return Err(Error::custom(format!("duplicate field '{}'", key)));
@@ -187,23 +288,56 @@ impl<'de> Deserialize<'de> for PathItem {
 }
 
 impl ValidateWithContext<Spec> for PathItem {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(operations) = &self.operations {
             for (method, operation) in operations.iter() {
-                operation.validate_with_context(ctx, format!("{}.{}", path, method));
+                operation.validate_with_context(ctx, path.field(method.to_owned()));
             }
         }
 
         if let Some(servers) = &self.servers {
             for (i, server) in servers.iter().enumerate() {
-                server.validate_with_context(ctx, format!("{}.servers[{}]", path, i));
+                server.validate_with_context(ctx, path.field("servers").index(i));
             }
         }
 
         if let Some(parameters) = &self.parameters {
             for (i, parameter) in parameters.iter().enumerate() {
-                parameter.validate_with_context(ctx, format!("{}.parameters[{}]", path, i));
+                parameter.validate_with_context(ctx, path.field("parameters").index(i));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_item_servers() {
+        let path_item = serde_json::from_value::<PathItem>(serde_json::json!({
+            "servers": [{"url": "https://{base path}.example.com"}],
+        }))
+        .unwrap();
+        assert_eq!(
+            path_item.servers,
+            Some(vec![Server {
+                url: "https://{base path}.example.com".to_owned(),
+                ..Default::default()
+            }]),
+            "deserialize servers",
+        );
+        assert!(path_item.parameters.is_none(), "no parameters");
+
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        path_item.validate_with_context(&mut ctx, "pathItem".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["pathItem.servers[0].url: `base path` is not defined in `variables`"],
+            "validate servers: {:?}",
+            ctx.errors
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Renaming of the components
+//!
+//! Renames a component and rewrites every reference to it,
+//! in the paths, the components and the callbacks.
+//! Renaming a schema also rewrites the discriminator mappings.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::spec::Spec;
+//!
+//! let mut spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {},
+//!     "components": {
+//!         "schemas": {
+//!             "Pet": {"type": "object"},
+//!             "Pets": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}},
+//!         },
+//!     },
+//! })).unwrap();
+//!
+//! assert!(spec.rename_schema("Pet", "Animal"));
+//! assert_eq!(
+//!     serde_json::to_value(spec.components.unwrap().schemas.unwrap()).unwrap(),
+//!     serde_json::json!({
+//!         "Animal": {"type": "object"},
+//!         "Pets": {"type": "array", "items": {"$ref": "#/components/schemas/Animal"}},
+//!     }),
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::common::http::Method;
+use crate::common::reference::Ref;
+use crate::common::visit::{pointer, RenameReference, VisitMut, WalkMut};
+use crate::v3_0::components::Components;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::schema::Schema;
+use crate::v3_0::spec::Spec;
+
+impl Spec {
+    /// Renames the schema and rewrites all references to it,
+    /// including the discriminator mappings.
+    ///
+    /// Returns `false` and leaves the specification unchanged
+    /// if there is no such schema or the new name is already taken.
+    pub fn rename_schema(&mut self, from: &str, to: &str) -> bool {
+        if !self.move_component(from, to, |x| &mut x.schemas) {
+            return false;
+        }
+        self.walk_mut(&mut RenameSchema::new(from, to));
+        true
+    }
+
+    /// Renames the response and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_response(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("responses", from, to, |x| &mut x.responses)
+    }
+
+    /// Renames the parameter and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_parameter(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("parameters", from, to, |x| &mut x.parameters)
+    }
+
+    /// Renames the example and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_example(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("examples", from, to, |x| &mut x.examples)
+    }
+
+    /// Renames the request body and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_request_body(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("requestBodies", from, to, |x| &mut x.request_bodies)
+    }
+
+    /// Renames the header and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_header(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("headers", from, to, |x| &mut x.headers)
+    }
+
+    /// Renames the security scheme, rewrites all references to it
+    /// and renames it in the security requirements of the specification and the operations,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_security_scheme(&mut self, from: &str, to: &str) -> bool {
+        if !self.rename_component("securitySchemes", from, to, |x| &mut x.security_schemes) {
+            return false;
+        }
+        rename_requirements(&mut self.security, from, to);
+        // the walker visits the operations of the callbacks as well
+        self.walk_mut(&mut RenameSecurityScheme { from, to });
+        true
+    }
+
+    /// Renames the link and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_link(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("links", from, to, |x| &mut x.links)
+    }
+
+    /// Renames the callback and rewrites all references to it,
+    /// see [Spec::rename_schema] for details.
+    pub fn rename_callback(&mut self, from: &str, to: &str) -> bool {
+        self.rename_component("callbacks", from, to, |x| &mut x.callbacks)
+    }
+
+    fn rename_component<T>(
+        &mut self,
+        kind: &str,
+        from: &str,
+        to: &str,
+        section: impl FnOnce(&mut Components) -> &mut Option<BTreeMap<String, T>>,
+    ) -> bool {
+        if !self.move_component(from, to, section) {
+            return false;
+        }
+        let kind = pointer("#/components", kind);
+        self.walk_mut(&mut RenameReference {
+            from: pointer(&kind, from),
+            to: pointer(&kind, to),
+        });
+        true
+    }
+
+    /// Moves the component to the new name, without rewriting the references.
+    fn move_component<T>(
+        &mut self,
+        from: &str,
+        to: &str,
+        section: impl FnOnce(&mut Components) -> &mut Option<BTreeMap<String, T>>,
+    ) -> bool {
+        let Some(items) = self.components.as_mut().and_then(|x| section(x).as_mut()) else {
+            return false;
+        };
+        if items.contains_key(to) {
+            return false;
+        }
+        let Some(item) = items.remove(from) else {
+            return false;
+        };
+        items.insert(to.to_owned(), item);
+        true
+    }
+}
+
+/// Rewrites the references to the renamed schema and the discriminator mappings.
+pub(crate) struct RenameSchema<'a> {
+    reference: RenameReference,
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> RenameSchema<'a> {
+    pub(crate) fn new(from: &'a str, to: &'a str) -> Self {
+        RenameSchema {
+            reference: RenameReference {
+                from: pointer("#/components/schemas", from),
+                to: pointer("#/components/schemas", to),
+            },
+            from,
+            to,
+        }
+    }
+}
+
+impl VisitMut<Spec> for RenameSchema<'_> {
+    fn visit_schema_mut(&mut self, _path: &str, schema: &mut Schema) {
+        let (discriminator, members) = match schema {
+            Schema::AllOf(s) => (&mut s.discriminator, &s.all_of),
+            Schema::AnyOf(s) => (&mut s.discriminator, &s.any_of),
+            Schema::OneOf(s) => (&mut s.discriminator, &s.one_of),
+            _ => return,
+        };
+        if let Some(discriminator) = discriminator {
+            discriminator.rename_schema(self.from, self.to, members);
+        }
+    }
+
+    fn visit_reference_mut(&mut self, path: &str, reference: &mut Ref) {
+        VisitMut::<Spec>::visit_reference_mut(&mut self.reference, path, reference);
+    }
+}
+
+/// Renames the security scheme in the security requirements of the operations.
+struct RenameSecurityScheme<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl VisitMut<Spec> for RenameSecurityScheme<'_> {
+    fn visit_operation_mut(&mut self, _path: &str, _method: &Method, operation: &mut Operation) {
+        rename_requirements(&mut operation.security, self.from, self.to);
+    }
+}
+
+fn rename_requirements(
+    security: &mut Option<Vec<BTreeMap<String, Vec<String>>>>,
+    from: &str,
+    to: &str,
+) {
+    for requirement in security.iter_mut().flatten() {
+        if let Some(scopes) = requirement.remove(from) {
+            requirement.insert(to.to_owned(), scopes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "security": [{"key": []}],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "security": [{"key": [], "oauth": ["read"]}],
+                        "parameters": [{"$ref": "#/components/parameters/Limit"}],
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": {"$ref": "#/components/schemas/Pet"},
+                                        },
+                                        "examples": {
+                                            "one": {"$ref": "#/components/examples/One"},
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                        "callbacks": {
+                            "created": {
+                                "{$request.body#/url}": {
+                                    "post": {
+                                        "security": [{"key": []}],
+                                        "requestBody": {
+                                            "content": {
+                                                "application/json": {
+                                                    "schema": {
+                                                        "$ref": "#/components/schemas/Pet/properties/name",
+                                                    },
+                                                },
+                                            },
+                                        },
+                                        "responses": {},
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {"type": "object", "properties": {"name": {"type": "string"}}},
+                    "PetList": {"$ref": "#/components/schemas/PetList"},
+                    "Cat": {"allOf": [{"$ref": "#/components/schemas/Pet"}]},
+                },
+                "parameters": {
+                    "Limit": {"in": "query", "name": "limit", "schema": {"type": "integer"}},
+                },
+                "examples": {"One": {"value": {}}},
+                "securitySchemes": {"key": {"type": "apiKey", "name": "key", "in": "header"}},
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rename_schema() {
+        let mut spec = spec();
+        assert!(spec.rename_schema("Pet", "Animal"));
+        let value = serde_json::to_value(&spec).unwrap();
+        let get = &value["paths"]["/pets"]["get"];
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["items"],
+            json!({"$ref": "#/components/schemas/Animal"}),
+        );
+        assert_eq!(
+            get["callbacks"]["created"]["{$request.body#/url}"]["post"]["requestBody"]["content"]
+                ["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Animal/properties/name"}),
+            "nested location",
+        );
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(
+            schemas["Cat"]["allOf"][0],
+            json!({"$ref": "#/components/schemas/Animal"})
+        );
+        assert_eq!(
+            schemas["PetList"],
+            json!({"$ref": "#/components/schemas/PetList"}),
+            "the prefix of another name",
+        );
+        assert!(schemas.get("Pet").is_none());
+        assert!(schemas.get("Animal").is_some());
+    }
+
+    #[test]
+    fn test_rename_schema_discriminator() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Cat": {"type": "object"},
+                    "Dog": {"type": "object"},
+                    "Explicit": {
+                        "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+                        "discriminator": {
+                            "propertyName": "kind",
+                            "mapping": {"cat": "Cat", "dog": "#/components/schemas/Dog"},
+                        },
+                    },
+                    "Implicit": {
+                        "anyOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+                        "discriminator": {"propertyName": "kind"},
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        assert!(spec.rename_schema("Cat", "Feline"));
+        assert!(spec.rename_schema("Dog", "Canine"));
+        let schemas = serde_json::to_value(spec.components.unwrap().schemas).unwrap();
+        assert_eq!(
+            schemas["Explicit"]["discriminator"]["mapping"],
+            json!({
+                "Cat": "#/components/schemas/Feline",
+                "Dog": "#/components/schemas/Canine",
+                "cat": "Feline",
+                "dog": "#/components/schemas/Canine",
+            }),
+        );
+        assert_eq!(
+            schemas["Implicit"]["discriminator"]["mapping"],
+            json!({
+                "Cat": "#/components/schemas/Feline",
+                "Dog": "#/components/schemas/Canine",
+            }),
+            "the implicit names are mapped explicitly",
+        );
+    }
+
+    #[test]
+    fn test_rename_other_components() {
+        let mut spec = spec();
+        assert!(spec.rename_parameter("Limit", "PageSize"));
+        assert!(spec.rename_example("One", "Single"));
+        assert!(spec.rename_security_scheme("key", "apiKey"));
+        let value = serde_json::to_value(&spec).unwrap();
+        let get = &value["paths"]["/pets"]["get"];
+        assert_eq!(
+            get["parameters"][0],
+            json!({"$ref": "#/components/parameters/PageSize"})
+        );
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["examples"]["one"],
+            json!({"$ref": "#/components/examples/Single"}),
+        );
+        assert_eq!(value["security"], json!([{"apiKey": []}]));
+        assert_eq!(get["security"], json!([{"apiKey": [], "oauth": ["read"]}]));
+        assert_eq!(
+            get["callbacks"]["created"]["{$request.body#/url}"]["post"]["security"],
+            json!([{"apiKey": []}]),
+        );
+    }
+
+    #[test]
+    fn test_rename_not_possible() {
+        let mut spec = spec();
+        assert!(!spec.rename_schema("Dog", "Animal"), "unknown component");
+        assert!(!spec.rename_schema("Pet", "Cat"), "name is taken");
+        assert!(!spec.rename_response("Pet", "Animal"), "no such section");
+        assert_eq!(spec, self::spec());
+    }
+}
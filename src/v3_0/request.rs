@@ -0,0 +1,627 @@
+//! Request validation
+//!
+//! Checks the parts of an incoming request against an operation:
+//! the path, query, header and cookie parameters, the request body
+//! and the presence of the credentials required by the security requirements.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::request::{RequestError, RequestParts};
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets": {
+//!             "get": {
+//!                 "parameters": [
+//!                     {"in": "query", "name": "limit", "schema": {"type": "integer", "maximum": 100}},
+//!                 ],
+//!                 "responses": {},
+//!             },
+//!         },
+//!     },
+//! })).unwrap();
+//! let operation = spec.paths["/pets"].get().unwrap();
+//!
+//! let parts = RequestParts {
+//!     query: "limit=500".to_owned(),
+//!     ..Default::default()
+//! };
+//! let errors = operation.validate_request(&spec, &parts).unwrap_err();
+//! assert_eq!(
+//!     errors.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+//!     vec!["query parameter `limit`: #: must be less than or equal to 100"],
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::media_type::match_key;
+use crate::common::paths::PathParams;
+use crate::common::reference::RefOr;
+use crate::v3_0::instance::InstanceError;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::parameter::Parameter;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::schema::{Schema, SingleSchema};
+use crate::v3_0::security_scheme::{ApiKeyLocation, SecurityScheme};
+use crate::v3_0::spec::Spec;
+use crate::v3_0::style::{parse_value, Style};
+
+/// The parts of an incoming request.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestParts {
+    /// The values of the path template expressions, e.g. from the router.
+    pub path: PathParams,
+
+    /// The query string without the leading `?`, not percent-decoded.
+    pub query: String,
+
+    /// The headers of the request, the names are case insensitive.
+    /// The cookies are read from the `Cookie` header.
+    pub headers: BTreeMap<String, String>,
+
+    /// The value of the `Content-Type` header,
+    /// the `Content-Type` entry of the headers is used if not set.
+    pub content_type: Option<String>,
+
+    /// The parsed request body.
+    pub body: Option<Value>,
+}
+
+impl RequestParts {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn cookies(&self) -> &str {
+        self.header("cookie").unwrap_or_default()
+    }
+
+    fn has_pair(raw: &str, name: &str) -> bool {
+        raw.split(['&', ';'])
+            .map(|x| x.trim().split('=').next().unwrap_or_default())
+            .any(|x| x == name || x.strip_prefix(name).is_some_and(|x| x.starts_with('[')))
+    }
+}
+
+/// RequestError describes a part of the request that does not match the operation.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum RequestError {
+    /// MissingParameter is returned when a required parameter is not provided.
+    #[error("{location} parameter `{name}` is required")]
+    MissingParameter { location: String, name: String },
+
+    /// InvalidParameter is returned when the value of a parameter does not match its schema.
+    #[error("{location} parameter `{name}`: {error}")]
+    InvalidParameter {
+        location: String,
+        name: String,
+        error: InstanceError,
+    },
+
+    /// MissingBody is returned when a required request body is not provided.
+    #[error("request body is required")]
+    MissingBody,
+
+    /// UnsupportedMediaType is returned when the request body does not define the content type.
+    #[error("media type `{0}` not supported")]
+    UnsupportedMediaType(String),
+
+    /// InvalidBody is returned when the request body does not match the schema.
+    #[error("request body: {0}")]
+    InvalidBody(InstanceError),
+
+    /// Unauthorized is returned when none of the security requirements is satisfied.
+    #[error("credentials required by the security requirements are missing")]
+    Unauthorized,
+
+    /// Reference is returned when a reference of the operation cannot be resolved.
+    #[error("{0}")]
+    Reference(String),
+}
+
+impl Operation {
+    /// Validates the request against the parameters, the request body
+    /// and the security requirements of the operation.
+    ///
+    /// The parameters defined on the path item are not known to the operation,
+    /// use [PathItem::validate_request] to take them into account.
+    /// Only the presence of the credentials is checked, not their validity.
+    pub fn validate_request(
+        &self,
+        spec: &Spec,
+        parts: &RequestParts,
+    ) -> Result<(), Vec<RequestError>> {
+        validate_request(spec, self, &[], parts)
+    }
+}
+
+impl PathItem {
+    /// Validates the request against the operation of the method,
+    /// taking into account the parameters defined on the path item.
+    ///
+    /// Does nothing if the path item has no operation for the method.
+    pub fn validate_request(
+        &self,
+        method: &crate::common::http::Method,
+        spec: &Spec,
+        parts: &RequestParts,
+    ) -> Result<(), Vec<RequestError>> {
+        match self.operation(method) {
+            Some(operation) => validate_request(
+                spec,
+                operation,
+                self.parameters.as_deref().unwrap_or_default(),
+                parts,
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+fn validate_request(
+    spec: &Spec,
+    operation: &Operation,
+    inherited: &[RefOr<Parameter>],
+    parts: &RequestParts,
+) -> Result<(), Vec<RequestError>> {
+    let mut errors = Vec::new();
+
+    let mut parameters = Vec::new();
+    for parameter in inherited
+        .iter()
+        .chain(operation.parameters.iter().flatten())
+    {
+        match parameter.get_item(spec) {
+            Ok(parameter) => {
+                // the operation parameters override the path item ones
+                parameters.retain(|x: &&Parameter| {
+                    x.name() != parameter.name() || x.location() != parameter.location()
+                });
+                parameters.push(parameter);
+            }
+            Err(e) => errors.push(RequestError::Reference(e.to_string())),
+        }
+    }
+    for parameter in parameters {
+        validate_parameter(spec, parameter, parts, &mut errors);
+    }
+
+    validate_body(spec, operation, parts, &mut errors);
+
+    let requirements = operation.security.as_ref().or(spec.security.as_ref());
+    if let Some(requirements) = requirements {
+        if !requirements.is_empty()
+            && !requirements
+                .iter()
+                .any(|x| x.keys().all(|name| has_credentials(spec, name, parts)))
+        {
+            errors.push(RequestError::Unauthorized);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_parameter(
+    spec: &Spec,
+    parameter: &Parameter,
+    parts: &RequestParts,
+    errors: &mut Vec<RequestError>,
+) {
+    let name = parameter.name();
+    if let Some(schema) = form_object(spec, parameter) {
+        validate_form_object(spec, parameter, schema, parts, errors);
+        return;
+    }
+    let raw = match parameter {
+        Parameter::Path(_) => parts.path.get(name).map(String::as_str),
+        Parameter::Query(_) => {
+            Some(parts.query.as_str()).filter(|x| RequestParts::has_pair(x, name))
+        }
+        Parameter::Header(_) => parts.header(name),
+        Parameter::Cookie(_) => Some(parts.cookies()).filter(|x| RequestParts::has_pair(x, name)),
+    };
+    let Some(raw) = raw else {
+        if parameter.is_required() {
+            errors.push(RequestError::MissingParameter {
+                location: parameter.location().to_owned(),
+                name: name.to_owned(),
+            });
+        }
+        return;
+    };
+
+    let (schema, value) = match parameter.schema() {
+        Some(schema) => match schema.get_item(spec) {
+            Ok(schema) => {
+                // the styles need the inline schema to know the shape of the value
+                let inline = RefOr::new_item(schema.clone());
+                let value = parse_value(
+                    &parameter.style(),
+                    parameter.explode(),
+                    name,
+                    raw,
+                    Some(&inline),
+                );
+                (schema, value)
+            }
+            Err(e) => {
+                errors.push(RequestError::Reference(e.to_string()));
+                return;
+            }
+        },
+        None => {
+            let Some(schema) = parameter_content_schema(parameter) else {
+                return;
+            };
+            let schema = match schema.get_item(spec) {
+                Ok(schema) => schema,
+                Err(e) => {
+                    errors.push(RequestError::Reference(e.to_string()));
+                    return;
+                }
+            };
+            let raw = match parameter {
+                Parameter::Query(_) | Parameter::Cookie(_) => raw
+                    .split(['&', ';'])
+                    .filter_map(|x| x.trim().split_once('='))
+                    .find(|(k, _)| *k == name)
+                    .map_or("", |(_, v)| v),
+                _ => raw,
+            };
+            let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_owned()));
+            (schema, value)
+        }
+    };
+    if let Err(e) = schema.validate_instance(&value, spec) {
+        errors.extend(e.into_iter().map(|error| RequestError::InvalidParameter {
+            location: parameter.location().to_owned(),
+            name: name.to_owned(),
+            error,
+        }));
+    }
+}
+
+/// Returns the schema of a query or cookie parameter serialized as the exploded form object,
+/// i.e. as the pairs of the properties without the name of the parameter.
+fn form_object<'a>(spec: &'a Spec, parameter: &'a Parameter) -> Option<&'a Schema> {
+    if !matches!(parameter, Parameter::Query(_) | Parameter::Cookie(_))
+        || parameter.style() != Style::Form
+        || !parameter.explode()
+    {
+        return None;
+    }
+    let schema = parameter.schema()?.get_item(spec).ok()?;
+    matches!(schema, Schema::Single(SingleSchema::Object(_))).then_some(schema)
+}
+
+fn validate_form_object(
+    spec: &Spec,
+    parameter: &Parameter,
+    schema: &Schema,
+    parts: &RequestParts,
+    errors: &mut Vec<RequestError>,
+) {
+    let raw = match parameter {
+        Parameter::Cookie(_) => parts.cookies(),
+        _ => parts.query.as_str(),
+    };
+    // the pairs that do not match the properties belong to the other parameters
+    let inline = RefOr::new_item(schema.clone());
+    let value = parse_value(&Style::Form, true, parameter.name(), raw, Some(&inline));
+    if value.as_object().is_none_or(|x| x.is_empty()) {
+        if parameter.is_required() {
+            errors.push(RequestError::MissingParameter {
+                location: parameter.location().to_owned(),
+                name: parameter.name().to_owned(),
+            });
+        }
+        return;
+    }
+    if let Err(e) = schema.validate_instance(&value, spec) {
+        errors.extend(e.into_iter().map(|error| RequestError::InvalidParameter {
+            location: parameter.location().to_owned(),
+            name: parameter.name().to_owned(),
+            error,
+        }));
+    }
+}
+
+fn parameter_content_schema(parameter: &Parameter) -> Option<&RefOr<Schema>> {
+    let content = match parameter {
+        Parameter::Path(p) => p.content.as_ref(),
+        Parameter::Query(p) => p.content.as_ref(),
+        Parameter::Header(p) => p.content.as_ref(),
+        Parameter::Cookie(p) => p.content.as_ref(),
+    }?;
+    content.values().next()?.schema.as_ref()
+}
+
+fn validate_body(
+    spec: &Spec,
+    operation: &Operation,
+    parts: &RequestParts,
+    errors: &mut Vec<RequestError>,
+) {
+    let Some(request_body) = &operation.request_body else {
+        return;
+    };
+    let request_body = match request_body.get_item(spec) {
+        Ok(request_body) => request_body,
+        Err(e) => {
+            errors.push(RequestError::Reference(e.to_string()));
+            return;
+        }
+    };
+    let Some(body) = &parts.body else {
+        if request_body.required.unwrap_or(false) {
+            errors.push(RequestError::MissingBody);
+        }
+        return;
+    };
+    let content_type = parts
+        .content_type
+        .as_deref()
+        .or_else(|| parts.header("content-type"))
+        .unwrap_or("application/json");
+    let Some(key) = match_key(
+        request_body.content.keys().map(String::as_str),
+        content_type,
+    ) else {
+        errors.push(RequestError::UnsupportedMediaType(content_type.to_owned()));
+        return;
+    };
+    let Some(schema) = &request_body.content[key].schema else {
+        return;
+    };
+    match schema.get_item(spec) {
+        Ok(schema) => {
+            if let Err(e) = schema.validate_instance(body, spec) {
+                errors.extend(e.into_iter().map(RequestError::InvalidBody));
+            }
+        }
+        Err(e) => errors.push(RequestError::Reference(e.to_string())),
+    }
+}
+
+/// Returns `true` if the request carries the credentials of the security scheme.
+fn has_credentials(spec: &Spec, name: &str, parts: &RequestParts) -> bool {
+    let scheme = spec
+        .components
+        .as_ref()
+        .and_then(|x| x.security_schemes.as_ref())
+        .and_then(|x| x.get(name))
+        .and_then(|x| x.get_item(spec).ok());
+    let authorization = parts.header("authorization");
+    match scheme {
+        Some(SecurityScheme::ApiKey(s)) => match s.location {
+            ApiKeyLocation::Query => RequestParts::has_pair(&parts.query, &s.name),
+            ApiKeyLocation::Header => parts.header(&s.name).is_some(),
+            ApiKeyLocation::Cookie => RequestParts::has_pair(parts.cookies(), &s.name),
+        },
+        Some(SecurityScheme::HTTP(s)) => authorization
+            .and_then(|x| x.split_whitespace().next())
+            .is_some_and(|x| x.eq_ignore_ascii_case(&s.scheme.to_string())),
+        Some(SecurityScheme::OAuth2(_) | SecurityScheme::OpenIdConnect(_)) => {
+            authorization.is_some()
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "security": [{"token": []}, {"key": []}],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [
+                        {"in": "path", "name": "id", "required": true, "schema": {"type": "integer"}},
+                        {"$ref": "#/components/parameters/Trace"},
+                    ],
+                    "put": {
+                        "parameters": [
+                            {"in": "query", "name": "tags", "schema": {"$ref": "#/components/schemas/Tags"}},
+                            {"in": "cookie", "name": "session", "required": true, "schema": {"type": "string"}},
+                            {"in": "header", "name": "X-Trace", "schema": {"type": "string", "maxLength": 2}},
+                        ],
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "required": ["name"],
+                                        "properties": {"name": {"type": "string"}},
+                                    },
+                                },
+                            },
+                        },
+                        "responses": {},
+                    },
+                },
+            },
+            "components": {
+                "parameters": {
+                    "Trace": {"in": "header", "name": "X-Trace", "required": true, "schema": {"type": "string"}},
+                },
+                "schemas": {
+                    "Tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2},
+                },
+                "securitySchemes": {
+                    "token": {"type": "http", "scheme": "bearer"},
+                    "key": {"type": "apiKey", "in": "query", "name": "api_key"},
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_request() {
+        let spec = spec();
+        let path_item = &spec.paths["/pets/{id}"];
+        let method = crate::common::http::Method::Put;
+
+        let parts = RequestParts {
+            path: PathParams::from([("id".to_owned(), "42".to_owned())]),
+            query: "tags=a&tags=b".to_owned(),
+            headers: BTreeMap::from([
+                ("Authorization".to_owned(), "Bearer x".to_owned()),
+                ("Cookie".to_owned(), "session=1; theme=dark".to_owned()),
+                ("x-trace".to_owned(), "ab".to_owned()),
+                (
+                    "content-type".to_owned(),
+                    "application/json; charset=utf-8".to_owned(),
+                ),
+            ]),
+            content_type: None,
+            body: Some(json!({"name": "Rex"})),
+        };
+        assert_eq!(
+            path_item.validate_request(&method, &spec, &parts),
+            Ok(()),
+            "valid request",
+        );
+
+        let parts = RequestParts {
+            path: PathParams::from([("id".to_owned(), "abc".to_owned())]),
+            query: "tags=a&tags=b&tags=c&api_key=1".to_owned(),
+            headers: BTreeMap::from([("X-Trace".to_owned(), "abc".to_owned())]),
+            content_type: None,
+            body: Some(json!({})),
+        };
+        assert_eq!(
+            path_item
+                .validate_request(&method, &spec, &parts)
+                .unwrap_err()
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "path parameter `id`: #: expected `integer`, found `string`",
+                "query parameter `tags`: #: must have at most 2 items",
+                "cookie parameter `session` is required",
+                "header parameter `X-Trace`: #: length must be at most 2",
+                "request body: #: property `name` is required",
+            ],
+            "invalid request",
+        );
+
+        let parts = RequestParts {
+            path: PathParams::from([("id".to_owned(), "1".to_owned())]),
+            headers: BTreeMap::from([
+                ("Authorization".to_owned(), "Basic x".to_owned()),
+                ("Cookie".to_owned(), "session=1".to_owned()),
+            ]),
+            content_type: Some("text/plain".to_owned()),
+            body: Some(json!("Rex")),
+            ..Default::default()
+        };
+        assert_eq!(
+            path_item.validate_request(&method, &spec, &parts),
+            Err(vec![
+                RequestError::UnsupportedMediaType("text/plain".to_owned()),
+                RequestError::Unauthorized,
+            ]),
+            "unsupported media type and unauthorized",
+        );
+    }
+
+    #[test]
+    fn test_validate_form_object() {
+        let spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            {
+                                "in": "query",
+                                "name": "filter",
+                                "required": true,
+                                "schema": {"$ref": "#/components/schemas/Filter"},
+                            },
+                            {"in": "query", "name": "limit", "schema": {"type": "integer"}},
+                        ],
+                        "responses": {},
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Filter": {
+                        "type": "object",
+                        "properties": {
+                            "color": {"type": "string"},
+                            "size": {"type": "integer", "maximum": 5},
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        let operation = spec.paths["/pets"].get().unwrap();
+
+        let parts = RequestParts {
+            query: "color=red&size=3&limit=1".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation.validate_request(&spec, &parts),
+            Ok(()),
+            "valid object"
+        );
+
+        let parts = RequestParts {
+            query: "color=red&size=10".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation
+                .validate_request(&spec, &parts)
+                .unwrap_err()
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>(),
+            vec!["query parameter `filter`: #/size: must be less than or equal to 5"],
+            "invalid property",
+        );
+
+        let parts = RequestParts {
+            query: "limit=1".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation.validate_request(&spec, &parts),
+            Err(vec![RequestError::MissingParameter {
+                location: "query".to_owned(),
+                name: "filter".to_owned(),
+            }]),
+            "missing object",
+        );
+    }
+}
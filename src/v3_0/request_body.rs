@@ -1,12 +1,17 @@
 //! Request Body Object
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{Context, ValidateWithContext};
+use crate::common::helpers::{Context, PushError, ValidateWithContext};
+use crate::common::media_type::best_match;
+use crate::common::path::Path;
+use crate::common::reference::RefOr;
 use crate::v3_0::media_type::MediaType;
+use crate::v3_0::schema::Schema;
 use crate::v3_0::spec::Spec;
+use crate::validation::Lints;
 
 /// Describes a single request body.
 ///
@@ -59,10 +64,137 @@ pub struct RequestBody {
     pub required: Option<bool>,
 }
 
+impl RequestBody {
+    /// Creates a request body with a single media type described by the schema.
+    pub fn with_media_type(media_type: impl Into<String>, schema: RefOr<Schema>) -> Self {
+        RequestBody {
+            content: BTreeMap::from([(
+                media_type.into(),
+                MediaType {
+                    schema: Some(schema),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a request body with the `application/json` media type described by the schema.
+    pub fn json(schema: RefOr<Schema>) -> Self {
+        Self::with_media_type("application/json", schema)
+    }
+
+    /// Returns the media type that best matches the actual content type,
+    /// see [best_match](crate::common::media_type::best_match) for details.
+    pub fn best_match(&self, content_type: &str) -> Option<(&str, &MediaType)> {
+        best_match(&self.content, content_type)
+    }
+}
+
 impl ValidateWithContext<Spec> for RequestBody {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        if self.content.is_empty() {
+            ctx.error(path.field("content"), "must not be empty");
+        }
         for (k, v) in &self.content {
-            v.validate_with_context(ctx, format!("{}.content[{}]", path, k));
+            let path = path.field("content").item(k);
+            if ctx.is_lint(Lints::RequiredReadOnlyProperties) {
+                if let Some(schema) = &v.schema {
+                    // a recursive schema is checked once, starting from the referenced one
+                    let mut visited = HashSet::new();
+                    if let RefOr::Ref(r) = schema {
+                        visited.insert(r.reference.clone());
+                    }
+                    if let Ok(schema) = schema.get_item(ctx.spec) {
+                        schema.validate_read_only_required(ctx, path.field("schema"), &mut visited);
+                    }
+                }
+            }
+            v.validate_with_context(ctx, path);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::Options;
+
+    #[test]
+    fn test_validate_read_only_required() {
+        let spec = serde_json::from_value::<Spec>(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "foo",
+                "version": "1",
+            },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Id": {
+                        "type": "string",
+                        "readOnly": true,
+                    },
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"$ref": "#/components/schemas/Id"},
+                            "name": {"type": "string"},
+                            "parent": {"$ref": "#/components/schemas/User"},
+                        },
+                        "required": ["id", "name"],
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        let request_body = serde_json::from_value::<RequestBody>(serde_json::json!({
+            "content": {
+                "application/json": {
+                    "schema": {"$ref": "#/components/schemas/User"},
+                },
+            },
+        }))
+        .unwrap();
+
+        let mut ctx = Context::new(&spec, Options::new())
+            .with_lints(Lints::RequiredReadOnlyProperties.only());
+        request_body.validate_with_context(&mut ctx, "requestBody".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "requestBody.content[application/json].schema.required: read-only property `id` must not be required in a request",
+            ],
+            "read-only required once: {:?}",
+            ctx.errors
+        );
+
+        let mut ctx = Context::new(&spec, Options::new());
+        request_body.validate_with_context(&mut ctx, "requestBody".into());
+        assert!(ctx.errors.is_empty(), "opt-in: {:?}", ctx.errors);
+    }
+
+    #[test]
+    fn test_json() {
+        let request_body =
+            RequestBody::json(RefOr::new_ref("#/components/schemas/User".to_owned()));
+        assert_eq!(
+            serde_json::to_value(&request_body).unwrap(),
+            serde_json::json!({
+                "content": {
+                    "application/json": {
+                        "schema": {"$ref": "#/components/schemas/User"},
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_validate_empty_content() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        RequestBody::default().validate_with_context(&mut ctx, "requestBody".into());
+        assert_eq!(ctx.errors, vec!["requestBody.content: must not be empty"]);
+    }
+}
@@ -7,12 +7,20 @@ use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, Context, PushError, ValidateWithContext,
+};
+use crate::common::http::{StatusCode, StatusCodeError};
+use crate::common::media_type::best_match;
+use crate::common::path::Path;
 use crate::common::reference::RefOr;
-use crate::v3_0::header::Header;
+use crate::common::status::is_registered;
+use crate::v3_0::header::{validate_header_name, Header};
 use crate::v3_0::link::Link;
 use crate::v3_0::media_type::MediaType;
 use crate::v3_0::spec::Spec;
+use crate::validation::{Lints, Options};
 
 /// A container for the expected responses of an operation.
 /// The container maps a HTTP response code to the expected response.
@@ -67,7 +75,7 @@ pub struct Responses {
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -101,7 +109,126 @@ pub struct Response {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Responses {
+    /// Returns the response for the status code,
+    /// the explicit code takes precedence over the range like `2XX`,
+    /// the `default` response is returned if neither is defined.
+    pub fn get(&self, status: &str) -> Option<&RefOr<Response>> {
+        let responses = self.responses.as_ref();
+        responses
+            .and_then(|x| x.get(status))
+            .or_else(|| {
+                let range = format!("{}XX", status.get(..1)?);
+                responses.and_then(|x| x.get(&range))
+            })
+            .or(self.default.as_ref())
+    }
+
+    /// Returns the response for the numeric status code, e.g. `http::StatusCode::as_u16`,
+    /// with the same precedence as [Responses::get].
+    pub fn get_response(&self, status: u16) -> Option<&RefOr<Response>> {
+        self.get(&status.to_string())
+    }
+
+    /// Returns `true` if neither the default response nor any response code is defined.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.responses.as_ref().is_none_or(BTreeMap::is_empty)
+    }
+
+    /// Returns a builder of the responses.
+    pub fn builder() -> ResponsesBuilder {
+        ResponsesBuilder {
+            responses: Responses::default(),
+            error: None,
+        }
+    }
+
+    /// Inserts the response for the status code or the range,
+    /// returns the previous response of the key.
+    ///
+    /// The key is validated before the insertion, see [StatusCode::validate].
+    pub fn insert(
+        &mut self,
+        status: impl Into<StatusCode>,
+        response: RefOr<Response>,
+    ) -> Result<Option<RefOr<Response>>, StatusCodeError> {
+        let status = status.into().validate()?;
+        Ok(self
+            .responses
+            .get_or_insert_with(BTreeMap::new)
+            .insert(status.to_string(), response))
+    }
+}
+
+/// Builds the [Responses] keyed by the status codes.
+///
+/// Example:
+///
+/// ```rust
+/// use roas::common::reference::RefOr;
+/// use roas::v3_0::response::{Response, Responses};
+///
+/// let responses = Responses::builder()
+///     .ok(RefOr::new_item(Response {
+///         description: "OK".to_owned(),
+///         ..Default::default()
+///     }))
+///     .status(404, RefOr::new_ref("#/components/responses/NotFound".to_owned()))
+///     .default(RefOr::new_ref("#/components/responses/Error".to_owned()))
+///     .build()
+///     .unwrap();
+/// assert!(responses.get_response(404).is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponsesBuilder {
+    responses: Responses,
+    error: Option<StatusCodeError>,
+}
+
+impl ResponsesBuilder {
+    /// Sets the response for the `200` status code.
+    pub fn ok(self, response: RefOr<Response>) -> Self {
+        self.status(200, response)
+    }
+
+    /// Sets the response for the status code, an invalid code is reported by [ResponsesBuilder::build].
+    pub fn status(mut self, status: impl Into<StatusCode>, response: RefOr<Response>) -> Self {
+        if self.error.is_none() {
+            self.error = self.responses.insert(status, response).err();
+        }
+        self
+    }
+
+    /// Sets the `default` response.
+    pub fn default(mut self, response: RefOr<Response>) -> Self {
+        self.responses.default = Some(response);
+        self
+    }
+
+    /// Returns the responses or the first error of the status codes.
+    pub fn build(self) -> Result<Responses, StatusCodeError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.responses),
+        }
+    }
+}
+
+/// Returns `true` if the key is a status code within [100..599] range
+/// or one of the ranges `1XX`, `2XX`, `3XX`, `4XX` and `5XX`.
+fn is_status_key(key: &str) -> bool {
+    key.parse::<StatusCode>().is_ok()
+}
+
+impl Response {
+    /// Returns the media type that best matches the actual content type,
+    /// see [best_match](crate::common::media_type::best_match) for details.
+    pub fn best_match(&self, content_type: &str) -> Option<(&str, &MediaType)> {
+        best_match(self.content.as_ref()?, content_type)
+    }
 }
 
 impl Serialize for Responses {
@@ -138,7 +265,7 @@ impl<'de> Deserialize<'de> for Responses {
     where
         D: Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["default", "x-...", "1xx", "2xx", "3xx", "4xx", "5xx"];
+        const FIELDS: &[&str] = &["default", "x-...", "1XX", "2XX", "3XX", "4XX", "5XX"];
 
         struct ResponsesVisitor;
 
@@ -155,7 +282,7 @@ impl<'de> Deserialize<'de> for Responses {
             {
                 let mut res = Responses::default();
                 let mut responses: BTreeMap<String, RefOr<Response>> = BTreeMap::new();
-                let mut extensions: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+                let mut extensions: Extensions = BTreeMap::new();
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "default" {
                         if res.default.is_some() {
@@ -167,19 +294,13 @@ impl<'de> Deserialize<'de> for Responses {
                             return Err(Error::custom(format_args!("duplicate field `{}`", key)));
                         }
                         extensions.insert(key, map.next_value()?);
-                    } else {
-                        match key.parse::<u16>() {
-                            Ok(100..=599) => {
-                                if responses.contains_key(key.as_str()) {
-                                    return Err(Error::custom(format_args!(
-                                        "duplicate field `{}`",
-                                        key
-                                    )));
-                                }
-                                responses.insert(key, map.next_value()?);
-                            }
-                            _ => return Err(Error::unknown_field(key.as_str(), FIELDS)),
+                    } else if is_status_key(&key) {
+                        if responses.contains_key(key.as_str()) {
+                            return Err(Error::custom(format_args!("duplicate field `{}`", key)));
                         }
+                        responses.insert(key, map.next_value()?);
+                    } else {
+                        return Err(Error::unknown_field(key.as_str(), FIELDS));
                     }
                 }
                 if !responses.is_empty() {
@@ -197,47 +318,210 @@ impl<'de> Deserialize<'de> for Responses {
 }
 
 impl ValidateWithContext<Spec> for Response {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.description, ctx, format!("{}.description", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.description, ctx, path.field("description"));
         if let Some(headers) = &self.headers {
             for (name, header) in headers {
-                header.validate_with_context(ctx, format!("{}.headers[{}]", path, name));
+                validate_header_name(name, ctx, path.field("headers").item(name));
+                header.validate_with_context(ctx, path.field("headers").item(name));
             }
         }
         if let Some(media_types) = &self.content {
             for (name, media_type) in media_types {
-                media_type.validate_with_context(ctx, format!("{}.mediaTypes[{}]", path, name));
+                media_type.validate_with_context(ctx, path.field("mediaTypes").item(name));
             }
         }
         if let Some(links) = &self.links {
             for (name, link) in links {
-                link.validate_with_context(ctx, format!("{}.links[{}]", path, name));
+                link.validate_with_context(ctx, path.field("links").item(name));
             }
         }
     }
 }
 
 impl ValidateWithContext<Spec> for Responses {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if self.is_empty() && !ctx.is_option(Options::IgnoreEmptyResponses) {
+            ctx.error(path.clone(), "must contain at least one response");
+        }
         if let Some(response) = &self.default {
-            response.validate_with_context(ctx, format!("{}.default", path));
+            response.validate_with_context(ctx, path.field("default"));
         }
         if let Some(responses) = &self.responses {
             for (name, response) in responses {
-                match name.parse::<u16>() {
-                    Ok(100..=599) => {}
-                    _ => {
-                        ctx.error(
-                            path.clone(),
-                            format_args!(
-                                "name must be an integer within [100..599] range, found `{}`",
-                                name
-                            ),
-                        );
-                    }
+                if !is_status_key(name) {
+                    ctx.error(
+                        path.clone(),
+                        format_args!(
+                            "name must be an integer within [100..599] range or a range like `2XX`, found `{}`",
+                            name
+                        ),
+                    );
+                } else if ctx.is_lint(Lints::UnregisteredStatusCodes) && !is_registered(name) {
+                    ctx.error(path.field(name.to_owned()), "status code is not registered");
                 }
-                response.validate_with_context(ctx, format!("{}.{}", path, name));
+                response.validate_with_context(ctx, path.field(name.to_owned()));
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_empty_responses() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        Responses::default().validate_with_context(&mut ctx, "responses".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["responses: must contain at least one response"]
+        );
+
+        let mut ctx = Context::new(&spec, Options::IgnoreEmptyResponses.only());
+        Responses::default().validate_with_context(&mut ctx, "responses".into());
+        assert!(ctx.errors.is_empty(), "ignored: {:?}", ctx.errors);
+    }
+
+    #[test]
+    fn test_get_response() {
+        let responses = serde_json::from_value::<Responses>(serde_json::json!({
+            "200": {"description": "ok"},
+            "2XX": {"description": "success"},
+            "default": {"description": "error"},
+        }))
+        .unwrap();
+        let description = |status| match responses.get_response(status) {
+            Some(RefOr::Item(response)) => response.description.as_str(),
+            _ => "",
+        };
+        assert_eq!(description(200), "ok", "explicit code");
+        assert_eq!(description(201), "success", "range");
+        assert_eq!(description(404), "error", "default");
+    }
+
+    #[test]
+    fn test_responses_ranges_deserialize() {
+        let responses = serde_json::from_value::<Responses>(serde_json::json!({
+            "1XX": {"description": "info"},
+            "5XX": {"description": "server error"},
+            "503": {"description": "unavailable"},
+        }))
+        .unwrap();
+        let description = |status| match responses.get_response(status) {
+            Some(RefOr::Item(response)) => response.description.as_str(),
+            _ => "",
+        };
+        assert_eq!(description(101), "info", "range");
+        assert_eq!(description(503), "unavailable", "explicit code wins");
+        assert_eq!(description(500), "server error", "range");
+        assert_eq!(description(200), "", "neither code nor default");
+
+        for key in ["6XX", "0XX", "2xx", "2X", "600"] {
+            assert!(
+                serde_json::from_value::<Responses>(
+                    serde_json::json!({key: {"description": "error"}})
+                )
+                .is_err(),
+                "`{}` must be rejected",
+                key
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<Responses>(serde_json::json!({"2xx": {"description": "ok"}}))
+                .unwrap_err()
+                .to_string(),
+            "unknown field `2xx`, expected one of `default`, `x-...`, `1XX`, `2XX`, `3XX`, `4XX`, `5XX`",
+        );
+    }
+
+    #[test]
+    fn test_response_best_match() {
+        let response = serde_json::from_value::<Response>(serde_json::json!({
+            "description": "ok",
+            "content": {
+                "text/*": {},
+                "application/json": {"example": 1},
+            },
+        }))
+        .unwrap();
+        let (key, media_type) = response.best_match("application/json").unwrap();
+        assert_eq!(key, "application/json");
+        assert_eq!(media_type.example, Some(serde_json::json!(1)));
+        assert_eq!(response.best_match("text/plain").unwrap().0, "text/*");
+        assert!(response.best_match("image/png").is_none());
+        assert!(Response::default().best_match("text/plain").is_none());
+    }
+
+    #[test]
+    fn test_responses_builder() {
+        let ok = RefOr::new_item(Response {
+            description: "ok".to_owned(),
+            ..Default::default()
+        });
+        let responses = Responses::builder()
+            .ok(ok.clone())
+            .status(
+                StatusCode::Range(4),
+                RefOr::new_ref("#/components/responses/Error".to_owned()),
+            )
+            .default(ok.clone())
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&responses).unwrap(),
+            serde_json::json!({
+                "200": {"description": "ok"},
+                "4XX": {"$ref": "#/components/responses/Error"},
+                "default": {"description": "ok"},
+            })
+        );
+
+        assert_eq!(
+            Responses::builder()
+                .status(600, ok.clone())
+                .status(StatusCode::Range(6), ok.clone())
+                .build(),
+            Err(StatusCodeError::Invalid("600".to_owned())),
+            "first error"
+        );
+
+        let mut responses = Responses::default();
+        assert_eq!(responses.insert(201, ok.clone()), Ok(None));
+        assert_eq!(responses.insert(201, ok.clone()), Ok(Some(ok.clone())));
+        assert_eq!(
+            responses.insert(99, ok),
+            Err(StatusCodeError::Invalid("99".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_validate_unregistered_status_codes() {
+        let responses = serde_json::from_value::<Responses>(serde_json::json!({
+            "200": {"description": "ok"},
+            "299": {"description": "custom"},
+            "4XX": {"description": "error"},
+        }))
+        .unwrap();
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        responses.validate_with_context(&mut ctx, "responses".into());
+        assert!(
+            ctx.errors.is_empty(),
+            "allowed by default: {:?}",
+            ctx.errors
+        );
+
+        let mut ctx =
+            Context::new(&spec, Options::new()).with_lints(Lints::UnregisteredStatusCodes.only());
+        responses.validate_with_context(&mut ctx, "responses".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["responses.299: status code is not registered"]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Request routing.
+//!
+//! The router orders the path templates of a specification once
+//! and resolves an incoming request, identified by the HTTP method, the path and
+//! the media type of the body, to the operation of the specification.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::http::Method;
+//! use roas::v3_0::router::Router;
+//! use roas::v3_0::spec::Spec;
+//!
+//! let spec = serde_json::from_value::<Spec>(serde_json::json!({
+//!     "openapi": "3.0.3",
+//!     "info": {"title": "foo", "version": "1"},
+//!     "paths": {
+//!         "/pets/{id}": {
+//!             "put": {
+//!                 "operationId": "updatePet",
+//!                 "requestBody": {"content": {"application/json": {}}},
+//!                 "responses": {},
+//!             },
+//!         },
+//!     },
+//! })).unwrap();
+//!
+//! let router = Router::from_spec(&spec);
+//! let route = router
+//!     .resolve(&Method::Put, "/pets/42", Some("application/json; charset=utf-8"))
+//!     .unwrap();
+//! assert_eq!(route.operation.operation_id.as_deref(), Some("updatePet"));
+//! assert_eq!(route.params["id"], "42");
+//! assert_eq!(route.media_type, Some("application/json"));
+//! ```
+
+use thiserror::Error;
+
+use crate::common::http::Method;
+use crate::common::media_type::match_key;
+use crate::common::paths::{match_template, PathParams};
+use crate::v3_0::operation::Operation;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::spec::Spec;
+
+/// RouteError is an error type for resolving a request.
+#[derive(Debug, Error, PartialEq)]
+pub enum RouteError {
+    /// NotFound is returned when no path template matches the request path.
+    #[error("path `{0}` not found")]
+    NotFound(String),
+
+    /// MethodNotAllowed is returned when the matched paths do not define the method.
+    #[error("method `{method}` not allowed for path `{path}`")]
+    MethodNotAllowed { method: Method, path: String },
+
+    /// UnsupportedMediaType is returned when the request body of the operation
+    /// does not define the media type.
+    #[error("media type `{0}` not supported")]
+    UnsupportedMediaType(String),
+}
+
+/// The operation resolved for a request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route<'a> {
+    /// The matched path template.
+    pub path: &'a str,
+
+    /// The matched path item.
+    pub path_item: &'a PathItem,
+
+    /// The resolved operation.
+    pub operation: &'a Operation,
+
+    /// The values of the path template expressions.
+    pub params: PathParams,
+
+    /// The matched key of the request body content,
+    /// `None` if the operation has no request body or the media type was not given.
+    pub media_type: Option<&'a str>,
+}
+
+/// Router resolves the requests to the operations of a specification.
+pub struct Router<'a> {
+    spec: &'a Spec,
+    /// The path templates and the path items, ordered by the number of the template expressions.
+    paths: Vec<(&'a str, &'a PathItem)>,
+}
+
+impl<'a> Router<'a> {
+    /// Orders the path templates of the specification.
+    ///
+    /// The concrete paths are matched before their templated counterparts,
+    /// so the paths are ordered by the number of template expressions.
+    /// The templates are matched as by [Paths::match_request_path](crate::common::paths::Paths::match_request_path).
+    pub fn from_spec(spec: &'a Spec) -> Self {
+        let mut paths = spec
+            .paths
+            .iter()
+            .map(|(template, item)| (template.as_str(), item))
+            .collect::<Vec<_>>();
+        paths.sort_by_key(|(template, _)| template.matches('{').count());
+        Router { spec, paths }
+    }
+
+    /// Resolves the request to the operation.
+    ///
+    /// The path must not include the server URL, the query string is ignored.
+    /// The media type is matched against the request body content keys,
+    /// see [match_key](crate::common::media_type::match_key) for details.
+    pub fn resolve(
+        &self,
+        method: &Method,
+        path: &str,
+        media_type: Option<&str>,
+    ) -> Result<Route<'a>, RouteError> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut matched = None;
+        for &(template, item) in &self.paths {
+            let Some(params) = match_template(template, path) else {
+                continue;
+            };
+            matched.get_or_insert(template);
+            let Some(operation) = item.operation(method) else {
+                continue;
+            };
+            return Ok(Route {
+                path: template,
+                path_item: item,
+                operation,
+                params,
+                media_type: self.match_media_type(operation, media_type)?,
+            });
+        }
+        match matched {
+            Some(template) => Err(RouteError::MethodNotAllowed {
+                method: method.clone(),
+                path: template.to_owned(),
+            }),
+            None => Err(RouteError::NotFound(path.to_owned())),
+        }
+    }
+
+    fn match_media_type(
+        &self,
+        operation: &'a Operation,
+        media_type: Option<&str>,
+    ) -> Result<Option<&'a str>, RouteError> {
+        let (Some(media_type), Some(request_body)) = (media_type, &operation.request_body) else {
+            return Ok(None);
+        };
+        let Ok(request_body) = request_body.get_item(self.spec) else {
+            return Ok(None);
+        };
+        match match_key(request_body.content.keys().map(String::as_str), media_type) {
+            Some(key) => Ok(Some(key)),
+            None => Err(RouteError::UnsupportedMediaType(media_type.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets/{id}": {
+                    "get": {"operationId": "getPet", "responses": {}},
+                    "delete": {"operationId": "deletePet", "responses": {}},
+                },
+                "/pets/mine": {
+                    "get": {"operationId": "getMyPets", "responses": {}},
+                },
+                "/files/{name}.{ext}": {
+                    "get": {"operationId": "getFile", "responses": {}},
+                },
+                "/pets": {
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": {"$ref": "#/components/requestBodies/Pet"},
+                        "responses": {},
+                    },
+                },
+            },
+            "components": {
+                "requestBodies": {
+                    "Pet": {
+                        "content": {
+                            "application/json": {},
+                            "image/*": {},
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let spec = spec();
+        let router = Router::from_spec(&spec);
+        for (method, path, media_type, operation_id, params, key) in [
+            (Method::Get, "/pets/mine", None, "getMyPets", vec![], None),
+            (
+                Method::Get,
+                "/pets/42?x=1",
+                None,
+                "getPet",
+                vec![("id", "42")],
+                None,
+            ),
+            (
+                Method::Delete,
+                "/pets/mine",
+                None,
+                "deletePet",
+                vec![("id", "mine")],
+                None,
+            ),
+            (
+                Method::Get,
+                "/files/archive.tar.gz",
+                None,
+                "getFile",
+                vec![("ext", "tar.gz"), ("name", "archive")],
+                None,
+            ),
+            (
+                Method::Post,
+                "/pets",
+                Some("Application/JSON; charset=utf-8"),
+                "createPet",
+                vec![],
+                Some("application/json"),
+            ),
+            (
+                Method::Post,
+                "/pets",
+                Some("image/png"),
+                "createPet",
+                vec![],
+                Some("image/*"),
+            ),
+        ] {
+            let route = router.resolve(&method, path, media_type).unwrap();
+            assert_eq!(
+                (
+                    route.operation.operation_id.as_deref(),
+                    route.params,
+                    route.media_type,
+                ),
+                (
+                    Some(operation_id),
+                    params
+                        .into_iter()
+                        .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                        .collect(),
+                    key,
+                ),
+                "{} {}",
+                method,
+                path,
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let spec = spec();
+        let router = Router::from_spec(&spec);
+        assert_eq!(
+            router.resolve(&Method::Get, "/users", None).unwrap_err(),
+            RouteError::NotFound("/users".to_owned()),
+            "not found",
+        );
+        assert_eq!(
+            router
+                .resolve(&Method::Put, "/pets/mine", None)
+                .unwrap_err()
+                .to_string(),
+            "method `put` not allowed for path `/pets/mine`",
+            "method not allowed",
+        );
+        assert_eq!(
+            router
+                .resolve(&Method::Post, "/pets", Some("text/plain"))
+                .unwrap_err(),
+            RouteError::UnsupportedMediaType("text/plain".to_owned()),
+            "unsupported media type",
+        );
+    }
+}
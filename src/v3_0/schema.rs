@@ -1,21 +1,32 @@
 //! Schema Object
 
 use monostate::MustBe;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
 use std::fmt::{Display, Formatter};
 
-use serde::{Deserialize, Serialize};
+use serde::de::{Error, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::common::bool_or::BoolOr;
+use crate::common::extensions::Extensions;
 use crate::common::formats::{IntegerFormat, NumberFormat, StringFormat};
-use crate::common::helpers::{validate_pattern, Context, ValidateWithContext};
-use crate::common::reference::RefOr;
+use crate::common::helpers::{
+    validate_extensions, validate_format, validate_pattern, Context, PushError, ValidateWithContext,
+};
+use crate::common::parse::from_map;
+use crate::common::path::Path;
+use crate::common::reference::{RefOr, ResolveReference};
+use crate::common::schema::{
+    enum_values, properties, Constraints, SchemaKind, SchemaLike, SchemaRef,
+};
 use crate::v3_0::discriminator::Discriminator;
 use crate::v3_0::external_documentation::ExternalDocumentation;
 use crate::v3_0::spec::Spec;
 use crate::v3_0::xml::XML;
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 #[serde(untagged)]
 pub enum Schema {
     AllOf(AllOfSchema),
@@ -37,13 +48,44 @@ pub struct AllOfSchema {
     #[serde(rename = "allOf")]
     pub all_of: Vec<RefOr<Box<Schema>>>,
 
+    /// A title to explain the purpose of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A short description of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Declares the property as "read only".
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    /// Declares the property as "write only".
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed values of the composition.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 
     /// Adds support for polymorphism.
     /// The discriminator is an object name that is used to differentiate between other schemas
@@ -59,6 +101,37 @@ pub struct AnyOfSchema {
     #[serde(rename = "anyOf")]
     pub any_of: Vec<RefOr<Box<Schema>>>,
 
+    /// A title to explain the purpose of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A short description of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Declares the property as "read only".
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    /// Declares the property as "write only".
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed values of the composition.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// Adds support for polymorphism.
     /// The discriminator is an object name that is used to differentiate between other schemas
     /// which may satisfy the payload description.
@@ -72,7 +145,7 @@ pub struct AnyOfSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -81,6 +154,37 @@ pub struct OneOfSchema {
     #[serde(rename = "oneOf")]
     pub one_of: Vec<RefOr<Box<Schema>>>,
 
+    /// A title to explain the purpose of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A short description of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Declares the property as "read only".
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    /// Declares the property as "write only".
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed values of the composition.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// Adds support for polymorphism.
     /// The discriminator is an object name that is used to differentiate between other schemas
     /// which may satisfy the payload description.
@@ -94,25 +198,55 @@ pub struct OneOfSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct NotSchema {
-    /// **Required** The list of schemas that this schema is composed of.
-    #[serde(rename = "allOf")]
+    /// **Required** The schema that the value must not be valid against.
     pub not: RefOr<Box<Schema>>,
 
+    /// A title to explain the purpose of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// A short description of the schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Declares the property as "read only".
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    /// Declares the property as "write only".
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed values of the composition.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// Allows extensions to the Swagger Schema.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
     /// The value can be null, a primitive, an array or an object.
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum SingleSchema {
     #[serde(rename = "string")]
@@ -157,6 +291,239 @@ impl Display for SingleSchema {
     }
 }
 
+const TYPES: &[&str] = &[
+    "string", "integer", "number", "boolean", "array", "null", "object",
+];
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RawVisitor)?.into_schema()
+    }
+}
+
+impl<'de> Deserialize<'de> for SingleSchema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RawVisitor)?.into_single()
+    }
+}
+
+/// The fields of a schema read in one pass.
+///
+/// The nested schemas are deserialized in place, so each level of the document is read once,
+/// the other keywords are collected to choose and build the variant.
+#[derive(Default)]
+struct RawSchema {
+    all_of: Option<Vec<RefOr<Box<Schema>>>>,
+    any_of: Option<Vec<RefOr<Box<Schema>>>>,
+    one_of: Option<Vec<RefOr<Box<Schema>>>>,
+    not: Option<RefOr<Box<Schema>>>,
+    items: Option<RefOr<Box<Schema>>>,
+    properties: Option<BTreeMap<String, RefOr<Box<Schema>>>>,
+    additional_properties: Option<BoolOr<RefOr<Box<Schema>>>>,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RawSchema {
+    /// Chooses the variant by the composition keywords, then by the `type` field.
+    fn into_schema<E: Error>(mut self) -> Result<Schema, E> {
+        // the placeholders of the required fields are replaced by the deserialized schemas
+        let schema = if let Some(all_of) = self.all_of {
+            self.fields
+                .insert("allOf".to_owned(), serde_json::Value::Array(Vec::new()));
+            Schema::AllOf(AllOfSchema {
+                all_of,
+                ..from_map(self.fields)?
+            })
+        } else if let Some(any_of) = self.any_of {
+            self.fields
+                .insert("anyOf".to_owned(), serde_json::Value::Array(Vec::new()));
+            Schema::AnyOf(AnyOfSchema {
+                any_of,
+                ..from_map(self.fields)?
+            })
+        } else if let Some(one_of) = self.one_of {
+            self.fields
+                .insert("oneOf".to_owned(), serde_json::Value::Array(Vec::new()));
+            Schema::OneOf(OneOfSchema {
+                one_of,
+                ..from_map(self.fields)?
+            })
+        } else if let Some(not) = self.not {
+            self.fields.insert(
+                "not".to_owned(),
+                serde_json::Value::Object(Default::default()),
+            );
+            Schema::Not(NotSchema {
+                not,
+                ..from_map(self.fields)?
+            })
+        } else {
+            Schema::Single(self.into_single()?)
+        };
+        Ok(schema)
+    }
+
+    /// Chooses the variant by the `type` field, the object schema when the type is not set.
+    fn into_single<E: Error>(self) -> Result<SingleSchema, E> {
+        let fields = self.fields;
+        let schema = match fields.get("type").and_then(serde_json::Value::as_str) {
+            Some("string") => SingleSchema::String(from_map(fields)?),
+            Some("integer") => SingleSchema::Integer(from_map(fields)?),
+            Some("number") => SingleSchema::Number(from_map(fields)?),
+            Some("boolean") => SingleSchema::Boolean(from_map(fields)?),
+            Some("array") => SingleSchema::Array(ArraySchema {
+                items: self.items,
+                ..from_map(fields)?
+            }),
+            Some("null") => SingleSchema::Null(from_map(fields)?),
+            Some("object") | None => SingleSchema::Object(ObjectSchema {
+                properties: self.properties,
+                additional_properties: self.additional_properties,
+                ..from_map(fields)?
+            }),
+            Some(unknown) => return Err(E::unknown_variant(unknown, TYPES)),
+        };
+        Ok(schema)
+    }
+
+    /// Returns the reference when the schema has the `$ref` string, the boxed schema otherwise.
+    fn into_ref_or<E: Error>(self) -> Result<RefOr<Box<Schema>>, E> {
+        if self
+            .fields
+            .get("$ref")
+            .is_some_and(serde_json::Value::is_string)
+        {
+            Ok(RefOr::Ref(from_map(self.fields)?))
+        } else {
+            Ok(RefOr::Item(Box::new(self.into_schema()?)))
+        }
+    }
+}
+
+/// Collects the fields of a schema, the nested schemas are read by [Nested].
+struct RawVisitor;
+
+impl<'de> Visitor<'de> for RawVisitor {
+    type Value = RawSchema;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        // the `struct` prefix makes the error locations use the field notation for the keywords
+        formatter.write_str("struct Schema")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        fn nested(x: Nested) -> RefOr<Box<Schema>> {
+            x.0
+        }
+
+        let mut raw = RawSchema::default();
+        let mut keys = HashSet::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if !keys.insert(key.clone()) {
+                return Err(Error::custom(format!("duplicate field '{}'", key)));
+            }
+            match key.as_str() {
+                "allOf" => {
+                    raw.all_of = Some(
+                        map.next_value::<Vec<Nested>>()?
+                            .into_iter()
+                            .map(nested)
+                            .collect(),
+                    )
+                }
+                "anyOf" => {
+                    raw.any_of = Some(
+                        map.next_value::<Vec<Nested>>()?
+                            .into_iter()
+                            .map(nested)
+                            .collect(),
+                    )
+                }
+                "oneOf" => {
+                    raw.one_of = Some(
+                        map.next_value::<Vec<Nested>>()?
+                            .into_iter()
+                            .map(nested)
+                            .collect(),
+                    )
+                }
+                "not" => raw.not = Some(map.next_value::<Nested>()?.0),
+                "items" => raw.items = Some(map.next_value::<Nested>()?.0),
+                "properties" => {
+                    let properties = map.next_value::<BTreeMap<String, Nested>>()?;
+                    raw.properties = Some(properties.into_iter().map(|(k, v)| (k, v.0)).collect());
+                }
+                "additionalProperties" => {
+                    raw.additional_properties = Some(map.next_value::<NestedOr>()?.0)
+                }
+                _ => {
+                    let value = map.next_value()?;
+                    raw.fields.insert(key, value);
+                }
+            }
+        }
+        Ok(raw)
+    }
+}
+
+/// A nested schema or a reference, read without buffering the value.
+struct Nested(RefOr<Box<Schema>>);
+
+impl<'de> Deserialize<'de> for Nested {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_map(RawVisitor)?
+            .into_ref_or()
+            .map(Nested)
+    }
+}
+
+/// The value of `additionalProperties`, a boolean or a nested schema.
+struct NestedOr(BoolOr<RefOr<Box<Schema>>>);
+
+impl<'de> Deserialize<'de> for NestedOr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(NestedOrVisitor)
+    }
+}
+
+struct NestedOrVisitor;
+
+impl<'de> Visitor<'de> for NestedOrVisitor {
+    type Value = NestedOr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean or a schema object")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(NestedOr(BoolOr::Bool(v)))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let raw = RawVisitor.visit_map(map)?;
+        Ok(NestedOr(BoolOr::Item(raw.into_ref_or()?)))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct StringSchema {
     #[serde(rename = "type")]
@@ -210,6 +577,26 @@ pub struct StringSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -231,7 +618,7 @@ pub struct StringSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -264,7 +651,7 @@ pub struct IntegerSchema {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<i64>,
+    pub minimum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly greater than the value of `minimum`
     #[serde(rename = "exclusiveMinimum")]
@@ -273,7 +660,7 @@ pub struct IntegerSchema {
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<i64>,
+    pub maximum: Option<serde_json::Number>,
 
     /// Declares that the value of the parameter is strictly less than the value of `maximum`
     #[serde(rename = "exclusiveMaximum")]
@@ -296,6 +683,26 @@ pub struct IntegerSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -317,7 +724,7 @@ pub struct IntegerSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -341,12 +748,12 @@ pub struct NumberSchema {
     ///
     /// **Note**: "default" has no meaning for required headers.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<f64>,
+    pub default: Option<serde_json::Number>,
 
     /// The list of strings that defines the possible values of this parameter.
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<f64>>,
+    pub enum_values: Option<Vec<serde_json::Number>>,
 
     /// Declares the minimum value of the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -382,6 +789,26 @@ pub struct NumberSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -403,7 +830,7 @@ pub struct NumberSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -436,6 +863,26 @@ pub struct BooleanSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -457,7 +904,7 @@ pub struct BooleanSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -507,6 +954,26 @@ pub struct ArraySchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -528,7 +995,7 @@ pub struct ArraySchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -585,6 +1052,26 @@ pub struct ObjectSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// A `true` value adds "null" to the allowed types specified by the type keyword.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -606,7 +1093,7 @@ pub struct ObjectSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl Default for ObjectSchema {
@@ -622,6 +1109,9 @@ impl Default for ObjectSchema {
             additional_properties: None,
             required: None,
             read_only: None,
+            write_only: None,
+            deprecated: None,
+            nullable: None,
             xml: None,
             external_docs: None,
             example: None,
@@ -654,6 +1144,21 @@ pub struct NullSchema {
     #[serde(rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    /// Relevant only for Schema "properties" definitions.
+    /// Declares the property as "write only".
+    /// Therefore, it MAY be sent as part of a request but SHOULD NOT be sent as part of
+    /// the response.
+    /// A property MUST NOT be marked as both readOnly and writeOnly being true.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     /// This MAY be used only on properties schemas.
     /// It has no effect on root schemas.
     /// Adds Additional metadata to describe the XML representation format of this property.
@@ -675,47 +1180,456 @@ pub struct NullSchema {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl Schema {
+    /// Returns the specification extensions of the schema.
+    pub fn extensions(&self) -> Option<&Extensions> {
+        match self {
+            Schema::AllOf(s) => s.extensions.as_ref(),
+            Schema::AnyOf(s) => s.extensions.as_ref(),
+            Schema::OneOf(s) => s.extensions.as_ref(),
+            Schema::Not(s) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::String(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Integer(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Number(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Boolean(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Array(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Object(s)) => s.extensions.as_ref(),
+            Schema::Single(SingleSchema::Null(s)) => s.extensions.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the specification extensions of the schema.
+    pub fn extensions_mut(&mut self) -> &mut Option<Extensions> {
+        match self {
+            Schema::AllOf(s) => &mut s.extensions,
+            Schema::AnyOf(s) => &mut s.extensions,
+            Schema::OneOf(s) => &mut s.extensions,
+            Schema::Not(s) => &mut s.extensions,
+            Schema::Single(SingleSchema::String(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Integer(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Number(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Boolean(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Array(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Object(s)) => &mut s.extensions,
+            Schema::Single(SingleSchema::Null(s)) => &mut s.extensions,
+        }
+    }
+
+    /// Returns `true` if the schema is declared as "read only".
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Schema::AllOf(s) => s.read_only.unwrap_or(false),
+            Schema::AnyOf(s) => s.read_only.unwrap_or(false),
+            Schema::OneOf(s) => s.read_only.unwrap_or(false),
+            Schema::Not(s) => s.read_only.unwrap_or(false),
+            Schema::Single(s) => s.is_read_only(),
+        }
+    }
+
+    /// Returns `true` if the schema is declared as "write only".
+    pub fn is_write_only(&self) -> bool {
+        match self {
+            Schema::AllOf(s) => s.write_only.unwrap_or(false),
+            Schema::AnyOf(s) => s.write_only.unwrap_or(false),
+            Schema::OneOf(s) => s.write_only.unwrap_or(false),
+            Schema::Not(s) => s.write_only.unwrap_or(false),
+            Schema::Single(s) => s.is_write_only(),
+        }
+    }
+
+    /// Returns `true` if the schema is declared as deprecated.
+    pub fn is_deprecated(&self) -> bool {
+        match self {
+            Schema::AllOf(s) => s.deprecated.unwrap_or(false),
+            Schema::AnyOf(s) => s.deprecated.unwrap_or(false),
+            Schema::OneOf(s) => s.deprecated.unwrap_or(false),
+            Schema::Not(s) => s.deprecated.unwrap_or(false),
+            Schema::Single(s) => s.is_deprecated(),
+        }
+    }
+
+    /// Returns `true` if the schema allows the `null` value.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Schema::AllOf(s) => s.nullable.unwrap_or(false),
+            Schema::AnyOf(s) => s.nullable.unwrap_or(false),
+            Schema::OneOf(s) => s.nullable.unwrap_or(false),
+            Schema::Not(s) => s.nullable.unwrap_or(false),
+            Schema::Single(s) => s.is_nullable(),
+        }
+    }
+
+    /// Returns whether the property is defined and whether it is required in the schema,
+    /// taking into account all the `allOf` schemas.
+    pub(crate) fn lookup_property<'a>(
+        &'a self,
+        name: &str,
+        spec: &'a Spec,
+        visited: &mut HashSet<&'a str>,
+    ) -> (bool, bool) {
+        match self {
+            Schema::Single(SingleSchema::Object(o)) => (
+                o.properties.as_ref().is_some_and(|x| x.contains_key(name)),
+                o.required
+                    .as_ref()
+                    .is_some_and(|x| x.iter().any(|x| x == name)),
+            ),
+            Schema::AllOf(s) => {
+                let mut res = (false, false);
+                for schema in s.all_of.iter() {
+                    let schema = match schema {
+                        RefOr::Ref(r) => {
+                            if !visited.insert(r.reference.as_str()) {
+                                continue;
+                            }
+                            match spec.resolve_reference(&r.reference) {
+                                Some(schema) => schema,
+                                None => continue,
+                            }
+                        }
+                        RefOr::Item(schema) => schema.as_ref(),
+                    };
+                    let (defined, required) = schema.lookup_property(name, spec, visited);
+                    res = (res.0 || defined, res.1 || required);
+                }
+                res
+            }
+            _ => (false, false),
+        }
+    }
+
+    /// Reports the read-only properties listed in `required` lists,
+    /// which makes the schema impossible to satisfy when it is sent as part of a request.
+    pub(crate) fn validate_read_only_required(
+        &self,
+        ctx: &mut Context<Spec>,
+        path: Path,
+        visited: &mut HashSet<String>,
+    ) {
+        let nested: Vec<(&RefOr<Box<Schema>>, Path)> = match self {
+            Schema::AllOf(s) => s
+                .all_of
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (x, path.field("allOf").index(i)))
+                .collect(),
+            Schema::AnyOf(s) => s
+                .any_of
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (x, path.field("anyOf").index(i)))
+                .collect(),
+            Schema::OneOf(s) => s
+                .one_of
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (x, path.field("oneOf").index(i)))
+                .collect(),
+            Schema::Not(_) => vec![],
+            Schema::Single(SingleSchema::Array(s)) => {
+                s.items.iter().map(|x| (x, path.field("items"))).collect()
+            }
+            Schema::Single(SingleSchema::Object(s)) => {
+                if let (Some(required), Some(properties)) = (&s.required, &s.properties) {
+                    for name in required {
+                        if properties
+                            .get(name)
+                            .and_then(|x| resolve_boxed(x, ctx.spec))
+                            .is_some_and(|x| x.is_read_only())
+                        {
+                            ctx.error(
+                                path.field("required"),
+                                format_args!(
+                                    "read-only property `{}` must not be required in a request",
+                                    name
+                                ),
+                            );
+                        }
+                    }
+                }
+                s.properties
+                    .iter()
+                    .flatten()
+                    .map(|(name, x)| (x, path.field("properties").field(name.to_owned())))
+                    .collect()
+            }
+            Schema::Single(_) => vec![],
+        };
+        for (schema, path) in nested {
+            if let RefOr::Ref(r) = schema {
+                if !visited.insert(r.reference.clone()) {
+                    continue;
+                }
+            }
+            if let Some(schema) = resolve_boxed(schema, ctx.spec) {
+                schema.validate_read_only_required(ctx, path, visited);
+            }
+        }
+    }
+}
+
+fn resolve_boxed<'a>(schema: &'a RefOr<Box<Schema>>, spec: &'a Spec) -> Option<&'a Schema> {
+    match schema {
+        RefOr::Ref(r) => spec.resolve_reference(&r.reference),
+        RefOr::Item(schema) => Some(schema.as_ref()),
+    }
+}
+
+impl SchemaLike for Schema {
+    fn kind(&self) -> SchemaKind {
+        match self {
+            Schema::AllOf(_) => SchemaKind::AllOf,
+            Schema::AnyOf(_) => SchemaKind::AnyOf,
+            Schema::OneOf(_) => SchemaKind::OneOf,
+            Schema::Not(_) => SchemaKind::Not,
+            Schema::Single(SingleSchema::String(_)) => SchemaKind::String,
+            Schema::Single(SingleSchema::Integer(_)) => SchemaKind::Integer,
+            Schema::Single(SingleSchema::Number(_)) => SchemaKind::Number,
+            Schema::Single(SingleSchema::Boolean(_)) => SchemaKind::Boolean,
+            Schema::Single(SingleSchema::Array(_)) => SchemaKind::Array,
+            Schema::Single(SingleSchema::Null(_)) => SchemaKind::Null,
+            Schema::Single(SingleSchema::Object(s)) if s.has_type() => SchemaKind::Object,
+            Schema::Single(SingleSchema::Object(_)) => SchemaKind::Any,
+        }
+    }
+
+    fn title(&self) -> Option<&str> {
+        match self {
+            Schema::Single(SingleSchema::String(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Integer(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Number(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Boolean(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Array(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Null(s)) => s.title.as_deref(),
+            Schema::Single(SingleSchema::Object(s)) => s.title.as_deref(),
+            Schema::AllOf(s) => s.title.as_deref(),
+            Schema::AnyOf(s) => s.title.as_deref(),
+            Schema::OneOf(s) => s.title.as_deref(),
+            Schema::Not(s) => s.title.as_deref(),
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Schema::Single(SingleSchema::String(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Integer(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Number(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Boolean(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Array(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Null(s)) => s.description.as_deref(),
+            Schema::Single(SingleSchema::Object(s)) => s.description.as_deref(),
+            Schema::AllOf(s) => s.description.as_deref(),
+            Schema::AnyOf(s) => s.description.as_deref(),
+            Schema::OneOf(s) => s.description.as_deref(),
+            Schema::Not(s) => s.description.as_deref(),
+        }
+    }
+
+    fn constraints(&self) -> Constraints<'_> {
+        let constraints = Constraints {
+            read_only: self.is_read_only(),
+            nullable: self.is_nullable(),
+            ..Default::default()
+        };
+        let single = match self {
+            Schema::Single(single) => single,
+            _ => return constraints,
+        };
+        match single {
+            SingleSchema::String(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                min_length: s.min_length,
+                max_length: s.max_length,
+                pattern: s.pattern.as_deref(),
+                ..constraints
+            },
+            SingleSchema::Integer(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                minimum: s.minimum.as_ref().and_then(|x| x.as_f64()),
+                exclusive_minimum: s.exclusive_minimum.unwrap_or(false),
+                maximum: s.maximum.as_ref().and_then(|x| x.as_f64()),
+                exclusive_maximum: s.exclusive_maximum.unwrap_or(false),
+                multiple_of: s.multiple_of,
+                ..constraints
+            },
+            SingleSchema::Number(s) => Constraints {
+                format: s.format.as_ref().map(|x| x.to_string()),
+                enum_values: enum_values(&s.enum_values),
+                minimum: s.minimum,
+                exclusive_minimum: s.exclusive_minimum.unwrap_or(false),
+                maximum: s.maximum,
+                exclusive_maximum: s.exclusive_maximum.unwrap_or(false),
+                multiple_of: s.multiple_of,
+                ..constraints
+            },
+            SingleSchema::Array(s) => Constraints {
+                min_items: s.min_items,
+                max_items: s.max_items,
+                unique_items: s.unique_items.unwrap_or(false),
+                ..constraints
+            },
+            SingleSchema::Object(s) => Constraints {
+                min_properties: s.min_properties,
+                max_properties: s.max_properties,
+                required: s.required.as_deref().unwrap_or_default(),
+                ..constraints
+            },
+            SingleSchema::Boolean(_) | SingleSchema::Null(_) => constraints,
+        }
+    }
+
+    fn properties(&self) -> Box<dyn Iterator<Item = (&str, SchemaRef<'_>)> + '_> {
+        match self {
+            Schema::Single(SingleSchema::Object(s)) => properties(&s.properties),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn items(&self) -> Option<SchemaRef<'_>> {
+        match self {
+            Schema::Single(SingleSchema::Array(s)) => s.items.as_ref().map(Into::into),
+            _ => None,
+        }
+    }
+
+    fn members(&self) -> Vec<SchemaRef<'_>> {
+        match self {
+            Schema::AllOf(s) => s.all_of.iter().map(Into::into).collect(),
+            Schema::AnyOf(s) => s.any_of.iter().map(Into::into).collect(),
+            Schema::OneOf(s) => s.one_of.iter().map(Into::into).collect(),
+            Schema::Not(s) => vec![(&s.not).into()],
+            Schema::Single(_) => vec![],
+        }
+    }
 }
 
 impl ValidateWithContext<Spec> for Schema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        if !matches!(self, Schema::Single(_)) && self.is_read_only() && self.is_write_only() {
+            ctx.error(
+                path.clone(),
+                "must not be marked as both `readOnly` and `writeOnly`",
+            );
+        }
         match self {
             Schema::Single(s) => s.validate_with_context(ctx, path),
             Schema::AllOf(s) => {
+                validate_extensions(&s.extensions, ctx, path.clone());
                 for (i, schema) in s.all_of.iter().enumerate() {
-                    schema.validate_with_context_boxed(ctx, format!("{}.allOf[{}]", path, i));
+                    schema.validate_with_context_boxed(ctx, path.field("allOf").index(i));
                 }
                 if let Some(discriminator) = &s.discriminator {
-                    discriminator.validate_with_context(ctx, format!("{}.discriminator", path));
+                    discriminator.validate_with_context(ctx, path.field("discriminator"));
                 }
             }
             Schema::AnyOf(s) => {
+                validate_extensions(&s.extensions, ctx, path.clone());
                 for (i, schema) in s.any_of.iter().enumerate() {
-                    schema.validate_with_context_boxed(ctx, format!("{}.anyOf[{}]", path, i));
+                    schema.validate_with_context_boxed(ctx, path.field("anyOf").index(i));
                 }
                 if let Some(discriminator) = &s.discriminator {
-                    discriminator.validate_with_context(ctx, format!("{}.discriminator", path));
+                    discriminator.validate_with_context(ctx, path.field("discriminator"));
+                    discriminator.validate_schemas(&s.any_of, ctx, path.field("anyOf"));
                 }
             }
             Schema::OneOf(s) => {
+                validate_extensions(&s.extensions, ctx, path.clone());
                 for (i, schema) in s.one_of.iter().enumerate() {
-                    schema.validate_with_context_boxed(ctx, format!("{}.oneOf[{}]", path, i));
+                    schema.validate_with_context_boxed(ctx, path.field("oneOf").index(i));
                 }
                 if let Some(discriminator) = &s.discriminator {
-                    discriminator.validate_with_context(ctx, format!("{}.discriminator", path));
+                    discriminator.validate_with_context(ctx, path.field("discriminator"));
+                    discriminator.validate_schemas(&s.one_of, ctx, path.field("oneOf"));
                 }
             }
             Schema::Not(s) => {
-                s.not
-                    .validate_with_context_boxed(ctx, format!("{}.not", path));
+                validate_extensions(&s.extensions, ctx, path.clone());
+                s.not.validate_with_context_boxed(ctx, path.field("not"));
             }
         }
     }
 }
 
+impl SingleSchema {
+    /// Returns `true` if the schema is declared as "read only".
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            SingleSchema::String(s) => s.read_only,
+            SingleSchema::Integer(s) => s.read_only,
+            SingleSchema::Number(s) => s.read_only,
+            SingleSchema::Boolean(s) => s.read_only,
+            SingleSchema::Array(s) => s.read_only,
+            SingleSchema::Object(s) => s.read_only,
+            SingleSchema::Null(s) => s.read_only,
+        }
+        .unwrap_or(false)
+    }
+
+    /// Returns `true` if the schema is declared as "write only".
+    pub fn is_write_only(&self) -> bool {
+        match self {
+            SingleSchema::String(s) => s.write_only,
+            SingleSchema::Integer(s) => s.write_only,
+            SingleSchema::Number(s) => s.write_only,
+            SingleSchema::Boolean(s) => s.write_only,
+            SingleSchema::Array(s) => s.write_only,
+            SingleSchema::Object(s) => s.write_only,
+            SingleSchema::Null(s) => s.write_only,
+        }
+        .unwrap_or(false)
+    }
+
+    /// Returns `true` if the schema is declared as deprecated.
+    pub fn is_deprecated(&self) -> bool {
+        match self {
+            SingleSchema::String(s) => s.deprecated,
+            SingleSchema::Integer(s) => s.deprecated,
+            SingleSchema::Number(s) => s.deprecated,
+            SingleSchema::Boolean(s) => s.deprecated,
+            SingleSchema::Array(s) => s.deprecated,
+            SingleSchema::Object(s) => s.deprecated,
+            SingleSchema::Null(s) => s.deprecated,
+        }
+        .unwrap_or(false)
+    }
+
+    /// Returns `true` if the schema allows the `null` value,
+    /// the null schema always does.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            SingleSchema::String(s) => s.nullable,
+            SingleSchema::Integer(s) => s.nullable,
+            SingleSchema::Number(s) => s.nullable,
+            SingleSchema::Boolean(s) => s.nullable,
+            SingleSchema::Array(s) => s.nullable,
+            SingleSchema::Object(s) => s.nullable,
+            SingleSchema::Null(_) => Some(true),
+        }
+        .unwrap_or(false)
+    }
+}
+
+impl ObjectSchema {
+    /// Returns `true` if the `type` field is defined,
+    /// a schema without the type accepts any value.
+    pub fn has_type(&self) -> bool {
+        !self._type.is_empty()
+    }
+}
+
 impl ValidateWithContext<Spec> for SingleSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        if self.is_read_only() && self.is_write_only() {
+            ctx.error(
+                path.clone(),
+                "must not be marked as both `readOnly` and `writeOnly`",
+            );
+        }
         match self {
             SingleSchema::String(s) => s.validate_with_context(ctx, path),
             SingleSchema::Integer(s) => s.validate_with_context(ctx, path),
@@ -729,79 +1643,89 @@ impl ValidateWithContext<Spec> for SingleSchema {
 }
 
 impl ValidateWithContext<Spec> for StringSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
+        validate_format(&self.format, ctx, path.field("format"));
         if let Some(pattern) = &self.pattern {
-            validate_pattern(pattern, ctx, format!("{}.pattern", path));
+            validate_pattern(pattern, ctx, path.field("pattern"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for IntegerSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for NumberSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for BooleanSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for ArraySchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
 
         if let Some(items) = &self.items {
-            items.validate_with_context_boxed(ctx, format!("{}.items", path));
+            items.validate_with_context_boxed(ctx, path.field("items"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for ObjectSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
 
         if let Some(properties) = &self.properties {
             for (name, schema) in properties {
-                schema.validate_with_context_boxed(ctx, format!("{}.properties.{}", path, name));
+                schema.validate_with_context_boxed(
+                    ctx,
+                    path.field("properties").field(name.to_owned()),
+                );
             }
         }
 
@@ -809,8 +1733,7 @@ impl ValidateWithContext<Spec> for ObjectSchema {
             match additional_properties {
                 BoolOr::Bool(_) => {}
                 BoolOr::Item(schema) => {
-                    schema
-                        .validate_with_context_boxed(ctx, format!("{}.additionalProperties", path));
+                    schema.validate_with_context_boxed(ctx, path.field("additionalProperties"));
                 }
             }
         }
@@ -818,12 +1741,13 @@ impl ValidateWithContext<Spec> for ObjectSchema {
 }
 
 impl ValidateWithContext<Spec> for NullSchema {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(ctx, format!("{}.externalDocs", path));
+            docs.validate_with_context(ctx, path.field("externalDocs"));
         }
         if let Some(xml) = &self.xml {
-            xml.validate_with_context(ctx, format!("{}.xml", path));
+            xml.validate_with_context(ctx, path.field("xml"));
         }
     }
 }
@@ -937,6 +1861,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_not_round_trip() {
+        let value = serde_json::json!({"not": {"type": "string"}});
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        assert!(
+            matches!(&schema, Schema::Not(s) if matches!(&s.not, RefOr::Item(x) if matches!(**x, Schema::Single(SingleSchema::String(_))))),
+            "not schema",
+        );
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value);
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let err = serde_json::from_str::<Schema>(r#"{"type": "string", "maxLength": "ten"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("invalid type: string \"ten\", expected u64"),
+            "error of the typed schema: {}",
+            err
+        );
+        let err = serde_json::from_str::<Schema>(r#"{"type": "strnig"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("unknown variant `strnig`"),
+            "unknown type: {}",
+            err
+        );
+        let err = serde_json::from_str::<Schema>(r#"{"type": "string", "type": "integer"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("duplicate field 'type'"),
+            "duplicate: {}",
+            err
+        );
+        assert!(
+            serde_json::from_str::<Schema>("[]").is_err(),
+            "not an object"
+        );
+        let err = crate::common::parse::from_str::<Schema>(
+            r#"{"properties": {"a": {"allOf": [{"type": "array", "items": {"maxLength": "ten", "type": "string"}}]}}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.path.to_string(),
+            "#.properties[a].allOf[0].items.maxLength",
+            "location of the nested error",
+        );
+    }
+
     #[test]
     fn test_all_of_serialize() {
         assert_eq!(
@@ -966,4 +1942,107 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn test_flags_round_trip() {
+        for value in [
+            serde_json::json!({"type": "string", "nullable": true, "writeOnly": true}),
+            serde_json::json!({"type": "integer", "deprecated": true, "nullable": false}),
+            serde_json::json!({"type": "number", "writeOnly": false}),
+            serde_json::json!({"type": "boolean", "deprecated": true}),
+            serde_json::json!({"type": "array", "nullable": true, "deprecated": true}),
+            serde_json::json!({"type": "object", "writeOnly": true, "nullable": true}),
+            serde_json::json!({"type": "null", "deprecated": true, "writeOnly": true}),
+        ] {
+            let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+            assert_eq!(serde_json::to_value(schema).unwrap(), value, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_composition_flags_round_trip() {
+        for value in [
+            serde_json::json!({"allOf": [{"type": "string"}], "title": "foo", "nullable": true}),
+            serde_json::json!({"anyOf": [{"type": "string"}], "description": "foo", "readOnly": true}),
+            serde_json::json!({"oneOf": [{"type": "string"}], "deprecated": true, "writeOnly": true}),
+            serde_json::json!({"not": {"type": "string"}, "title": "foo", "nullable": false}),
+        ] {
+            let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&schema).unwrap(), value, "{}", value);
+        }
+
+        let schema = serde_json::from_value::<Schema>(serde_json::json!({
+            "oneOf": [{"type": "string"}, {"type": "integer"}],
+            "title": "foo",
+            "description": "bar",
+            "nullable": true,
+            "readOnly": true,
+            "deprecated": true,
+        }))
+        .unwrap();
+        assert_eq!(SchemaLike::title(&schema), Some("foo"));
+        assert_eq!(SchemaLike::description(&schema), Some("bar"));
+        assert!(schema.is_nullable(), "nullable");
+        assert!(schema.is_read_only(), "read only");
+        assert!(schema.is_deprecated(), "deprecated");
+        assert!(!schema.is_write_only(), "not write only");
+        assert!(schema.constraints().nullable, "nullable constraint");
+    }
+
+    #[test]
+    fn test_validate_read_only_and_write_only() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Default::default());
+        serde_json::from_value::<Schema>(serde_json::json!({
+            "type": "string",
+            "readOnly": true,
+            "writeOnly": true,
+        }))
+        .unwrap()
+        .validate_with_context(&mut ctx, Path::root().field("schema"));
+        assert_eq!(
+            ctx.errors,
+            vec!["#.schema: must not be marked as both `readOnly` and `writeOnly`"],
+            "read only and write only",
+        );
+
+        let mut ctx = Context::new(&spec, Default::default());
+        serde_json::from_value::<Schema>(serde_json::json!({
+            "allOf": [{"type": "string"}],
+            "readOnly": true,
+            "writeOnly": true,
+        }))
+        .unwrap()
+        .validate_with_context(&mut ctx, Path::root().field("schema"));
+        assert_eq!(
+            ctx.errors,
+            vec!["#.schema: must not be marked as both `readOnly` and `writeOnly`"],
+            "composition read only and write only",
+        );
+    }
+
+    #[test]
+    fn test_integer_bounds_round_trip() {
+        let value = serde_json::json!({
+            "type": "integer",
+            "minimum": i64::MIN,
+            "maximum": u64::MAX,
+        });
+        let schema = serde_json::from_value::<Schema>(value.clone()).unwrap();
+        if let Schema::Single(SingleSchema::Integer(integer)) = &schema {
+            assert_eq!(integer.minimum, Some(i64::MIN.into()), "minimum");
+            assert_eq!(integer.maximum, Some(u64::MAX.into()), "maximum");
+        } else {
+            panic!("expected IntegerSchema");
+        }
+        assert_eq!(serde_json::to_value(schema).unwrap(), value, "round trip");
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn test_number_arbitrary_precision() {
+        let raw = r#"{"type":"number","default":0.1000000000000000055,"enum":[0.1000000000000000055,9223372036854775807.5]}"#;
+        let schema = serde_json::from_str::<Schema>(raw).unwrap();
+        assert_eq!(serde_json::to_string(&schema).unwrap(), raw, "round trip");
+    }
 }
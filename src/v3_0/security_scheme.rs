@@ -3,12 +3,15 @@
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::common::extensions::Extensions;
 use crate::common::helpers::{
-    validate_optional_url, validate_required_string, validate_required_url, Context, PushError,
-    ValidateWithContext,
+    validate_extensions, validate_required_string, validate_required_uri_reference,
+    validate_uri_reference, Context, PushError, ValidateWithContext,
 };
+use crate::common::path::Path;
+use crate::common::url::{parse_optional, Url, UrlError};
 use crate::v3_0::spec::Spec;
 
 /// Defines a security scheme that can be used by the operations.
@@ -51,6 +54,7 @@ use crate::v3_0::spec::Spec;
 ///       read:pets: read your pets
 /// ```
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 #[serde(tag = "type")]
 pub enum SecurityScheme {
     /// Basic Authentication Type
@@ -74,7 +78,7 @@ impl Display for SecurityScheme {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             SecurityScheme::HTTP(_) => write!(f, "http"),
-            SecurityScheme::ApiKey(_) => write!(f, "aoiKey"),
+            SecurityScheme::ApiKey(_) => write!(f, "apiKey"),
             SecurityScheme::OAuth2(_) => write!(f, "oauth2"),
             SecurityScheme::OpenIdConnect(_) => write!(f, "openIdConnect"),
         }
@@ -106,70 +110,60 @@ pub struct HttpSecurityScheme {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// The HTTP Authorization schemes from
 /// [IANA Authentication Scheme registry](https://www.iana.org/assignments/http-authschemes/http-authschemes.xhtml).
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub enum HttpScheme {
     /// Basic Authentication Scheme
     /// [RFC7617](https://www.rfc-editor.org/rfc/rfc7617).
     #[default]
-    #[serde(alias = "basic")]
     Basic,
 
     /// Bearer Authentication Scheme
     /// [RFC6750](https://www.rfc-editor.org/rfc/rfc6750).
-    #[serde(alias = "bearer")]
     Bearer,
 
     /// Digest Authentication Scheme
     /// [RFC7616](https://www.rfc-editor.org/rfc/rfc7616).
-    #[serde(alias = "digest")]
     Digest,
 
     /// DPoP Authentication Scheme
     /// [RFC9449, Section 7.1](https://www.iana.org/go/rfc9449).
-    #[serde(alias = "dpop")]
     DPoP,
 
     /// HOBA Authentication Scheme
     /// [RFC7486, Section 3](https://www.rfc-editor.org/rfc/rfc7486).
-    #[serde(alias = "hoba")]
     HOBA,
 
     /// Mutual Authentication Scheme
     /// [RFC8120](https://www.rfc-editor.org/rfc/rfc8120).
-    #[serde(alias = "mutual")]
     Mutual,
 
     /// Negotiate Authentication Scheme
     /// [RFC4559, Section 3](https://www.rfc-editor.org/rfc/rfc4559).
-    #[serde(alias = "negotiate")]
     Negotiate,
 
     /// OAuth Authentication Scheme
     /// [RFC5849, Section 3.5.1](https://www.rfc-editor.org/rfc/rfc5849).
-    #[serde(alias = "oauth")]
     OAuth,
 
     /// SCRAM SHA 1 Authentication Scheme
     /// [RFC7804](https://www.iana.org/go/rfc7804).
-    #[serde(alias = "scram-sha-1")]
-    #[serde(rename = "SCRAM-SHA-1")]
     SCRAMSHA1,
 
     /// SCRAM SHA 256 Authentication Scheme
     /// [RFC7804](https://www.iana.org/go/rfc7804).
-    #[serde(alias = "scram-sha-256")]
-    #[serde(rename = "SCRAM-SHA-256")]
     SCRAMSHA256,
 
     /// Vapid Authentication Scheme
     /// [RFC8292, Section 3](https://www.iana.org/go/rfc8292).
-    #[serde(rename = "vapid")]
     Vapid,
+
+    /// A scheme that is not registered in the IANA registry, stored as is.
+    Custom(String),
 }
 
 impl Display for HttpScheme {
@@ -186,8 +180,83 @@ impl Display for HttpScheme {
             HttpScheme::SCRAMSHA1 => write!(f, "SCRAM-SHA-1"),
             HttpScheme::SCRAMSHA256 => write!(f, "SCRAM-SHA-256"),
             HttpScheme::Vapid => write!(f, "vapid"),
+            HttpScheme::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl HttpScheme {
+    /// The registered schemes.
+    const REGISTERED: [HttpScheme; 11] = [
+        HttpScheme::Basic,
+        HttpScheme::Bearer,
+        HttpScheme::Digest,
+        HttpScheme::DPoP,
+        HttpScheme::HOBA,
+        HttpScheme::Mutual,
+        HttpScheme::Negotiate,
+        HttpScheme::OAuth,
+        HttpScheme::SCRAMSHA1,
+        HttpScheme::SCRAMSHA256,
+        HttpScheme::Vapid,
+    ];
+
+    /// Returns the registered scheme that is likely misspelled as the custom scheme.
+    fn suggestion(&self) -> Option<&'static HttpScheme> {
+        let HttpScheme::Custom(custom) = self else {
+            return None;
+        };
+        let custom = custom.to_lowercase();
+        HttpScheme::REGISTERED
+            .iter()
+            .find(|x| edit_distance(&custom, &x.to_string().to_lowercase()) <= 2)
+    }
+}
+
+impl From<&str> for HttpScheme {
+    /// The scheme names are case-insensitive.
+    fn from(value: &str) -> Self {
+        let lowercase = value.to_lowercase();
+        HttpScheme::REGISTERED
+            .iter()
+            .find(|x| x.to_string().to_lowercase() == lowercase)
+            .cloned()
+            .unwrap_or_else(|| HttpScheme::Custom(value.to_owned()))
+    }
+}
+
+impl Serialize for HttpScheme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HttpScheme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(HttpScheme::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if a == *b {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
         }
     }
+    row[b.len()]
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -210,7 +279,7 @@ pub struct ApiKeySecurityScheme {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// The location of the API key.
@@ -256,7 +325,7 @@ pub struct OAuth2SecurityScheme {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Allows configuration of the supported OAuth Flows.
@@ -288,7 +357,50 @@ pub struct OAuth2Flows {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl OAuth2Flows {
+    /// Returns all scopes defined by the flows, mapped to their descriptions.
+    ///
+    /// If a scope is defined by several flows, the description of the first flow is used
+    /// in the order: implicit, password, client credentials and authorization code.
+    pub fn all_scopes(&self) -> BTreeMap<&str, &str> {
+        let mut scopes = BTreeMap::new();
+        for flow_scopes in [
+            self.implicit.as_ref().map(|x| &x.scopes),
+            self.password.as_ref().map(|x| &x.scopes),
+            self.client_credentials.as_ref().map(|x| &x.scopes),
+            self.authorization_code.as_ref().map(|x| &x.scopes),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for (name, description) in flow_scopes {
+                scopes.entry(name.as_str()).or_insert(description.as_str());
+            }
+        }
+        scopes
+    }
+
+    /// Returns `true` if any of the flows defines the scope.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.implicit
+            .as_ref()
+            .is_some_and(|x| x.scopes.contains_key(scope))
+            || self
+                .password
+                .as_ref()
+                .is_some_and(|x| x.scopes.contains_key(scope))
+            || self
+                .client_credentials
+                .as_ref()
+                .is_some_and(|x| x.scopes.contains_key(scope))
+            || self
+                .authorization_code
+                .as_ref()
+                .is_some_and(|x| x.scopes.contains_key(scope))
+    }
 }
 
 /// Configuration details for the Implicit OAuth Flow
@@ -316,7 +428,19 @@ pub struct ImplicitOAuth2Flow {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl ImplicitOAuth2Flow {
+    /// Returns the authorization URL, an error if it is not a valid URL.
+    pub fn parsed_authorization_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.authorization_url)
+    }
+
+    /// Returns the refresh URL, an error if it is not a valid URL.
+    pub fn parsed_refresh_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.refresh_url)
+    }
 }
 
 /// Configuration details for the Password OAuth Flow
@@ -344,7 +468,19 @@ pub struct PasswordOAuth2Flow {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl PasswordOAuth2Flow {
+    /// Returns the token URL, an error if it is not a valid URL.
+    pub fn parsed_token_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.token_url)
+    }
+
+    /// Returns the refresh URL, an error if it is not a valid URL.
+    pub fn parsed_refresh_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.refresh_url)
+    }
 }
 
 /// Configuration details for the ClientCredentials OAuth Flow
@@ -372,7 +508,19 @@ pub struct ClientCredentialsOAuth2Flow {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl ClientCredentialsOAuth2Flow {
+    /// Returns the token URL, an error if it is not a valid URL.
+    pub fn parsed_token_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.token_url)
+    }
+
+    /// Returns the refresh URL, an error if it is not a valid URL.
+    pub fn parsed_refresh_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.refresh_url)
+    }
 }
 
 /// Configuration details for the ClientCredentials OAuth Flow
@@ -405,7 +553,24 @@ pub struct AuthorizationCodeOAuth2Flow {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+impl AuthorizationCodeOAuth2Flow {
+    /// Returns the authorization URL, an error if it is not a valid URL.
+    pub fn parsed_authorization_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.authorization_url)
+    }
+
+    /// Returns the token URL, an error if it is not a valid URL.
+    pub fn parsed_token_url(&self) -> Result<Url, UrlError> {
+        Url::parse(&self.token_url)
+    }
+
+    /// Returns the refresh URL, an error if it is not a valid URL.
+    pub fn parsed_refresh_url(&self) -> Result<Option<Url>, UrlError> {
+        parse_optional(&self.refresh_url)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -426,11 +591,56 @@ pub struct OpenIdConnectSecurityScheme {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+/// The OpenID Provider configuration returned by the URL of the OpenID Connect security scheme,
+/// as defined in [OpenID Connect Discovery](https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata).
+///
+/// The crate does not fetch the configuration,
+/// the type allows tooling to attach the discovered values, like the supported scopes.
+#[cfg(feature = "openid")]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct OpenIdConfiguration {
+    /// **Required** The URL that the OpenID Provider asserts as its Issuer Identifier.
+    pub issuer: String,
+
+    /// The URL of the OAuth 2.0 Authorization Endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_endpoint: Option<String>,
+
+    /// The URL of the OAuth 2.0 Token Endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_endpoint: Option<String>,
+
+    /// The URL of the JSON Web Key Set document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+
+    /// The list of the OAuth 2.0 scope values that the OpenID Provider supports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes_supported: Option<Vec<String>>,
+
+    /// The other provider metadata.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[cfg(feature = "openid")]
+impl OpenIdConfiguration {
+    /// Returns the scopes of the list that are not supported by the OpenID Provider.
+    ///
+    /// All scopes are supported if the provider does not publish `scopes_supported`.
+    pub fn unsupported_scopes<'a>(&self, scopes: &'a [String]) -> Vec<&'a String> {
+        match &self.scopes_supported {
+            Some(supported) => scopes.iter().filter(|x| !supported.contains(x)).collect(),
+            None => Vec::new(),
+        }
+    }
 }
 
 impl ValidateWithContext<Spec> for SecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
         match self {
             SecurityScheme::HTTP(http) => http.validate_with_context(ctx, path),
             SecurityScheme::ApiKey(api_key) => api_key.validate_with_context(ctx, path),
@@ -443,7 +653,17 @@ impl ValidateWithContext<Spec> for SecurityScheme {
 }
 
 impl ValidateWithContext<Spec> for HttpSecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if let Some(suggestion) = self.scheme.suggestion() {
+            ctx.error(
+                path.clone(),
+                format_args!(
+                    ".scheme: unknown scheme `{}`, did you mean `{}`?",
+                    self.scheme, suggestion
+                ),
+            );
+        }
         if let Some(bearer_format) = &self.bearer_format {
             if !bearer_format.is_empty() && self.scheme != HttpScheme::Bearer {
                 ctx.error(
@@ -456,78 +676,93 @@ impl ValidateWithContext<Spec> for HttpSecurityScheme {
 }
 
 impl ValidateWithContext<Spec> for ApiKeySecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.name, ctx, format!("{}.name", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.name, ctx, path.field("name"));
     }
 }
 
 impl ValidateWithContext<Spec> for OAuth2SecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        self.flows
-            .validate_with_context(ctx, format!("{}.flow", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        self.flows.validate_with_context(ctx, path.field("flows"));
     }
 }
 
 impl ValidateWithContext<Spec> for OAuth2Flows {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
         if let Some(flow) = &self.implicit {
-            flow.validate_with_context(ctx, format!("{}.implicit", path));
+            flow.validate_with_context(ctx, path.field("implicit"));
         }
         if let Some(flow) = &self.password {
-            flow.validate_with_context(ctx, format!("{}.password", path));
+            flow.validate_with_context(ctx, path.field("password"));
         }
         if let Some(flow) = &self.client_credentials {
-            flow.validate_with_context(ctx, format!("{}.clientCredentials", path));
+            flow.validate_with_context(ctx, path.field("clientCredentials"));
         }
         if let Some(flow) = &self.authorization_code {
-            flow.validate_with_context(ctx, format!("{}.authorizationCode", path));
+            flow.validate_with_context(ctx, path.field("authorizationCode"));
         }
     }
 }
 
 impl ValidateWithContext<Spec> for ImplicitOAuth2Flow {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_url(
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(
             &self.authorization_url,
             ctx,
-            format!("{}.authorizationUrl", path),
+            path.field("authorizationUrl"),
         );
-        validate_optional_url(&self.refresh_url, ctx, format!("{}.refreshUrl", path));
+        if let Some(url) = &self.refresh_url {
+            validate_uri_reference(url, ctx, path.field("refreshUrl"));
+        }
     }
 }
 
 impl ValidateWithContext<Spec> for PasswordOAuth2Flow {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_url(&self.token_url, ctx, format!("{}.tokenUrl", path));
-        validate_optional_url(&self.refresh_url, ctx, format!("{}.refreshUrl", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(&self.token_url, ctx, path.field("tokenUrl"));
+        if let Some(url) = &self.refresh_url {
+            validate_uri_reference(url, ctx, path.field("refreshUrl"));
+        }
     }
 }
 
 impl ValidateWithContext<Spec> for ClientCredentialsOAuth2Flow {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_url(&self.token_url, ctx, format!("{}.tokenUrl", path));
-        validate_optional_url(&self.refresh_url, ctx, format!("{}.refreshUrl", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(&self.token_url, ctx, path.field("tokenUrl"));
+        if let Some(url) = &self.refresh_url {
+            validate_uri_reference(url, ctx, path.field("refreshUrl"));
+        }
     }
 }
 
 impl ValidateWithContext<Spec> for AuthorizationCodeOAuth2Flow {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_url(
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(
             &self.authorization_url,
             ctx,
-            format!("{}.authorizationUrl", path),
+            path.field("authorizationUrl"),
         );
-        validate_required_url(&self.token_url, ctx, format!("{}.tokenUrl", path));
-        validate_optional_url(&self.refresh_url, ctx, format!("{}.refreshUrl", path));
+        validate_required_uri_reference(&self.token_url, ctx, path.field("tokenUrl"));
+        if let Some(url) = &self.refresh_url {
+            validate_uri_reference(url, ctx, path.field("refreshUrl"));
+        }
     }
 }
 
 impl ValidateWithContext<Spec> for OpenIdConnectSecurityScheme {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_url(
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_uri_reference(
             &self.open_id_connect_url,
             ctx,
-            format!("{}.openIdConnectUrl", path),
+            path.field("openIdConnectUrl"),
         );
     }
 }
@@ -1210,7 +1445,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert!(
             ctx.errors.is_empty(),
             "OpenIdConnect: no errors: {:?}",
@@ -1222,7 +1457,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             1,
@@ -1236,7 +1471,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert!(
             ctx.errors.is_empty(),
             "HTTP::Basic: no errors: {:?}",
@@ -1249,7 +1484,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert!(
             ctx.errors.is_empty(),
             "HTTP::Bearer with format: no errors: {:?}",
@@ -1262,7 +1497,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             1,
@@ -1276,7 +1511,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert!(ctx.errors.is_empty(), "ApiKey: no errors: {:?}", ctx.errors);
 
         SecurityScheme::ApiKey(ApiKeySecurityScheme {
@@ -1284,7 +1519,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(ctx.errors.len(), 1, "ApiKey: one error: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Options::new());
@@ -1295,14 +1530,14 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert!(ctx.errors.is_empty(), "OAuth2: no errors: {:?}", ctx.errors);
 
         SecurityScheme::OAuth2(OAuth2SecurityScheme {
             flows: OAuth2Flows {
                 implicit: Some(ImplicitOAuth2Flow {
-                    authorization_url: String::from("foo"),
-                    refresh_url: Some(String::from("bar")),
+                    authorization_url: String::from("fo o"),
+                    refresh_url: Some(String::from("ba r")),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -1310,7 +1545,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             2,
@@ -1322,8 +1557,8 @@ mod tests {
         SecurityScheme::OAuth2(OAuth2SecurityScheme {
             flows: OAuth2Flows {
                 password: Some(PasswordOAuth2Flow {
-                    token_url: String::from("foo"),
-                    refresh_url: Some(String::from("bar")),
+                    token_url: String::from("fo o"),
+                    refresh_url: Some(String::from("ba r")),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -1331,7 +1566,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             2,
@@ -1343,8 +1578,8 @@ mod tests {
         SecurityScheme::OAuth2(OAuth2SecurityScheme {
             flows: OAuth2Flows {
                 client_credentials: Some(ClientCredentialsOAuth2Flow {
-                    token_url: String::from("foo"),
-                    refresh_url: Some(String::from("bar")),
+                    token_url: String::from("fo o"),
+                    refresh_url: Some(String::from("ba r")),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -1352,7 +1587,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             2,
@@ -1364,9 +1599,9 @@ mod tests {
         SecurityScheme::OAuth2(OAuth2SecurityScheme {
             flows: OAuth2Flows {
                 authorization_code: Some(AuthorizationCodeOAuth2Flow {
-                    authorization_url: String::from("xyz"),
-                    token_url: String::from("foo"),
-                    refresh_url: Some(String::from("bar")),
+                    authorization_url: String::from("x yz"),
+                    token_url: String::from("fo o"),
+                    refresh_url: Some(String::from("ba r")),
                     ..Default::default()
                 }),
                 ..Default::default()
@@ -1374,7 +1609,7 @@ mod tests {
             description: Some(String::from("A short description for security scheme.")),
             ..Default::default()
         })
-        .validate_with_context(&mut ctx, String::from("securityScheme"));
+        .validate_with_context(&mut ctx, "securityScheme".into());
         assert_eq!(
             ctx.errors.len(),
             3,
@@ -1382,4 +1617,146 @@ mod tests {
             ctx.errors
         );
     }
+
+    #[test]
+    fn test_oauth2_flows_scopes() {
+        let flows = serde_json::from_value::<OAuth2Flows>(json!({
+            "implicit": {
+                "authorizationUrl": "https://example.com/api/oauth/dialog",
+                "scopes": {"read:pets": "read your pets"},
+            },
+            "authorizationCode": {
+                "authorizationUrl": "https://example.com/api/oauth/dialog",
+                "tokenUrl": "https://example.com/api/oauth/token",
+                "scopes": {"read:pets": "read pets", "write:pets": "modify pets"},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            flows.all_scopes(),
+            BTreeMap::from([
+                ("read:pets", "read your pets"),
+                ("write:pets", "modify pets")
+            ]),
+            "all scopes",
+        );
+        assert!(flows.has_scope("write:pets"), "authorization code scope");
+        assert!(!flows.has_scope("delete:pets"), "unknown scope");
+    }
+
+    #[test]
+    fn test_oauth2_flows_validation() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        SecurityScheme::OAuth2(OAuth2SecurityScheme {
+            flows: OAuth2Flows {
+                password: Some(PasswordOAuth2Flow {
+                    token_url: String::from("https:///token"),
+                    refresh_url: Some(String::from("https://example.com/re fresh")),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .validate_with_context(&mut ctx, "securityScheme".into());
+        assert_eq!(
+            ctx.errors,
+            vec![
+                "securityScheme.flows.password.tokenUrl: must be a valid URI reference, found `https:///token`",
+                "securityScheme.flows.password.refreshUrl: must be a valid URI reference, found `https://example.com/re fresh`",
+            ],
+            "malformed urls",
+        );
+
+        ctx = Context::new(&spec, Options::new());
+        SecurityScheme::OAuth2(OAuth2SecurityScheme {
+            flows: OAuth2Flows {
+                password: Some(PasswordOAuth2Flow {
+                    token_url: String::from("/oauth/token"),
+                    refresh_url: Some(String::from("../oauth/refresh")),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .validate_with_context(&mut ctx, "securityScheme".into());
+        assert!(ctx.errors.is_empty(), "relative urls: {:?}", ctx.errors);
+    }
+
+    #[test]
+    fn test_open_id_connect_url_validation() {
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        SecurityScheme::OpenIdConnect(OpenIdConnectSecurityScheme {
+            open_id_connect_url: String::from(
+                "https:// example.com/.well-known/openid-configuration",
+            ),
+            ..Default::default()
+        })
+        .validate_with_context(&mut ctx, "securityScheme".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["securityScheme.openIdConnectUrl: must be a valid URI reference, found `https:// example.com/.well-known/openid-configuration`"],
+            "malformed url",
+        );
+    }
+
+    #[cfg(feature = "openid")]
+    #[test]
+    fn test_open_id_configuration() {
+        let config = serde_json::from_value::<OpenIdConfiguration>(json!({
+            "issuer": "https://example.com",
+            "token_endpoint": "https://example.com/token",
+            "scopes_supported": ["openid", "email"],
+            "claims_supported": ["sub"],
+        }))
+        .unwrap();
+        assert_eq!(
+            config.other,
+            BTreeMap::from([("claims_supported".to_owned(), json!(["sub"]))]),
+            "other metadata",
+        );
+        let scopes = vec!["openid".to_owned(), "profile".to_owned()];
+        assert_eq!(
+            config.unsupported_scopes(&scopes),
+            vec!["profile"],
+            "unsupported scopes",
+        );
+    }
+
+    #[test]
+    fn test_http_scheme_custom() {
+        assert_eq!(
+            serde_json::from_value::<HttpScheme>(json!("bearer")).unwrap(),
+            HttpScheme::Bearer,
+            "case-insensitive",
+        );
+        assert_eq!(
+            serde_json::from_value::<HttpScheme>(json!("AWS4-HMAC-SHA256")).unwrap(),
+            HttpScheme::Custom(String::from("AWS4-HMAC-SHA256")),
+            "custom scheme",
+        );
+        assert_eq!(
+            serde_json::to_value(HttpScheme::Custom(String::from("AWS4-HMAC-SHA256"))).unwrap(),
+            json!("AWS4-HMAC-SHA256"),
+            "serialize custom scheme",
+        );
+
+        let spec = Spec::default();
+        let mut ctx = Context::new(&spec, Options::new());
+        for scheme in ["AWS4-HMAC-SHA256", "baerer"] {
+            SecurityScheme::HTTP(HttpSecurityScheme {
+                scheme: HttpScheme::from(scheme),
+                ..Default::default()
+            })
+            .validate_with_context(&mut ctx, "securityScheme".into());
+        }
+        assert_eq!(
+            ctx.errors,
+            vec!["securityScheme.scheme: unknown scheme `baerer`, did you mean `Bearer`?"],
+            "misspelled scheme",
+        );
+    }
 }
@@ -4,8 +4,15 @@ use std::collections::{BTreeMap, HashSet};
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::common::helpers::{validate_required_string, Context, PushError, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    validate_extensions, validate_required_string, validate_uri_reference, Context, PushError,
+    ValidateWithContext,
+};
+use crate::common::path::Path;
+use crate::common::url::{Url, UrlError};
 use crate::v3_0::spec::Spec;
 use crate::validation::Options;
 
@@ -56,7 +63,7 @@ pub struct Server {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// An object representing a Server Variable for server URL template substitution.
@@ -75,18 +82,19 @@ pub struct ServerVariable {
     /// An enumeration of string values to be used if the substitution options are from a limited set.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
-    enum_values: Option<Vec<String>>,
+    pub enum_values: Option<Vec<String>>,
 
     /// **Required** The default value to use for substitution,
     /// which SHALL be sent if an alternate value is not supplied.
     /// Note this behavior is different than the Schema Object’s treatment of default values,
     /// because in those cases parameter values are optional.
     /// If the enum is defined, the value SHOULD exist in the enum’s values.
-    default: String,
+    pub default: String,
 
     /// An optional description for the server variable.
     /// [CommonMark](https://spec.commonmark.org) syntax MAY be used for rich text representation.
-    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 
     /// This object MAY be extended with Specification Extensions.
     /// The field name MUST begin with `x-`, for example, `x-internal-id`.
@@ -94,20 +102,85 @@ pub struct ServerVariable {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
+}
+
+/// ServerUrlError is an error type for resolving the server URL.
+#[derive(Debug, Error, PartialEq)]
+pub enum ServerUrlError {
+    /// UndefinedVariable is returned when the variable has neither a value nor a default.
+    #[error("variable `{0}` is not defined")]
+    UndefinedVariable(String),
+
+    /// InvalidValue is returned when the value of the variable is not in its enum values.
+    #[error("value `{value}` of variable `{name}` must be in enum values: {enum_values:?}")]
+    InvalidValue {
+        name: String,
+        value: String,
+        enum_values: Vec<String>,
+    },
+
+    /// InvalidUrl is returned when the resolved URL is not an absolute URL.
+    #[error(transparent)]
+    InvalidUrl(#[from] UrlError),
+}
+
+impl Server {
+    /// Returns the server URL with all the variables substituted by the given values
+    /// or by their default values.
+    /// The given values are validated against the enum values of the variables.
+    pub fn resolve_url(&self, vars: &BTreeMap<&str, &str>) -> Result<String, ServerUrlError> {
+        let re = Regex::new(r"\{([^{}]+)}").unwrap();
+        let mut res = String::with_capacity(self.url.len());
+        let mut last = 0;
+        for c in re.captures_iter(&self.url) {
+            let (m, [name]) = (c.get(0).unwrap(), c.extract().1);
+            let variable = self.variables.as_ref().and_then(|x| x.get(name));
+            let value = match (vars.get(name), variable) {
+                (Some(value), _) => *value,
+                (None, Some(variable)) => variable.default.as_str(),
+                (None, None) => return Err(ServerUrlError::UndefinedVariable(name.to_owned())),
+            };
+            if let Some(enum_values) = variable.and_then(|x| x.enum_values.as_ref()) {
+                if !enum_values.iter().any(|x| x == value) {
+                    return Err(ServerUrlError::InvalidValue {
+                        name: name.to_owned(),
+                        value: value.to_owned(),
+                        enum_values: enum_values.clone(),
+                    });
+                }
+            }
+            res.push_str(&self.url[last..m.start()]);
+            res.push_str(value);
+            last = m.end();
+        }
+        res.push_str(&self.url[last..]);
+        Ok(res)
+    }
+
+    /// Returns the server URL with the variables substituted by their default values,
+    /// a relative URL, like `/v1`, is reported as [ServerUrlError::InvalidUrl]
+    /// and must be resolved against the location of the document instead.
+    pub fn parsed_url(&self) -> Result<Url, ServerUrlError> {
+        Ok(Url::parse(&self.resolve_url(&BTreeMap::new())?)?)
+    }
 }
 
 impl ValidateWithContext<Spec> for Server {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.url, ctx, format!("{}.url", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.url, ctx, path.field("url"));
         let mut visited = HashSet::<String>::new();
         if let Some(variables) = &self.variables {
             for (name, variable) in variables {
-                variable.validate_with_context(ctx, format!("{}.variables[{}]", path, name));
+                variable.validate_with_context(ctx, path.field("variables").item(name));
                 visited.insert(name.clone());
             }
         };
-        let re = Regex::new(r"\{([a-zA-Z0-9.\-_]+)}").unwrap();
+        let re = Regex::new(r"\{([^{}]+)}").unwrap();
+        // the variable names may contain any characters, so they are substituted
+        let url = re.replace_all(&self.url, "x");
+        validate_uri_reference(&url, ctx, path.field("url"));
         for (_, [name]) in re.captures_iter(&self.url).map(|c| c.extract()) {
             if !visited.remove(name) {
                 ctx.error(
@@ -128,10 +201,13 @@ impl ValidateWithContext<Spec> for Server {
 }
 
 impl ValidateWithContext<Spec> for ServerVariable {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_required_string(&self.default, ctx, format!("{}.default", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        validate_required_string(&self.default, ctx, path.field("default"));
         if let Some(enum_values) = &self.enum_values {
-            if !enum_values.contains(&self.default) {
+            if enum_values.is_empty() {
+                ctx.error(path.clone(), ".enum: must not be empty");
+            } else if !enum_values.contains(&self.default) {
                 ctx.error(
                     path,
                     format!(
@@ -205,7 +281,7 @@ mod tests {
             default: String::from("8443"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("serverVariable"));
+        .validate_with_context(&mut ctx, "serverVariable".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         ServerVariable {
@@ -213,12 +289,35 @@ mod tests {
             default: String::from("8443"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("serverVariable"));
+        .validate_with_context(&mut ctx, "serverVariable".into());
         assert_eq!(ctx.errors.len(), 1, "one error: {:?}", ctx.errors);
         assert_eq!(
             ctx.errors[0],
             "serverVariable.default: `8443` must be in enum values: [\"443\"]",
         );
+
+        ctx.errors.clear();
+        ServerVariable {
+            enum_values: Some(vec![]),
+            default: String::from("8443"),
+            ..Default::default()
+        }
+        .validate_with_context(&mut ctx, "serverVariable".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["serverVariable.enum: must not be empty"],
+            "empty enum: {:?}",
+            ctx.errors
+        );
+
+        ctx.errors.clear();
+        ServerVariable::default().validate_with_context(&mut ctx, "serverVariable".into());
+        assert_eq!(
+            ctx.errors,
+            vec!["serverVariable.default: must not be empty"],
+            "empty default: {:?}",
+            ctx.errors
+        );
     }
 
     #[test]
@@ -415,14 +514,14 @@ mod tests {
             url: String::from("https://development.gigantic-server.com/v1"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("server"));
+        .validate_with_context(&mut ctx, "server".into());
         assert_eq!(ctx.errors.len(), 0, "no errors: {:?}", ctx.errors);
 
         Server {
             url: String::from("https://{username}.gigantic-server.com:{port}/{basePath}"),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("server"));
+        .validate_with_context(&mut ctx, "server".into());
         assert_eq!(ctx.errors.len(), 3, "3 errors: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, Default::default());
@@ -455,7 +554,7 @@ mod tests {
             }),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("server"));
+        .validate_with_context(&mut ctx, "server".into());
         assert_eq!(
             ctx.errors.len(),
             0,
@@ -499,7 +598,7 @@ mod tests {
             }),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("server"));
+        .validate_with_context(&mut ctx, "server".into());
         assert_eq!(ctx.errors.len(), 1, "with used variable: {:?}", ctx.errors);
 
         ctx = Context::new(&spec, EnumSet::only(Options::IgnoreUnusedServerVariables));
@@ -539,7 +638,7 @@ mod tests {
             }),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, String::from("server"));
+        .validate_with_context(&mut ctx, "server".into());
         assert_eq!(
             ctx.errors.len(),
             0,
@@ -547,4 +646,74 @@ mod tests {
             ctx.errors
         );
     }
+
+    #[test]
+    fn test_server_resolve_url() {
+        let server = serde_json::from_value::<Server>(serde_json::json!({
+            "url": "https://{username}.gigantic-server.com:{port}/{basePath}",
+            "variables": {
+                "username": {"default": "demo"},
+                "port": {"enum": ["8443", "443"], "default": "8443"},
+                "basePath": {"default": "v2"},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            server.resolve_url(&BTreeMap::new()),
+            Ok("https://demo.gigantic-server.com:8443/v2".to_owned()),
+            "default values",
+        );
+        assert_eq!(
+            server.resolve_url(&BTreeMap::from([("username", "foo"), ("port", "443")])),
+            Ok("https://foo.gigantic-server.com:443/v2".to_owned()),
+            "given values",
+        );
+        assert_eq!(
+            server.resolve_url(&BTreeMap::from([("port", "80")])),
+            Err(ServerUrlError::InvalidValue {
+                name: "port".to_owned(),
+                value: "80".to_owned(),
+                enum_values: vec!["8443".to_owned(), "443".to_owned()],
+            }),
+            "value not in enum",
+        );
+        assert_eq!(
+            Server {
+                url: "https://{host}/".to_owned(),
+                ..Default::default()
+            }
+            .resolve_url(&BTreeMap::new()),
+            Err(ServerUrlError::UndefinedVariable("host".to_owned())),
+            "undefined variable",
+        );
+    }
+
+    #[test]
+    fn test_server_parsed_url() {
+        let server = Server {
+            url: "https://{host}/v1".to_owned(),
+            variables: Some(BTreeMap::from([(
+                "host".to_owned(),
+                ServerVariable {
+                    default: "example.com".to_owned(),
+                    ..Default::default()
+                },
+            )])),
+            ..Default::default()
+        };
+        let url = server.parsed_url().unwrap();
+        assert_eq!(url.as_str(), "https://example.com/v1");
+        assert_eq!(url.host(), Some("example.com"));
+        assert_eq!(
+            Server {
+                url: "/v1".to_owned(),
+                ..Default::default()
+            }
+            .parsed_url(),
+            Err(ServerUrlError::InvalidUrl(UrlError::Invalid(
+                "/v1".to_owned()
+            ))),
+            "relative url",
+        );
+    }
 }
@@ -3,19 +3,43 @@
 use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::str::FromStr;
 
 use enumset::EnumSet;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{Context, PushError, ValidateWithContext};
-use crate::common::reference::{resolve_in_map, ResolveReference};
-use crate::v3_0::callback::Callback;
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{
+    normalize_path_template, validate_each, validate_extensions, validate_tag_groups, Context,
+    PushError, ValidateWithContext,
+};
+use crate::common::http::Method;
+use crate::common::operation_id::{OperationIdStrategy, OperationIds};
+use crate::common::parse::from_reader;
+use crate::common::patch::{
+    apply_patch_typed, merge_patch_typed, split_subtree, PatchError, PatchOp,
+};
+use crate::common::path::Path;
+use crate::common::paths::Paths;
+use crate::common::reference::{resolve_in_map, RefOr, ResolveReference};
+use crate::common::semantic::{semantically_eq, EqOptions};
+use crate::common::spec::{OperationView, SpecLike};
+use crate::common::stats::{is_secured, Stats};
+use crate::common::strict::{from_value_strict, StrictError};
+use crate::error::{from_value_versioned, parse_versioned};
+use crate::v3_0::callback::{
+    collect_callback_operations, collect_located_callback_operations, collect_located_operations,
+    collect_nested_operations, Callback,
+};
 use crate::v3_0::components::Components;
 use crate::v3_0::example::Example;
 use crate::v3_0::external_documentation::ExternalDocumentation;
 use crate::v3_0::header::Header;
 use crate::v3_0::info::Info;
 use crate::v3_0::link::Link;
+use crate::v3_0::operation::Operation;
 use crate::v3_0::parameter::Parameter;
 use crate::v3_0::path_item::PathItem;
 use crate::v3_0::request_body::RequestBody;
@@ -24,7 +48,7 @@ use crate::v3_0::schema::Schema;
 use crate::v3_0::security_scheme::SecurityScheme;
 use crate::v3_0::server::Server;
 use crate::v3_0::tag::Tag;
-use crate::validation::{Error, Options, Validate};
+use crate::validation::{Error, Lints, Options, Validate};
 
 /// This is the root document object of the OpenAPI document.
 ///
@@ -198,7 +222,7 @@ pub struct Spec {
     ///               items:
     ///                 $ref: '#/components/schemas/pet'
     /// ```
-    pub paths: BTreeMap<String, PathItem>,
+    pub paths: Paths<PathItem>,
 
     /// An element to hold various schemas for the specification.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -241,7 +265,7 @@ pub struct Spec {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 /// The Swagger Specification version.
@@ -276,6 +300,26 @@ impl Display for Version {
     }
 }
 
+/// The supported versions of the specification.
+pub(crate) const VERSIONS: [&str; 4] = ["3.0.0", "3.0.1", "3.0.2", "3.0.3"];
+
+impl FromStr for Spec {
+    type Err = crate::Error;
+
+    /// Parses the JSON document, the document of another version is reported as unsupported.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_versioned(s, &VERSIONS)
+    }
+}
+
+impl Display for Spec {
+    /// Writes the specification as the pretty printed JSON.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let raw = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&raw)
+    }
+}
+
 impl ResolveReference<Response> for Spec {
     fn resolve_reference(&self, reference: &str) -> Option<&Response> {
         self.components
@@ -367,61 +411,314 @@ impl ResolveReference<Tag> for Spec {
     }
 }
 
+impl Spec {
+    /// Deserializes the JSON specification from the reader without buffering the whole document.
+    /// The reader is not buffered, wrap it in a [std::io::BufReader] when reading a file.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, crate::Error> {
+        Ok(from_reader(reader)?)
+    }
+
+    /// Converts the document of another OpenAPI library, e.g. `utoipa::openapi::OpenApi`,
+    /// through its JSON representation,
+    /// the document of another version is reported as unsupported.
+    ///
+    /// ```rust
+    /// use roas::v3_0::spec::Spec;
+    ///
+    /// let document = serde_json::json!({
+    ///     "openapi": "3.0.3",
+    ///     "info": {"title": "Pets", "version": "1"},
+    ///     "paths": {},
+    /// });
+    /// let spec = Spec::convert_from(&document).unwrap();
+    /// assert_eq!(spec.info.title, "Pets");
+    /// assert_eq!(spec.convert_into::<serde_json::Value>().unwrap(), document);
+    ///
+    /// let document = serde_json::json!({"openapi": "3.1.0"});
+    /// let err = Spec::convert_from(&document).unwrap_err();
+    /// assert_eq!(err.to_string(), "unsupported version: 3.1.0");
+    /// ```
+    pub fn convert_from<T: Serialize>(document: &T) -> Result<Self, crate::Error> {
+        let value = serde_json::to_value(document).map_err(crate::Error::Serialize)?;
+        from_value_versioned(&value, &VERSIONS)
+    }
+
+    /// Converts the specification into the document of another OpenAPI library,
+    /// e.g. `utoipa::openapi::OpenApi`, through its JSON representation.
+    pub fn convert_into<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::to_value(self).and_then(serde_json::from_value)
+    }
+
+    /// Serializes the specification as JSON to the writer.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Returns an iterator over all operations as `(path, method, operation)` tuples.
+    pub fn operations(&self) -> impl Iterator<Item = (&String, &String, &Operation)> {
+        self.paths.iter().flat_map(|(path, item)| {
+            item.operations
+                .iter()
+                .flatten()
+                .map(move |(method, operation)| (path, method, operation))
+        })
+    }
+
+    /// Returns an iterator over all operations as `(path, method, operation)` tuples,
+    /// allowing to modify the operations.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&String, &String, &mut Operation)> {
+        self.paths.iter_mut().flat_map(|(path, item)| {
+            item.operations
+                .iter_mut()
+                .flatten()
+                .map(move |(method, operation)| (path, method, operation))
+        })
+    }
+
+    /// Returns all operations, including the operations of the inline callbacks
+    /// and of the callbacks in the components, as `(path, method, operation)` tuples,
+    /// the path of a callback operation is its runtime expression.
+    pub fn operations_with_callbacks(
+        &self,
+    ) -> impl Iterator<Item = (&String, &String, &Operation)> {
+        let mut operations = Vec::new();
+        for (path, method, operation) in self.operations() {
+            operations.push((path, method, operation));
+            collect_nested_operations(operation, &mut operations);
+        }
+        let callbacks = self.components.iter().flat_map(|x| x.callbacks.iter());
+        for callback in callbacks.flat_map(|x| x.values()) {
+            if let RefOr::Item(callback) = callback {
+                collect_callback_operations(callback, &mut operations);
+            }
+        }
+        operations.into_iter()
+    }
+
+    /// Returns the operation with the given `operationId` as `(path, method, operation)` tuple.
+    pub fn operation_by_id(&self, operation_id: &str) -> Option<(&String, &String, &Operation)> {
+        self.operations()
+            .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(operation_id))
+    }
+
+    /// Sets the missing `operationId`s using the strategy
+    /// and returns the number of the generated ids,
+    /// see [crate::common::operation_id] for details.
+    pub fn ensure_operation_ids<S: OperationIdStrategy>(&mut self, strategy: S) -> usize {
+        let mut ids = OperationIds::new(
+            &strategy,
+            self.operations()
+                .filter_map(|(_, _, operation)| operation.operation_id.as_deref()),
+        );
+        let mut generated = 0;
+        for (path, method, operation) in self.operations_mut() {
+            if operation.operation_id.is_none() {
+                operation.operation_id = Some(ids.generate(&Method::from(method.as_str()), path));
+                generated += 1;
+            }
+        }
+        generated
+    }
+
+    /// Returns the statistics of the specification, see [Stats] for details.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            paths: self.paths.len(),
+            tags: self.tags.as_ref().map_or(0, |x| x.len()),
+            ..Default::default()
+        };
+        for (_, method, operation) in self.operations() {
+            stats.add_operation(
+                method,
+                operation.deprecated.unwrap_or(false),
+                operation.summary.is_some() || operation.description.is_some(),
+                operation.operation_id.is_some(),
+                is_secured(&operation.security, &self.security),
+            );
+        }
+        if let Some(components) = &self.components {
+            stats.schemas = components.schemas.as_ref().map_or(0, |x| x.len());
+            stats.parameters = components.parameters.as_ref().map_or(0, |x| x.len());
+            stats.responses = components.responses.as_ref().map_or(0, |x| x.len());
+            stats.security_schemes = components.security_schemes.as_ref().map_or(0, |x| x.len());
+        }
+        stats
+    }
+
+    /// Returns `true` if the specifications describe the same API,
+    /// see [crate::common::semantic] for the ignored differences.
+    pub fn semantically_eq(&self, other: &Self, options: EqOptions) -> bool {
+        semantically_eq(self, other, &options)
+    }
+
+    /// Deserializes the specification from the given value and fails on any field
+    /// that is not defined by the specification and does not start with `x-`.
+    pub fn from_value_strict(value: serde_json::Value) -> Result<Self, StrictError> {
+        from_value_strict(value)
+    }
+
+    /// Applies the JSON Patch to the specification.
+    ///
+    /// If all operations target a single path item, only that path item is re-deserialized.
+    /// The specification is not modified if the patch fails.
+    pub fn apply_json_patch(&mut self, ops: &[PatchOp]) -> Result<(), PatchError> {
+        if let Some((path, root, ops)) = split_subtree(ops, "/paths/") {
+            if let Some(item) = self.paths.get_mut(&path) {
+                return apply_patch_typed(item, &ops).map_err(|e| e.with_root(&root));
+            }
+        }
+        apply_patch_typed(self, ops)
+    }
+
+    /// Applies the JSON Merge Patch to the specification.
+    ///
+    /// The specification is not modified if the patch fails.
+    pub fn apply_merge_patch(&mut self, patch: serde_json::Value) -> Result<(), PatchError> {
+        merge_patch_typed(self, &patch)
+    }
+}
+
+impl SpecLike for Spec {
+    fn spec_version(&self) -> String {
+        self.openapi.to_string()
+    }
+
+    fn title(&self) -> &str {
+        &self.info.title
+    }
+
+    fn api_version(&self) -> &str {
+        &self.info.version
+    }
+
+    fn operations(&self) -> Box<dyn Iterator<Item = OperationView<'_>> + '_> {
+        Box::new(
+            Spec::operations(self).map(|(path, method, operation)| OperationView {
+                path,
+                method: Method::from(method.as_str()),
+                operation_id: operation.operation_id.as_deref(),
+                summary: operation.summary.as_deref(),
+                description: operation.description.as_deref(),
+                tags: operation.tags.as_deref().unwrap_or_default(),
+                deprecated: operation.deprecated.unwrap_or(false),
+            }),
+        )
+    }
+
+    fn schema_names(&self) -> Vec<&str> {
+        self.components
+            .as_ref()
+            .and_then(|x| x.schemas.as_ref())
+            .iter()
+            .flat_map(|x| x.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn schema_value(&self, name: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self.components.as_ref()?.schemas.as_ref()?.get(name)?).ok()
+    }
+
+    fn stats(&self) -> Stats {
+        Spec::stats(self)
+    }
+}
+
 impl Validate for Spec {
-    fn validate(&self, options: EnumSet<Options>) -> Result<(), Error> {
-        let mut ctx = Context::new(self, options);
+    fn validate_with_lints(
+        &self,
+        options: EnumSet<Options>,
+        lints: EnumSet<Lints>,
+    ) -> Result<(), Error> {
+        let mut ctx = Context::new(self, options).with_lints(lints);
+
+        validate_extensions(&self.extensions, &mut ctx, Path::root());
+        validate_tag_groups(
+            &self.extensions,
+            &self
+                .tags
+                .iter()
+                .flatten()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>(),
+            &mut ctx,
+        );
 
         self.info
-            .validate_with_context(&mut ctx, "#.info".to_owned());
+            .validate_with_context(&mut ctx, Path::root().field("info"));
 
         if let Some(servers) = &self.servers {
             for (i, server) in servers.iter().enumerate() {
-                server.validate_with_context(&mut ctx, format!("#.servers[{}]", i))
+                server.validate_with_context(&mut ctx, Path::root().field("servers").index(i))
             }
         }
 
         // memorize all operation ids for all paths first, so we can check the links
-        for (name, item) in self.paths.iter() {
-            if let Some(operations) = &item.operations {
-                for (method, operation) in operations.iter() {
-                    if let Some(operation_id) = &operation.operation_id {
-                        if !ctx
-                            .visited
-                            .insert(format!("#/paths/operations/{}", operation_id))
-                        {
-                            ctx.error(
-                                "#".to_owned(),
-                                format!(
-                                    ".paths[{}].{}.operationId: `{}` already in use",
-                                    name, method, operation_id
-                                ),
-                            );
-                        }
-                    }
+        let mut operations = Vec::new();
+        for (name, method, operation) in self.operations() {
+            let path = Path::root().field("paths").item(name).field(method.clone());
+            collect_located_operations(operation, path, &mut operations);
+        }
+        for (name, callback) in self
+            .components
+            .iter()
+            .flat_map(|x| x.callbacks.iter().flatten())
+        {
+            if let RefOr::Item(callback) = callback {
+                let path = Path::root()
+                    .field("components")
+                    .field("callbacks")
+                    .item(name);
+                collect_located_callback_operations(callback, path, &mut operations);
+            }
+        }
+        for (path, operation) in operations {
+            if let Some(operation_id) = &operation.operation_id {
+                if !ctx
+                    .visited
+                    .insert(format!("#/paths/operations/{}", operation_id))
+                {
+                    ctx.error(
+                        path.field("operationId"),
+                        format_args!("`{}` already in use", operation_id),
+                    );
                 }
             }
         }
 
-        for (name, item) in self.paths.iter() {
-            let path = format!("#.paths[{}]", name);
+        let mut templates: BTreeMap<String, &String> = BTreeMap::new();
+        let items = self
+            .paths
+            .iter()
+            .map(|(name, item)| {
+                let equivalent = templates.insert(normalize_path_template(name), name);
+                (name, item, equivalent)
+            })
+            .collect::<Vec<_>>();
+        validate_each(&mut ctx, &items, |ctx, (name, item, equivalent)| {
+            let path = Path::root().field("paths").item(name);
             if !name.starts_with('/') {
                 ctx.error(path.clone(), "must start with `/`");
             }
-            item.validate_with_context(&mut ctx, path);
-        }
+            if let Some(other) = equivalent {
+                ctx.error(path.clone(), format_args!("is equivalent to `{}`", other));
+            }
+            item.validate_with_context(ctx, path);
+        });
 
         if let Some(components) = &self.components {
-            components.validate_with_context(&mut ctx, "{}.components".to_owned());
+            components.validate_with_context(&mut ctx, Path::root().field("components"));
         }
 
         if let Some(docs) = &self.external_docs {
-            docs.validate_with_context(&mut ctx, "#.externalDocs".to_owned())
+            docs.validate_with_context(&mut ctx, Path::root().field("externalDocs"))
         }
 
         if let Some(tags) = &self.tags {
             for tag in tags.iter() {
                 let path = format!("#/tags/{}", tag.name);
                 if ctx.visit(path.clone()) {
+                    let path = Path::from(path);
                     if !ctx.is_option(Options::IgnoreUnusedTags) {
                         ctx.error(path.clone(), "unused");
                     }
@@ -0,0 +1,566 @@
+//! Serialization of the parameter values.
+//!
+//! Implements the `style` and `explode` rules of the
+//! [Style Values](https://spec.openapis.org/oas/v3.0.3#style-values)
+//! for the parameters, the headers and the encodings of the form properties.
+//!
+//! The values are neither percent-encoded nor percent-decoded,
+//! except that `spaceDelimited` style uses `%20` as the delimiter.
+//! The query and cookie styles work with the `name=value` pairs joined by `&`,
+//! the pairs joined by `;` are accepted as well when parsing.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::common::reference::RefOr;
+//! use roas::v3_0::schema::Schema;
+//! use roas::v3_0::style::{encode_value, parse_value, Style};
+//!
+//! let schema = serde_json::from_value::<RefOr<Schema>>(serde_json::json!({
+//!     "type": "array",
+//!     "items": {"type": "integer"},
+//! })).unwrap();
+//!
+//! let value = serde_json::json!([3, 4]);
+//! let raw = encode_value(&Style::Matrix, true, "id", &value);
+//! assert_eq!(raw, ";id=3;id=4");
+//! assert_eq!(parse_value(&Style::Matrix, true, "id", &raw, Some(&schema)), value);
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::common::reference::RefOr;
+use crate::v3_0::parameter::{InCookieStyle, InHeaderStyle, InPathStyle, InQueryStyle};
+use crate::v3_0::schema::{Schema, SingleSchema};
+
+/// The serialization style of a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// Path-style parameters, `;color=blue`.
+    Matrix,
+
+    /// Label style parameters, `.blue`.
+    Label,
+
+    /// Form style parameters, `color=blue`.
+    Form,
+
+    /// Simple style parameters, `blue`.
+    Simple,
+
+    /// Space separated array values, `blue%20black`.
+    SpaceDelimited,
+
+    /// Pipe separated array values, `blue|black`.
+    PipeDelimited,
+
+    /// Nested objects rendered with form parameters, `color[R]=100`.
+    DeepObject,
+}
+
+impl Style {
+    /// Returns the default value of `explode` for the style.
+    pub fn default_explode(&self) -> bool {
+        *self == Style::Form
+    }
+}
+
+impl From<&InPathStyle> for Style {
+    fn from(value: &InPathStyle) -> Self {
+        match value {
+            InPathStyle::Matrix => Style::Matrix,
+            InPathStyle::Label => Style::Label,
+            InPathStyle::Simple => Style::Simple,
+        }
+    }
+}
+
+impl From<&InQueryStyle> for Style {
+    fn from(value: &InQueryStyle) -> Self {
+        match value {
+            InQueryStyle::Form => Style::Form,
+            InQueryStyle::SpaceDelimited => Style::SpaceDelimited,
+            InQueryStyle::PipeDelimited => Style::PipeDelimited,
+            InQueryStyle::DeepObject => Style::DeepObject,
+        }
+    }
+}
+
+impl From<&InHeaderStyle> for Style {
+    fn from(value: &InHeaderStyle) -> Self {
+        match value {
+            InHeaderStyle::Simple => Style::Simple,
+        }
+    }
+}
+
+impl From<&InCookieStyle> for Style {
+    fn from(value: &InCookieStyle) -> Self {
+        match value {
+            InCookieStyle::Form => Style::Form,
+        }
+    }
+}
+
+/// Serializes the value according to the style.
+///
+/// The `null` value is serialized as an empty value,
+/// the nested arrays and objects are serialized as JSON.
+pub fn encode_value(style: &Style, explode: bool, name: &str, value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let items = items.iter().map(to_string).collect::<Vec<_>>();
+            encode_array(style, explode, name, &items)
+        }
+        Value::Object(map) => {
+            let pairs = map
+                .iter()
+                .map(|(k, v)| (k.as_str(), to_string(v)))
+                .collect::<Vec<_>>();
+            encode_object(style, explode, name, &pairs)
+        }
+        _ => {
+            let value = to_string(value);
+            match style {
+                Style::Matrix if value.is_empty() => format!(";{}", name),
+                Style::Matrix => format!(";{}={}", name, value),
+                Style::Label => format!(".{}", value),
+                Style::Simple => value,
+                _ => format!("{}={}", name, value),
+            }
+        }
+    }
+}
+
+fn encode_array(style: &Style, explode: bool, name: &str, items: &[String]) -> String {
+    match (style, explode) {
+        (Style::Matrix, _) if items.is_empty() => format!(";{}", name),
+        (Style::Matrix, true) => items.iter().map(|x| format!(";{}={}", name, x)).collect(),
+        (Style::Matrix, false) => format!(";{}={}", name, items.join(",")),
+        (Style::Label, true) => format!(".{}", items.join(".")),
+        (Style::Label, false) => format!(".{}", items.join(",")),
+        (Style::Simple, _) => items.join(","),
+        (Style::Form, false) => format!("{}={}", name, items.join(",")),
+        (Style::SpaceDelimited, false) => items.join("%20"),
+        (Style::PipeDelimited, false) => items.join("|"),
+        (_, _) if items.is_empty() => format!("{}=", name),
+        (_, _) => items
+            .iter()
+            .map(|x| format!("{}={}", name, x))
+            .collect::<Vec<_>>()
+            .join("&"),
+    }
+}
+
+fn encode_object(style: &Style, explode: bool, name: &str, pairs: &[(&str, String)]) -> String {
+    let flat = pairs
+        .iter()
+        .flat_map(|(k, v)| [k.to_string(), v.clone()])
+        .collect::<Vec<_>>();
+    let joined = |prefix: &str, separator: &str| {
+        pairs
+            .iter()
+            .map(|(k, v)| format!("{}{}={}", prefix, k, v))
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+    match (style, explode) {
+        (Style::Matrix, _) if pairs.is_empty() => format!(";{}", name),
+        (Style::Matrix, true) => joined(";", ""),
+        (Style::Matrix, false) => format!(";{}={}", name, flat.join(",")),
+        (Style::Label, true) => format!(".{}", joined("", ".")),
+        (Style::Label, false) => format!(".{}", flat.join(",")),
+        (Style::Simple, true) => joined("", ","),
+        (Style::Simple, false) => flat.join(","),
+        (Style::SpaceDelimited, false) => flat.join("%20"),
+        (Style::PipeDelimited, false) => flat.join("|"),
+        (Style::DeepObject, _) => pairs
+            .iter()
+            .map(|(k, v)| format!("{}[{}]={}", name, k, v))
+            .collect::<Vec<_>>()
+            .join("&"),
+        (_, false) => format!("{}={}", name, flat.join(",")),
+        (_, true) => joined("", "&"),
+    }
+}
+
+fn to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Parses the raw value according to the style.
+///
+/// The schema defines whether the value is a primitive, an array or an object
+/// and the types of the primitive values.
+/// Only the inline schemas are taken into account, the references are not resolved,
+/// so the value is parsed as a string when the schema is not given or is a reference.
+/// `null` is returned when the value of a query parameter is missing.
+pub fn parse_value(
+    style: &Style,
+    explode: bool,
+    name: &str,
+    raw: &str,
+    schema: Option<&RefOr<Schema>>,
+) -> Value {
+    let schema = schema.and_then(|x| match x {
+        RefOr::Item(Schema::Single(s)) => Some(s),
+        _ => None,
+    });
+    match schema {
+        Some(SingleSchema::Array(a)) => {
+            let items = a.items.as_ref().and_then(inline);
+            Value::Array(
+                parse_array(style, explode, name, raw)
+                    .into_iter()
+                    .map(|x| primitive(x, items))
+                    .collect(),
+            )
+        }
+        Some(SingleSchema::Object(o)) => {
+            let known = |key: &str| {
+                o.additional_properties.is_some()
+                    || o.properties
+                        .as_ref()
+                        .is_none_or(|x| x.is_empty() || x.contains_key(key))
+            };
+            let mut map = Map::new();
+            for (key, value) in parse_object(style, explode, name, raw) {
+                if style == &Style::Form && explode && !known(key) {
+                    continue;
+                }
+                let schema = o
+                    .properties
+                    .as_ref()
+                    .and_then(|x| x.get(key))
+                    .and_then(inline);
+                map.insert(key.to_owned(), primitive(value, schema));
+            }
+            Value::Object(map)
+        }
+        _ => match parse_primitive(style, name, raw) {
+            Some(value) => primitive(value, schema),
+            None => Value::Null,
+        },
+    }
+}
+
+fn inline(schema: &RefOr<Box<Schema>>) -> Option<&SingleSchema> {
+    match schema {
+        RefOr::Item(s) => match s.as_ref() {
+            Schema::Single(s) => Some(s),
+            _ => None,
+        },
+        RefOr::Ref(_) => None,
+    }
+}
+
+/// Converts the raw value to the type of the schema,
+/// the value is kept as a string if it does not match the type.
+fn primitive(raw: &str, schema: Option<&SingleSchema>) -> Value {
+    match schema {
+        Some(SingleSchema::Integer(_) | SingleSchema::Number(_)) => raw
+            .parse::<serde_json::Number>()
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::String(raw.to_owned())),
+        Some(SingleSchema::Boolean(_)) => raw
+            .parse::<bool>()
+            .map(Value::Bool)
+            .unwrap_or_else(|_| Value::String(raw.to_owned())),
+        _ => Value::String(raw.to_owned()),
+    }
+}
+
+fn query_pairs(raw: &str) -> impl Iterator<Item = (&str, &str)> {
+    raw.split(['&', ';'])
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(|x| x.split_once('=').unwrap_or((x, "")))
+}
+
+fn query_value<'a>(raw: &'a str, name: &str) -> Option<&'a str> {
+    query_pairs(raw).find(|(k, _)| *k == name).map(|(_, v)| v)
+}
+
+fn parse_primitive<'a>(style: &Style, name: &str, raw: &'a str) -> Option<&'a str> {
+    match style {
+        Style::Matrix => {
+            let rest = raw.strip_prefix(';').unwrap_or(raw);
+            let rest = rest.strip_prefix(name).unwrap_or(rest);
+            Some(rest.strip_prefix('=').unwrap_or(rest))
+        }
+        Style::Label => Some(raw.strip_prefix('.').unwrap_or(raw)),
+        Style::Simple => Some(raw),
+        _ => query_value(raw, name),
+    }
+}
+
+fn split<'a>(raw: &'a str, separator: &str) -> Vec<&'a str> {
+    if raw.is_empty() {
+        return Vec::new();
+    }
+    raw.split(separator).collect()
+}
+
+fn parse_array<'a>(style: &Style, explode: bool, name: &str, raw: &'a str) -> Vec<&'a str> {
+    match (style, explode) {
+        (Style::Matrix, true) => raw
+            .split(';')
+            .filter(|x| !x.is_empty())
+            .map(|x| x.split_once('=').map_or("", |(_, v)| v))
+            .collect(),
+        (Style::Matrix, false) => split(parse_primitive(style, name, raw).unwrap_or_default(), ","),
+        (Style::Label, true) => split(raw.strip_prefix('.').unwrap_or(raw), "."),
+        (Style::Label, false) => split(raw.strip_prefix('.').unwrap_or(raw), ","),
+        (Style::Simple, _) => split(raw, ","),
+        (Style::Form, false) => split(query_value(raw, name).unwrap_or_default(), ","),
+        (Style::SpaceDelimited, false) => {
+            let raw = query_value(raw, name).unwrap_or(raw);
+            raw.split("%20")
+                .flat_map(|x| x.split(' '))
+                .filter(|x| !x.is_empty())
+                .collect()
+        }
+        (Style::PipeDelimited, false) => split(query_value(raw, name).unwrap_or(raw), "|"),
+        (_, _) => query_pairs(raw)
+            .filter(|(k, _)| *k == name)
+            .map(|(_, v)| v)
+            .collect(),
+    }
+}
+
+fn parse_object<'a>(
+    style: &Style,
+    explode: bool,
+    name: &str,
+    raw: &'a str,
+) -> Vec<(&'a str, &'a str)> {
+    let chunks = |items: Vec<&'a str>| {
+        items
+            .chunks(2)
+            .map(|x| (x[0], x.get(1).copied().unwrap_or_default()))
+            .collect::<Vec<_>>()
+    };
+    let pairs = |items: Vec<&'a str>| {
+        items
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .map(|x| x.split_once('=').unwrap_or((x, "")))
+            .collect::<Vec<_>>()
+    };
+    match (style, explode) {
+        (Style::Matrix, true) => pairs(raw.split(';').collect()),
+        (Style::Label, true) => pairs(split(raw.strip_prefix('.').unwrap_or(raw), ".")),
+        (Style::Simple, true) => pairs(split(raw, ",")),
+        (Style::DeepObject, _) => query_pairs(raw)
+            .filter_map(|(k, v)| {
+                let key = k.strip_prefix(name)?.strip_prefix('[')?.strip_suffix(']')?;
+                Some((key, v))
+            })
+            .collect(),
+        (Style::Form | Style::SpaceDelimited | Style::PipeDelimited, true) => {
+            query_pairs(raw).collect()
+        }
+        (_, false) => chunks(parse_array(style, false, name, raw)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_value() {
+        let primitive = json!("blue");
+        let array = json!(["blue", "black", "brown"]);
+        let object = json!({"B": 150, "G": 200, "R": 100});
+        for (style, explode, expected) in [
+            (
+                Style::Matrix,
+                false,
+                [
+                    ";color=blue",
+                    ";color=blue,black,brown",
+                    ";color=B,150,G,200,R,100",
+                ],
+            ),
+            (
+                Style::Matrix,
+                true,
+                [
+                    ";color=blue",
+                    ";color=blue;color=black;color=brown",
+                    ";B=150;G=200;R=100",
+                ],
+            ),
+            (
+                Style::Label,
+                false,
+                [".blue", ".blue,black,brown", ".B,150,G,200,R,100"],
+            ),
+            (
+                Style::Label,
+                true,
+                [".blue", ".blue.black.brown", ".B=150.G=200.R=100"],
+            ),
+            (
+                Style::Form,
+                false,
+                [
+                    "color=blue",
+                    "color=blue,black,brown",
+                    "color=B,150,G,200,R,100",
+                ],
+            ),
+            (
+                Style::Form,
+                true,
+                [
+                    "color=blue",
+                    "color=blue&color=black&color=brown",
+                    "B=150&G=200&R=100",
+                ],
+            ),
+            (
+                Style::Simple,
+                false,
+                ["blue", "blue,black,brown", "B,150,G,200,R,100"],
+            ),
+            (
+                Style::Simple,
+                true,
+                ["blue", "blue,black,brown", "B=150,G=200,R=100"],
+            ),
+            (
+                Style::SpaceDelimited,
+                false,
+                [
+                    "color=blue",
+                    "blue%20black%20brown",
+                    "B%20150%20G%20200%20R%20100",
+                ],
+            ),
+            (
+                Style::PipeDelimited,
+                false,
+                ["color=blue", "blue|black|brown", "B|150|G|200|R|100"],
+            ),
+            (
+                Style::DeepObject,
+                true,
+                [
+                    "color=blue",
+                    "color=blue&color=black&color=brown",
+                    "color[B]=150&color[G]=200&color[R]=100",
+                ],
+            ),
+        ] {
+            for (value, expected) in [&primitive, &array, &object].into_iter().zip(expected) {
+                assert_eq!(
+                    encode_value(&style, explode, "color", value),
+                    expected,
+                    "{:?} explode={} {}",
+                    style,
+                    explode,
+                    value,
+                );
+            }
+        }
+        assert_eq!(
+            encode_value(&Style::Matrix, false, "color", &Value::Null),
+            ";color",
+            "empty matrix",
+        );
+        assert_eq!(
+            encode_value(&Style::Form, false, "color", &Value::Null),
+            "color=",
+            "empty form",
+        );
+    }
+
+    #[test]
+    fn test_parse_value() {
+        let array = serde_json::from_value::<RefOr<Schema>>(json!({
+            "type": "array",
+            "items": {"type": "string"},
+        }))
+        .unwrap();
+        let object = serde_json::from_value::<RefOr<Schema>>(json!({
+            "type": "object",
+            "properties": {
+                "R": {"type": "integer"},
+                "G": {"type": "integer"},
+                "B": {"type": "integer"},
+            },
+        }))
+        .unwrap();
+        let integer = serde_json::from_value::<RefOr<Schema>>(json!({"type": "integer"})).unwrap();
+        let expected_array = json!(["blue", "black", "brown"]);
+        let expected_object = json!({"B": 150, "G": 200, "R": 100});
+        for style in [
+            Style::Matrix,
+            Style::Label,
+            Style::Form,
+            Style::Simple,
+            Style::SpaceDelimited,
+            Style::PipeDelimited,
+            Style::DeepObject,
+        ] {
+            for explode in [false, true] {
+                if style == Style::DeepObject && !explode {
+                    continue;
+                }
+                let context = format!("{:?} explode={}", style, explode);
+                let raw = encode_value(&style, explode, "color", &expected_array);
+                assert_eq!(
+                    parse_value(&style, explode, "color", &raw, Some(&array)),
+                    expected_array,
+                    "array {}",
+                    context,
+                );
+                if !matches!(style, Style::SpaceDelimited | Style::PipeDelimited) || !explode {
+                    let raw = encode_value(&style, explode, "color", &expected_object);
+                    assert_eq!(
+                        parse_value(&style, explode, "color", &raw, Some(&object)),
+                        expected_object,
+                        "object {}",
+                        context,
+                    );
+                }
+                let raw = encode_value(&style, explode, "color", &json!(5));
+                assert_eq!(
+                    parse_value(&style, explode, "color", &raw, Some(&integer)),
+                    json!(5),
+                    "integer {}",
+                    context,
+                );
+            }
+        }
+        assert_eq!(
+            parse_value(&Style::Form, true, "id", "limit=1&id=5;x=2", Some(&integer)),
+            json!(5),
+            "query with other parameters",
+        );
+        assert_eq!(
+            parse_value(&Style::Form, true, "id", "limit=1", None),
+            Value::Null,
+            "missing query parameter",
+        );
+        assert_eq!(
+            parse_value(
+                &Style::Form,
+                true,
+                "color",
+                "R=100&G=200&B=150&x=1",
+                Some(&object)
+            ),
+            expected_object,
+            "unknown properties of an exploded object",
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Random specifications for property testing
+//!
+//! [Generator] builds random but valid specifications of a bounded depth,
+//! the same seed always produces the same specifications,
+//! so a failing case can be reproduced by its seed.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::v3_0::testing::{assert_round_trip, Generator};
+//! use roas::validation::{Options, Validate};
+//!
+//! for seed in 0..10 {
+//!     let spec = Generator::new(seed).spec();
+//!     assert_round_trip(&spec);
+//!     spec.validate(Options::new()).unwrap();
+//! }
+//! ```
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::v3_0::schema::Schema;
+use crate::v3_0::spec::Spec;
+
+const NAMES: &[&str] = &[
+    "id", "name", "count", "tags", "created", "price", "enabled", "parent",
+];
+
+const MODELS: &[&str] = &["Pet", "Owner", "Order", "Address", "Category", "User"];
+
+/// Generates random specifications and schemas.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+    max_depth: usize,
+    components: usize,
+}
+
+impl Generator {
+    /// Creates a generator with the seed and the maximum schema depth of `3`.
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            state: seed,
+            max_depth: 3,
+            components: 0,
+        }
+    }
+
+    /// Sets the maximum depth of the nested schemas.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns a valid specification.
+    pub fn spec(&mut self) -> Spec {
+        serde_json::from_value(self.spec_value()).expect("generated specification must be valid")
+    }
+
+    /// Returns a valid specification as a JSON value.
+    ///
+    /// Each component schema is used by an operation of its own path,
+    /// a component schema may reference the previously generated ones.
+    pub fn spec_value(&mut self) -> Value {
+        let count = 1 + self.below(MODELS.len());
+        let mut schemas = Map::new();
+        let mut paths = Map::new();
+        for (i, model) in MODELS.iter().take(count).enumerate() {
+            self.components = i;
+            schemas.insert(model.to_string(), self.schema_value_at(0));
+            let mut responses = json!({
+                "200": {
+                    "description": "ok",
+                    "content": {
+                        "application/json": {
+                            "schema": {"$ref": format!("#/components/schemas/{}", model)},
+                        },
+                    },
+                },
+            });
+            if self.below(2) == 0 {
+                responses["default"] = json!({"description": "error"});
+            }
+            paths.insert(
+                format!("/{}s/{{id}}", model.to_lowercase()),
+                json!({
+                    "parameters": [{
+                        "name": "id",
+                        "in": "path",
+                        "required": true,
+                        "schema": self.schema_value_at(self.max_depth),
+                    }],
+                    "get": {
+                        "operationId": format!("get{}", model),
+                        "responses": responses,
+                    },
+                }),
+            );
+        }
+        self.components = 0;
+        json!({
+            "openapi": "3.0.3",
+            "info": {"title": format!("API {}", self.state % 1000), "version": "1.0.0"},
+            "paths": paths,
+            "components": {"schemas": schemas},
+        })
+    }
+
+    /// Returns a schema without references.
+    pub fn schema(&mut self) -> Schema {
+        serde_json::from_value(self.schema_value()).expect("generated schema must be valid")
+    }
+
+    /// Returns a schema without references as a JSON value.
+    pub fn schema_value(&mut self) -> Value {
+        self.schema_value_at(0)
+    }
+
+    fn schema_value_at(&mut self, depth: usize) -> Value {
+        let kinds = if depth < self.max_depth { 9 } else { 5 };
+        let mut schema = match self.below(kinds) {
+            0 => match self.below(4) {
+                0 => json!({"type": "string", "format": "date-time"}),
+                1 => json!({"type": "string", "minLength": 1, "maxLength": 64}),
+                2 => json!({"type": "string", "enum": ["a", "b"]}),
+                _ => json!({"type": "string"}),
+            },
+            1 => json!({"type": "integer", "format": "int64", "minimum": 0}),
+            2 => json!({"type": "number", "maximum": 100.5}),
+            3 => json!({"type": "boolean"}),
+            4 if self.components > 0 => {
+                let model = MODELS[self.below(self.components)];
+                return json!({"$ref": format!("#/components/schemas/{}", model)});
+            }
+            4 => json!({"type": "string"}),
+            5 => json!({
+                "type": "array",
+                "items": self.schema_value_at(depth + 1),
+                "maxItems": 10,
+            }),
+            6 | 7 => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for name in NAMES {
+                    if self.below(3) == 0 {
+                        properties.insert(name.to_string(), self.schema_value_at(depth + 1));
+                        if self.below(2) == 0 {
+                            required.push(name.to_string());
+                        }
+                    }
+                }
+                let mut schema = json!({"type": "object", "properties": properties});
+                if !required.is_empty() {
+                    schema["required"] = json!(required);
+                }
+                schema
+            }
+            _ => {
+                let keyword = ["allOf", "anyOf", "oneOf"][self.below(3)];
+                let schemas = (0..2)
+                    .map(|_| self.schema_value_at(depth + 1))
+                    .collect::<Vec<_>>();
+                return json!({keyword: schemas});
+            }
+        };
+        if self.below(4) == 0 {
+            schema["nullable"] = json!(true);
+        }
+        schema
+    }
+
+    /// Returns the next pseudo-random number, the SplitMix64 algorithm.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random number in `[0, n)` range.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Asserts that the value is serialized and deserialized back unchanged.
+#[track_caller]
+pub fn assert_round_trip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let serialized = serde_json::to_value(value).expect("value must be serializable");
+    let deserialized = serde_json::from_value::<T>(serialized.clone())
+        .unwrap_or_else(|e| panic!("value must be deserializable: {}\n{}", e, serialized));
+    assert_eq!(&deserialized, value, "round trip of {}", serialized);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::{Options, Validate};
+
+    #[test]
+    fn test_generated_specs() {
+        for seed in 0..200 {
+            let value = Generator::new(seed).spec_value();
+            assert_eq!(Generator::new(seed).spec_value(), value, "seed {}", seed);
+            let spec = serde_json::from_value::<Spec>(value.clone()).unwrap();
+            assert_round_trip(&spec);
+            if let Err(e) = spec.validate(Options::new()) {
+                panic!("seed {}: {}\n{}", seed, e, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_schemas() {
+        let mut generator = Generator::new(1).with_max_depth(1);
+        for _ in 0..200 {
+            assert_round_trip(&generator.schema());
+        }
+    }
+}
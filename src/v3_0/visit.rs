@@ -0,0 +1,1049 @@
+//! Traversal of the v3.0 documents, see [crate::common::visit] for details.
+
+use std::collections::BTreeMap;
+
+use crate::common::bool_or::BoolOr;
+use crate::common::extensions::Extensions;
+use crate::common::reference::RefOr;
+use crate::common::visit::{pointer, Document, Visit, VisitMut, Walk, WalkMut};
+use crate::v3_0::callback::Callback;
+use crate::v3_0::components::Components;
+use crate::v3_0::example::Example;
+use crate::v3_0::external_documentation::ExternalDocumentation;
+use crate::v3_0::header::Header;
+use crate::v3_0::info::Info;
+use crate::v3_0::link::Link;
+use crate::v3_0::media_type::MediaType;
+use crate::v3_0::operation::Operation;
+use crate::v3_0::parameter::Parameter;
+use crate::v3_0::path_item::PathItem;
+use crate::v3_0::request_body::RequestBody;
+use crate::v3_0::response::{Response, Responses};
+use crate::v3_0::schema::{Schema, SingleSchema};
+use crate::v3_0::security_scheme::SecurityScheme;
+use crate::v3_0::server::Server;
+use crate::v3_0::spec::Spec;
+use crate::v3_0::tag::Tag;
+use crate::v3_0::xml::XML;
+
+impl Document for Spec {
+    type PathItem = PathItem;
+    type Operation = Operation;
+    type Parameter = Parameter;
+    type Response = Response;
+    type Schema = Schema;
+}
+
+impl Walk for Spec {
+    fn walk<V: Visit<Self>>(&self, visitor: &mut V) {
+        let mut walker = Walker { visitor };
+        walker.extensions("#", &self.extensions);
+        walker.info("#/info".to_owned(), &self.info);
+        walker.servers("#", &self.servers);
+        for (name, path_item) in &self.paths {
+            walker.path_item(pointer("#/paths", name), path_item);
+        }
+        if let Some(components) = &self.components {
+            walker.components("#/components".to_owned(), components);
+        }
+        for (i, tag) in self.tags.iter().flatten().enumerate() {
+            walker.tag(pointer("#/tags", &i.to_string()), tag);
+        }
+        walker.external_docs("#", &self.external_docs);
+    }
+}
+
+struct Walker<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<V: Visit<Spec>> Walker<'_, V> {
+    fn extensions(&mut self, path: &str, extensions: &Option<Extensions>) {
+        if let Some(extensions) = extensions {
+            self.visitor.visit_extensions(path, extensions);
+        }
+    }
+
+    fn ref_or<T>(&mut self, path: String, value: &RefOr<T>, f: impl FnOnce(&mut Self, String, &T)) {
+        match value {
+            RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+            RefOr::Item(item) => f(self, path, item),
+        }
+    }
+
+    fn info(&mut self, path: String, info: &Info) {
+        self.extensions(&path, &info.extensions);
+        if let Some(contact) = &info.contact {
+            self.extensions(&pointer(&path, "contact"), &contact.extensions);
+        }
+        if let Some(license) = &info.license {
+            self.extensions(&pointer(&path, "license"), &license.extensions);
+        }
+    }
+
+    fn servers(&mut self, path: &str, servers: &Option<Vec<Server>>) {
+        let path = pointer(path, "servers");
+        for (i, server) in servers.iter().flatten().enumerate() {
+            self.server(pointer(&path, &i.to_string()), server);
+        }
+    }
+
+    fn server(&mut self, path: String, server: &Server) {
+        self.extensions(&path, &server.extensions);
+        let variables = pointer(&path, "variables");
+        for (name, variable) in server.variables.iter().flatten() {
+            self.extensions(&pointer(&variables, name), &variable.extensions);
+        }
+    }
+
+    fn tag(&mut self, path: String, tag: &Tag) {
+        self.extensions(&path, &tag.extensions);
+        self.external_docs(&path, &tag.external_docs);
+    }
+
+    fn external_docs(&mut self, path: &str, external_docs: &Option<ExternalDocumentation>) {
+        if let Some(external_docs) = external_docs {
+            self.extensions(&pointer(path, "externalDocs"), &external_docs.extensions);
+        }
+    }
+
+    fn path_item(&mut self, path: String, path_item: &PathItem) {
+        self.visitor.visit_path_item(&path, path_item);
+        self.extensions(&path, &path_item.extensions);
+        self.servers(&path, &path_item.servers);
+        self.parameters(&path, &path_item.parameters);
+        for (method, operation) in path_item.iter() {
+            let path = pointer(&path, method.as_str());
+            self.visitor.visit_operation(&path, &method, operation);
+            self.operation(path, operation);
+        }
+    }
+
+    fn operation(&mut self, path: String, operation: &Operation) {
+        self.extensions(&path, &operation.extensions);
+        self.external_docs(&path, &operation.external_docs);
+        self.parameters(&path, &operation.parameters);
+        if let Some(request_body) = &operation.request_body {
+            self.ref_or(
+                pointer(&path, "requestBody"),
+                request_body,
+                Self::request_body,
+            );
+        }
+        self.responses(pointer(&path, "responses"), &operation.responses);
+        for (name, callback) in operation.callbacks.iter().flatten() {
+            let path = pointer(&pointer(&path, "callbacks"), name);
+            self.ref_or(path, callback, Self::callback);
+        }
+        self.servers(&path, &operation.servers);
+    }
+
+    fn parameters(&mut self, path: &str, parameters: &Option<Vec<RefOr<Parameter>>>) {
+        let path = pointer(path, "parameters");
+        for (i, parameter) in parameters.iter().flatten().enumerate() {
+            self.ref_or(pointer(&path, &i.to_string()), parameter, Self::parameter);
+        }
+    }
+
+    fn parameter(&mut self, path: String, parameter: &Parameter) {
+        self.visitor.visit_parameter(&path, parameter);
+        if let Some(extensions) = parameter.extensions() {
+            self.visitor.visit_extensions(&path, extensions);
+        }
+        if let Some(schema) = parameter.schema() {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        let (examples, content) = match parameter {
+            Parameter::Path(p) => (&p.examples, &p.content),
+            Parameter::Query(p) => (&p.examples, &p.content),
+            Parameter::Header(p) => (&p.examples, &p.content),
+            Parameter::Cookie(p) => (&p.examples, &p.content),
+        };
+        self.examples(&path, examples.as_ref());
+        self.content(&path, content.as_ref());
+    }
+
+    fn request_body(&mut self, path: String, request_body: &RequestBody) {
+        self.content(&path, Some(&request_body.content));
+    }
+
+    fn content(&mut self, path: &str, content: Option<&BTreeMap<String, MediaType>>) {
+        let path = pointer(path, "content");
+        for (name, media_type) in content.into_iter().flatten() {
+            self.media_type(pointer(&path, name), media_type);
+        }
+    }
+
+    fn media_type(&mut self, path: String, media_type: &MediaType) {
+        self.extensions(&path, &media_type.extensions);
+        if let Some(schema) = &media_type.schema {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        self.examples(&path, media_type.examples.as_ref());
+        for (name, encoding) in media_type.encoding.iter().flatten() {
+            let path = pointer(&pointer(&path, "encoding"), name);
+            self.extensions(&path, &encoding.extensions);
+            self.headers(&path, encoding.headers.as_ref());
+        }
+    }
+
+    fn responses(&mut self, path: String, responses: &Responses) {
+        self.extensions(&path, &responses.extensions);
+        if let Some(response) = &responses.default {
+            self.ref_or(pointer(&path, "default"), response, Self::response);
+        }
+        for (status, response) in responses.responses.iter().flatten() {
+            self.ref_or(pointer(&path, status), response, Self::response);
+        }
+    }
+
+    fn response(&mut self, path: String, response: &Response) {
+        self.visitor.visit_response(&path, response);
+        self.extensions(&path, &response.extensions);
+        self.headers(&path, response.headers.as_ref());
+        self.content(&path, response.content.as_ref());
+        self.links(&path, response.links.as_ref());
+    }
+
+    fn examples(&mut self, path: &str, examples: Option<&BTreeMap<String, RefOr<Example>>>) {
+        let path = pointer(path, "examples");
+        for (name, example) in examples.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), example, |w, path, x| {
+                w.extensions(&path, &x.extensions)
+            });
+        }
+    }
+
+    fn links(&mut self, path: &str, links: Option<&BTreeMap<String, RefOr<Link>>>) {
+        let path = pointer(path, "links");
+        for (name, link) in links.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), link, Self::link);
+        }
+    }
+
+    fn link(&mut self, path: String, link: &Link) {
+        self.extensions(&path, &link.extensions);
+        if let Some(server) = &link.server {
+            self.server(pointer(&path, "server"), server);
+        }
+    }
+
+    fn security_scheme(&mut self, path: String, security_scheme: &SecurityScheme) {
+        match security_scheme {
+            SecurityScheme::HTTP(s) => self.extensions(&path, &s.extensions),
+            SecurityScheme::ApiKey(s) => self.extensions(&path, &s.extensions),
+            SecurityScheme::OpenIdConnect(s) => self.extensions(&path, &s.extensions),
+            SecurityScheme::OAuth2(s) => {
+                self.extensions(&path, &s.extensions);
+                let flows = pointer(&path, "flows");
+                self.extensions(&flows, &s.flows.extensions);
+                if let Some(flow) = &s.flows.implicit {
+                    self.extensions(&pointer(&flows, "implicit"), &flow.extensions);
+                }
+                if let Some(flow) = &s.flows.password {
+                    self.extensions(&pointer(&flows, "password"), &flow.extensions);
+                }
+                if let Some(flow) = &s.flows.client_credentials {
+                    self.extensions(&pointer(&flows, "clientCredentials"), &flow.extensions);
+                }
+                if let Some(flow) = &s.flows.authorization_code {
+                    self.extensions(&pointer(&flows, "authorizationCode"), &flow.extensions);
+                }
+            }
+        }
+    }
+
+    fn headers(&mut self, path: &str, headers: Option<&BTreeMap<String, RefOr<Header>>>) {
+        let path = pointer(path, "headers");
+        for (name, header) in headers.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), header, Self::header);
+        }
+    }
+
+    fn header(&mut self, path: String, header: &Header) {
+        self.extensions(&path, &header.extensions);
+        if let Some(schema) = &header.schema {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        self.examples(&path, header.examples.as_ref());
+        self.content(&path, header.content.as_ref());
+    }
+
+    fn callback(&mut self, path: String, callback: &Callback) {
+        self.extensions(&path, &callback.extensions);
+        for (expression, path_item) in &callback.paths {
+            self.path_item(pointer(&path, expression), path_item);
+        }
+    }
+
+    fn schema(&mut self, path: String, schema: &Schema) {
+        self.visitor.visit_schema(&path, schema);
+        if let Some(extensions) = schema.extensions() {
+            self.visitor.visit_extensions(&path, extensions);
+        }
+        if let Schema::Single(single) = schema {
+            let (xml, external_docs) = documentation(single);
+            if let Some(xml) = xml {
+                self.extensions(&pointer(&path, "xml"), &xml.extensions);
+            }
+            self.external_docs(&path, external_docs);
+        }
+        match schema {
+            Schema::AllOf(s) => self.schemas(pointer(&path, "allOf"), &s.all_of),
+            Schema::AnyOf(s) => self.schemas(pointer(&path, "anyOf"), &s.any_of),
+            Schema::OneOf(s) => self.schemas(pointer(&path, "oneOf"), &s.one_of),
+            Schema::Not(s) => self.nested(pointer(&path, "not"), &s.not),
+            Schema::Single(SingleSchema::Array(s)) => {
+                if let Some(items) = &s.items {
+                    self.nested(pointer(&path, "items"), items);
+                }
+            }
+            Schema::Single(SingleSchema::Object(s)) => {
+                let properties = pointer(&path, "properties");
+                for (name, schema) in s.properties.iter().flatten() {
+                    self.nested(pointer(&properties, name), schema);
+                }
+                if let Some(BoolOr::Item(schema)) = &s.additional_properties {
+                    self.nested(pointer(&path, "additionalProperties"), schema);
+                }
+            }
+            Schema::Single(_) => {}
+        }
+    }
+
+    fn nested(&mut self, path: String, schema: &RefOr<Box<Schema>>) {
+        match schema {
+            RefOr::Ref(r) => self.visitor.visit_reference(&path, r),
+            RefOr::Item(schema) => self.schema(path, schema),
+        }
+    }
+
+    fn schemas(&mut self, path: String, schemas: &[RefOr<Box<Schema>>]) {
+        for (i, schema) in schemas.iter().enumerate() {
+            self.nested(pointer(&path, &i.to_string()), schema);
+        }
+    }
+
+    fn components(&mut self, path: String, components: &Components) {
+        self.extensions(&path, &components.extensions);
+        let section = |name: &str| pointer(&path, name);
+        for (name, schema) in components.schemas.iter().flatten() {
+            self.ref_or(pointer(&section("schemas"), name), schema, Self::schema);
+        }
+        for (name, response) in components.responses.iter().flatten() {
+            self.ref_or(
+                pointer(&section("responses"), name),
+                response,
+                Self::response,
+            );
+        }
+        for (name, parameter) in components.parameters.iter().flatten() {
+            self.ref_or(
+                pointer(&section("parameters"), name),
+                parameter,
+                Self::parameter,
+            );
+        }
+        for (name, request_body) in components.request_bodies.iter().flatten() {
+            self.ref_or(
+                pointer(&section("requestBodies"), name),
+                request_body,
+                Self::request_body,
+            );
+        }
+        self.examples(&path, components.examples.as_ref());
+        for (name, header) in components.headers.iter().flatten() {
+            self.ref_or(pointer(&section("headers"), name), header, Self::header);
+        }
+        for (name, security_scheme) in components.security_schemes.iter().flatten() {
+            self.ref_or(
+                pointer(&section("securitySchemes"), name),
+                security_scheme,
+                Self::security_scheme,
+            );
+        }
+        self.links(&path, components.links.as_ref());
+        for (name, callback) in components.callbacks.iter().flatten() {
+            self.ref_or(
+                pointer(&section("callbacks"), name),
+                callback,
+                Self::callback,
+            );
+        }
+    }
+}
+
+impl WalkMut for Spec {
+    fn walk_mut<V: VisitMut<Self>>(&mut self, visitor: &mut V) {
+        let mut walker = WalkerMut { visitor };
+        walker
+            .visitor
+            .visit_extensions_mut("#", &mut self.extensions);
+        walker.info("#/info".to_owned(), &mut self.info);
+        walker.servers("#", &mut self.servers);
+        for (name, path_item) in &mut self.paths {
+            walker.path_item(pointer("#/paths", name), path_item);
+        }
+        if let Some(components) = &mut self.components {
+            walker.components("#/components".to_owned(), components);
+        }
+        for (i, tag) in self.tags.iter_mut().flatten().enumerate() {
+            walker.tag(pointer("#/tags", &i.to_string()), tag);
+        }
+        walker.external_docs("#", &mut self.external_docs);
+    }
+}
+
+/// Walks the components only, e.g. to inspect the components without the paths.
+pub(crate) fn walk_components<V: Visit<Spec>>(components: &Components, visitor: &mut V) {
+    Walker { visitor }.components("#/components".to_owned(), components);
+}
+
+/// Walks the components only, e.g. to rewrite the references between the components.
+pub(crate) fn walk_components_mut<V: VisitMut<Spec>>(components: &mut Components, visitor: &mut V) {
+    WalkerMut { visitor }.components("#/components".to_owned(), components);
+}
+
+struct WalkerMut<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<V: VisitMut<Spec>> WalkerMut<'_, V> {
+    fn ref_or<T>(
+        &mut self,
+        path: String,
+        value: &mut RefOr<T>,
+        f: impl FnOnce(&mut Self, String, &mut T),
+    ) {
+        match value {
+            RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+            RefOr::Item(item) => f(self, path, item),
+        }
+    }
+
+    fn info(&mut self, path: String, info: &mut Info) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut info.extensions);
+        if let Some(contact) = &mut info.contact {
+            self.visitor
+                .visit_extensions_mut(&pointer(&path, "contact"), &mut contact.extensions);
+        }
+        if let Some(license) = &mut info.license {
+            self.visitor
+                .visit_extensions_mut(&pointer(&path, "license"), &mut license.extensions);
+        }
+    }
+
+    fn servers(&mut self, path: &str, servers: &mut Option<Vec<Server>>) {
+        let path = pointer(path, "servers");
+        for (i, server) in servers.iter_mut().flatten().enumerate() {
+            self.server(pointer(&path, &i.to_string()), server);
+        }
+    }
+
+    fn server(&mut self, path: String, server: &mut Server) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut server.extensions);
+        let variables = pointer(&path, "variables");
+        for (name, variable) in server.variables.iter_mut().flatten() {
+            self.visitor
+                .visit_extensions_mut(&pointer(&variables, name), &mut variable.extensions);
+        }
+    }
+
+    fn tag(&mut self, path: String, tag: &mut Tag) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut tag.extensions);
+        self.external_docs(&path, &mut tag.external_docs);
+    }
+
+    fn external_docs(&mut self, path: &str, external_docs: &mut Option<ExternalDocumentation>) {
+        if let Some(external_docs) = external_docs {
+            self.visitor.visit_extensions_mut(
+                &pointer(path, "externalDocs"),
+                &mut external_docs.extensions,
+            );
+        }
+    }
+
+    fn path_item(&mut self, path: String, path_item: &mut PathItem) {
+        self.visitor.visit_path_item_mut(&path, path_item);
+        self.visitor
+            .visit_extensions_mut(&path, &mut path_item.extensions);
+        self.servers(&path, &mut path_item.servers);
+        self.parameters(&path, &mut path_item.parameters);
+        for (method, operation) in path_item.iter_mut() {
+            let path = pointer(&path, method.as_str());
+            self.visitor.visit_operation_mut(&path, &method, operation);
+            self.operation(path, operation);
+        }
+    }
+
+    fn operation(&mut self, path: String, operation: &mut Operation) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut operation.extensions);
+        self.external_docs(&path, &mut operation.external_docs);
+        self.parameters(&path, &mut operation.parameters);
+        if let Some(request_body) = &mut operation.request_body {
+            self.ref_or(pointer(&path, "requestBody"), request_body, |w, path, x| {
+                w.content(&path, Some(&mut x.content))
+            });
+        }
+        self.responses(pointer(&path, "responses"), &mut operation.responses);
+        for (name, callback) in operation.callbacks.iter_mut().flatten() {
+            let path = pointer(&pointer(&path, "callbacks"), name);
+            self.ref_or(path, callback, Self::callback);
+        }
+        self.servers(&path, &mut operation.servers);
+    }
+
+    fn parameters(&mut self, path: &str, parameters: &mut Option<Vec<RefOr<Parameter>>>) {
+        let path = pointer(path, "parameters");
+        for (i, parameter) in parameters.iter_mut().flatten().enumerate() {
+            self.ref_or(pointer(&path, &i.to_string()), parameter, Self::parameter);
+        }
+    }
+
+    fn parameter(&mut self, path: String, parameter: &mut Parameter) {
+        self.visitor.visit_parameter_mut(&path, parameter);
+        self.visitor
+            .visit_extensions_mut(&path, parameter.extensions_mut());
+        let (schema, examples, content) = match parameter {
+            Parameter::Path(p) => (&mut p.schema, &mut p.examples, &mut p.content),
+            Parameter::Query(p) => (&mut p.schema, &mut p.examples, &mut p.content),
+            Parameter::Header(p) => (&mut p.schema, &mut p.examples, &mut p.content),
+            Parameter::Cookie(p) => (&mut p.schema, &mut p.examples, &mut p.content),
+        };
+        if let Some(schema) = schema {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        self.examples(&path, examples.as_mut());
+        self.content(&path, content.as_mut());
+    }
+
+    fn content(&mut self, path: &str, content: Option<&mut BTreeMap<String, MediaType>>) {
+        let path = pointer(path, "content");
+        for (name, media_type) in content.into_iter().flatten() {
+            self.media_type(pointer(&path, name), media_type);
+        }
+    }
+
+    fn media_type(&mut self, path: String, media_type: &mut MediaType) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut media_type.extensions);
+        if let Some(schema) = &mut media_type.schema {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        self.examples(&path, media_type.examples.as_mut());
+        for (name, encoding) in media_type.encoding.iter_mut().flatten() {
+            let path = pointer(&pointer(&path, "encoding"), name);
+            self.visitor
+                .visit_extensions_mut(&path, &mut encoding.extensions);
+            self.headers(&path, encoding.headers.as_mut());
+        }
+    }
+
+    fn responses(&mut self, path: String, responses: &mut Responses) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut responses.extensions);
+        if let Some(response) = &mut responses.default {
+            self.ref_or(pointer(&path, "default"), response, Self::response);
+        }
+        for (status, response) in responses.responses.iter_mut().flatten() {
+            self.ref_or(pointer(&path, status), response, Self::response);
+        }
+    }
+
+    fn response(&mut self, path: String, response: &mut Response) {
+        self.visitor.visit_response_mut(&path, response);
+        self.visitor
+            .visit_extensions_mut(&path, &mut response.extensions);
+        self.headers(&path, response.headers.as_mut());
+        self.content(&path, response.content.as_mut());
+        self.links(&path, response.links.as_mut());
+    }
+
+    fn examples(&mut self, path: &str, examples: Option<&mut BTreeMap<String, RefOr<Example>>>) {
+        let path = pointer(path, "examples");
+        for (name, example) in examples.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), example, |w, path, x| {
+                w.visitor.visit_extensions_mut(&path, &mut x.extensions)
+            });
+        }
+    }
+
+    fn links(&mut self, path: &str, links: Option<&mut BTreeMap<String, RefOr<Link>>>) {
+        let path = pointer(path, "links");
+        for (name, link) in links.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), link, Self::link);
+        }
+    }
+
+    fn link(&mut self, path: String, link: &mut Link) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut link.extensions);
+        if let Some(server) = &mut link.server {
+            self.server(pointer(&path, "server"), server);
+        }
+    }
+
+    fn security_scheme(&mut self, path: String, security_scheme: &mut SecurityScheme) {
+        match security_scheme {
+            SecurityScheme::HTTP(s) => self.visitor.visit_extensions_mut(&path, &mut s.extensions),
+            SecurityScheme::ApiKey(s) => {
+                self.visitor.visit_extensions_mut(&path, &mut s.extensions)
+            }
+            SecurityScheme::OpenIdConnect(s) => {
+                self.visitor.visit_extensions_mut(&path, &mut s.extensions)
+            }
+            SecurityScheme::OAuth2(s) => {
+                self.visitor.visit_extensions_mut(&path, &mut s.extensions);
+                let flows = pointer(&path, "flows");
+                self.visitor
+                    .visit_extensions_mut(&flows, &mut s.flows.extensions);
+                if let Some(flow) = &mut s.flows.implicit {
+                    self.visitor
+                        .visit_extensions_mut(&pointer(&flows, "implicit"), &mut flow.extensions);
+                }
+                if let Some(flow) = &mut s.flows.password {
+                    self.visitor
+                        .visit_extensions_mut(&pointer(&flows, "password"), &mut flow.extensions);
+                }
+                if let Some(flow) = &mut s.flows.client_credentials {
+                    self.visitor.visit_extensions_mut(
+                        &pointer(&flows, "clientCredentials"),
+                        &mut flow.extensions,
+                    );
+                }
+                if let Some(flow) = &mut s.flows.authorization_code {
+                    self.visitor.visit_extensions_mut(
+                        &pointer(&flows, "authorizationCode"),
+                        &mut flow.extensions,
+                    );
+                }
+            }
+        }
+    }
+
+    fn headers(&mut self, path: &str, headers: Option<&mut BTreeMap<String, RefOr<Header>>>) {
+        let path = pointer(path, "headers");
+        for (name, header) in headers.into_iter().flatten() {
+            self.ref_or(pointer(&path, name), header, Self::header);
+        }
+    }
+
+    fn header(&mut self, path: String, header: &mut Header) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut header.extensions);
+        if let Some(schema) = &mut header.schema {
+            self.ref_or(pointer(&path, "schema"), schema, Self::schema);
+        }
+        self.examples(&path, header.examples.as_mut());
+        self.content(&path, header.content.as_mut());
+    }
+
+    fn callback(&mut self, path: String, callback: &mut Callback) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut callback.extensions);
+        for (expression, path_item) in &mut callback.paths {
+            self.path_item(pointer(&path, expression), path_item);
+        }
+    }
+
+    fn schema(&mut self, path: String, schema: &mut Schema) {
+        self.visitor.visit_schema_mut(&path, schema);
+        self.visitor
+            .visit_extensions_mut(&path, schema.extensions_mut());
+        if let Schema::Single(single) = schema {
+            let (xml, external_docs) = documentation_mut(single);
+            if let Some(xml) = xml {
+                self.visitor
+                    .visit_extensions_mut(&pointer(&path, "xml"), &mut xml.extensions);
+            }
+            self.external_docs(&path, external_docs);
+        }
+        match schema {
+            Schema::AllOf(s) => self.schemas(pointer(&path, "allOf"), &mut s.all_of),
+            Schema::AnyOf(s) => self.schemas(pointer(&path, "anyOf"), &mut s.any_of),
+            Schema::OneOf(s) => self.schemas(pointer(&path, "oneOf"), &mut s.one_of),
+            Schema::Not(s) => self.nested(pointer(&path, "not"), &mut s.not),
+            Schema::Single(SingleSchema::Array(s)) => {
+                if let Some(items) = &mut s.items {
+                    self.nested(pointer(&path, "items"), items);
+                }
+            }
+            Schema::Single(SingleSchema::Object(s)) => {
+                let properties = pointer(&path, "properties");
+                for (name, schema) in s.properties.iter_mut().flatten() {
+                    self.nested(pointer(&properties, name), schema);
+                }
+                if let Some(BoolOr::Item(schema)) = &mut s.additional_properties {
+                    self.nested(pointer(&path, "additionalProperties"), schema);
+                }
+            }
+            Schema::Single(_) => {}
+        }
+    }
+
+    fn nested(&mut self, path: String, schema: &mut RefOr<Box<Schema>>) {
+        match schema {
+            RefOr::Ref(r) => self.visitor.visit_reference_mut(&path, r),
+            RefOr::Item(schema) => self.schema(path, schema),
+        }
+    }
+
+    fn schemas(&mut self, path: String, schemas: &mut [RefOr<Box<Schema>>]) {
+        for (i, schema) in schemas.iter_mut().enumerate() {
+            self.nested(pointer(&path, &i.to_string()), schema);
+        }
+    }
+
+    fn components(&mut self, path: String, components: &mut Components) {
+        self.visitor
+            .visit_extensions_mut(&path, &mut components.extensions);
+        let section = |name: &str| pointer(&path, name);
+        for (name, schema) in components.schemas.iter_mut().flatten() {
+            self.ref_or(pointer(&section("schemas"), name), schema, Self::schema);
+        }
+        for (name, response) in components.responses.iter_mut().flatten() {
+            self.ref_or(
+                pointer(&section("responses"), name),
+                response,
+                Self::response,
+            );
+        }
+        for (name, parameter) in components.parameters.iter_mut().flatten() {
+            self.ref_or(
+                pointer(&section("parameters"), name),
+                parameter,
+                Self::parameter,
+            );
+        }
+        for (name, request_body) in components.request_bodies.iter_mut().flatten() {
+            self.ref_or(
+                pointer(&section("requestBodies"), name),
+                request_body,
+                |w, path, x| w.content(&path, Some(&mut x.content)),
+            );
+        }
+        self.examples(&path, components.examples.as_mut());
+        for (name, header) in components.headers.iter_mut().flatten() {
+            self.ref_or(pointer(&section("headers"), name), header, Self::header);
+        }
+        for (name, security_scheme) in components.security_schemes.iter_mut().flatten() {
+            self.ref_or(
+                pointer(&section("securitySchemes"), name),
+                security_scheme,
+                Self::security_scheme,
+            );
+        }
+        self.links(&path, components.links.as_mut());
+        for (name, callback) in components.callbacks.iter_mut().flatten() {
+            self.ref_or(
+                pointer(&section("callbacks"), name),
+                callback,
+                Self::callback,
+            );
+        }
+    }
+}
+
+/// Returns the XML object and the external documentation of the schema.
+fn documentation(schema: &SingleSchema) -> (Option<&XML>, &Option<ExternalDocumentation>) {
+    match schema {
+        SingleSchema::String(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Integer(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Number(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Boolean(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Array(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Object(s) => (s.xml.as_ref(), &s.external_docs),
+        SingleSchema::Null(s) => (s.xml.as_ref(), &s.external_docs),
+    }
+}
+
+/// Returns the XML object and the external documentation of the schema for the rewrites.
+fn documentation_mut(
+    schema: &mut SingleSchema,
+) -> (Option<&mut XML>, &mut Option<ExternalDocumentation>) {
+    match schema {
+        SingleSchema::String(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Integer(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Number(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Boolean(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Array(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Object(s) => (s.xml.as_mut(), &mut s.external_docs),
+        SingleSchema::Null(s) => (s.xml.as_mut(), &mut s.external_docs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::http::Method;
+    use crate::common::reference::Ref;
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Visit<Spec> for Recorder {
+        fn visit_path_item(&mut self, path: &str, _: &PathItem) {
+            self.0.push(format!("path_item {}", path));
+        }
+
+        fn visit_operation(&mut self, path: &str, method: &Method, _: &Operation) {
+            self.0.push(format!("operation {} {}", method, path));
+        }
+
+        fn visit_parameter(&mut self, path: &str, _: &Parameter) {
+            self.0.push(format!("parameter {}", path));
+        }
+
+        fn visit_response(&mut self, path: &str, _: &Response) {
+            self.0.push(format!("response {}", path));
+        }
+
+        fn visit_schema(&mut self, path: &str, _: &Schema) {
+            self.0.push(format!("schema {}", path));
+        }
+
+        fn visit_reference(&mut self, path: &str, reference: &Ref) {
+            self.0
+                .push(format!("reference {} {}", path, reference.reference));
+        }
+
+        fn visit_extensions(&mut self, path: &str, extensions: &Extensions) {
+            for name in extensions.keys() {
+                self.0.push(format!("extension {} {}", path, name));
+            }
+        }
+    }
+
+    #[test]
+    fn test_walk() {
+        let spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "x-root": true,
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [{"$ref": "#/components/parameters/Id"}],
+                    "get": {
+                        "x-internal": true,
+                        "responses": {
+                            "200": {
+                                "description": "pet",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": {"$ref": "#/components/schemas/Pet"},
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string", "x-nullable": false}},
+                    },
+                },
+                "parameters": {
+                    "Id": {"in": "path", "name": "id", "required": true, "schema": {"type": "string"}},
+                },
+            },
+        }))
+        .unwrap();
+        let mut recorder = Recorder::default();
+        spec.walk(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            vec![
+                "extension # x-root",
+                "path_item #/paths/~1pets~1{id}",
+                "reference #/paths/~1pets~1{id}/parameters/0 #/components/parameters/Id",
+                "operation get #/paths/~1pets~1{id}/get",
+                "extension #/paths/~1pets~1{id}/get x-internal",
+                "response #/paths/~1pets~1{id}/get/responses/200",
+                "schema #/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema",
+                "reference #/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema/items #/components/schemas/Pet",
+                "schema #/components/schemas/Pet",
+                "schema #/components/schemas/Pet/properties/name",
+                "extension #/components/schemas/Pet/properties/name x-nullable",
+                "parameter #/components/parameters/Id",
+                "schema #/components/parameters/Id/schema",
+            ],
+        );
+    }
+
+    fn extended_spec() -> Spec {
+        serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "foo",
+                "version": "1",
+                "x-info": 1,
+                "contact": {"x-contact": 1},
+                "license": {"name": "MIT", "x-license": 1},
+            },
+            "servers": [{
+                "url": "https://{host}",
+                "variables": {"host": {"default": "example.com", "x-variable": 1}},
+                "x-server": 1,
+            }],
+            "paths": {
+                "/pets": {
+                    "servers": [{"url": "/", "x-path-server": 1}],
+                    "get": {
+                        "externalDocs": {"url": "https://example.com", "x-docs": 1},
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "links": {"next": {"operationId": "list", "x-link": 1}},
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {"type": "object", "xml": {"name": "pet", "x-xml": 1}},
+                },
+                "examples": {"Pet": {"value": {}, "x-example": 1}},
+                "securitySchemes": {
+                    "oauth": {
+                        "type": "oauth2",
+                        "flows": {
+                            "password": {"tokenUrl": "/token", "scopes": {}, "x-flow": 1},
+                        },
+                        "x-scheme": 1,
+                    },
+                },
+            },
+            "tags": [{"name": "pets", "x-tag": 1}],
+            "externalDocs": {"url": "https://example.com", "x-root-docs": 1},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_walk_all_extensions() {
+        let spec = extended_spec();
+        let mut recorder = Recorder::default();
+        spec.walk(&mut recorder);
+        assert_eq!(
+            recorder
+                .0
+                .iter()
+                .filter(|x| x.starts_with("extension "))
+                .collect::<Vec<_>>(),
+            vec![
+                "extension #/info x-info",
+                "extension #/info/contact x-contact",
+                "extension #/info/license x-license",
+                "extension #/servers/0 x-server",
+                "extension #/servers/0/variables/host x-variable",
+                "extension #/paths/~1pets/servers/0 x-path-server",
+                "extension #/paths/~1pets/get/externalDocs x-docs",
+                "extension #/paths/~1pets/get/responses/200/links/next x-link",
+                "extension #/components/schemas/Pet/xml x-xml",
+                "extension #/components/examples/Pet x-example",
+                "extension #/components/securitySchemes/oauth x-scheme",
+                "extension #/components/securitySchemes/oauth/flows/password x-flow",
+                "extension #/tags/0 x-tag",
+                "extension #/externalDocs x-root-docs",
+            ],
+        );
+
+        struct Strip;
+
+        impl VisitMut<Spec> for Strip {
+            fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Option<Extensions>) {
+                *extensions = None;
+            }
+        }
+
+        let mut spec = spec;
+        spec.walk_mut(&mut Strip);
+        let value = serde_json::to_string(&spec).unwrap();
+        assert!(!value.contains("\"x-"), "all extensions removed: {}", value);
+    }
+
+    struct Rewriter;
+
+    impl VisitMut<Spec> for Rewriter {
+        fn visit_response_mut(&mut self, _: &str, response: &mut Response) {
+            response.headers.get_or_insert_with(BTreeMap::new).insert(
+                "X-Request-Id".to_owned(),
+                RefOr::new_item(Header::default()),
+            );
+        }
+
+        fn visit_reference_mut(&mut self, _: &str, reference: &mut Ref) {
+            if reference.reference == "#/components/schemas/Pet" {
+                reference.reference = "#/components/schemas/Animal".to_owned();
+            }
+        }
+
+        fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Option<Extensions>) {
+            *extensions = None;
+        }
+    }
+
+    #[test]
+    fn test_walk_mut() {
+        let mut spec = serde_json::from_value::<Spec>(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "foo", "version": "1"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": {"$ref": "#/components/schemas/Pet"},
+                                            "x-nullable": false,
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+        spec.walk_mut(&mut Rewriter);
+        assert_eq!(
+            serde_json::to_value(&spec.paths["/pets"]).unwrap(),
+            json!({
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "pets",
+                            "headers": {"X-Request-Id": {}},
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "items": {"$ref": "#/components/schemas/Animal"},
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            }),
+        );
+    }
+}
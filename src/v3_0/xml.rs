@@ -1,10 +1,10 @@
 //! XML Object
 
-use std::collections::BTreeMap;
-
 use serde::{Deserialize, Serialize};
 
-use crate::common::helpers::{validate_optional_url, Context, ValidateWithContext};
+use crate::common::extensions::Extensions;
+use crate::common::helpers::{validate_extensions, validate_uri, Context, ValidateWithContext};
+use crate::common::path::Path;
 use crate::v3_0::spec::Spec;
 
 /// A metadata object that allows for more fine-tuned XML model definitions.
@@ -112,17 +112,22 @@ pub struct XML {
     #[serde(flatten)]
     #[serde(with = "crate::common::extensions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<BTreeMap<String, serde_json::Value>>,
+    pub extensions: Option<Extensions>,
 }
 
 impl ValidateWithContext<Spec> for XML {
-    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: String) {
-        validate_optional_url(&self.namespace, ctx, format!("{}.namespace", path));
+    fn validate_with_context(&self, ctx: &mut Context<Spec>, path: Path) {
+        validate_extensions(&self.extensions, ctx, path.clone());
+        if let Some(namespace) = &self.namespace {
+            validate_uri(namespace, ctx, path.field("namespace"));
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use crate::validation::Options;
 
     use super::*;
@@ -207,24 +212,24 @@ mod tests {
             wrapped: Some(true),
             extensions: None,
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         XML {
             namespace: Some("https://example.com/schema/sample".to_owned()),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert!(ctx.errors.is_empty(), "no errors: {:?}", ctx.errors);
 
         XML {
             namespace: Some("foo-bar".to_owned()),
             ..Default::default()
         }
-        .validate_with_context(&mut ctx, "xml".to_owned());
+        .validate_with_context(&mut ctx, "xml".into());
         assert_eq!(
             ctx.errors,
-            vec!["xml.namespace: must be a valid URL, found `foo-bar`"],
+            vec!["xml.namespace: must be a valid URI, found `foo-bar`"],
             "invalid URL: {:?}",
             ctx.errors
         );
@@ -17,6 +17,8 @@ impl Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
 #[derive(EnumSetType, Debug)]
 pub enum Options {
     /// Ignore missing tags.
@@ -74,6 +76,14 @@ pub enum Options {
     /// Ignore unused callbacks.
     /// Applies for v3.0
     IgnoreUnusedCallbacks,
+
+    /// Ignore responses objects without any response, allowed since v3.1.
+    /// Applies for v3.0
+    IgnoreEmptyResponses,
+
+    /// Ignore schemas with an unknown or vendor specific type.
+    /// Applies for v2.0
+    IgnoreUnsupportedSchemas,
 }
 
 impl Options {
@@ -102,7 +112,57 @@ impl Options {
     }
 }
 
+/// The opt-in checks of what the specifications allow, but is likely a mistake.
+/// Unlike [Options], each lint makes the validation stricter.
+#[derive(EnumSetType, Debug)]
+pub enum Lints {
+    /// Report the custom string formats, which are not defined by the OpenAPI
+    /// or JSON Schema specifications.
+    /// Applies for v2.0, v3.0
+    UnknownFormats,
+
+    /// Report the response status codes without a reason phrase registered
+    /// in the IANA HTTP Status Code Registry, e.g. `299`.
+    /// Applies for v2.0, v3.0
+    UnregisteredStatusCodes,
+
+    /// Report the invalid `x-tagGroups` extension, used by the documentation renderers,
+    /// and its tags not declared by the specification.
+    /// Applies for v2.0, v3.0
+    TagGroups,
+
+    /// Report the read-only properties listed as required in the request bodies,
+    /// such properties cannot be sent by the clients.
+    /// Applies for v3.0
+    RequiredReadOnlyProperties,
+
+    /// Report the `Content-Type`, `Accept` and `Authorization` headers declared
+    /// in the responses and the encodings, which are ignored by the specification.
+    /// Applies for v3.0
+    ReservedHeaders,
+}
+
+impl Lints {
+    /// Create an empty set of lints.
+    pub fn new() -> EnumSet<Lints> {
+        EnumSet::empty()
+    }
+
+    pub fn only(&self) -> EnumSet<Lints> {
+        EnumSet::only(*self)
+    }
+}
+
 /// Validate a OpenAPI specification.
 pub trait Validate {
-    fn validate(&self, options: EnumSet<Options>) -> Result<(), Error>;
+    fn validate(&self, options: EnumSet<Options>) -> Result<(), Error> {
+        self.validate_with_lints(options, Lints::new())
+    }
+
+    /// Validates the specification with the options and the opt-in lints.
+    fn validate_with_lints(
+        &self,
+        options: EnumSet<Options>,
+        lints: EnumSet<Lints>,
+    ) -> Result<(), Error>;
 }
@@ -0,0 +1,114 @@
+//! Entry points for the WebAssembly bindings
+//!
+//! The functions take and return plain strings,
+//! so they can be exported by `wasm-bindgen` as is,
+//! e.g. to validate the specifications in a browser-based editor.
+//!
+//! Example:
+//!
+//! ```rust
+//! use roas::wasm::validate_json;
+//!
+//! let raw = r#"{"openapi": "3.0.3", "info": {"title": "", "version": "1"}, "paths": {}}"#;
+//! assert_eq!(
+//!     validate_json(raw),
+//!     r##"{"valid":false,"version":"3.0.3","errors":["#.info.title: must not be empty"]}"##,
+//! );
+//! ```
+
+use enumset::EnumSet;
+use serde::Serialize;
+
+use crate::common::versioned::AnySpec;
+use crate::validation::Options;
+
+/// The result of the validation of a specification.
+#[derive(Clone, Debug, Serialize, PartialEq, Default)]
+pub struct Report {
+    /// `true` if the specification has been parsed and no errors have been found.
+    pub valid: bool,
+
+    /// The version of the specification, the `openapi` or the `swagger` field.
+    pub version: Option<String>,
+
+    /// The parsing or the validation errors.
+    pub errors: Vec<String>,
+}
+
+/// Parses and validates the specification given as a JSON string,
+/// the version of the specification is detected by the `openapi` or the `swagger` field.
+pub fn validate(raw: &str, options: EnumSet<Options>) -> Report {
+    let (version, result) = match AnySpec::parse(raw) {
+        Ok(spec) => (
+            Some(spec.version()),
+            spec.validate_with_lints(options, EnumSet::empty()),
+        ),
+        Err((version, err)) => (version, Err(vec![err])),
+    };
+    Report {
+        valid: result.is_ok(),
+        version,
+        errors: result.err().unwrap_or_default(),
+    }
+}
+
+/// Validates the specification given as a JSON string with the strict options,
+/// returns the [Report] serialized as a JSON string.
+pub fn validate_json(raw: &str) -> String {
+    validate_json_with_options(raw, Options::new())
+}
+
+/// Validates the specification given as a JSON string with the options,
+/// returns the [Report] serialized as a JSON string.
+pub fn validate_json_with_options(raw: &str, options: EnumSet<Options>) -> String {
+    serde_json::to_string(&validate(raw, options)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        #[cfg(feature = "v3_0")]
+        assert_eq!(
+            validate(
+                r#"{"openapi": "3.0.3", "info": {"title": "foo", "version": "1"}, "paths": {}}"#,
+                Options::new()
+            ),
+            Report {
+                valid: true,
+                version: Some("3.0.3".to_owned()),
+                errors: vec![],
+            },
+        );
+        #[cfg(feature = "v2")]
+        assert_eq!(
+            validate(
+                r#"{"swagger": "2.0", "info": {"title": "", "version": "1"}, "paths": {}}"#,
+                Options::new()
+            ),
+            Report {
+                valid: false,
+                version: Some("2.0".to_owned()),
+                errors: vec!["#.info.title: must not be empty".to_owned()],
+            },
+        );
+        assert_eq!(
+            validate(r#"{"openapi": "4.0.0"}"#, Options::new()),
+            Report {
+                valid: false,
+                version: Some("4.0.0".to_owned()),
+                errors: vec!["unsupported version: 4.0.0".to_owned()],
+            },
+        );
+        assert_eq!(
+            validate(r#"{"openapi": "3.0.9"}"#, Options::new()).errors,
+            vec!["unsupported version: 3.0.9"],
+        );
+        let report = validate("{", Options::new());
+        assert!(!report.valid);
+        assert_eq!(report.version, None);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+    }
+}
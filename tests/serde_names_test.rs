@@ -0,0 +1,176 @@
+//! Checks the serialized names of the fields of all the (de)serializable structs.
+//!
+//! The specifications use the camelCase names, a field without `rename_all = "camelCase"`
+//! on its struct or an explicit `rename` is silently (de)serialized in snake_case,
+//! so the structs are collected from the sources and each field is checked.
+
+use std::fs;
+use std::path::Path;
+
+/// The structs mirroring the documents with snake_case names.
+const SNAKE_CASE_STRUCTS: &[&str] = &[
+    // OpenID Connect Discovery metadata
+    "OpenIdConfiguration",
+    // the `x-google-backend` vendor extension
+    "GoogleBackend",
+    // the statistics report of the crate
+    "Stats",
+];
+
+/// The fields renamed to the keywords of the specifications.
+const KEYWORDS: &[(&str, &str)] = &[
+    ("_type", "type"),
+    ("enum_values", "enum"),
+    ("location", "in"),
+    ("reference", "$ref"),
+];
+
+struct Field {
+    location: String,
+    strukt: String,
+    name: String,
+    serialized: String,
+    renamed: bool,
+}
+
+fn camel_case(name: &str) -> String {
+    let mut res = String::new();
+    for (i, part) in name.trim_start_matches('_').split('_').enumerate() {
+        let mut chars = part.chars();
+        match chars.next() {
+            Some(c) if i > 0 => {
+                res.push(c.to_ascii_uppercase());
+                res.extend(chars);
+            }
+            _ => res.push_str(part),
+        }
+    }
+    res
+}
+
+fn attribute_value<'a>(attributes: &'a [String], key: &str) -> Option<&'a str> {
+    attributes.iter().find_map(|x| {
+        let start = x.find(&format!("{} = \"", key))? + key.len() + 4;
+        let len = x[start..].find('"')?;
+        Some(&x[start..start + len])
+    })
+}
+
+/// Collects the fields of the structs deriving `Serialize` or `Deserialize` in the file.
+fn collect_fields(path: &Path, fields: &mut Vec<Field>) {
+    let source = fs::read_to_string(path).unwrap();
+    let mut attributes: Vec<String> = Vec::new();
+    let mut current: Option<(String, Option<String>)> = None;
+    for (i, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with("///") || line.starts_with("//") {
+            continue;
+        }
+        if line.starts_with("#[") {
+            attributes.push(line.to_owned());
+            continue;
+        }
+        match &current {
+            None => {
+                let is_serde = attributes
+                    .iter()
+                    .any(|x| x.contains("Serialize") || x.contains("Deserialize"));
+                let name = line
+                    .split_once("struct ")
+                    .filter(|_| is_serde && line.ends_with('{'))
+                    .and_then(|(_, x)| x.split(['<', ' ']).next());
+                if let Some(name) = name {
+                    let rename_all = attribute_value(&attributes, "rename_all").map(str::to_owned);
+                    current = Some((name.to_owned(), rename_all));
+                }
+            }
+            Some(_) if line == "}" => current = None,
+            Some((strukt, rename_all)) => {
+                let declaration = line.strip_prefix("pub ").unwrap_or(line);
+                let declaration = declaration
+                    .strip_prefix("pub(crate) ")
+                    .unwrap_or(declaration);
+                if let Some((name, _)) = declaration.split_once(':') {
+                    let skipped = attributes.iter().any(|x| {
+                        x.contains("flatten") || x.contains("skip)") || x.contains("skip,")
+                    });
+                    if !skipped {
+                        let rename = attribute_value(&attributes, "rename");
+                        let serialized = match (rename, rename_all.as_deref()) {
+                            (Some(rename), _) => rename.to_owned(),
+                            (None, Some("camelCase")) => camel_case(name),
+                            (None, _) => name.to_owned(),
+                        };
+                        fields.push(Field {
+                            location: format!("{}:{}", path.display(), i + 1),
+                            strukt: strukt.clone(),
+                            name: name.to_owned(),
+                            serialized,
+                            renamed: rename.is_some(),
+                        });
+                    }
+                }
+            }
+        }
+        attributes.clear();
+    }
+}
+
+fn collect_dir(dir: &Path, fields: &mut Vec<Field>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_dir(&path, fields);
+        } else if path.extension().is_some_and(|x| x == "rs") {
+            collect_fields(&path, fields);
+        }
+    }
+}
+
+#[test]
+fn test_serde_names() {
+    let mut fields = Vec::new();
+    collect_dir(Path::new("src"), &mut fields);
+    assert!(
+        fields.len() > 300,
+        "too few fields found: {}, the parser is broken",
+        fields.len()
+    );
+
+    let mut errors = Vec::new();
+    for field in &fields {
+        if SNAKE_CASE_STRUCTS.contains(&field.strukt.as_str()) {
+            continue;
+        }
+        let expected = KEYWORDS
+            .iter()
+            .find(|(name, keyword)| *name == field.name && *keyword == field.serialized)
+            .map_or_else(
+                || camel_case(&field.name),
+                |(_, keyword)| keyword.to_string(),
+            );
+        if field.serialized != expected {
+            errors.push(format!(
+                "{}: {}.{} is serialized as `{}`, expected `{}`{}",
+                field.location,
+                field.strukt,
+                field.name,
+                field.serialized,
+                expected,
+                if field.renamed {
+                    ""
+                } else {
+                    ", add `rename` or `rename_all = \"camelCase\"`"
+                },
+            ));
+        }
+    }
+    assert!(errors.is_empty(), "\n{}", errors.join("\n"));
+}
+
+#[test]
+fn test_camel_case() {
+    assert_eq!(camel_case("max_properties"), "maxProperties");
+    assert_eq!(camel_case("_type"), "type");
+    assert_eq!(camel_case("name"), "name");
+}
@@ -12,6 +12,7 @@ mod v2_tests {
             println!("validating: {:?}", path_buf);
             let json_spec = fs::read_to_string(&path_buf).unwrap();
             let spec = serde_json::from_str::<Spec>(&json_spec).unwrap();
+            Spec::from_value_strict(serde_json::from_str(&json_spec).unwrap()).unwrap();
             spec.validate(Options::IgnoreMissingTags | Options::IgnoreExternalReferences)
                 .unwrap();
             assert_eq!(
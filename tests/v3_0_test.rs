@@ -12,6 +12,7 @@ mod v3_0_tests {
             println!("validating: {:?}", path_buf);
             let json_spec = fs::read_to_string(&path_buf).unwrap();
             let spec = serde_json::from_str::<Spec>(&json_spec).unwrap();
+            Spec::from_value_strict(serde_json::from_str(&json_spec).unwrap()).unwrap();
             match spec.validate(Options::IgnoreMissingTags.only()) {
                 Ok(_) => {}
                 Err(err) => {